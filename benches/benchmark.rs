@@ -55,6 +55,8 @@ fn colorer<G: StaticGraph>(c: ColoringAlgo, g: &G) {
         ColoringAlgo::LF => lf_coloring(g),
         ColoringAlgo::SL => sl_coloring(g),
         ColoringAlgo::SDO => sdo_coloring(g),
+        ColoringAlgo::Genetic => genetic_coloring(g),
+        ColoringAlgo::IG => iterated_greedy(g, 100),
     };
 }
 
@@ -180,5 +182,70 @@ fn graphs(c: &mut Criterion) {
     c.bench_functions("Graph Coloring Hybrid", functions, Hybrid::random(n, p));
 }
 
-criterion_group!(benches, graphs);
+fn dense(c: &mut Criterion) {
+    let n = 300;
+    let p = 0.9;
+
+    // The blocked color computation dominates greedy colorings on dense graphs.
+    // EdgeList is left out, scanning all edges for the neighbors dominates instead.
+    let adjmatrix = Fun::new("AdjMatrix", move |b, g| {
+        b.iter(|| colorer::<AdjMatrix>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions(
+        "Dense Coloring AdjMatrix",
+        vec![adjmatrix],
+        AdjMatrix::random(n, p),
+    );
+
+    let adjlist = Fun::new("AdjList", move |b, g| {
+        b.iter(|| colorer::<AdjList>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions(
+        "Dense Coloring AdjList",
+        vec![adjlist],
+        AdjList::random(n, p),
+    );
+
+    let hybrid = Fun::new("Hybrid", move |b, g| {
+        b.iter(|| colorer::<Hybrid>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions("Dense Coloring Hybrid", vec![hybrid], Hybrid::random(n, p));
+
+    let csr = Fun::new("Csr", move |b, g| {
+        b.iter(|| colorer::<Csr>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions("Dense Coloring Csr", vec![csr], Csr::random(n, p));
+}
+
+fn index_width(c: &mut Criterion) {
+    let n = 300;
+    let p = 0.9;
+
+    // Narrow indices halve the neighbor array, see Csr32
+    let csr = Fun::new("Csr", move |b, g| {
+        b.iter(|| colorer::<Csr>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions("Index Width Csr", vec![csr], Csr::random(n, p));
+
+    let csr32 = Fun::new("Csr32", move |b, g| {
+        b.iter(|| colorer::<Csr32>(ColoringAlgo::LF, g))
+    });
+    c.bench_functions("Index Width Csr32", vec![csr32], Csr32::random(n, p));
+}
+
+fn saturation(c: &mut Criterion) {
+    let n = 2000;
+    let p = 0.05;
+
+    // Both compute the same DSATUR coloring, with and without SaturationTracker
+    let sdo = Fun::new("sdo", |b, g| b.iter(|| sdo_coloring::<Csr>(g)));
+    let dsatur = Fun::new("dsatur", |b, g| b.iter(|| dsatur_coloring::<Csr>(g)));
+    c.bench_functions("Saturation Csr", vec![sdo, dsatur], Csr::random(n, p));
+
+    // Backtracking colors and uncolors vertices through the tracker
+    let exact = Fun::new("exact", |b, g| b.iter(|| exact_coloring::<AdjList>(g)));
+    c.bench_functions("Saturation Exact", vec![exact], AdjList::random(40, 0.5));
+}
+
+criterion_group!(benches, graphs, dense, index_width, saturation);
 criterion_main!(benches);
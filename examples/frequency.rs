@@ -0,0 +1,310 @@
+//! Frequency assignment: assigns channels to radio transmitters such that
+//! interfering transmitters are far enough apart, a T-coloring of the interference
+//! graph.
+//!
+//! ```
+//! cargo run --release --example frequency -- examples/frequency.json > channels.json
+//! ```
+//!
+//! The input lists the transmitters, optionally with channels they must not use,
+//! and pairs of interfering transmitters with the required channel separation.
+//! A pair with a weight may be violated at this cost, one without must not be.
+//!
+//! ```json
+//! {
+//!   "channels": 6,
+//!   "budget_ms": 500,
+//!   "transmitters": [{ "name": "north", "forbidden": [0] }, { "name": "south" }],
+//!   "interference": [{ "a": "north", "b": "south", "separation": 2, "weight": 1.5 }]
+//! }
+//! ```
+//!
+//! Interfering pairs become the weighted edges of the graph, hard pairs of infinite
+//! weight, and forbidden channels become ```Constraints```. The search starts from a
+//! ```min_penalty_coloring```, which keeps interfering transmitters apart by at least
+//! one channel, and moves transmitters to the channel of least penalty until the
+//! budget is used up or nothing is violated. The output lists the channel of every
+//! transmitter and the violated requirements.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+use std::time::{Duration, Instant};
+
+use graml::coloring::{min_penalty_coloring, Constraints};
+use graml::graph::*;
+
+/// Iterations of the initial coloring.
+const START_ITERATIONS: usize = 1000;
+
+/// Probability of a random move, which escapes local minima.
+const NOISE: f64 = 0.1;
+
+#[derive(Debug, Deserialize)]
+struct Input {
+    channels: usize,
+    budget_ms: u64,
+    transmitters: Vec<Transmitter>,
+    interference: Vec<Interference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transmitter {
+    name: String,
+    #[serde(default)]
+    forbidden: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Interference {
+    a: String,
+    b: String,
+    separation: usize,
+    /// Cost of a violation, ```None``` if the separation is required.
+    weight: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Output {
+    channels: BTreeMap<String, usize>,
+    /// Highest channel used.
+    span: usize,
+    /// Total weight of the violated soft requirements.
+    penalty: f64,
+    violations: Vec<Violation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Violation {
+    Separation {
+        a: String,
+        b: String,
+        required: usize,
+        actual: usize,
+        weight: Option<f64>,
+    },
+    Forbidden {
+        transmitter: String,
+        channel: usize,
+    },
+}
+
+/// Penalty compared by the number of violated hard requirements first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+struct Cost {
+    hard: usize,
+    soft: f64,
+}
+
+impl Cost {
+    fn add(&mut self, weight: f64) {
+        if weight == f64::INFINITY {
+            self.hard += 1;
+        } else {
+            self.soft += weight;
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.hard == 0 && self.soft == 0.0
+    }
+}
+
+/// Interference graph with the requirements of its edges.
+struct Problem {
+    graph: Labeled<AdjList, String>,
+    weights: EdgeWeights,
+    separations: HashMap<(usize, usize), usize>,
+    constraints: Constraints,
+    channels: usize,
+}
+
+impl Problem {
+    fn new(input: &Input) -> io::Result<Self> {
+        let names = input
+            .transmitters
+            .iter()
+            .map(|t| t.name.clone())
+            .collect::<Vec<_>>();
+        let mut graph = Labeled::new(AdjList::with_capacity(names.len()), names);
+        let mut weights = EdgeWeights::default();
+        let mut separations = HashMap::new();
+        let mut constraints = Constraints::new();
+
+        for pair in &input.interference {
+            let (u, v) = match (graph.vertex(&pair.a), graph.vertex(&pair.b)) {
+                (Some(u), Some(v)) if u != v => (u.min(v), u.max(v)),
+                _ => return Err(invalid(&format!("invalid pair {} {}", pair.a, pair.b))),
+            };
+
+            graph.graph.add_edge(u, v);
+            weights.set(u, v, pair.weight.unwrap_or(f64::INFINITY));
+            let separation = separations.entry((u, v)).or_insert(0);
+            *separation = pair.separation.max(*separation);
+        }
+        for (v, t) in input.transmitters.iter().enumerate() {
+            constraints
+                .forbidden
+                .extend(t.forbidden.iter().map(|&c| (v, c)));
+        }
+        constraints.validate(&graph.graph)?;
+
+        Ok(Self {
+            graph,
+            weights,
+            separations,
+            constraints,
+            channels: input.channels,
+        })
+    }
+
+    /// Penalty of ```v``` on ```channel``` with the others on their channels.
+    fn cost(&self, channels: &[usize], v: usize, channel: usize) -> Cost {
+        let mut cost = Cost::default();
+        for u in self.graph.graph.neighbors(v) {
+            if channel.abs_diff(channels[u]) < self.separations[&(u.min(v), u.max(v))] {
+                cost.add(self.weights.get(u, v));
+            }
+        }
+        if self.constraints.forbidden.contains(&(v, channel)) {
+            cost.add(f64::INFINITY);
+        }
+
+        cost
+    }
+
+    fn total(&self, channels: &[usize]) -> Cost {
+        let mut total = Cost::default();
+        for (u, v) in self.graph.graph.edges() {
+            if channels[u].abs_diff(channels[v]) < self.separations[&(u.min(v), u.max(v))] {
+                total.add(self.weights.get(u, v));
+            }
+        }
+        for &(v, channel) in &self.constraints.forbidden {
+            if channels[v] == channel {
+                total.add(f64::INFINITY);
+            }
+        }
+
+        total
+    }
+
+    /// Min-conflicts search for channels until the deadline.
+    fn search<R: Rng>(&self, deadline: Instant, rng: &mut R) -> Vec<usize> {
+        let start = min_penalty_coloring(
+            &self.graph.graph,
+            &self.weights,
+            self.channels,
+            START_ITERATIONS,
+            rng,
+        );
+        let mut channels = start.coloring;
+        let mut best = (self.total(&channels), channels.clone());
+
+        while !best.0.is_zero() && Instant::now() < deadline {
+            let violating = self
+                .graph
+                .graph
+                .vertices()
+                .filter(|&v| !self.cost(&channels, v, channels[v]).is_zero())
+                .collect::<Vec<_>>();
+            let v = violating[rng.gen_range(0, violating.len())];
+
+            channels[v] = if rng.gen_bool(NOISE) {
+                rng.gen_range(0, self.channels)
+            } else {
+                (0..self.channels)
+                    .map(|c| (self.cost(&channels, v, c), c))
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap()
+                    .1
+            };
+
+            let total = self.total(&channels);
+            if total < best.0 {
+                best = (total, channels.clone());
+            }
+        }
+
+        best.1
+    }
+
+    fn report(&self, input: &Input, channels: &[usize]) -> Output {
+        let names = &self.graph.labels;
+        let mut violations = Vec::new();
+
+        for pair in &input.interference {
+            let (u, v) = (
+                self.graph.vertex(&pair.a).unwrap(),
+                self.graph.vertex(&pair.b).unwrap(),
+            );
+            let actual = channels[u].abs_diff(channels[v]);
+            if actual < pair.separation {
+                violations.push(Violation::Separation {
+                    a: pair.a.clone(),
+                    b: pair.b.clone(),
+                    required: pair.separation,
+                    actual,
+                    weight: pair.weight,
+                });
+            }
+        }
+        for &(v, channel) in &self.constraints.forbidden {
+            if channels[v] == channel {
+                violations.push(Violation::Forbidden {
+                    transmitter: names[v].clone(),
+                    channel,
+                });
+            }
+        }
+
+        Output {
+            channels: self
+                .graph
+                .assignment(channels)
+                .map(|(name, channel)| (name.clone(), channel))
+                .collect(),
+            span: channels.iter().cloned().max().unwrap_or(0),
+            penalty: self.total(channels).soft,
+            violations,
+        }
+    }
+}
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| {
+        eprintln!("usage: frequency <input.json>");
+        process::exit(2);
+    });
+
+    if let Err(e) = run(&path) {
+        eprintln!("{}: {}", path, e);
+        process::exit(1);
+    }
+}
+
+fn run(path: &str) -> io::Result<()> {
+    let input: Input = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    if input.channels == 0 {
+        return Err(invalid("no channels"));
+    }
+
+    let problem = Problem::new(&input)?;
+    let deadline = Instant::now() + Duration::from_millis(input.budget_ms);
+    let channels = problem.search(deadline, &mut rand::thread_rng());
+
+    let output = problem.report(&input, &channels);
+    serde_json::to_writer_pretty(io::stdout(), &output)?;
+    println!();
+
+    Ok(())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
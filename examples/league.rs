@@ -0,0 +1,235 @@
+//! Sports league scheduling: plays a single round robin on a limited number of
+//! pitches per round, and prints the schedule as CSV or as an iCalendar file.
+//!
+//! ```
+//! cargo run --release --example league -- [--teams N] [--pitches 3,3,2] [--ics]
+//! ```
+//!
+//! Teams are the vertices and meetings the edges of a complete graph. A round is a
+//! color of an edge coloring, as no team plays twice in a round, which is found as
+//! a vertex coloring of the line graph of meetings. ```capacitated_coloring``` limits
+//! every round to its number of pitches, given as a list repeated over the rounds.
+//! The fewest rounds allowed by the pitches and teams are tried first, then one
+//! more at a time. Rounds are extracted with ```color_classes``` and played weekly.
+
+use std::env;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use graml::coloring::{
+    capacitated_coloring, check_capacities, check_edge_coloring, color_classes, Coloring,
+};
+use graml::graph::*;
+
+/// Day of the first round, 2026-01-03, counted from 1970-01-01.
+const FIRST_DAY: i64 = 20456;
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let arg = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .map(|i| args.get(i + 1).cloned().unwrap_or_default())
+    };
+
+    let ics = args.iter().any(|a| a == "--ics");
+    let teams = arg("--teams").map_or(Ok(8), |t| t.parse::<usize>());
+    let pitches = arg("--pitches").map_or(Ok(vec![3]), |p| {
+        p.split(',')
+            .map(str::parse::<usize>)
+            .collect::<Result<Vec<_>, _>>()
+    });
+    let (teams, pitches) = match (teams, pitches) {
+        (Ok(t), Ok(p)) if t >= 2 && !p.is_empty() && p.iter().all(|&p| p > 0) => (t, p),
+        _ => {
+            eprintln!("usage: league [--teams N] [--pitches P1,P2,...] [--ics]");
+            process::exit(2);
+        }
+    };
+
+    let mut league = AdjList::with_capacity(teams);
+    for a in 0..teams {
+        for b in a + 1..teams {
+            league.add_edge(a, b);
+        }
+    }
+    let meetings = league.edges().collect::<Vec<_>>();
+    let (rounds, capacities) = schedule(&league, &meetings, &pitches);
+
+    let names = (1..=teams)
+        .map(|t| format!("Team {}", t))
+        .collect::<Vec<_>>();
+    let classes = color_classes(&rounds)
+        .into_iter()
+        .filter(|class| !class.is_empty())
+        .collect::<Vec<_>>();
+    eprintln!(
+        "{} meetings in {} rounds on at most {} pitches",
+        meetings.len(),
+        classes.len(),
+        capacities.iter().max().unwrap()
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    if ics {
+        println!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//graml//league//EN\r");
+    } else {
+        println!("round,date,pitch,home,away");
+    }
+    for (round, class) in classes.iter().enumerate() {
+        let date = date(FIRST_DAY + 7 * round as i64);
+        for (pitch, &m) in class.iter().enumerate() {
+            let (home, away) = home_away(meetings[m]);
+            let (home, away) = (&names[home], &names[away]);
+
+            if ics {
+                println!("BEGIN:VEVENT\r");
+                println!("UID:league-{}-{}@graml\r", round + 1, pitch + 1);
+                println!("DTSTAMP:{}\r", timestamp(now));
+                println!("DTSTART;VALUE=DATE:{}\r", date.replace('-', ""));
+                println!("SUMMARY:{} - {}\r", home, away);
+                println!("LOCATION:Pitch {}\r", pitch + 1);
+                println!("END:VEVENT\r");
+            } else {
+                println!("{},{},{},{},{}", round + 1, date, pitch + 1, home, away);
+            }
+        }
+    }
+    if ics {
+        println!("END:VCALENDAR\r");
+    }
+}
+
+/// Assigns every meeting a round, returning the rounds and the pitches of each round.
+fn schedule(
+    league: &AdjList,
+    meetings: &[(usize, usize)],
+    pitches: &[usize],
+) -> (Coloring, Vec<usize>) {
+    let line = line_graph(league.num_vertices(), meetings);
+    let sizes = vec![1; meetings.len()];
+
+    // Every team plays once per round, and the pitches must hold all meetings
+    let most = pitches.iter().max().unwrap();
+    let mut count = league
+        .max_degree()
+        .max(meetings.len().div_ceil(*most))
+        .max(1);
+
+    loop {
+        let capacities = (0..count)
+            .map(|r| pitches[r % pitches.len()])
+            .collect::<Vec<_>>();
+
+        if let Some(rounds) = capacitated_coloring(&line, &sizes, &capacities) {
+            assert!(check_capacities(&line, &rounds, &sizes, &capacities));
+            assert!(check_edge_coloring(league, &rounds));
+            return (rounds, capacities);
+        }
+        count += 1;
+    }
+}
+
+/// Graph whose vertices are the meetings, adjacent if they share a team.
+fn line_graph(teams: usize, meetings: &[(usize, usize)]) -> AdjList {
+    let mut incident = vec![Vec::new(); teams];
+    for (m, &(a, b)) in meetings.iter().enumerate() {
+        incident[a].push(m);
+        incident[b].push(m);
+    }
+
+    let mut line = AdjList::with_capacity(meetings.len());
+    for ms in &incident {
+        for (i, &m) in ms.iter().enumerate() {
+            for &k in &ms[i + 1..] {
+                line.add_edge(m, k);
+            }
+        }
+    }
+
+    line
+}
+
+/// Alternates home games between the smaller and the larger team of a meeting.
+fn home_away((a, b): (usize, usize)) -> (usize, usize) {
+    if (a + b) % 2 == 0 {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Formats a day counted from 1970-01-01 as ```YYYY-MM-DD``` in the proleptic
+/// Gregorian calendar.
+fn date(days: i64) -> String {
+    // Days since 0000-03-01, in eras of 400 years
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Formats seconds since 1970-01-01 as an iCalendar UTC date-time,
+/// ```YYYYMMDDTHHMMSSZ```.
+fn timestamp(secs: i64) -> String {
+    let time = secs.rem_euclid(86_400);
+    format!(
+        "{}T{:02}{:02}{:02}Z",
+        date(secs.div_euclid(86_400)).replace('-', ""),
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_valid() {
+        for &(teams, ref pitches) in &[(8, vec![3]), (7, vec![3, 3, 2]), (2, vec![1])] {
+            let mut league = AdjList::with_capacity(teams);
+            for a in 0..teams {
+                for b in a + 1..teams {
+                    league.add_edge(a, b);
+                }
+            }
+            let meetings = league.edges().collect::<Vec<_>>();
+            let (rounds, capacities) = schedule(&league, &meetings, pitches);
+
+            assert_eq!(rounds.len(), teams * (teams - 1) / 2);
+            for (r, class) in color_classes(&rounds).iter().enumerate() {
+                assert!(class.len() <= capacities[r]);
+                assert!(class.len() <= pitches[r % pitches.len()]);
+
+                let mut playing = class
+                    .iter()
+                    .flat_map(|&m| vec![meetings[m].0, meetings[m].1])
+                    .collect::<Vec<_>>();
+                playing.sort_unstable();
+                playing.dedup();
+                assert_eq!(playing.len(), 2 * class.len());
+            }
+        }
+    }
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(FIRST_DAY), "2026-01-03");
+        assert_eq!(date(FIRST_DAY + 7 * 9), "2026-03-07");
+        assert_eq!(timestamp(0), "19700101T000000Z");
+        assert_eq!(timestamp(FIRST_DAY * 86_400 + 45_296), "20260103T123456Z");
+    }
+}
@@ -0,0 +1,212 @@
+//! Soak test running the coloring algorithms on an endless stream of random graphs.
+//!
+//! Every graph is generated from its own seed, colored with all algorithms and the
+//! results are checked against invariants that must hold for any graph. Violations
+//! and panics are reported with the seed, which reproduces the graph with
+//! ```--seed <seed> --count 1```.
+//!
+//! ```
+//! cargo run --release --example soak -- [--seconds S] [--seed N] [--count C]
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::time::{Duration, Instant};
+
+use graml::analysis::degeneracy;
+use graml::coloring::metaheuristics::{genetic_coloring_with, GeneticParams, Monitor};
+use graml::coloring::reductions::with_reductions;
+use graml::coloring::*;
+use graml::graph::*;
+
+/// Largest graphs colored exactly.
+const EXACT_VERTICES: usize = 14;
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let arg = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .map(|i| args.get(i + 1).and_then(|v| v.parse::<u64>().ok()))
+            .map(|v| v.unwrap_or_else(|| panic!("{} requires a number", name)))
+    };
+
+    let seconds = arg("--seconds").map(Duration::from_secs);
+    let first = arg("--seed").unwrap_or_else(rand::random);
+    let count = arg("--count").unwrap_or(u64::MAX);
+
+    // Reported with the seed instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let mut failures = 0;
+    let mut graphs = 0;
+
+    for seed in (first..).take(count as usize) {
+        if seconds.is_some_and(|s| start.elapsed() >= s) {
+            break;
+        }
+
+        let violations = match panic::catch_unwind(AssertUnwindSafe(|| soak(seed))) {
+            Ok(violations) => violations,
+            Err(e) => vec![format!(
+                "panic: {}",
+                e.downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| e.downcast_ref::<&str>().cloned())
+                    .unwrap_or("unknown")
+            )],
+        };
+
+        for violation in &violations {
+            println!("seed {}: {}", seed, violation);
+        }
+        failures += violations.len().min(1);
+        graphs += 1;
+
+        if graphs % 100 == 0 {
+            println!("{} graphs, {} failed", graphs, failures);
+        }
+    }
+
+    println!("{} graphs, {} failed", graphs, failures);
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+/// Generates the graph of ```seed```, colors it and returns the violated invariants.
+fn soak(seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (model, g, planted) = generate(&mut rng);
+    let mut violations = Vec::new();
+    let mut check = |ok: bool, what: String| {
+        if !ok {
+            violations.push(format!("{} (n = {}) {}", model, g.num_vertices(), what));
+        }
+    };
+
+    let params = GeneticParams {
+        generations: 5,
+        seed: Some(seed),
+        ..GeneticParams::default()
+    };
+
+    let mut colorings = vec![
+        ("rs", rs_coloring(&g)),
+        ("cs", cs_coloring(&g)),
+        ("lf", lf_coloring(&g)),
+        ("sl", sl_coloring(&g)),
+        ("sdo", sdo_coloring(&g)),
+        ("balanced", color_balanced(&g)),
+        (
+            "genetic",
+            genetic_coloring_with(&g, &params, &mut Monitor::new()),
+        ),
+        (
+            "sampled",
+            sampled_coloring(&g, g.num_vertices() / 4, &mut rng),
+        ),
+        ("reduced", with_reductions(&g, sdo_coloring)),
+    ];
+    colorings.extend(planar_coloring(&g).map(|c| ("planar", c)));
+    colorings.extend(treewidth_coloring(&g, 6).map(|c| ("treewidth", c)));
+
+    let (lower, upper) = chromatic_bounds(&g);
+    let max_degree = g.max_degree();
+    let k = degeneracy(&g);
+
+    for (name, c) in &colorings {
+        let colors = num_colors(c);
+
+        check(check_coloring(&g, c), format!("{} coloring invalid", name));
+        check(colors >= lower, format!("{} below clique bound", name));
+        check(
+            colors >= planted.unwrap_or(0),
+            format!("{} below planted", name),
+        );
+        check(
+            colors <= max_degree + 1,
+            format!("{} above max degree + 1", name),
+        );
+    }
+    check(lower <= upper, "bounds crossed".to_string());
+
+    let sl = num_colors(&colorings[3].1);
+    check(
+        sl <= k + 1,
+        format!("sl uses {} colors, degeneracy {}", sl, k),
+    );
+
+    if g.num_vertices() <= EXACT_VERTICES {
+        let chi = num_colors(&exact_coloring(&g));
+
+        check(
+            lower <= chi && chi <= upper,
+            "chromatic number outside bounds".to_string(),
+        );
+        check(
+            two_coloring(&g).is_some() == (chi <= 2),
+            "two coloring wrong".to_string(),
+        );
+        for (name, c) in &colorings {
+            check(
+                num_colors(c) >= chi,
+                format!("{} below chromatic number", name),
+            );
+        }
+    }
+
+    violations
+}
+
+/// Generates a graph from a random model and size. Returns the name of the model,
+/// the graph and the chromatic number if it is known by construction.
+fn generate(rng: &mut StdRng) -> (&'static str, AdjList, Option<usize>) {
+    let n = match rng.gen_range(0, 4) {
+        0 => rng.gen_range(0, EXACT_VERTICES + 1),
+        1 => rng.gen_range(10, 100),
+        _ => rng.gen_range(100, 400),
+    };
+
+    match rng.gen_range(0, 5) {
+        0 => {
+            let p = rng.gen::<f32>();
+            ("erdos-renyi", AdjList::random_with(n, |_, _| p, rng), None)
+        }
+        1 => {
+            let blocks = rng.gen_range(1, 8);
+            let sizes = vec![n / blocks + 1; blocks];
+            let p_out = rng.gen::<f32>();
+            let g = stochastic_block_model(&sizes, 0.0, p_out, rng);
+            ("block model", g, None)
+        }
+        2 if n >= 2 => {
+            let k = rng.gen_range(2, n.min(20) + 1);
+            let planted = leighton::<AdjList, _>(n, k, n * rng.gen_range(1, 10), rng);
+            ("leighton", planted.graph, Some(k))
+        }
+        3 if n >= 2 => {
+            let k = rng.gen_range(2, n.min(20) + 1);
+            let planted = flat::<AdjList, _>(n, k, n * rng.gen_range(1, 10), rng);
+            ("flat", planted.graph, None)
+        }
+        _ => {
+            // Grid, planar and bipartite
+            let w = (n as f64).sqrt() as usize + 1;
+            let mut g = AdjList::with_capacity(w * w);
+            for v in 0..w * w {
+                if v % w + 1 < w {
+                    g.add_edge(v, v + 1);
+                }
+                if v + w < w * w {
+                    g.add_edge(v, v + w);
+                }
+            }
+            ("grid", g, Some(2.min(w * w)))
+        }
+    }
+}
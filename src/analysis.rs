@@ -0,0 +1,620 @@
+//! Structural features of graph instances.
+//!
+//! Features describe an instance independently of any coloring and are used to
+//! predict which coloring methods work well on it.
+//!
+//! Community structure, found by label propagation, predicts whether heuristics
+//! coloring dense clusters first beat the purely degree based ones.
+//!
+//! Invariants needed repeatedly can be cached with the graph in a ```Cached```
+//! wrapper, which recomputes them lazily after the graph changes. Colorings,
+//! bounds and features of a cached graph take the invariants from the cache.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::coloring::exact::grow_clique;
+use crate::graph::{GraphObserver, Observed, StaticGraph};
+
+/// A single instance feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Feature {
+    Vertices,
+    Edges,
+    Density,
+    MaxDegree,
+    AvgDegree,
+    Degeneracy,
+    Assortativity,
+    Communities,
+    Modularity,
+}
+
+/// Rounds after which ```label_propagation``` stops even if labels still change.
+const MAX_ROUNDS: usize = 100;
+
+/// Features of a graph instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Features {
+    pub vertices: usize,
+    pub edges: usize,
+    /// Fraction of all possible edges present in the graph.
+    pub density: f64,
+    pub max_degree: usize,
+    pub avg_degree: f64,
+    /// Smallest ```k``` such that every subgraph has a vertex of degree at most ```k```.
+    pub degeneracy: usize,
+    /// Correlation of the degrees at both ends of the edges, see ```degree_assortativity```.
+    #[serde(default)]
+    pub assortativity: f64,
+    /// Number of communities found by ```communities```.
+    #[serde(default)]
+    pub communities: usize,
+    /// Modularity of these communities.
+    #[serde(default)]
+    pub modularity: f64,
+}
+
+impl Features {
+    /// Computes the features of the graph.
+    /// Invariants cached with the graph are used instead of recomputed.
+    pub fn new<G: StaticGraph + ?Sized>(g: &G) -> Self {
+        let communities = communities(g);
+        let n = g.num_vertices();
+        let m = g.edges().count();
+
+        let density = if n < 2 {
+            0.0
+        } else {
+            2.0 * m as f64 / (n * (n - 1)) as f64
+        };
+
+        let avg_degree = if n == 0 {
+            0.0
+        } else {
+            2.0 * m as f64 / n as f64
+        };
+
+        Self {
+            vertices: n,
+            edges: m,
+            density,
+            max_degree: g.max_degree(),
+            avg_degree,
+            degeneracy: degeneracy(g),
+            assortativity: degree_assortativity(g),
+            communities: communities.iter().map(|&c| c + 1).max().unwrap_or(0),
+            modularity: modularity(g, &communities),
+        }
+    }
+
+    /// Returns the value of a single feature.
+    pub fn get(&self, feature: Feature) -> f64 {
+        match feature {
+            Feature::Vertices => self.vertices as f64,
+            Feature::Edges => self.edges as f64,
+            Feature::Density => self.density,
+            Feature::MaxDegree => self.max_degree as f64,
+            Feature::AvgDegree => self.avg_degree,
+            Feature::Degeneracy => self.degeneracy as f64,
+            Feature::Assortativity => self.assortativity,
+            Feature::Communities => self.communities as f64,
+            Feature::Modularity => self.modularity,
+        }
+    }
+}
+
+/// Returns the degeneracy of the graph.
+/// Computed by repeatedly removing a vertex of minimum degree, using buckets of
+/// vertices by their remaining degree.
+pub fn degeneracy<G: StaticGraph + ?Sized>(g: &G) -> usize {
+    match g.invariants() {
+        Some(invariants) => invariants.degeneracy(g),
+        None => degeneracy_of(g),
+    }
+}
+
+fn degeneracy_of<G: StaticGraph + ?Sized>(g: &G) -> usize {
+    let n = g.num_vertices();
+    let mut degree = degrees(g).into_owned();
+    let max_degree = degree.iter().cloned().max().unwrap_or(0);
+
+    let mut buckets = vec![Vec::new(); max_degree + 1];
+    for v in 0..n {
+        buckets[degree[v]].push(v);
+    }
+
+    let mut removed = vec![false; n];
+    let mut k = 0;
+    let mut d = 0;
+
+    for _ in 0..n {
+        // Buckets may contain stale entries of vertices whose degree has decreased
+        let v = loop {
+            match buckets[d].pop() {
+                Some(v) if !removed[v] && degree[v] == d => break v,
+                Some(_) => continue,
+                None => d += 1,
+            }
+        };
+
+        k = k.max(d);
+        removed[v] = true;
+
+        for u in g.neighbors(v) {
+            if !removed[u] {
+                degree[u] -= 1;
+                buckets[degree[u]].push(u);
+            }
+        }
+
+        // Degree of neighbors decreased by at most one
+        d = d.saturating_sub(1);
+    }
+
+    k
+}
+
+/// Returns the degree assortativity, the Pearson correlation of the degrees at both
+/// ends of the edges. Positive if high degree vertices tend to be adjacent to each
+/// other, negative if they tend to be adjacent to low degree vertices. Zero if the
+/// graph has no edges or all edges join vertices of the same degrees.
+pub fn degree_assortativity<G: StaticGraph + ?Sized>(g: &G) -> f64 {
+    let degree = degrees(g).iter().map(|&d| d as f64).collect::<Vec<_>>();
+
+    // Sums over both orientations of every edge
+    let (mut m, mut sum, mut squares, mut products) = (0.0, 0.0, 0.0, 0.0);
+    for (u, v) in g.edges() {
+        let (a, b) = (degree[u], degree[v]);
+        m += 1.0;
+        sum += a + b;
+        squares += a * a + b * b;
+        products += 2.0 * a * b;
+    }
+    if m == 0.0 {
+        return 0.0;
+    }
+
+    let mean = sum / (2.0 * m);
+    let variance = squares / (2.0 * m) - mean * mean;
+    if variance <= f64::EPSILON * mean * mean {
+        return 0.0;
+    }
+
+    (products / (2.0 * m) - mean * mean) / variance
+}
+
+/// Returns the community of every vertex, found by label propagation with a fixed
+/// seed, such that the result only depends on the graph. Communities are numbered
+/// by their smallest vertex.
+pub fn communities<G: StaticGraph + ?Sized>(g: &G) -> Vec<usize> {
+    match g.invariants() {
+        Some(invariants) => invariants.communities(g).to_vec(),
+        None => label_propagation(g, &mut StdRng::seed_from_u64(0)),
+    }
+}
+
+/// Detects communities by label propagation. Every vertex starts with its own
+/// label and repeatedly, in random order, adopts the label most frequent among its
+/// neighbors, until no label changes. Runs in ```O(m)``` per round and usually
+/// converges within a few rounds. Communities are numbered by their smallest vertex.
+pub fn label_propagation<G: StaticGraph + ?Sized, R: Rng>(g: &G, rng: &mut R) -> Vec<usize> {
+    let n = g.num_vertices();
+    let mut label = (0..n).collect::<Vec<_>>();
+    let mut order = label.clone();
+    let mut counts = HashMap::new();
+    let mut best = Vec::new();
+
+    for _ in 0..MAX_ROUNDS {
+        order.shuffle(rng);
+        let mut changed = false;
+
+        for &v in &order {
+            counts.clear();
+            for u in g.neighbors(v) {
+                *counts.entry(label[u]).or_insert(0usize) += 1;
+            }
+            let most = match counts.values().max() {
+                Some(&most) => most,
+                None => continue,
+            };
+
+            // Keep the current label among the most frequent to converge
+            if counts.get(&label[v]) == Some(&most) {
+                continue;
+            }
+            best.clear();
+            best.extend(counts.iter().filter(|&(_, &c)| c == most).map(|(&l, _)| l));
+            best.sort_unstable();
+            label[v] = best[rng.gen_range(0, best.len())];
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    // Renumber by smallest vertex
+    let mut index = HashMap::new();
+    label
+        .iter()
+        .map(|&l| {
+            let next = index.len();
+            *index.entry(l).or_insert(next)
+        })
+        .collect()
+}
+
+/// Returns the modularity of a partition of the vertices into communities, the
+/// fraction of edges within communities minus the fraction expected if edges were
+/// placed at random with the same degrees. Between -1/2 and 1, zero without edges.
+pub fn modularity<G: StaticGraph + ?Sized>(g: &G, communities: &[usize]) -> f64 {
+    let k = communities.iter().map(|&c| c + 1).max().unwrap_or(0);
+    let mut inside = vec![0usize; k];
+    let mut degrees = vec![0usize; k];
+
+    let mut m = 0;
+    for (u, v) in g.edges() {
+        m += 1;
+        if communities[u] == communities[v] {
+            inside[communities[u]] += 1;
+        }
+        degrees[communities[u]] += 1;
+        degrees[communities[v]] += 1;
+    }
+    if m == 0 {
+        return 0.0;
+    }
+
+    let m = m as f64;
+    (0..k)
+        .map(|c| inside[c] as f64 / m - (degrees[c] as f64 / (2.0 * m)).powi(2))
+        .sum()
+}
+
+/// Graph with lazily computed invariants, see ```Invariants```.
+pub type Cached<G> = Observed<G, Invariants>;
+
+/// Invariants of a graph, each computed on first use and memoized.
+/// Attached to a graph in a ```Cached``` wrapper, all of them are invalidated
+/// when an edge is added through the wrapper. Algorithms find them through
+/// ```StaticGraph::invariants```, such that coloring or bounding a ```Cached```
+/// graph repeatedly computes every invariant only once.
+#[derive(Debug, Clone, Default)]
+pub struct Invariants {
+    degrees: OnceLock<Vec<usize>>,
+    degeneracy: OnceLock<usize>,
+    components: OnceLock<(Vec<usize>, usize)>,
+    clique: OnceLock<Vec<usize>>,
+    communities: OnceLock<Vec<usize>>,
+}
+
+impl Invariants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The graph passed to the accessors below has to be the graph the
+    // invariants belong to.
+
+    pub(crate) fn degrees<G: StaticGraph + ?Sized>(&self, g: &G) -> &[usize] {
+        self.degrees
+            .get_or_init(|| g.vertices().map(|v| g.neighbors(v).count()).collect())
+    }
+
+    pub(crate) fn max_degree<G: StaticGraph + ?Sized>(&self, g: &G) -> usize {
+        self.degrees(g).iter().cloned().max().unwrap_or(0)
+    }
+
+    pub(crate) fn degeneracy<G: StaticGraph + ?Sized>(&self, g: &G) -> usize {
+        *self.degeneracy.get_or_init(|| degeneracy_of(g))
+    }
+
+    pub(crate) fn clique<G: StaticGraph + ?Sized>(&self, g: &G) -> &[usize] {
+        self.clique.get_or_init(|| grow_clique(g))
+    }
+
+    pub(crate) fn communities<G: StaticGraph + ?Sized>(&self, g: &G) -> &[usize] {
+        self.communities
+            .get_or_init(|| label_propagation(g, &mut StdRng::seed_from_u64(0)))
+    }
+
+    fn component_labels<G: StaticGraph + ?Sized>(&self, g: &G) -> &(Vec<usize>, usize) {
+        self.components.get_or_init(|| {
+            let n = g.num_vertices();
+            let mut component = vec![usize::MAX; n];
+            let mut stack = Vec::new();
+            let mut count = 0;
+
+            for first in 0..n {
+                if component[first] != usize::MAX {
+                    continue;
+                }
+
+                component[first] = count;
+                stack.push(first);
+                while let Some(v) = stack.pop() {
+                    for u in g.neighbors(v) {
+                        if component[u] == usize::MAX {
+                            component[u] = count;
+                            stack.push(u);
+                        }
+                    }
+                }
+                count += 1;
+            }
+
+            (component, count)
+        })
+    }
+}
+
+impl GraphObserver for Invariants {
+    fn on_edge_added(&mut self, _: usize, _: usize) {
+        *self = Self::default();
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        Some(self)
+    }
+}
+
+impl<G: StaticGraph> Cached<G> {
+    /// Attaches an empty invariant cache to ```graph```.
+    pub fn cached(graph: G) -> Self {
+        Self::new(graph, Invariants::new())
+    }
+
+    /// Returns the degree of every vertex.
+    pub fn degrees(&self) -> &[usize] {
+        self.observer().degrees(self)
+    }
+
+    /// Returns the maximum degree.
+    pub fn max_degree(&self) -> usize {
+        self.observer().max_degree(self)
+    }
+
+    /// Returns the degeneracy, see ```degeneracy```.
+    pub fn degeneracy(&self) -> usize {
+        self.observer().degeneracy(self)
+    }
+
+    /// Returns the index of the connected component of every vertex.
+    /// Components are numbered by their smallest vertex.
+    pub fn components(&self) -> &[usize] {
+        &self.observer().component_labels(self).0
+    }
+
+    /// Returns the number of connected components.
+    pub fn num_components(&self) -> usize {
+        self.observer().component_labels(self).1
+    }
+
+    /// Returns the size of a greedily found clique, a lower bound on the chromatic number.
+    pub fn clique_bound(&self) -> usize {
+        self.observer().clique(self).len()
+    }
+
+    /// Returns the community of every vertex, see ```communities```.
+    pub fn communities(&self) -> &[usize] {
+        self.observer().communities(self)
+    }
+
+    /// Returns the features of the graph, using the cached invariants.
+    pub fn features(&self) -> Features {
+        Features::new(self)
+    }
+}
+
+/// Returns the degree of every vertex, borrowed from the cached invariants if
+/// the graph has them.
+pub(crate) fn degrees<G: StaticGraph + ?Sized>(g: &G) -> Cow<'_, [usize]> {
+    match g.invariants() {
+        Some(invariants) => Cow::Borrowed(invariants.degrees(g)),
+        None => Cow::Owned(g.vertices().map(|v| g.neighbors(v).count()).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::exact::greedy_clique;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn degeneracy_complete() {
+        let g = AdjList::complete(10);
+
+        assert_eq!(degeneracy(&g), 9);
+    }
+
+    #[test]
+    fn degeneracy_tree() {
+        let mut g = AdjList::new();
+
+        for i in 0..63 {
+            g.add_edge(i, 2 * i + 1);
+            g.add_edge(i, 2 * i + 2);
+        }
+
+        assert_eq!(degeneracy(&g), 1);
+    }
+
+    #[test]
+    fn degeneracy_cycle_with_isolated() {
+        let mut g = AdjList::with_capacity(12);
+
+        for i in 0..10 {
+            g.add_edge(i, (i + 1) % 10);
+        }
+
+        assert_eq!(degeneracy(&g), 2);
+    }
+
+    #[test]
+    fn features_complete() {
+        let g = AdjMatrix::complete(20);
+        let f = Features::new(&g);
+
+        assert_eq!(f.vertices, 20);
+        assert_eq!(f.edges, 190);
+        assert_eq!(f.density, 1.0);
+        assert_eq!(f.max_degree, 19);
+        assert_eq!(f.avg_degree, 19.0);
+        assert_eq!(f.degeneracy, 19);
+        assert_eq!(f.assortativity, 0.0);
+        assert_eq!(f.communities, 1);
+        assert_eq!(f.modularity, 0.0);
+        assert_eq!(f.get(Feature::Density), 1.0);
+    }
+
+    #[test]
+    fn assortativity_star() {
+        let mut g = AdjList::new();
+        for i in 1..5 {
+            g.add_edge(0, i);
+        }
+        assert!((degree_assortativity(&g) + 1.0).abs() < 1e-9);
+
+        // Two stars with joined centers
+        for i in 6..10 {
+            g.add_edge(5, i);
+        }
+        g.add_edge(0, 5);
+        assert!(degree_assortativity(&g) < 0.0);
+        assert_eq!(degree_assortativity(&AdjList::new()), 0.0);
+    }
+
+    #[test]
+    fn communities_joined_cliques() {
+        // Two cliques of five joined by a single edge
+        let mut g = AdjList::new();
+        for u in 0..10 {
+            for v in (u + 1..10).filter(|v| v / 5 == u / 5) {
+                g.add_edge(u, v);
+            }
+        }
+        g.add_edge(4, 5);
+
+        let c = communities(&g);
+        assert_eq!(c, vec![0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+        assert!((modularity(&g, &c) - 2.0 * (10.0 / 21.0 - 0.25)).abs() < 1e-9);
+        assert_eq!(modularity(&g, &[0; 10]), 0.0);
+
+        let f = Features::new(&g);
+        assert_eq!(f.communities, 2);
+        assert_eq!(f.get(Feature::Modularity), modularity(&g, &c));
+    }
+
+    #[test]
+    fn features_empty() {
+        let g = AdjList::new();
+        let f = Features::new(&g);
+
+        assert_eq!(f.vertices, 0);
+        assert_eq!(f.density, 0.0);
+        assert_eq!(f.degeneracy, 0);
+        assert_eq!(f.communities, 0);
+    }
+
+    #[test]
+    fn cached_invalidated() {
+        let mut g = Cached::cached(AdjList::with_capacity(6));
+        g.add_edge(0, 1);
+        g.add_edge(2, 3);
+
+        assert_eq!(g.num_components(), 4);
+        assert_eq!(g.components(), &[0, 0, 1, 1, 2, 3]);
+        assert_eq!(g.degeneracy(), 1);
+        assert_eq!(g.clique_bound(), 2);
+
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+
+        assert_eq!(g.num_components(), 3);
+        assert_eq!(g.degrees(), &[2, 2, 3, 1, 0, 0]);
+        assert_eq!(g.degeneracy(), 2);
+        assert_eq!(g.clique_bound(), 3);
+        assert_eq!(g.features(), Features::new(g.graph()));
+    }
+
+    /// Graph counting how often the neighbors of a vertex are listed.
+    struct Counting {
+        graph: AdjList,
+        calls: Cell<usize>,
+    }
+
+    impl StaticGraph for Counting {
+        fn with_capacity(n: usize) -> Self {
+            Self::from_graph(&AdjList::with_capacity(n))
+        }
+
+        fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+            Counting {
+                graph: AdjList::from_graph(graph),
+                calls: Cell::new(0),
+            }
+        }
+
+        fn has_edge(&self, u: usize, v: usize) -> bool {
+            self.graph.has_edge(u, v)
+        }
+
+        fn add_edge(&mut self, u: usize, v: usize) {
+            self.graph.add_edge(u, v);
+        }
+
+        fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
+            self.graph.edges()
+        }
+
+        fn num_vertices(&self) -> usize {
+            self.graph.num_vertices()
+        }
+
+        fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
+            self.calls.set(self.calls.get() + 1);
+            self.graph.neighbors(v)
+        }
+    }
+
+    #[test]
+    fn cached_computed_once() {
+        let base = AdjList::random_with(60, |_, _| 0.3, &mut StdRng::seed_from_u64(7));
+        let g = Cached::cached(Counting::from_graph(&base));
+        let calls = || g.graph().calls.replace(0);
+
+        // Computes the degrees and the clique
+        let (lower, upper) = chromatic_bounds(&g);
+        let first = calls();
+        assert_eq!(chromatic_bounds(&g), (lower, upper));
+        let second = calls();
+        assert_eq!(first - second, 60);
+
+        let features = Features::new(&g);
+        assert!(calls() > 0);
+        assert_eq!(Features::new(&g), features);
+        assert_eq!(g.features(), features);
+        assert!(check_coloring(&g, &color_detailed(&g).coloring));
+        calls();
+
+        // Everything is cached now
+        assert_eq!(degeneracy(&g), features.degeneracy);
+        assert_eq!(g.max_degree(), features.max_degree);
+        assert_eq!(greedy_clique(&g).len(), lower);
+        assert_eq!(communities(&g).len(), 60);
+        assert_eq!(calls(), 0);
+
+        assert_eq!(Features::new(g.graph()), features);
+        assert!(calls() > 0);
+    }
+}
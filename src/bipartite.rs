@@ -0,0 +1,288 @@
+//! Algorithms specific to bipartite graphs.
+//!
+//! All functions return ```None``` for graphs which are not bipartite, as detected
+//! by ```two_coloring```. Its colors are the sides of the graph, side ```0``` is
+//! called left and side ```1``` right.
+
+use std::collections::VecDeque;
+
+use crate::coloring::two_coloring;
+use crate::graph::StaticGraph;
+
+/// Returns a maximum matching of the graph, where ```mate[v]``` is the vertex
+/// matched to ```v```, if any.
+///
+/// Hopcroft-Karp algorithm: each phase finds the length of shortest augmenting
+/// paths by breadth first search from the free left vertices, then augments along
+/// a maximal set of disjoint shortest paths by depth first search. Takes
+/// ```O(m sqrt(n))``` time.
+pub fn maximum_matching<G: StaticGraph + ?Sized>(graph: &G) -> Option<Vec<Option<usize>>> {
+    let side = two_coloring(graph)?;
+    let adj = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).collect())
+        .collect::<Vec<_>>();
+
+    Some(hopcroft_karp(&adj, &side))
+}
+
+/// Returns a minimum vertex cover of the graph, a smallest set of vertices
+/// containing an endpoint of every edge, in increasing order.
+///
+/// By König's theorem it has the size of a maximum matching. Left vertices are
+/// part of the cover unless reachable from a free left vertex by a path
+/// alternating between unmatched and matched edges, right vertices if they are.
+pub fn minimum_vertex_cover<G: StaticGraph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    let side = two_coloring(graph)?;
+    let adj = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).collect())
+        .collect::<Vec<_>>();
+    let mate = hopcroft_karp(&adj, &side);
+
+    let n = adj.len();
+    let mut reached = vec![false; n];
+    let mut queue = (0..n)
+        .filter(|&v| side[v] == 0 && mate[v].is_none())
+        .collect::<VecDeque<_>>();
+    for &v in &queue {
+        reached[v] = true;
+    }
+
+    while let Some(u) = queue.pop_front() {
+        for &v in &adj[u] {
+            if !reached[v] && mate[u] != Some(v) {
+                reached[v] = true;
+                if let Some(w) = mate[v] {
+                    if !reached[w] {
+                        reached[w] = true;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+    }
+
+    Some((0..n).filter(|&v| (side[v] == 0) != reached[v]).collect())
+}
+
+/// Colors the edges of the graph with the maximum degree many colors, such that
+/// edges sharing a vertex have different colors. The color of the ```i```-th edge
+/// of ```graph.edges()``` is at index ```i```.
+///
+/// Every color class is a matching. Edges are added one by one with a color free
+/// at one endpoint. If it is used at the other endpoint, the path alternating
+/// between that color and a color free there is exchanged, which in a bipartite
+/// graph never reaches the first endpoint (König's edge coloring theorem).
+/// Takes ```O(m n)``` time.
+pub fn bipartite_edge_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    two_coloring(graph)?;
+
+    let edges = graph.edges().collect::<Vec<_>>();
+    let delta = graph.max_degree();
+
+    // Neighbor and edge of every vertex along every color
+    let mut at = vec![vec![None; delta]; graph.num_vertices()];
+    let mut colors = vec![0; edges.len()];
+
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        let free = |x: usize, at: &[Vec<Option<(usize, usize)>>]| {
+            (0..delta).find(|&c| at[x][c].is_none()).unwrap()
+        };
+        let a = free(u, &at);
+
+        if at[v][a].is_some() {
+            let b = free(v, &at);
+
+            // Path from 'v' alternating between colors 'a' and 'b'
+            let mut path = Vec::new();
+            let (mut x, mut c) = (v, a);
+            while let Some((y, e)) = at[x][c] {
+                path.push((x, y, e, c));
+                x = y;
+                c = if c == a { b } else { a };
+            }
+
+            for &(x, y, _, c) in &path {
+                at[x][c] = None;
+                at[y][c] = None;
+            }
+            for &(x, y, e, c) in &path {
+                let swapped = if c == a { b } else { a };
+                at[x][swapped] = Some((y, e));
+                at[y][swapped] = Some((x, e));
+                colors[e] = swapped;
+            }
+        }
+
+        at[u][a] = Some((v, i));
+        at[v][a] = Some((u, i));
+        colors[i] = a;
+    }
+
+    Some(colors)
+}
+
+/// Maximum matching of a bipartite graph given by adjacency lists and sides.
+pub(crate) fn hopcroft_karp(adj: &[Vec<usize>], side: &[usize]) -> Vec<Option<usize>> {
+    let n = adj.len();
+    let left = (0..n).filter(|&v| side[v] == 0).collect::<Vec<_>>();
+    let mut mate = vec![None; n];
+    let mut dist = vec![usize::MAX; n];
+    let mut next = vec![0; n];
+
+    loop {
+        // Layers of left vertices, up to the first from which a free vertex is reachable
+        let mut queue = VecDeque::new();
+        for &u in &left {
+            dist[u] = if mate[u].is_none() {
+                queue.push_back(u);
+                0
+            } else {
+                usize::MAX
+            };
+        }
+
+        let mut limit = usize::MAX;
+        while let Some(u) = queue.pop_front() {
+            if dist[u] >= limit {
+                break;
+            }
+            for &v in &adj[u] {
+                match mate[v] {
+                    None => limit = limit.min(dist[u]),
+                    Some(w) if dist[w] == usize::MAX => {
+                        dist[w] = dist[u] + 1;
+                        queue.push_back(w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if limit == usize::MAX {
+            return mate;
+        }
+
+        for &u in &left {
+            next[u] = 0;
+        }
+        for &u in &left {
+            if mate[u].is_none() {
+                augment(u, adj, &mut mate, &mut dist, &mut next, limit);
+            }
+        }
+    }
+}
+
+/// Searches a shortest augmenting path from the free left vertex ```root``` along
+/// the layers and flips it. Vertices without a path are removed from the layers.
+fn augment(
+    root: usize,
+    adj: &[Vec<usize>],
+    mate: &mut [Option<usize>],
+    dist: &mut [usize],
+    next: &mut [usize],
+    limit: usize,
+) -> bool {
+    // Left vertices of the path, each reached through the mate of the previous one
+    let mut path = vec![root];
+
+    while let Some(&u) = path.last() {
+        if next[u] == adj[u].len() {
+            dist[u] = usize::MAX;
+            path.pop();
+            continue;
+        }
+
+        let v = adj[u][next[u]];
+        next[u] += 1;
+
+        match mate[v] {
+            None if dist[u] == limit => {
+                // Flip the path, every left vertex takes the right vertex after it
+                let mut v = v;
+                for &u in path.iter().rev() {
+                    let previous = mate[u];
+                    mate[u] = Some(v);
+                    mate[v] = Some(u);
+                    v = match previous {
+                        Some(p) => p,
+                        None => break,
+                    };
+                }
+                return true;
+            }
+            Some(w) if dist[w] == dist[u] + 1 => path.push(w),
+            _ => {}
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_edge_coloring;
+    use crate::graph::*;
+
+    fn matching_size(mate: &[Option<usize>]) -> usize {
+        mate.iter().filter(|m| m.is_some()).count() / 2
+    }
+
+    #[test]
+    fn edge_coloring_delta() {
+        for _ in 0..20 {
+            let g = AdjList::random_with(
+                40,
+                |u, v| if u < 20 && v >= 20 { 0.3 } else { 0.0 },
+                &mut rand::thread_rng(),
+            );
+
+            let colors = bipartite_edge_coloring(&g).unwrap();
+
+            assert!(check_edge_coloring(&g, &colors));
+            assert!(colors.iter().all(|&c| c < g.max_degree()));
+        }
+
+        assert!(bipartite_edge_coloring(&AdjList::complete(3)).is_none());
+    }
+
+    #[test]
+    fn matching_and_cover() {
+        // Left 0..3, right 3..7, all edges
+        let mut g = AdjList::new();
+        for u in 0..3 {
+            for v in 3..7 {
+                g.add_edge(u, v);
+            }
+        }
+
+        assert_eq!(matching_size(&maximum_matching(&g).unwrap()), 3);
+        assert_eq!(minimum_vertex_cover(&g).unwrap(), vec![0, 1, 2]);
+        assert!(maximum_matching(&AdjList::complete(3)).is_none());
+
+        // Random bipartite graphs, matching and cover have equal size by König's theorem
+        for _ in 0..20 {
+            let g = AdjList::random_with(
+                60,
+                |u, v| if u % 2 != v % 2 { 0.08 } else { 0.0 },
+                &mut rand::thread_rng(),
+            );
+
+            let mate = maximum_matching(&g).unwrap();
+            let cover = minimum_vertex_cover(&g).unwrap();
+
+            for (v, m) in mate.iter().enumerate() {
+                if let Some(u) = *m {
+                    assert!(g.has_edge(u, v) && mate[u] == Some(v));
+                }
+            }
+            assert!(g
+                .edges()
+                .all(|(u, v)| cover.contains(&u) || cover.contains(&v)));
+            assert_eq!(cover.len(), matching_size(&mate));
+        }
+    }
+}
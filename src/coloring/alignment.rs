@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+/// Returns a relabeling of the colors of ```b``` maximizing the number of vertices
+/// on which the relabeled ```b``` agrees with ```a```.
+/// Entry ```c``` is the new label of color ```c``` of ```b```. Classes of ```b``` not matched
+/// to a class of ```a``` receive labels not used by ```a```.
+pub fn align_colorings(a: &[usize], b: &[usize]) -> Vec<usize> {
+    assert_eq!(a.len(), b.len(), "colorings of different graphs");
+
+    let (a_index, a_labels) = compact(a);
+    let (b_index, b_labels) = compact(b);
+    let k = a_labels.len().max(b_labels.len());
+
+    let mut overlap = vec![vec![0; k]; k];
+    for (&x, &y) in a.iter().zip(b) {
+        overlap[b_index[&y]][a_index[&x]] += 1;
+    }
+
+    let assignment = hungarian(&overlap);
+
+    let mut fresh = a_labels.iter().max().map_or(0, |&c| c + 1);
+    let mut relabel = vec![0; b_labels.iter().max().map_or(0, |&c| c + 1)];
+
+    for (i, &c) in b_labels.iter().enumerate() {
+        relabel[c] = match a_labels.get(assignment[i]) {
+            Some(&label) => label,
+            None => {
+                fresh += 1;
+                fresh - 1
+            }
+        };
+    }
+
+    relabel
+}
+
+/// Returns the fraction of vertices on which ```b``` agrees with ```a``` after
+/// relabeling it by ```align_colorings```. It is ```1``` if both colorings
+/// partition the vertices into the same classes.
+pub fn coloring_agreement(a: &[usize], b: &[usize]) -> f64 {
+    if a.is_empty() {
+        return 1.0;
+    }
+
+    (a.len() - coloring_distance(a, b)) as f64 / a.len() as f64
+}
+
+/// Returns the minimum number of vertices of ```b``` which have to change their color
+/// such that it partitions the vertices as ```a``` does.
+pub fn coloring_distance(a: &[usize], b: &[usize]) -> usize {
+    let relabel = align_colorings(a, b);
+
+    a.iter().zip(b).filter(|&(&x, &y)| x != relabel[y]).count()
+}
+
+/// Rand index of the partitions into color classes.
+/// Returns the fraction of vertex pairs which are either in the same class in both
+/// colorings or in different classes in both. Independent of the labels used.
+pub fn rand_index(a: &[usize], b: &[usize]) -> f64 {
+    assert_eq!(a.len(), b.len(), "colorings of different graphs");
+
+    let pairs = |n: usize| (n * n.saturating_sub(1) / 2) as u64;
+
+    let mut joint = HashMap::new();
+    let mut a_sizes = HashMap::new();
+    let mut b_sizes = HashMap::new();
+    for (&x, &y) in a.iter().zip(b) {
+        *joint.entry((x, y)).or_insert(0) += 1;
+        *a_sizes.entry(x).or_insert(0) += 1;
+        *b_sizes.entry(y).or_insert(0) += 1;
+    }
+
+    let total = pairs(a.len());
+    if total == 0 {
+        return 1.0;
+    }
+
+    let both = joint.values().map(|&n| pairs(n)).sum::<u64>();
+    let in_a = a_sizes.values().map(|&n| pairs(n)).sum::<u64>();
+    let in_b = b_sizes.values().map(|&n| pairs(n)).sum::<u64>();
+
+    (total + 2 * both - in_a - in_b) as f64 / total as f64
+}
+
+/// Maps the distinct colors of a coloring to consecutive indices.
+/// Returns the map together with the colors in order of their index.
+fn compact(coloring: &[usize]) -> (HashMap<usize, usize>, Vec<usize>) {
+    let mut labels = coloring.to_vec();
+    labels.sort_unstable();
+    labels.dedup();
+
+    let index = labels.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+    (index, labels)
+}
+
+/// Maximum weight perfect matching in a complete bipartite graph by the Hungarian
+/// method with potentials, in cubic time.
+/// Returns the column assigned to each row of the square weight matrix.
+fn hungarian(weights: &[Vec<i64>]) -> Vec<usize> {
+    let n = weights.len();
+
+    // Minimizes negated weights, rows and columns are 1-based with 0 as sentinel
+    let mut u = vec![0; n + 1];
+    let mut v = vec![0; n + 1];
+    let mut row = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+
+    for i in 1..=n {
+        row[0] = i;
+        let mut j0 = 0;
+        let mut min = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+
+        loop {
+            used[j0] = true;
+            let i0 = row[j0];
+            let mut delta = i64::MAX;
+            let mut j1 = 0;
+
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+
+                let cost = -weights[i0 - 1][j - 1] - u[i0] - v[j];
+                if cost < min[j] {
+                    min[j] = cost;
+                    way[j] = j0;
+                }
+                if min[j] < delta {
+                    delta = min[j];
+                    j1 = j;
+                }
+            }
+
+            for j in 0..=n {
+                if used[j] {
+                    u[row[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min[j] -= delta;
+                }
+            }
+
+            j0 = j1;
+            if row[j0] == 0 {
+                break;
+            }
+        }
+
+        // Augment along the alternating path
+        while j0 != 0 {
+            let j1 = way[j0];
+            row[j0] = row[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=n {
+        assignment[row[j] - 1] = j - 1;
+    }
+
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hungarian_optimal() {
+        let weights = vec![vec![7, 5, 1], vec![8, 2, 0], vec![6, 9, 3]];
+
+        // 7 + 2 + 3 is beaten by 1 + 8 + 9
+        assert_eq!(hungarian(&weights), vec![2, 0, 1]);
+        assert!(hungarian(&[]).is_empty());
+    }
+
+    #[test]
+    fn align_permuted() {
+        let a = vec![0, 0, 1, 1, 2, 2];
+        let b = vec![5, 5, 3, 3, 1, 0];
+
+        let relabel = align_colorings(&a, &b);
+
+        assert_eq!(relabel[5], 0);
+        assert_eq!(relabel[3], 1);
+        assert!(relabel[1] == 2 || relabel[0] == 2);
+        assert!(relabel[1] == 3 || relabel[0] == 3);
+        assert!((coloring_agreement(&a, &b) - 5.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn agreement_bounds() {
+        let a = vec![0, 1, 2, 0, 1, 2];
+        let b = vec![7, 8, 9, 7, 8, 9];
+        let c = vec![0, 0, 0, 1, 1, 1];
+
+        assert_eq!(coloring_agreement(&a, &b), 1.0);
+        assert_eq!(coloring_agreement(&a, &c), 2.0 / 6.0);
+        assert_eq!(coloring_agreement(&[], &[]), 1.0);
+    }
+
+    #[test]
+    fn distance_and_rand_index() {
+        let a = vec![0, 0, 1, 1, 2];
+        let b = vec![4, 4, 3, 2, 2];
+
+        assert_eq!(coloring_distance(&a, &b), 1);
+        assert_eq!(coloring_distance(&a, &a), 0);
+
+        // Pairs (2,3) and (3,4) disagree out of 10
+        assert!((rand_index(&a, &b) - 0.8).abs() < 1e-9);
+        assert_eq!(rand_index(&a, &[9, 9, 7, 7, 8]), 1.0);
+        assert_eq!(rand_index(&[0], &[1]), 1.0);
+    }
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::analysis::{Feature, Features};
+use crate::coloring::metaheuristics::{
+    genetic_coloring_with, GeneticParams, Initialization, Monitor,
+};
+use crate::coloring::{check_coloring, num_colors, Coloring, ColoringAlgo};
+use crate::graph::StaticGraph;
+
+/// Decision tree selecting the most promising coloring methods for an instance
+/// based on its features.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DecisionTree {
+    /// Methods to run, most promising first.
+    Leaf(Vec<ColoringAlgo>),
+    /// Continue in ```below``` if the feature is at most ```threshold```,
+    /// in ```above``` otherwise.
+    Split {
+        feature: Feature,
+        threshold: f64,
+        below: Box<DecisionTree>,
+        above: Box<DecisionTree>,
+    },
+}
+
+impl DecisionTree {
+    /// Returns the methods selected for an instance with the given features.
+    pub fn select(&self, features: &Features) -> &[ColoringAlgo] {
+        match self {
+            DecisionTree::Leaf(algos) => algos,
+            DecisionTree::Split {
+                feature,
+                threshold,
+                below,
+                above,
+            } => {
+                if features.get(*feature) <= *threshold {
+                    below.select(features)
+                } else {
+                    above.select(features)
+                }
+            }
+        }
+    }
+
+    /// Reads a decision tree from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        serde_json::from_reader(file).map_err(io::Error::from)
+    }
+
+    /// Writes the decision tree to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(file, self).map_err(io::Error::from)
+    }
+}
+
+impl Default for DecisionTree {
+    /// Smallest-last works well on sparse graphs, saturation degree ordering on
+    /// dense ones. The latter is quadratic in the number of vertices and thus
+    /// avoided on large instances.
+    fn default() -> Self {
+        DecisionTree::Split {
+            feature: Feature::Density,
+            threshold: 0.1,
+            below: Box::new(DecisionTree::Leaf(vec![
+                ColoringAlgo::SL,
+                ColoringAlgo::SDO,
+                ColoringAlgo::Genetic,
+            ])),
+            above: Box::new(DecisionTree::Split {
+                feature: Feature::Vertices,
+                threshold: 1000.0,
+                below: Box::new(DecisionTree::Leaf(vec![
+                    ColoringAlgo::SDO,
+                    ColoringAlgo::SL,
+                    ColoringAlgo::Genetic,
+                ])),
+                above: Box::new(DecisionTree::Leaf(vec![ColoringAlgo::SL, ColoringAlgo::LF])),
+            }),
+        }
+    }
+}
+
+/// Color the graph with the methods the default decision tree selects for it
+/// and return the best coloring.
+/// See ```color_auto_with``` for the handling of the time budget.
+pub fn color_auto<G: StaticGraph + ?Sized>(graph: &G, budget: Duration) -> Coloring {
+    color_auto_with(graph, budget, &DecisionTree::default())
+}
+
+/// Color the graph with the methods the decision tree selects for it and return
+/// the best coloring.
+/// The selected methods are run in order until ```budget``` is used up, the first
+/// one is always run. Metaheuristics run for the remaining budget, with their
+/// initial population seeded from smallest-last and largest-first colorings.
+pub fn color_auto_with<G: StaticGraph + ?Sized>(
+    graph: &G,
+    budget: Duration,
+    tree: &DecisionTree,
+) -> Coloring {
+    let start = Instant::now();
+    let features = Features::new(graph);
+    let mut best: Option<Coloring> = None;
+
+    for &algo in tree.select(&features) {
+        if best.is_some() && start.elapsed() >= budget {
+            break;
+        }
+
+        let c = match algo {
+            ColoringAlgo::Genetic => {
+                let params = GeneticParams {
+                    generations: usize::MAX,
+                    time_budget: Some(budget.checked_sub(start.elapsed()).unwrap_or_default()),
+                    init: Initialization { sl: 0.2, lf: 0.2 },
+                    ..GeneticParams::default()
+                };
+                genetic_coloring_with(graph, &params, &mut Monitor::new())
+            }
+            _ => algo.color(graph),
+        };
+
+        assert!(check_coloring(graph, &c));
+
+        if best.as_ref().is_none_or(|b| num_colors(&c) < num_colors(b)) {
+            best = Some(c);
+        }
+    }
+
+    best.unwrap_or_else(|| ColoringAlgo::SL.color(graph))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn select_default() {
+        let tree = DecisionTree::default();
+
+        let sparse = Features::new(&AdjList::random(100, 0.05));
+        let dense = Features::new(&AdjList::random(100, 0.5));
+
+        assert_eq!(tree.select(&sparse)[0], ColoringAlgo::SL);
+        assert_eq!(tree.select(&dense)[0], ColoringAlgo::SDO);
+    }
+
+    #[test]
+    fn auto_random() {
+        let g = AdjList::random(60, 0.5);
+
+        let c = color_auto(&g, Duration::from_millis(100));
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= g.max_degree() + 1);
+    }
+
+    #[test]
+    fn auto_empty_leaf() {
+        let g = AdjList::random(30, 0.5);
+        let tree = DecisionTree::Leaf(vec![]);
+
+        let c = color_auto_with(&g, Duration::from_millis(10), &tree);
+
+        assert!(check_coloring(&g, &c));
+    }
+
+    #[test]
+    fn tree_roundtrip() {
+        let path = env::temp_dir().join(format!("graml-tree-{}.json", process::id()));
+        let tree = DecisionTree::default();
+
+        tree.save(&path).unwrap();
+        let loaded = DecisionTree::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, tree);
+    }
+}
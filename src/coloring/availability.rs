@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::ops::RangeInclusive;
+
+use crate::coloring::{constrained_coloring, k_coloring, Coloring, Constraints};
+use crate::graph::StaticGraph;
+
+/// Slots in which each vertex is available, such as the time slots of a
+/// timetable. Colors are slots numbered from 0. A vertex without windows is
+/// available in every slot.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Availability {
+    /// Number of slots.
+    pub slots: usize,
+    /// Windows of consecutive slots per vertex, first and last slot included.
+    pub windows: BTreeMap<usize, Vec<RangeInclusive<usize>>>,
+}
+
+impl Availability {
+    pub fn new(slots: usize) -> Self {
+        Self {
+            slots,
+            windows: BTreeMap::new(),
+        }
+    }
+
+    /// Makes ```v``` available in the slots of ```window```, in addition to its
+    /// other windows.
+    pub fn window(&mut self, v: usize, window: RangeInclusive<usize>) -> &mut Self {
+        self.windows.entry(v).or_default().push(window);
+        self
+    }
+
+    /// Whether ```v``` is available in ```slot```.
+    pub fn is_available(&self, v: usize, slot: usize) -> bool {
+        slot < self.slots
+            && self
+                .windows
+                .get(&v)
+                .is_none_or(|windows| windows.iter().any(|w| w.contains(&slot)))
+    }
+
+    /// Returns the slots in which ```v``` is available, in increasing order.
+    pub fn allowed(&self, v: usize) -> Vec<usize> {
+        (0..self.slots)
+            .filter(|&slot| self.is_available(v, slot))
+            .collect()
+    }
+
+    /// Checks that all vertices exist in the graph, that windows lie within the
+    /// slots and that every vertex is available at all, reporting violations as
+    /// ```InvalidInput``` errors.
+    pub fn validate<G: StaticGraph + ?Sized>(&self, graph: &G) -> io::Result<()> {
+        for (&v, windows) in &self.windows {
+            if v >= graph.num_vertices() {
+                return Err(invalid_input(&format!("vertex {} not in the graph", v)));
+            }
+            if windows.iter().any(|w| *w.end() >= self.slots) {
+                return Err(invalid_input(&format!(
+                    "window of vertex {} beyond the last slot",
+                    v
+                )));
+            }
+            if self.allowed(v).is_empty() {
+                return Err(invalid_input(&format!("vertex {} never available", v)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Translates the windows into constraints forbidding every slot outside them.
+    pub fn to_constraints(&self) -> Constraints {
+        let mut constraints = Constraints::new();
+        for &v in self.windows.keys() {
+            constraints.forbidden.extend(
+                (0..self.slots)
+                    .filter(|&slot| !self.is_available(v, slot))
+                    .map(|slot| (v, slot)),
+            );
+        }
+
+        constraints
+    }
+
+    /// Returns the vertices whose slot in the coloring is outside their windows.
+    pub fn unavailable(&self, coloring: &[usize]) -> Vec<usize> {
+        (0..coloring.len())
+            .filter(|&v| !self.is_available(v, coloring[v]))
+            .collect()
+    }
+}
+
+/// Assigns every vertex a slot in which it is available, such that adjacent
+/// vertices get different slots. Returns ```None``` if no such assignment exists
+/// and an ```InvalidInput``` error if the availability is invalid as checked by
+/// ```Availability::validate```.
+///
+/// The windows are compiled into forbidden colors for ```constrained_coloring```,
+/// which is solved exactly with at most ```slots``` colors. Exponential in the
+/// worst case.
+pub fn availability_coloring<G: StaticGraph>(
+    graph: &G,
+    availability: &Availability,
+) -> io::Result<Option<Coloring>> {
+    availability.validate(graph)?;
+
+    let mut feasible = true;
+    let coloring = constrained_coloring(graph, &availability.to_constraints(), |reduced| {
+        k_coloring(reduced, availability.slots).unwrap_or_else(|| {
+            feasible = false;
+            (0..reduced.num_vertices()).collect()
+        })
+    })?;
+
+    if !feasible {
+        return Ok(None);
+    }
+    debug_assert!(availability.unavailable(&coloring).is_empty());
+
+    Ok(Some(coloring))
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn availability_windows() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+
+        let mut availability = Availability::new(4);
+        availability
+            .window(0, 0..=0)
+            .window(1, 0..=1)
+            .window(2, 1..=2);
+        availability.window(3, 0..=0).window(3, 2..=3);
+
+        assert_eq!(availability.allowed(3), vec![0, 2, 3]);
+        let c = availability_coloring(&g, &availability).unwrap().unwrap();
+        assert!(check_coloring(&g, &c));
+        assert_eq!(&c[..3], &[0, 1, 2]);
+        assert!(availability.unavailable(&c).is_empty());
+        assert_eq!(availability.unavailable(&[1, 0, 2, 0]), vec![0]);
+
+        availability.window(2, 0..=0);
+        availability.windows.insert(1, vec![0..=0]);
+        assert_eq!(availability_coloring(&g, &availability).unwrap(), None);
+
+        availability.window(1, 3..=4);
+        assert!(availability_coloring(&g, &availability).is_err());
+    }
+}
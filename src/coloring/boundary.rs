@@ -0,0 +1,120 @@
+use std::cmp::Reverse;
+
+use crate::coloring::{color_classes, Coloring};
+use crate::graph::StaticGraph;
+
+/// Combines independently found colorings of the parts of a partition into a
+/// valid coloring of the whole graph.
+///
+/// ```parts``` holds the part of every vertex, ```colorings[p]``` the colors of the
+/// vertices of part ```p``` in increasing order of the vertices, valid on the
+/// subgraph induced by the part. Parts may use the same colors, such that only edges
+/// between parts can conflict.
+///
+/// Parts are placed from the largest on, each with its colors rotated modulo the
+/// largest number of colors of any part, choosing the rotation with the fewest
+/// conflicts to the parts placed before. The remaining conflicts are repaired in a
+/// single pass over the vertices, recoloring a vertex first fit if it shares its
+/// color with a neighbor in another part. Extra colors beyond those of the parts
+/// are only used by repaired vertices without a free color.
+pub fn merge_part_colorings<G: StaticGraph + ?Sized>(
+    graph: &G,
+    parts: &[usize],
+    colorings: &[Coloring],
+) -> Coloring {
+    let members = color_classes(parts);
+    assert!(
+        members.len() <= colorings.len()
+            && (0..colorings.len())
+                .all(|p| colorings[p].len() == members.get(p).map_or(0, Vec::len)),
+        "every part needs a color for each of its vertices"
+    );
+
+    let k = colorings.iter().flatten().max().map_or(0, |&c| c + 1);
+    let mut coloring = vec![usize::MAX; parts.len()];
+
+    let mut order = (0..members.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&p| Reverse(members[p].len()));
+    let mut conflicts = vec![0usize; k];
+    for p in order {
+        // A neighbor of color 'd' conflicts with color 'c' rotated by 'd - c'
+        conflicts.iter_mut().for_each(|x| *x = 0);
+        for (&v, &c) in members[p].iter().zip(&colorings[p]) {
+            for u in graph.neighbors(v) {
+                if parts[u] != p && coloring[u] != usize::MAX {
+                    conflicts[(coloring[u] + k - c) % k] += 1;
+                }
+            }
+        }
+
+        let rotation = (0..k).min_by_key(|&r| conflicts[r]).unwrap_or(0);
+        for (&v, &c) in members[p].iter().zip(&colorings[p]) {
+            coloring[v] = (c + rotation) % k;
+        }
+    }
+
+    // Vertices recolored earlier are respected by later ones
+    let mut used = Vec::new();
+    for v in 0..coloring.len() {
+        let conflicting = graph
+            .neighbors(v)
+            .any(|u| parts[u] != parts[v] && coloring[u] == coloring[v]);
+        if !conflicting {
+            continue;
+        }
+
+        used.clear();
+        for u in graph.neighbors(v) {
+            if coloring[u] >= used.len() {
+                used.resize(coloring[u] + 1, false);
+            }
+            used[coloring[u]] = true;
+        }
+        coloring[v] = (0..).find(|&c| !used.get(c).unwrap_or(&false)).unwrap();
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, num_colors, sdo_coloring};
+    use crate::graph::*;
+
+    #[test]
+    fn merge_rotates_prism() {
+        // Two triangles colored alike, joined by a perfect matching
+        let mut g = AdjList::new();
+        for i in 0..3 {
+            g.add_edge(i, (i + 1) % 3);
+            g.add_edge(i + 3, (i + 1) % 3 + 3);
+            g.add_edge(i, i + 3);
+        }
+
+        let c = merge_part_colorings(&g, &[0, 0, 0, 1, 1, 1], &[vec![0, 1, 2], vec![0, 1, 2]]);
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 3);
+    }
+
+    #[test]
+    fn merge_repairs_random_parts() {
+        let g = AdjList::random(60, 0.2);
+        let parts = g.vertices().map(|v| v % 4).collect::<Vec<_>>();
+        let colorings = (0..4)
+            .map(|p| {
+                let mut part = AdjList::with_capacity(15);
+                for (u, v) in g.edges() {
+                    if u % 4 == p && v % 4 == p {
+                        part.add_edge(u / 4, v / 4);
+                    }
+                }
+                sdo_coloring(&part)
+            })
+            .collect::<Vec<_>>();
+
+        let c = merge_part_colorings(&g, &parts, &colorings);
+        assert!(check_coloring(&g, &c));
+        assert!(merge_part_colorings(&AdjList::new(), &[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+use std::cmp::Reverse;
+
+use crate::coloring::{check_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Colors the graph such that the total size of the vertices of color ```c``` is at
+/// most ```capacities[c]```, as when assigning events of ```sizes``` attendees to
+/// rooms with ```capacities``` seats. Only the colors ```0..capacities.len()``` are
+/// available. Returns ```None``` if the heuristic fails to place some vertex, which
+/// does not prove that no such coloring exists.
+///
+/// Inspired by first-fit decreasing for bin packing, vertices are placed in order of
+/// decreasing size, ties broken by decreasing degree, into the first color with
+/// enough remaining capacity and no neighbor of that color.
+pub fn capacitated_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    sizes: &[usize],
+    capacities: &[usize],
+) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    assert_eq!(sizes.len(), n, "one size per vertex required");
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by_key(|&v| (Reverse(sizes[v]), Reverse(graph.neighbors(v).count()), v));
+
+    let mut coloring = vec![usize::MAX; n];
+    let mut load = vec![0; capacities.len()];
+    let mut blocked = vec![false; capacities.len()];
+
+    for v in order {
+        for u in graph.neighbors(v) {
+            if let Some(b) = blocked.get_mut(coloring[u]) {
+                *b = true;
+            }
+        }
+
+        let c = (0..capacities.len()).find(|&c| !blocked[c] && load[c] + sizes[v] <= capacities[c]);
+
+        for u in graph.neighbors(v) {
+            if let Some(b) = blocked.get_mut(coloring[u]) {
+                *b = false;
+            }
+        }
+
+        coloring[v] = c?;
+        load[coloring[v]] += sizes[v];
+    }
+
+    Some(coloring)
+}
+
+/// Check whether the coloring is valid, uses only colors with a capacity and no
+/// color exceeds its capacity with the sizes of its vertices.
+pub fn check_capacities<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+    sizes: &[usize],
+    capacities: &[usize],
+) -> bool {
+    if !check_coloring(graph, coloring) || sizes.len() != coloring.len() {
+        return false;
+    }
+
+    let mut load = vec![0; capacities.len()];
+    for (v, &c) in coloring.iter().enumerate() {
+        match load.get_mut(c) {
+            Some(l) => *l += sizes[v],
+            None => return false,
+        }
+    }
+
+    load.iter().zip(capacities).all(|(l, c)| l <= c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn capacitated_rooms() {
+        // Two conflicting pairs of events, one large room and two small ones
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(2, 3);
+        let sizes = [30, 20, 10, 10];
+        let capacities = [40, 20, 20];
+
+        let c = capacitated_coloring(&g, &sizes, &capacities).unwrap();
+
+        assert!(check_capacities(&g, &c, &sizes, &capacities));
+        assert_eq!(c[0], 0);
+        assert!(!check_capacities(&g, &[0, 1, 1, 0], &sizes, &capacities));
+        assert!(!check_capacities(&g, &[0, 1, 3, 1], &sizes, &capacities));
+
+        // Too little total capacity
+        assert_eq!(capacitated_coloring(&g, &sizes, &[40, 20]), None);
+    }
+
+    #[test]
+    fn capacitated_unit_sizes() {
+        for _ in 0..10 {
+            let g = AdjList::random(60, 0.2);
+            let sizes = vec![1; 60];
+
+            // At most ten vertices per color, with enough colors to spare
+            let c = capacitated_coloring(&g, &sizes, &[10; 60]).unwrap();
+
+            assert!(check_capacities(&g, &c, &sizes, &[10; 60]));
+        }
+    }
+}
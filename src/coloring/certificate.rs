@@ -0,0 +1,282 @@
+//! Certificates of bounds on the chromatic number, stored next to an instance.
+//!
+//! A clique proves the lower and a coloring the upper bound, both of which are
+//! checked when the certificate is loaded: the coloring in linear time, and the
+//! clique of ```k``` vertices by ```O(k²)``` edge queries. Certificates are stored
+//! in a line based format similar to DIMACS, with vertices numbered from 1 as in
+//! the graph file and colors from 0:
+//! ```text
+//! c comment
+//! q v      vertex v belongs to the clique
+//! v v c    vertex v has color c
+//! s done   an exact search proved the coloring optimal
+//! ```
+//! The last line records a claim which can not be checked without repeating the
+//! search.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::coloring::exact::{greedy_clique, warm_coloring};
+use crate::coloring::{check_coloring, num_colors, sdo_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Lower and upper bound on the chromatic number of a graph, with their proofs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Certificate {
+    /// Vertices of a clique, proving the lower bound.
+    pub clique: Vec<usize>,
+    /// Coloring of all vertices, proving the upper bound.
+    pub coloring: Coloring,
+    /// Whether an exact search proved the coloring optimal.
+    pub done: bool,
+}
+
+impl Certificate {
+    /// Certificate of a greedily grown clique and the saturation degree coloring.
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        Self {
+            clique: greedy_clique(graph),
+            coloring: sdo_coloring(graph),
+            done: false,
+        }
+    }
+
+    /// Returns the path of the certificate of an instance, which appends
+    /// ```.cert``` to its file name.
+    pub fn path_for(instance: impl AsRef<Path>) -> PathBuf {
+        let mut path = OsString::from(instance.as_ref().as_os_str());
+        path.push(".cert");
+        PathBuf::from(path)
+    }
+
+    /// Loads and verifies the certificate stored next to ```instance```.
+    /// Returns ```None``` if there is none.
+    pub fn load_for<G: StaticGraph + ?Sized>(
+        instance: impl AsRef<Path>,
+        graph: &G,
+    ) -> io::Result<Option<Self>> {
+        let certificate = match Self::load(Self::path_for(instance)) {
+            Ok(certificate) => certificate,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        certificate.verify(graph)?;
+        Ok(Some(certificate))
+    }
+
+    /// Load a certificate from file, see ```parse```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(BufReader::new(File::open(path)?))
+    }
+
+    /// Parses a certificate in the format described in the module documentation.
+    /// Malformed lines are reported as ```InvalidData``` errors. The bounds are
+    /// not checked, see ```verify```.
+    pub fn parse(reader: impl BufRead) -> io::Result<Self> {
+        let mut certificate = Self::default();
+
+        for line in reader.lines() {
+            let line = line?;
+            let mut splits = line.split_whitespace();
+
+            match splits.next() {
+                None | Some("c") => continue,
+                Some("q") => certificate.clique.push(vertex(splits.next())?),
+                Some("v") => {
+                    let v = vertex(splits.next())?;
+                    let color = number(splits.next())?;
+
+                    if v >= certificate.coloring.len() {
+                        certificate.coloring.resize(v + 1, usize::MAX);
+                    }
+                    certificate.coloring[v] = color;
+                }
+                Some("s") if splits.next() == Some("done") => certificate.done = true,
+                Some(_) => return Err(invalid(&format!("unexpected line '{}'", line))),
+            }
+        }
+
+        if certificate.coloring.contains(&usize::MAX) {
+            return Err(invalid("vertex without color"));
+        }
+
+        Ok(certificate)
+    }
+
+    /// Writes the certificate to a temporary file next to ```path``` and moves it
+    /// into place, such that an interrupted run never leaves a partial certificate.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = OsString::from(path.as_os_str());
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        {
+            let mut out = BufWriter::new(File::create(&tmp)?);
+            self.write(&mut out)?;
+            out.flush()?;
+        }
+
+        fs::rename(&tmp, path)
+    }
+
+    /// Writes the certificate in the format described in the module documentation.
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "c lower {}", self.lower())?;
+        writeln!(out, "c upper {}", self.upper())?;
+        for &v in &self.clique {
+            writeln!(out, "q {}", v + 1)?;
+        }
+        for (v, color) in self.coloring.iter().enumerate() {
+            writeln!(out, "v {} {}", v + 1, color)?;
+        }
+        if self.done {
+            writeln!(out, "s done")?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the clique is a clique and the coloring a valid coloring of the
+    /// graph, reporting violations as ```InvalidData``` errors.
+    pub fn verify<G: StaticGraph + ?Sized>(&self, graph: &G) -> io::Result<()> {
+        let n = graph.num_vertices();
+
+        if self.clique.iter().any(|&v| v >= n) {
+            return Err(invalid("clique vertex not in graph"));
+        }
+        for (i, &u) in self.clique.iter().enumerate() {
+            if self.clique[i + 1..].iter().any(|&v| !graph.has_edge(u, v)) {
+                return Err(invalid("clique vertices not adjacent"));
+            }
+        }
+        if self.coloring.len() != n || !check_coloring(graph, &self.coloring) {
+            return Err(invalid("invalid coloring"));
+        }
+
+        Ok(())
+    }
+
+    /// Proven lower bound on the chromatic number.
+    pub fn lower(&self) -> usize {
+        self.clique.len()
+    }
+
+    /// Proven upper bound on the chromatic number.
+    pub fn upper(&self) -> usize {
+        num_colors(&self.coloring)
+    }
+
+    /// Whether the upper bound is known to be the chromatic number.
+    pub fn is_optimal(&self) -> bool {
+        self.done || self.lower() == self.upper()
+    }
+
+    /// Improves the bounds within ```budget```.
+    /// A larger greedily grown clique replaces the clique, and the exact search
+    /// starts from the best known coloring and clique. If it runs out of time, the
+    /// best coloring found so far is kept, such that later calls resume from it.
+    /// Returns whether the coloring is known to be optimal.
+    pub fn improve<G: StaticGraph + ?Sized>(&mut self, graph: &G, budget: Duration) -> bool {
+        let clique = greedy_clique(graph);
+        if clique.len() > self.clique.len() {
+            self.clique = clique;
+        }
+        let sdo = sdo_coloring(graph);
+        if self.coloring.len() != graph.num_vertices() || num_colors(&sdo) < self.upper() {
+            self.coloring = sdo;
+        }
+
+        if !self.is_optimal() {
+            let coloring = std::mem::take(&mut self.coloring);
+            let deadline = Instant::now().checked_add(budget);
+            let (coloring, done) = warm_coloring(graph, coloring, &self.clique, deadline);
+
+            self.coloring = coloring;
+            self.done = done;
+        }
+
+        self.is_optimal()
+    }
+}
+
+fn number(split: Option<&str>) -> io::Result<usize> {
+    split
+        .ok_or_else(|| invalid("missing number"))?
+        .parse()
+        .map_err(|_| invalid("malformed number"))
+}
+
+/// Parses a vertex numbered from 1 and returns it numbered from 0.
+fn vertex(split: Option<&str>) -> io::Result<usize> {
+    number(split)?
+        .checked_sub(1)
+        .ok_or_else(|| invalid("vertex 0 in certificate"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::chromatic_number;
+    use crate::graph::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn certificate_roundtrip() {
+        let g = AdjList::random(30, 0.5);
+        let mut certificate = Certificate::new(&g);
+
+        assert!(certificate.improve(&g, Duration::from_secs(60)));
+        assert_eq!(certificate.upper(), chromatic_number(&g));
+
+        let mut out = Vec::new();
+        certificate.write(&mut out).unwrap();
+        let parsed = Certificate::parse(&out[..]).unwrap();
+
+        assert_eq!(parsed, certificate);
+        assert!(parsed.verify(&g).is_ok());
+    }
+
+    #[test]
+    fn certificate_rejected() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+
+        let wrong_clique = Certificate::parse(&b"q 1\nq 3\nv 1 0\nv 2 1\nv 3 0\n"[..]).unwrap();
+        let wrong_coloring = Certificate::parse(&b"q 1\nq 2\nv 1 0\nv 2 0\nv 3 1\n"[..]).unwrap();
+
+        assert!(wrong_clique.verify(&g).is_err());
+        assert!(wrong_coloring.verify(&g).is_err());
+        assert!(Certificate::parse(&b"v 2 0\n"[..]).is_err());
+        assert!(Certificate::parse(&b"q 0\n"[..]).is_err());
+    }
+
+    #[test]
+    fn certificate_next_to_instance() {
+        let instance = env::temp_dir().join(format!("graml-cert-{}.col", process::id()));
+        let g = AdjList::complete(4);
+
+        assert_eq!(Certificate::load_for(&instance, &g).unwrap(), None);
+
+        let certificate = Certificate::new(&g);
+        certificate.save(Certificate::path_for(&instance)).unwrap();
+        let loaded = Certificate::load_for(&instance, &g).unwrap().unwrap();
+        let mismatched = Certificate::load_for(&instance, &AdjList::complete(5));
+        fs::remove_file(Certificate::path_for(&instance)).unwrap();
+
+        assert_eq!(loaded, certificate);
+        assert!(loaded.is_optimal());
+        assert!(mismatched.is_err());
+    }
+}
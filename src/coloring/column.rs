@@ -0,0 +1,154 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::coloring::fractional::{cover_lp, greedy_independent_set, maximal, EPS};
+use crate::coloring::{
+    color_classes, lf_coloring, num_colors, sdo_coloring, sl_coloring, Coloring,
+};
+use crate::graph::StaticGraph;
+
+/// Random independent sets added to the pool per round of ```column_gen_coloring```.
+const RANDOM_SETS: usize = 5;
+
+/// Colors the graph by covering its vertices with independent sets from a pool of
+/// at most ```pool_size``` sets, each of which becomes a color class.
+///
+/// The pool starts with the color classes of the saturation, smallest-last and
+/// largest-first colorings, extended to maximal independent sets. Every round
+/// solves the covering linear program over the pool, as in
+/// ```fractional_chromatic```, adds the independent set found greedily for its dual
+/// weights if it improves the program, and a few random maximal independent sets.
+/// The cover is then chosen greedily, once by the weights of the linear program
+/// and once by the number of newly covered vertices, and a vertex takes the color
+/// of the first chosen set containing it.
+///
+/// Never uses more colors than the best of the three greedy colorings. The time
+/// grows quickly with ```pool_size```, as the linear program is solved anew for
+/// every few sets.
+pub fn column_gen_coloring<G: StaticGraph + ?Sized>(graph: &G, pool_size: usize) -> Coloring {
+    let n = graph.num_vertices();
+    let greedy = [sdo_coloring(graph), sl_coloring(graph), lf_coloring(graph)];
+    let best = greedy.iter().min_by_key(|c| num_colors(c)).unwrap().clone();
+    if n == 0 {
+        return best;
+    }
+
+    let mut pool = Vec::new();
+    let mut known = HashSet::new();
+    for coloring in &greedy {
+        for class in color_classes(coloring) {
+            let set = maximal(graph, class);
+            if known.insert(set.clone()) {
+                pool.push(set);
+            }
+        }
+    }
+
+    let mut rng = thread_rng();
+    let mut order = graph.vertices().collect::<Vec<_>>();
+    let mut lp = cover_lp(n, &pool);
+    while pool.len() < pool_size {
+        let before = pool.len();
+
+        let (priced, weight) = greedy_independent_set(graph, &lp.weights);
+        if weight > 1.0 + EPS && known.insert(priced.clone()) {
+            pool.push(priced);
+        }
+        for _ in 0..RANDOM_SETS {
+            order.shuffle(&mut rng);
+            let set = maximal(graph, order.clone());
+            if pool.len() < pool_size && known.insert(set.clone()) {
+                pool.push(set);
+            }
+        }
+
+        if pool.len() == before {
+            break;
+        }
+        lp = cover_lp(n, &pool);
+    }
+
+    let by_weight = cover(n, &pool, |s, _| lp.cover[s]);
+    let by_size = cover(n, &pool, |_, new| new as f64);
+
+    vec![by_weight, by_size, best]
+        .into_iter()
+        .min_by_key(num_colors)
+        .unwrap()
+}
+
+/// Repeatedly chooses the set of the highest ```priority``` among those covering a
+/// new vertex, given the set and its number of newly covered vertices. Ties are
+/// broken by the number of newly covered vertices. Returns the resulting coloring.
+fn cover(n: usize, pool: &[Vec<usize>], priority: impl Fn(usize, usize) -> f64) -> Coloring {
+    let mut coloring = vec![usize::MAX; n];
+    let mut uncovered = n;
+    let mut color = 0;
+
+    while uncovered > 0 {
+        let (s, _) = pool
+            .iter()
+            .enumerate()
+            .map(|(s, set)| {
+                (
+                    s,
+                    set.iter().filter(|&&v| coloring[v] == usize::MAX).count(),
+                )
+            })
+            .filter(|&(_, new)| new > 0)
+            .max_by(|&(a, x), &(b, y)| {
+                priority(a, x)
+                    .partial_cmp(&priority(b, y))
+                    .unwrap_or(Ordering::Equal)
+                    .then(x.cmp(&y))
+            })
+            .expect("the pool covers every vertex");
+
+        for &v in &pool[s] {
+            if coloring[v] == usize::MAX {
+                coloring[v] = color;
+                uncovered -= 1;
+            }
+        }
+        color += 1;
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn column_gen_never_worse() {
+        for _ in 0..3 {
+            let g = AdjList::random(40, 0.3);
+            let c = column_gen_coloring(&g, 80);
+            let greedy = [sdo_coloring(&g), sl_coloring(&g), lf_coloring(&g)];
+
+            assert!(check_coloring(&g, &c));
+            assert!(greedy.iter().all(|g| num_colors(&c) <= num_colors(g)));
+        }
+        assert!(column_gen_coloring(&AdjList::new(), 10).is_empty());
+    }
+
+    #[test]
+    fn column_gen_crown() {
+        // Crown graph, where largest-first uses a color per pair of vertices
+        let mut g = AdjList::new();
+        for u in 0..8 {
+            for v in (0..8).filter(|&v| v != u) {
+                g.add_edge(2 * u, 2 * v + 1);
+            }
+        }
+
+        let c = column_gen_coloring(&g, 50);
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 2);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::analysis::communities;
+use crate::coloring::{color_classes, merge_part_colorings, Coloring, ColoringAlgo};
+use crate::graph::{AdjList, StaticGraph};
+
+/// Colors the graph by coloring each of its communities independently with
+/// ```algo``` and repairing the conflicts between communities afterwards.
+///
+/// Communities are found by ```communities```. Every community is colored on its
+/// induced subgraph with colors from zero on, such that only edges between
+/// communities can conflict, which ```merge_part_colorings``` repairs. On modular
+/// graphs few edges cross communities and the repair is cheap.
+pub fn community_coloring<G: StaticGraph + ?Sized>(graph: &G, algo: ColoringAlgo) -> Coloring {
+    let (community, subgraphs) = decompose(graph);
+    let colorings = subgraphs.iter().map(|g| algo.color(g)).collect::<Vec<_>>();
+
+    merge_part_colorings(graph, &community, &colorings)
+}
+
+/// Same as ```community_coloring``` but colors the communities on one thread per
+/// processor.
+#[cfg(feature = "parallel")]
+pub fn community_coloring_parallel<G: StaticGraph + Sync + ?Sized>(
+    graph: &G,
+    algo: ColoringAlgo,
+) -> Coloring {
+    let (community, subgraphs) = decompose(graph);
+    let threads = num_cpus::get().max(1);
+
+    let mut colorings = crossbeam::scope(|s| {
+        let handles = (0..threads)
+            .map(|t| {
+                let subgraphs = &subgraphs;
+
+                s.spawn(move |_| {
+                    (t..subgraphs.len())
+                        .step_by(threads)
+                        .map(|i| (i, algo.color(&subgraphs[i])))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .unwrap();
+    colorings.sort_by_key(|&(i, _)| i);
+    let colorings = colorings.into_iter().map(|(_, c)| c).collect::<Vec<_>>();
+
+    merge_part_colorings(graph, &community, &colorings)
+}
+
+/// Returns the community of every vertex and the subgraph induced by every
+/// community, whose vertices are numbered in increasing order of the originals.
+fn decompose<G: StaticGraph + ?Sized>(graph: &G) -> (Vec<usize>, Vec<AdjList>) {
+    let community = communities(graph);
+    let members = color_classes(&community);
+
+    let mut position = vec![0; community.len()];
+    for part in &members {
+        for (i, &v) in part.iter().enumerate() {
+            position[v] = i;
+        }
+    }
+
+    let subgraphs = members
+        .iter()
+        .map(|part| {
+            let mut g = AdjList::with_capacity(part.len());
+            for &v in part {
+                for u in graph.neighbors(v) {
+                    if u < v && community[u] == community[v] {
+                        g.add_edge(position[u], position[v]);
+                    }
+                }
+            }
+            g
+        })
+        .collect();
+
+    (community, subgraphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, num_colors};
+    use crate::graph::*;
+
+    /// Cliques of five in a ring, each joined to the next by a single edge.
+    fn ring_of_cliques(k: usize) -> AdjList {
+        let mut g = AdjList::new();
+        for c in 0..k {
+            for u in 5 * c..5 * c + 5 {
+                for v in u + 1..5 * c + 5 {
+                    g.add_edge(u, v);
+                }
+            }
+            g.add_edge(5 * c + 4, (5 * c + 5) % (5 * k));
+        }
+        g
+    }
+
+    #[test]
+    fn community_coloring_valid() {
+        let g = ring_of_cliques(8);
+        let c = community_coloring(&g, ColoringAlgo::SDO);
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 5);
+
+        let g = AdjList::random(100, 0.1);
+        assert!(check_coloring(
+            &g,
+            &community_coloring(&g, ColoringAlgo::LF)
+        ));
+        assert!(community_coloring(&AdjList::new(), ColoringAlgo::SDO).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn community_coloring_parallel_same() {
+        let g = ring_of_cliques(20);
+
+        assert_eq!(
+            community_coloring_parallel(&g, ColoringAlgo::SDO),
+            community_coloring(&g, ColoringAlgo::SDO)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::graph::{Labeled, StaticGraph};
+
+/// Builds the conflict graph of a list of labeled items.
+/// Two items are connected by an edge if ```conflicts``` holds for their attributes,
+/// such that a coloring of the graph assigns conflicting items different colors.
+/// Vertex ```i``` corresponds to the ```i```th item. ```conflicts``` should be symmetric.
+pub fn conflict_graph<G, L, A, F>(items: Vec<(L, A)>, conflicts: F) -> Labeled<G, L>
+where
+    G: StaticGraph,
+    F: Fn(&A, &A) -> bool,
+{
+    let (labels, attributes): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+    let mut graph = G::with_capacity(labels.len());
+
+    for u in 0..attributes.len() {
+        for v in u + 1..attributes.len() {
+            if conflicts(&attributes[u], &attributes[v]) {
+                graph.add_edge(u, v);
+            }
+        }
+    }
+
+    Labeled::new(graph, labels)
+}
+
+/// Same as ```conflict_graph``` but evaluates ```conflicts``` on one thread per processor.
+/// Worthwhile for large item counts or expensive predicates.
+/// Requires the ```parallel``` feature.
+#[cfg(feature = "parallel")]
+pub fn conflict_graph_parallel<G, L, A, F>(items: Vec<(L, A)>, conflicts: F) -> Labeled<G, L>
+where
+    G: StaticGraph,
+    A: Sync,
+    F: Fn(&A, &A) -> bool + Sync,
+{
+    let (labels, attributes): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+    let n = attributes.len();
+    let threads = num_cpus::get().max(1);
+
+    let edges = crossbeam::scope(|s| {
+        let mut handles = Vec::new();
+
+        for t in 0..threads {
+            let attributes = &attributes;
+            let conflicts = &conflicts;
+
+            // Rows are assigned round-robin to balance the triangular workload
+            handles.push(s.spawn(move |_| {
+                let mut edges = Vec::new();
+                for u in (t..n).step_by(threads) {
+                    for v in u + 1..n {
+                        if conflicts(&attributes[u], &attributes[v]) {
+                            edges.push((u, v));
+                        }
+                    }
+                }
+                edges
+            }));
+        }
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .unwrap();
+
+    let mut graph = G::with_capacity(n);
+    for (u, v) in edges {
+        graph.add_edge(u, v);
+    }
+
+    Labeled::new(graph, labels)
+}
+
+/// Conflict predicate for items requiring sets of resources.
+/// Holds if the items share a resource.
+pub fn shares_resource<T: Hash + Eq>(a: &HashSet<T>, b: &HashSet<T>) -> bool {
+    !a.is_disjoint(b)
+}
+
+/// Conflict predicate for items occupying half-open time windows.
+/// Holds if the windows overlap.
+pub fn overlaps<T: Ord>(a: &Range<T>, b: &Range<T>) -> bool {
+    a.start < b.end && b.start < a.end && a.start < a.end && b.start < b.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn conflict_windows() {
+        let items = vec![("a", 0..4), ("b", 3..6), ("c", 6..8), ("d", 1..1)];
+
+        let g: Labeled<AdjList, _> = conflict_graph(items, overlaps);
+
+        assert_eq!(g.graph.num_vertices(), 4);
+        assert_eq!(g.graph.edges().collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(g.label(1), &"b");
+    }
+
+    #[test]
+    fn conflict_resources() {
+        let set = |r: &[u8]| r.iter().cloned().collect::<HashSet<_>>();
+        let items = vec![(0, set(&[1, 2])), (1, set(&[3])), (2, set(&[2, 3]))];
+
+        let g: Labeled<AdjList, _> = conflict_graph(items, shares_resource);
+
+        assert!(g.graph.has_edge(0, 2));
+        assert!(g.graph.has_edge(1, 2));
+        assert!(!g.graph.has_edge(0, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn conflict_parallel() {
+        let items = (0..200)
+            .map(|i| (i, (i * 7 % 50)..(i * 7 % 50 + 5)))
+            .collect::<Vec<_>>();
+
+        let a: Labeled<AdjList, _> = conflict_graph(items.clone(), overlaps);
+        let b: Labeled<AdjList, _> = conflict_graph_parallel(items, overlaps);
+
+        let mut ea = a.graph.edges().collect::<Vec<_>>();
+        let mut eb = b.graph.edges().collect::<Vec<_>>();
+        ea.sort();
+        eb.sort();
+
+        assert_eq!(ea, eb);
+        assert_eq!(a.labels, b.labels);
+    }
+}
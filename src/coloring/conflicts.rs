@@ -0,0 +1,77 @@
+use crate::graph::StaticGraph;
+
+/// Returns an iterator over the edges whose endpoints share a color, in the order
+/// of ```graph.edges()```. The coloring needs a color for every vertex.
+pub fn conflicts<'a, G: StaticGraph + ?Sized>(
+    graph: &'a G,
+    coloring: &'a [usize],
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    graph
+        .edges()
+        .filter(move |&(u, v)| coloring[u] == coloring[v])
+}
+
+/// Returns the number of edges whose endpoints share a color.
+pub fn num_conflicts<G: StaticGraph + ?Sized>(graph: &G, coloring: &[usize]) -> usize {
+    conflicts(graph, coloring).count()
+}
+
+/// Same as ```num_conflicts``` but counts on one thread per processor, each
+/// walking the neighbors of every ```threads```-th vertex.
+#[cfg(feature = "parallel")]
+pub fn num_conflicts_parallel<G: StaticGraph + Sync + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+) -> usize {
+    let n = graph.num_vertices();
+    let threads = num_cpus::get().max(1);
+
+    crossbeam::scope(|s| {
+        let handles = (0..threads)
+            .map(|t| {
+                s.spawn(move |_| {
+                    (t..n)
+                        .step_by(threads)
+                        .map(|v| {
+                            graph
+                                .neighbors(v)
+                                .filter(|&u| u > v && coloring[u] == coloring[v])
+                                .count()
+                        })
+                        .sum::<usize>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn conflicts_of_path() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+
+        let c = vec![0, 0, 0, 1];
+        assert_eq!(conflicts(&g, &c).collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(num_conflicts(&g, &c), 2);
+        assert_eq!(num_conflicts(&g, &[0, 1, 0, 1]), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn conflicts_parallel_same() {
+        let g = AdjList::random(300, 0.2);
+        let c = g.vertices().map(|v| v % 13).collect::<Vec<_>>();
+
+        assert_eq!(num_conflicts_parallel(&g, &c), num_conflicts(&g, &c));
+    }
+}
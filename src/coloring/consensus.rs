@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::coloring::{align_colorings, compact_colors, fix_coloring, num_colors, Coloring};
+use crate::graph::StaticGraph;
+
+/// Combines several valid colorings of the same graph into one.
+/// The colorings are relabeled to agree as much as possible with the one using the
+/// fewest colors, then every vertex takes the color most of them agree on, ties
+/// broken in favor of the reference. Conflicts introduced by the vote are repaired
+/// with ```fix_coloring``` and the colors are compacted to ```0..k```.
+/// The result is a valid coloring, but may use more colors than the best input.
+/// It is mostly useful as a starting point for local search.
+pub fn consensus_coloring<G: StaticGraph + ?Sized>(graph: &G, colorings: &[Coloring]) -> Coloring {
+    let reference = match colorings.iter().min_by_key(|c| num_colors(c)) {
+        Some(c) => c,
+        None => return vec![0; graph.num_vertices()],
+    };
+
+    let aligned = colorings
+        .iter()
+        .map(|c| {
+            let relabel = align_colorings(reference, c);
+            c.iter().map(|&x| relabel[x]).collect::<Coloring>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut votes = HashMap::new();
+    let mut consensus = reference
+        .iter()
+        .enumerate()
+        .map(|(v, &own)| {
+            votes.clear();
+            for c in &aligned {
+                *votes.entry(c[v]).or_insert(0) += 1;
+            }
+
+            let top = votes.values().cloned().max().unwrap_or(0);
+            if votes.get(&own) == Some(&top) {
+                own
+            } else {
+                *votes.iter().filter(|&(_, &n)| n == top).min().unwrap().0
+            }
+        })
+        .collect::<Coloring>();
+
+    fix_coloring(graph, &mut consensus);
+    compact_colors(&mut consensus);
+
+    consensus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn consensus_of_permutations() {
+        let mut g = AdjList::new();
+        for i in 0..9 {
+            g.add_edge(i, (i + 1) % 9);
+        }
+        let c = sl_coloring(&g);
+        let permuted = c.iter().map(|&x| (x + 1) % 3 + 10).collect::<Coloring>();
+
+        let consensus = consensus_coloring(&g, &[c.clone(), permuted, c.clone()]);
+
+        assert_eq!(consensus, c);
+    }
+
+    #[test]
+    fn consensus_random() {
+        for _ in 0..10 {
+            let g = AdjList::random(60, 0.3);
+            let colorings = vec![
+                sl_coloring(&g),
+                lf_coloring(&g),
+                sdo_coloring(&g),
+                rs_coloring(&g),
+            ];
+
+            let c = consensus_coloring(&g, &colorings);
+
+            assert!(check_coloring(&g, &c));
+        }
+        assert!(consensus_coloring(&AdjList::new(), &[]).is_empty());
+    }
+}
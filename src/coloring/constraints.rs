@@ -0,0 +1,379 @@
+//! Side constraints on colorings, such as precolored vertices.
+//!
+//! Constraints are stored next to a graph in a line based format similar to
+//! DIMACS, with vertices numbered from 1 as in the graph file and colors from 0:
+//! ```text
+//! c comment
+//! f v c    vertex v has the fixed color c
+//! x v c    vertex v must not have color c
+//! s u v    vertices u and v have the same color
+//! d u v    vertices u and v have different colors
+//! ```
+//!
+//! ```constrained_coloring``` enforces all constraints with any solver. Vertices
+//! required to have the same color are merged, and the graph is extended by a clique
+//! of additional vertices, one per color. Vertices with a fixed color are merged into
+//! the clique vertex of their color, forbidden colors become edges to the clique and
+//! different pairs become edges. Any valid coloring of this graph satisfies the constraints after
+//! renaming the colors to those of the clique.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::coloring::{check_coloring, Coloring};
+use crate::graph::{contract_vertices, StaticGraph};
+
+/// Constraints on a coloring, with vertices numbered from 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    /// Pairs ```(v, c)``` of vertices with a prescribed color.
+    pub fixed: Vec<(usize, usize)>,
+    /// Pairs ```(v, c)``` of vertices which must not have a color.
+    pub forbidden: Vec<(usize, usize)>,
+    /// Pairs of vertices which must have the same color.
+    pub same: Vec<(usize, usize)>,
+    /// Pairs of vertices which must have different colors.
+    pub different: Vec<(usize, usize)>,
+}
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load constraints from file, see ```parse```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(BufReader::new(File::open(path)?))
+    }
+
+    /// Parses constraints in the format described in the module documentation.
+    /// Empty lines are skipped, malformed lines are reported as ```InvalidData``` errors.
+    pub fn parse(reader: impl BufRead) -> io::Result<Self> {
+        let mut constraints = Self::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let mut splits = line.split_whitespace();
+
+            // Second number is a color for 'f' and 'x' lines, a vertex otherwise
+            let (list, color) = match splits.next() {
+                None | Some("c") => continue,
+                Some("f") => (&mut constraints.fixed, true),
+                Some("x") => (&mut constraints.forbidden, true),
+                Some("s") => (&mut constraints.same, false),
+                Some("d") => (&mut constraints.different, false),
+                Some(_) => return Err(invalid(&format!("unexpected line '{}'", line))),
+            };
+
+            let v = vertex(splits.next())?;
+            let second = if color {
+                number(splits.next())?
+            } else {
+                vertex(splits.next())?
+            };
+            list.push((v, second));
+        }
+
+        Ok(constraints)
+    }
+
+    /// Returns the number of constraints the coloring violates.
+    pub fn violations(&self, coloring: &[usize]) -> usize {
+        let fixed = self.fixed.iter().filter(|&&(v, c)| coloring[v] != c);
+        let forbidden = self.forbidden.iter().filter(|&&(v, c)| coloring[v] == c);
+        let same = self
+            .same
+            .iter()
+            .filter(|&&(u, v)| coloring[u] != coloring[v]);
+        let different = self
+            .different
+            .iter()
+            .filter(|&&(u, v)| coloring[u] == coloring[v]);
+
+        fixed.count() + forbidden.count() + same.count() + different.count()
+    }
+
+    /// Whether the coloring satisfies all constraints.
+    pub fn satisfied_by(&self, coloring: &[usize]) -> bool {
+        self.violations(coloring) == 0
+    }
+
+    /// Checks that all vertices exist in the graph and rejects constraints which
+    /// contradict each other or the graph as ```InvalidInput``` errors. This includes
+    /// same color pairs connecting adjacent vertices or vertices of different fixed
+    /// colors, directly or through other pairs.
+    pub fn validate<G: StaticGraph + ?Sized>(&self, graph: &G) -> io::Result<()> {
+        self.partition(graph).map(|_| ())
+    }
+
+    /// Assigns every vertex to a vertex of the reduced graph described in the module
+    /// documentation. The first ```palette``` vertices are the clique, which is returned
+    /// as well.
+    fn partition<G: StaticGraph + ?Sized>(&self, graph: &G) -> io::Result<(Vec<usize>, usize)> {
+        let n = graph.num_vertices();
+        let pairs = self.same.iter().chain(&self.different);
+        let vertices = self.fixed.iter().chain(&self.forbidden).map(|&(v, _)| v);
+        if vertices
+            .chain(pairs.flat_map(|&(u, v)| vec![u, v]))
+            .any(|v| v >= n)
+        {
+            return Err(invalid_input("constraint on vertex not in the graph"));
+        }
+
+        let palette = self
+            .fixed
+            .iter()
+            .chain(&self.forbidden)
+            .map(|&(_, c)| c + 1)
+            .max()
+            .unwrap_or(0);
+
+        let group = same_color_groups(n, &self.same);
+        let mut fixed = vec![None; n];
+        for &(v, c) in &self.fixed {
+            if *fixed[group[v]].get_or_insert(c) != c {
+                return Err(invalid_input("vertex fixed to two colors"));
+            }
+        }
+
+        let part = (0..n)
+            .map(|v| fixed[group[v]].unwrap_or(palette + group[v]))
+            .collect::<Vec<_>>();
+
+        if self.forbidden.iter().any(|&(v, c)| part[v] == c) {
+            return Err(invalid_input("vertex fixed to a forbidden color"));
+        }
+        let mut edges = graph.edges().chain(self.different.iter().cloned());
+        if edges.any(|(u, v)| part[u] == part[v]) {
+            return Err(invalid_input("adjacent vertices forced to the same color"));
+        }
+
+        Ok((part, palette))
+    }
+}
+
+/// Graph in which groups of vertices required to have the same color are merged.
+#[derive(Debug, Clone)]
+pub struct Merged<G> {
+    /// The merged graph, vertex ```v``` of the original graph corresponds to
+    /// ```group[v]```.
+    pub graph: G,
+    pub group: Vec<usize>,
+}
+
+impl<G: StaticGraph> Merged<G> {
+    /// Extends a coloring of the merged graph to the original graph.
+    pub fn lift(&self, coloring: &[usize]) -> Coloring {
+        self.group.iter().map(|&g| coloring[g]).collect()
+    }
+}
+
+/// Merges the vertices of each pair, and transitively all vertices connected by pairs,
+/// with ```contract_vertices```. Returns ```None``` if a group contains adjacent
+/// vertices, in which case no coloring satisfies the pairs.
+pub fn merge_same_color<G: StaticGraph>(graph: &G, pairs: &[(usize, usize)]) -> Option<Merged<G>> {
+    let group = same_color_groups(graph.num_vertices(), pairs);
+    if graph.edges().any(|(u, v)| group[u] == group[v]) {
+        return None;
+    }
+
+    Some(Merged {
+        graph: contract_vertices(graph, &group),
+        group,
+    })
+}
+
+/// Colors the graph with ```solver``` such that the coloring satisfies the constraints.
+/// See the module documentation for the method. Returns an ```InvalidInput``` error if
+/// the constraints are invalid as checked by ```Constraints::validate```.
+pub fn constrained_coloring<G, F>(
+    graph: &G,
+    constraints: &Constraints,
+    solver: F,
+) -> io::Result<Coloring>
+where
+    G: StaticGraph,
+    F: FnOnce(&G) -> Coloring,
+{
+    let (part, palette) = constraints.partition(graph)?;
+    let merged = part.iter().max().map_or(palette, |&p| p + 1);
+
+    let mut reduced = G::with_capacity(merged.max(palette));
+    for c in 0..palette {
+        for d in c + 1..palette {
+            reduced.add_edge(c, d);
+        }
+    }
+    for (u, v) in graph.edges().chain(constraints.different.iter().cloned()) {
+        reduced.add_edge(part[u], part[v]);
+    }
+    for &(v, c) in &constraints.forbidden {
+        reduced.add_edge(part[v], c);
+    }
+
+    let solution = solver(&reduced);
+    assert!(check_coloring(&reduced, &solution));
+
+    // Color of the clique vertex 'c' becomes 'c', others follow in order of appearance
+    let mut rename = (0..palette)
+        .map(|c| (solution[c], c))
+        .collect::<HashMap<_, _>>();
+    let coloring = part
+        .iter()
+        .map(|&p| {
+            let next = rename.len();
+            *rename.entry(solution[p]).or_insert(next)
+        })
+        .collect::<Coloring>();
+
+    debug_assert!(constraints.satisfied_by(&coloring));
+    Ok(coloring)
+}
+
+/// Labels the vertices by the groups of vertices connected through ```pairs```,
+/// numbered from 0 in order of their smallest vertex.
+fn same_color_groups(n: usize, pairs: &[(usize, usize)]) -> Vec<usize> {
+    let mut parent = (0..n).collect::<Vec<_>>();
+    fn root(parent: &mut [usize], mut v: usize) -> usize {
+        while parent[v] != v {
+            parent[v] = parent[parent[v]];
+            v = parent[v];
+        }
+        v
+    }
+
+    for &(u, v) in pairs {
+        let (ru, rv) = (root(&mut parent, u), root(&mut parent, v));
+        parent[ru.max(rv)] = ru.min(rv);
+    }
+
+    let mut label = vec![usize::MAX; n];
+    let mut groups = 0;
+    for v in 0..n {
+        let r = root(&mut parent, v);
+        if label[r] == usize::MAX {
+            label[r] = groups;
+            groups += 1;
+        }
+        label[v] = label[r];
+    }
+
+    label
+}
+
+fn number(split: Option<&str>) -> io::Result<usize> {
+    split
+        .ok_or_else(|| invalid("missing number"))?
+        .parse()
+        .map_err(|_| invalid("malformed number"))
+}
+
+/// Parses a vertex numbered from 1 and returns it numbered from 0.
+fn vertex(split: Option<&str>) -> io::Result<usize> {
+    number(split)?
+        .checked_sub(1)
+        .ok_or_else(|| invalid("vertex 0 in constraint"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn constraints_parse() {
+        let input = "c slots\nf 1 2\n\nx 2 0\ns 3 4\nd 1 4\n";
+        let c = Constraints::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(c.fixed, vec![(0, 2)]);
+        assert_eq!(c.forbidden, vec![(1, 0)]);
+        assert_eq!(c.same, vec![(2, 3)]);
+        assert_eq!(c.different, vec![(0, 3)]);
+        assert!(Constraints::parse("f 0 1".as_bytes()).is_err());
+        assert!(Constraints::parse("q 1 1".as_bytes()).is_err());
+        assert!(Constraints::parse("d 1".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn constraints_honored() {
+        let mut g = AdjList::new();
+        for i in 0..9 {
+            g.add_edge(i, (i + 1) % 10);
+        }
+
+        let constraints = Constraints {
+            fixed: vec![(0, 3), (5, 0)],
+            forbidden: vec![(1, 0), (1, 1), (2, 1)],
+            same: vec![(1, 8), (8, 3), (6, 9)],
+            different: vec![(0, 9), (2, 7)],
+        };
+
+        let solvers: [fn(&AdjList) -> Coloring; 3] = [lf_coloring, sdo_coloring, genetic_coloring];
+        for solver in solvers.iter() {
+            let c = constrained_coloring(&g, &constraints, solver).unwrap();
+
+            assert!(check_coloring(&g, &c));
+            assert!(constraints.satisfied_by(&c));
+        }
+    }
+
+    #[test]
+    fn constraints_invalid() {
+        let g = AdjList::complete(3);
+        let check = |c: Constraints| constrained_coloring(&g, &c, sdo_coloring).is_err();
+
+        assert!(check(Constraints {
+            fixed: vec![(0, 1), (1, 1)],
+            ..Constraints::new()
+        }));
+        assert!(check(Constraints {
+            fixed: vec![(0, 1), (0, 2)],
+            ..Constraints::new()
+        }));
+        assert!(check(Constraints {
+            fixed: vec![(0, 1)],
+            forbidden: vec![(0, 1)],
+            ..Constraints::new()
+        }));
+        assert!(check(Constraints {
+            different: vec![(0, 3)],
+            ..Constraints::new()
+        }));
+        assert!(check(Constraints {
+            same: vec![(0, 1)],
+            ..Constraints::new()
+        }));
+        assert!(check(Constraints {
+            fixed: vec![(0, 1)],
+            same: vec![(0, 1)],
+            ..Constraints::new()
+        }));
+    }
+
+    #[test]
+    fn merge_same_color_path() {
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, i + 1);
+        }
+
+        let merged = merge_same_color(&g, &[(0, 4), (4, 2), (1, 5)]).unwrap();
+        let c = merged.lift(&sdo_coloring(&merged.graph));
+
+        assert_eq!(merged.graph.num_vertices(), 3);
+        assert!(check_coloring(&g, &c));
+        assert!(c[0] == c[2] && c[2] == c[4] && c[1] == c[5]);
+        assert!(merge_same_color(&g, &[(0, 2), (2, 1)]).is_none());
+    }
+}
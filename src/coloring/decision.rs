@@ -0,0 +1,148 @@
+use rand::thread_rng;
+use std::time::{Duration, Instant};
+
+use crate::coloring::exact::{bounded_coloring, greedy_clique};
+use crate::coloring::metaheuristics::select_kernel;
+use crate::coloring::metaheuristics::tabucol::tabu_moves;
+use crate::coloring::tolerant::min_penalty_search;
+use crate::coloring::{color_fast, dsatur_coloring, num_colors, Coloring, TolerantColoring};
+use crate::graph::{EdgeWeights, StaticGraph};
+
+/// Steps per vertex of each local search before falling back to the exhaustive search.
+const STEPS_PER_VERTEX: usize = 100;
+
+/// Answer to whether a graph can be colored with a fixed number of colors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KColoring {
+    /// A valid coloring using at most ```k``` colors.
+    Colorable(Coloring),
+    /// Proven that ```k``` colors do not suffice.
+    Infeasible,
+    /// Undecided within the time budget. Contains the coloring with ```k``` colors
+    /// and the fewest conflicts found.
+    Unknown(TolerantColoring),
+}
+
+impl KColoring {
+    /// Returns the coloring if the graph was shown to be colorable.
+    pub fn coloring(self) -> Option<Coloring> {
+        match self {
+            KColoring::Colorable(c) => Some(c),
+            _ => None,
+        }
+    }
+}
+
+/// Returns a coloring with at most ```k``` colors, ```None``` if none exists.
+/// See ```k_coloring_within``` for the method, here without a time limit, such
+/// that the answer is always definitive. Exponential in the worst case.
+pub fn k_coloring<G: StaticGraph + ?Sized>(graph: &G, k: usize) -> Option<Coloring> {
+    match k_coloring_within(graph, k, Duration::MAX) {
+        KColoring::Colorable(c) => Some(c),
+        KColoring::Infeasible => None,
+        KColoring::Unknown(_) => unreachable!("search without deadline is complete"),
+    }
+}
+
+/// Decides whether the graph can be colored with ```k``` colors within about
+/// ```budget```. The budget covers both local searches and the exhaustive search,
+/// the clique bound and the greedy colorings before them always run to completion.
+///
+/// Tries the cheapest methods first:
+/// - A clique of more than ```k``` vertices proves infeasibility.
+/// - The greedy heuristics and saturation degree ordering may use ```k``` colors.
+/// - Min-conflicts local search with ```k``` colors may remove all conflicts.
+/// - Tabu search with ```k``` colors, see ```tabucol```, may find a coloring the
+///   min-conflicts search missed.
+///
+/// Each local search stops after ```STEPS_PER_VERTEX``` steps per vertex or at the
+/// end of the budget, whichever comes first.
+/// - Backtracking in saturation degree order decides the remaining cases.
+pub fn k_coloring_within<G: StaticGraph + ?Sized>(
+    graph: &G,
+    k: usize,
+    budget: Duration,
+) -> KColoring {
+    let deadline = Instant::now().checked_add(budget);
+    let n = graph.num_vertices();
+
+    if n == 0 {
+        return KColoring::Colorable(Vec::new());
+    }
+    if greedy_clique(graph).len() > k {
+        return KColoring::Infeasible;
+    }
+
+    let dsatur = dsatur_coloring(graph);
+    for c in [color_fast(graph), dsatur.clone()] {
+        if num_colors(&c) <= k {
+            return KColoring::Colorable(c);
+        }
+    }
+
+    let stop = |step: usize| {
+        step >= STEPS_PER_VERTEX * n
+            || step.is_multiple_of(128) && deadline.is_some_and(|d| Instant::now() >= d)
+    };
+    let local = min_penalty_search(graph, &EdgeWeights::default(), k, stop, &mut thread_rng());
+    if local.conflicts.is_empty() {
+        return KColoring::Colorable(local.coloring);
+    }
+    let kernel = select_kernel(graph);
+    if let Some(c) = tabu_moves(&*kernel, &dsatur, k, stop, &mut thread_rng()) {
+        return KColoring::Colorable(c);
+    }
+
+    match bounded_coloring(graph, k, deadline) {
+        Some(Some(c)) => KColoring::Colorable(c),
+        Some(None) => KColoring::Infeasible,
+        None => KColoring::Unknown(local),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn k_coloring_decides() {
+        let mut g = AdjList::new();
+        for i in 0..7 {
+            g.add_edge(i, (i + 1) % 7);
+        }
+
+        assert_eq!(k_coloring(&g, 2), None);
+        assert!(check_coloring(&g, &k_coloring(&g, 3).unwrap()));
+        assert_eq!(k_coloring(&AdjList::complete(4), 3), None);
+        assert_eq!(k_coloring(&AdjList::new(), 0), Some(Vec::new()));
+
+        for _ in 0..10 {
+            let g = AdjList::random(14, 0.5);
+            let chi = chromatic_number(&g);
+
+            let c = k_coloring(&g, chi).unwrap();
+            assert!(check_coloring(&g, &c) && num_colors(&c) <= chi);
+            assert_eq!(k_coloring(&g, chi - 1), None);
+        }
+    }
+
+    #[test]
+    fn k_coloring_budget() {
+        // Sparse, such that small cliques do not rule out five colors
+        let g = AdjList::random_with(1000, |_, _| 0.02, &mut StdRng::seed_from_u64(0));
+
+        // The local searches alone would take seconds without the budget
+        let start = Instant::now();
+        let answer = k_coloring_within(&g, 5, Duration::from_millis(10));
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        match answer {
+            KColoring::Unknown(best) => assert!(num_colors(&best.coloring) <= 5),
+            other => panic!("expected no answer, got {:?}", other),
+        }
+    }
+}
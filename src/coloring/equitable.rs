@@ -0,0 +1,181 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::coloring::{check_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Colors the graph with the colors ```0..k``` such that the sizes of any two
+/// color classes differ by at most one, as when balancing load over ```k```
+/// machines. Returns ```None``` if the heuristic fails, which does not prove that no
+/// such coloring exists. By the Hajnal-Szemerédi theorem one exists whenever ```k```
+/// exceeds the maximum degree.
+///
+/// Vertices are placed in order of decreasing degree into the smallest class
+/// without a neighbor, as long as it holds fewer than ```ceil(n / k)``` vertices.
+/// Afterwards vertices are moved from the largest to the smallest classes, along
+/// a shortest chain of classes in which every class passes a vertex without
+/// neighbors in the next class on to it.
+pub fn equitable_coloring<G: StaticGraph + ?Sized>(graph: &G, k: usize) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    if k == 0 {
+        return if n == 0 { Some(Vec::new()) } else { None };
+    }
+
+    let cap = n.div_ceil(k);
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by_key(|&v| (Reverse(graph.neighbors(v).count()), v));
+
+    let mut coloring = vec![usize::MAX; n];
+    let mut sizes = vec![0; k];
+    let mut blocked = vec![false; k];
+    for v in order {
+        blocked.iter_mut().for_each(|b| *b = false);
+        for u in graph.neighbors(v) {
+            if let Some(b) = blocked.get_mut(coloring[u]) {
+                *b = true;
+            }
+        }
+
+        // A full class is only used if no other class is free
+        let c = (0..k)
+            .filter(|&c| !blocked[c])
+            .min_by_key(|&c| (sizes[c] >= cap, sizes[c], c))?;
+        coloring[v] = c;
+        sizes[c] += 1;
+    }
+
+    loop {
+        let max = *sizes.iter().max().unwrap();
+        let min = *sizes.iter().min().unwrap();
+        if max <= min + 1 {
+            return Some(coloring);
+        }
+
+        // Every move reduces the sum of the squared sizes, such that this ends
+        let moved = move_chain(
+            graph,
+            &mut coloring,
+            &mut sizes,
+            |s| s == max,
+            |s| s + 2 <= max,
+        ) || move_chain(
+            graph,
+            &mut coloring,
+            &mut sizes,
+            |s| s >= min + 2,
+            |s| s == min,
+        );
+        if !moved {
+            return None;
+        }
+    }
+}
+
+/// Checks that the coloring is valid, uses only the colors ```0..k``` and the sizes
+/// of its classes differ by at most one.
+pub fn check_equitable<G: StaticGraph + ?Sized>(graph: &G, coloring: &[usize], k: usize) -> bool {
+    if !check_coloring(graph, coloring) || coloring.iter().any(|&c| c >= k) {
+        return false;
+    }
+
+    let mut sizes = vec![0; k];
+    for &c in coloring {
+        sizes[c] += 1;
+    }
+
+    match (sizes.iter().max(), sizes.iter().min()) {
+        (Some(max), Some(min)) => *max <= min + 1,
+        _ => true,
+    }
+}
+
+/// Searches a shortest chain of classes from a class whose size satisfies ```source```
+/// to one whose size satisfies ```target```, in which every class passes a vertex
+/// without neighbors in the next class on to it, and performs its moves. Returns
+/// whether there was such a chain.
+fn move_chain<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &mut [usize],
+    sizes: &mut [usize],
+    source: impl Fn(usize) -> bool,
+    target: impl Fn(usize) -> bool,
+) -> bool {
+    let k = sizes.len();
+    let mut members = vec![Vec::new(); k];
+    for (v, &c) in coloring.iter().enumerate() {
+        members[c].push(v);
+    }
+
+    // Vertex moved into every reached class
+    let mut via = vec![None; k];
+    let mut queue = (0..k)
+        .filter(|&c| source(sizes[c]))
+        .collect::<VecDeque<_>>();
+    let mut reached = (0..k).map(|c| source(sizes[c])).collect::<Vec<_>>();
+    let mut adjacent = vec![false; k];
+
+    while let Some(c) = queue.pop_front() {
+        for &v in &members[c] {
+            adjacent.iter_mut().for_each(|a| *a = false);
+            for u in graph.neighbors(v) {
+                adjacent[coloring[u]] = true;
+            }
+
+            for d in 0..k {
+                if reached[d] || adjacent[d] {
+                    continue;
+                }
+                reached[d] = true;
+                via[d] = Some(v);
+
+                if target(sizes[d]) {
+                    // Every class of the chain passes its vertex on to the next
+                    let mut d = d;
+                    while let Some(v) = via[d] {
+                        let c = coloring[v];
+                        coloring[v] = d;
+                        sizes[c] -= 1;
+                        sizes[d] += 1;
+                        d = c;
+                    }
+                    return true;
+                }
+                queue.push_back(d);
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn equitable_random() {
+        for _ in 0..10 {
+            let g = AdjList::random(100, 0.1);
+            let k = g.max_degree() + 1;
+
+            let c = equitable_coloring(&g, k).unwrap();
+            assert!(check_equitable(&g, &c, k));
+        }
+        assert_eq!(equitable_coloring(&AdjList::new(), 3), Some(Vec::new()));
+    }
+
+    #[test]
+    fn equitable_star() {
+        // The center needs a class of its own
+        let mut g = AdjList::new();
+        for v in 1..6 {
+            g.add_edge(0, v);
+        }
+
+        assert_eq!(equitable_coloring(&g, 2), None);
+        let c = equitable_coloring(&g, 4).unwrap();
+        assert!(check_equitable(&g, &c, 4));
+        assert!(!check_equitable(&g, &[0, 1, 1, 1, 1, 1], 2));
+    }
+}
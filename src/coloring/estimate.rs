@@ -0,0 +1,192 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{
+    exact_coloring, greedy_coloring_with, num_colors, sdo_coloring, sl_coloring, two_coloring,
+    Coloring, ColoringWorkspace,
+};
+use crate::graph::StaticGraph;
+
+/// Graphs up to this size are colored exactly.
+const EXACT_VERTICES: usize = 20;
+
+/// Combined answer to whether a coloring uses the chromatic number of colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChromaticEstimate {
+    /// Proven lower bound on the chromatic number.
+    pub lower: usize,
+    /// Fewest colors found, an upper bound on the chromatic number.
+    pub best: usize,
+    pub coloring: Coloring,
+    /// Number of colors of every randomized restart, mapped to how many runs used them.
+    pub distribution: BTreeMap<usize, usize>,
+    /// Human readable reasoning behind the estimate.
+    pub notes: Vec<String>,
+}
+
+impl ChromaticEstimate {
+    /// Whether ```best``` is proven to be the chromatic number.
+    pub fn is_exact(&self) -> bool {
+        self.lower == self.best
+    }
+
+    /// Fraction of the randomized restarts which found ```best``` colors.
+    /// A value close to one suggests that better colorings are hard to find
+    /// by restarting, not that they do not exist.
+    pub fn best_frequency(&self) -> f64 {
+        let runs = self.distribution.values().sum::<usize>();
+        match runs {
+            0 => 0.0,
+            runs => self.distribution.get(&self.best).cloned().unwrap_or(0) as f64 / runs as f64,
+        }
+    }
+}
+
+/// Estimates the chromatic number within ```budget```.
+/// The lower bound is the largest of a greedily found clique and, for graphs which
+/// are not bipartite, three. Small graphs are colored exactly. Otherwise the
+/// smallest-last and saturation colorings are improved by greedy colorings in
+/// random orders until the budget is used up or the lower bound is reached. The
+/// number of colors of the restarts is summarized in the notes.
+pub fn estimate_chromatic<G: StaticGraph + ?Sized>(
+    graph: &G,
+    budget: Duration,
+) -> ChromaticEstimate {
+    let start = Instant::now();
+    let n = graph.num_vertices();
+    let mut notes = Vec::new();
+
+    let clique = greedy_clique(graph).len();
+    let mut lower = clique;
+    notes.push(format!("found a clique of size {}", clique));
+
+    if let Some(c) = two_coloring(graph) {
+        notes.push("graph is bipartite".to_string());
+        return estimate(lower, num_colors(&c), c, BTreeMap::new(), notes);
+    }
+    if lower < 3 {
+        lower = 3;
+        notes.push("graph has an odd cycle, at least 3 colors are needed".to_string());
+    }
+
+    if n <= EXACT_VERTICES {
+        let c = exact_coloring(graph);
+        notes.push(format!(
+            "colored exactly, graph has at most {} vertices",
+            EXACT_VERTICES
+        ));
+        return estimate(num_colors(&c), num_colors(&c), c, BTreeMap::new(), notes);
+    }
+
+    let mut best = [sl_coloring(graph), sdo_coloring(graph)]
+        .iter()
+        .cloned()
+        .min_by_key(num_colors)
+        .unwrap();
+    let mut distribution = BTreeMap::new();
+    let mut order = graph.vertices().collect::<Vec<_>>();
+    let mut workspace = ColoringWorkspace::new();
+    let mut rng = thread_rng();
+
+    while num_colors(&best) > lower && start.elapsed() < budget {
+        order.shuffle(&mut rng);
+        greedy_coloring_with(graph, &order, &mut workspace);
+        let colors = workspace.num_colors();
+
+        *distribution.entry(colors).or_insert(0) += 1;
+        if colors < num_colors(&best) {
+            best = workspace.coloring().to_vec();
+        }
+    }
+
+    let runs = distribution.values().sum::<usize>();
+    notes.push(format!("{} randomized restarts within the budget", runs));
+
+    let colors = num_colors(&best);
+    estimate(lower, colors, best, distribution, notes)
+}
+
+/// Adds the conclusion to the notes.
+fn estimate(
+    lower: usize,
+    best: usize,
+    coloring: Coloring,
+    distribution: BTreeMap<usize, usize>,
+    mut notes: Vec<String>,
+) -> ChromaticEstimate {
+    let mut e = ChromaticEstimate {
+        lower,
+        best,
+        coloring,
+        distribution,
+        notes: Vec::new(),
+    };
+
+    if e.is_exact() {
+        notes.push(format!("the chromatic number is {}", best));
+    } else {
+        notes.push(format!(
+            "the chromatic number is between {} and {}",
+            lower, best
+        ));
+
+        let frequency = e.best_frequency();
+        if e.distribution.contains_key(&best) {
+            notes.push(format!(
+                "{:.0}% of the restarts found {} colors",
+                100.0 * frequency,
+                best
+            ));
+        } else {
+            notes.push(format!(
+                "no restart matched the {} colors of the constructive heuristics",
+                best
+            ));
+        }
+    }
+
+    e.notes = notes;
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, chromatic_number};
+    use crate::graph::*;
+
+    #[test]
+    fn estimate_exact_cases() {
+        let mut cycle = AdjList::new();
+        for i in 0..9 {
+            cycle.add_edge(i, (i + 1) % 9);
+        }
+        let e = estimate_chromatic(&cycle, Duration::from_millis(10));
+        assert!(e.is_exact());
+        assert_eq!(e.best, 3);
+
+        let e = estimate_chromatic(&AdjList::complete(30), Duration::from_millis(10));
+        assert!(e.is_exact());
+        assert_eq!(e.best, 30);
+
+        let g = AdjList::random(15, 0.5);
+        let e = estimate_chromatic(&g, Duration::from_millis(10));
+        assert_eq!(e.best, chromatic_number(&g));
+        assert!(check_coloring(&g, &e.coloring));
+    }
+
+    #[test]
+    fn estimate_random() {
+        let g = AdjList::random(80, 0.5);
+        let e = estimate_chromatic(&g, Duration::from_millis(50));
+
+        assert!(check_coloring(&g, &e.coloring));
+        assert_eq!(num_colors(&e.coloring), e.best);
+        assert!(e.lower <= e.best);
+        assert!(e.best <= num_colors(&sdo_coloring(&g)));
+        assert!(e.is_exact() || e.notes.iter().any(|n| n.contains("between")));
+    }
+}
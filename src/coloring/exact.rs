@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::analysis::degrees;
+use crate::coloring::zykov::zykov_coloring;
+use crate::coloring::{compact_colors, num_colors, sdo_coloring, Coloring, SaturationTracker};
+use crate::graph::StaticGraph;
+
+/// Returns the chromatic number of the graph.
+/// See ```exact_coloring``` for the method, only feasible for small graphs.
+pub fn chromatic_number<G: StaticGraph + ?Sized>(graph: &G) -> usize {
+    num_colors(&exact_coloring(graph))
+}
+
+/// Returns a lower and an upper bound on the chromatic number.
+/// The lower bound is the size of a greedily grown clique, the upper bound the
+/// number of colors of the saturation degree ordering coloring.
+pub fn chromatic_bounds<G: StaticGraph + ?Sized>(graph: &G) -> (usize, usize) {
+    (greedy_clique(graph).len(), num_colors(&sdo_coloring(graph)))
+}
+
+/// Optimal coloring by branch and bound.
+/// Vertices are colored in order of saturation degree, trying every color used
+/// so far plus one new color. Branches which can not improve on the best coloring
+/// found are cut, and the search stops early once a coloring matches the clique
+/// lower bound. Exponential in the worst case.
+///
+/// Colorings differing only by a permutation of the colors are explored once:
+/// the vertices of the clique are fixed to the first colors, and a new color is
+/// only introduced as the smallest unused one.
+pub fn exact_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    exact_coloring_with(graph, &ExactOptions::default())
+}
+
+/// Branching scheme of the exact solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExactEngine {
+    /// Assigns colors to vertices in order of saturation degree.
+    Dsatur,
+    /// Decides for pairs of non-adjacent vertices whether they share a color,
+    /// usually faster on dense graphs.
+    Zykov,
+}
+
+/// Options of ```exact_coloring_with```.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExactOptions {
+    pub engine: ExactEngine,
+    /// Explore colorings differing only by a permutation of the colors once.
+    /// Turning it off makes every vertex try every color below the best number
+    /// of colors found, which is only useful to verify the symmetry breaking on
+    /// small graphs. The Zykov engine has no such symmetries.
+    pub symmetry_breaking: bool,
+}
+
+impl Default for ExactOptions {
+    fn default() -> Self {
+        Self {
+            engine: ExactEngine::Dsatur,
+            symmetry_breaking: true,
+        }
+    }
+}
+
+/// Same as ```exact_coloring``` with the given options.
+/// Both engines start from the same saturation coloring and clique bound.
+pub fn exact_coloring_with<G: StaticGraph + ?Sized>(graph: &G, options: &ExactOptions) -> Coloring {
+    if options.engine == ExactEngine::Zykov {
+        return zykov_coloring(graph);
+    }
+
+    let mut best = sdo_coloring(graph);
+    let clique = greedy_clique(graph);
+
+    if num_colors(&best) <= clique.len() {
+        return best;
+    }
+
+    let upper = num_colors(&best);
+    let mut search = Search::new(graph, &mut best, upper, clique.len(), None);
+    if options.symmetry_breaking {
+        search.fix_clique(&clique);
+    } else {
+        search.symmetric = false;
+        search.branch(0);
+    }
+
+    best
+}
+
+/// Searches a coloring with at most ```k``` colors as ```exact_coloring``` does.
+/// Returns ```Some(None)``` if there is none and ```None``` if ```deadline``` passes
+/// before the search is complete.
+pub(crate) fn bounded_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    k: usize,
+    deadline: Option<Instant>,
+) -> Option<Option<Coloring>> {
+    let clique = greedy_clique(graph);
+    if clique.len() > k {
+        return Some(None);
+    }
+
+    let mut best = Vec::new();
+    let mut search = Search::new(graph, &mut best, k + 1, k, deadline);
+    search.fix_clique(&clique);
+
+    if search.timed_out {
+        None
+    } else if search.upper <= k {
+        Some(Some(best))
+    } else {
+        Some(None)
+    }
+}
+
+/// Continues the search of ```exact_coloring``` from a known coloring and clique
+/// until ```deadline```. Returns the best coloring found and whether the search
+/// completed, proving it optimal.
+pub(crate) fn warm_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    mut best: Coloring,
+    clique: &[usize],
+    deadline: Option<Instant>,
+) -> (Coloring, bool) {
+    compact_colors(&mut best);
+    let upper = num_colors(&best);
+    if upper <= clique.len() {
+        return (best, true);
+    }
+
+    let mut search = Search::new(graph, &mut best, upper, clique.len(), deadline);
+    search.fix_clique(clique);
+    let complete = !search.timed_out;
+
+    (best, complete)
+}
+
+struct Search<'a, G: StaticGraph + ?Sized> {
+    graph: &'a G,
+    tracker: SaturationTracker,
+    best: &'a mut Coloring,
+    upper: usize,
+    lower: usize,
+    deadline: Option<Instant>,
+    nodes: usize,
+    timed_out: bool,
+    /// Whether new colors are introduced in increasing order only.
+    symmetric: bool,
+}
+
+impl<'a, G: StaticGraph + ?Sized> Search<'a, G> {
+    fn new(
+        graph: &'a G,
+        best: &'a mut Coloring,
+        upper: usize,
+        lower: usize,
+        deadline: Option<Instant>,
+    ) -> Self {
+        Search {
+            graph,
+            tracker: SaturationTracker::new(graph),
+            best,
+            upper,
+            lower,
+            deadline,
+            nodes: 0,
+            timed_out: false,
+            symmetric: true,
+        }
+    }
+
+    /// Colors the vertices of ```clique``` with the first colors and searches the rest.
+    fn fix_clique(&mut self, clique: &[usize]) {
+        for (c, &v) in clique.iter().enumerate() {
+            self.tracker.color(self.graph, v, c);
+        }
+        self.branch(clique.len());
+    }
+
+    /// Extends the partial coloring using ```used``` colors.
+    /// Returns whether an optimal coloring was found or the deadline passed.
+    fn branch(&mut self, used: usize) -> bool {
+        // Checking the clock is comparatively expensive, only do so occasionally
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(1024) && self.deadline.is_some_and(|d| Instant::now() >= d) {
+            self.timed_out = true;
+        }
+        if self.timed_out {
+            return true;
+        }
+
+        let v = match self.tracker.most_saturated() {
+            Some(v) => v,
+            None => {
+                *self.best = self.tracker.coloring().unwrap();
+                self.upper = used;
+                return used <= self.lower;
+            }
+        };
+
+        let end = if self.symmetric { used } else { self.upper };
+        for c in 0..=end {
+            // Colors beyond 'upper - 1' can not lead to an improvement
+            if c + 1 >= self.upper {
+                break;
+            }
+
+            if !self.tracker.is_free(v, c) {
+                continue;
+            }
+
+            self.tracker.color(self.graph, v, c);
+            if self.branch(used.max(c + 1)) {
+                return true;
+            }
+            self.tracker.uncolor(self.graph, v);
+        }
+
+        false
+    }
+}
+
+/// Returns the clique of ```grow_clique```, taken from the cached invariants if
+/// the graph has them.
+pub(crate) fn greedy_clique<G: StaticGraph + ?Sized>(graph: &G) -> Vec<usize> {
+    match graph.invariants() {
+        Some(invariants) => invariants.clique(graph).to_vec(),
+        None => grow_clique(graph),
+    }
+}
+
+/// Grows a clique by repeatedly adding the vertex of highest degree adjacent to
+/// all vertices in the clique.
+pub(crate) fn grow_clique<G: StaticGraph + ?Sized>(graph: &G) -> Vec<usize> {
+    let degree = degrees(graph);
+    let mut candidates = graph.vertices().collect::<Vec<_>>();
+    candidates.sort_by_key(|&v| std::cmp::Reverse(degree[v]));
+
+    let mut clique: Vec<usize> = Vec::new();
+    for v in candidates {
+        if clique.iter().all(|&u| graph.has_edge(u, v)) {
+            clique.push(v);
+        }
+    }
+
+    clique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn exact_small_classes() {
+        let mut cycle = AdjList::new();
+        for i in 0..7 {
+            cycle.add_edge(i, (i + 1) % 7);
+        }
+
+        assert_eq!(chromatic_number(&cycle), 3);
+        assert_eq!(chromatic_number(&AdjList::complete(6)), 6);
+        assert_eq!(chromatic_number(&AdjList::new()), 0);
+    }
+
+    #[test]
+    fn exact_mycielski() {
+        // Groetzsch graph, triangle free with chromatic number 4
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 4) % 5);
+            g.add_edge(i + 5, 10);
+        }
+
+        let c = exact_coloring(&g);
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 4);
+        assert_eq!(chromatic_bounds(&g).0, 2);
+    }
+
+    #[test]
+    fn exact_symmetry_breaking() {
+        for _ in 0..5 {
+            let g = AdjList::random(12, 0.5);
+            let with = exact_coloring(&g);
+            let without = exact_coloring_with(
+                &g,
+                &ExactOptions {
+                    symmetry_breaking: false,
+                    ..ExactOptions::default()
+                },
+            );
+
+            assert!(check_coloring(&g, &with) && check_coloring(&g, &without));
+            assert_eq!(num_colors(&with), num_colors(&without));
+        }
+    }
+
+    #[test]
+    fn exact_random() {
+        for _ in 0..10 {
+            let g = AdjList::random(14, 0.5);
+            let c = exact_coloring(&g);
+            let (lower, upper) = chromatic_bounds(&g);
+
+            assert!(check_coloring(&g, &c));
+            assert!(lower <= num_colors(&c) && num_colors(&c) <= upper);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+use crate::graph::StaticGraph;
+
+/// Why a coloring needs a color class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reason {
+    /// Clique of ```color + 1``` vertices including a vertex of the class, which
+    /// no coloring can color with fewer colors.
+    Clique(Vec<usize>),
+    /// Vertex of the class with a neighbor in every lower color, one per color in
+    /// ```witnesses```. The class is needed unless these neighbors are recolored.
+    Blocked {
+        vertex: usize,
+        witnesses: Vec<usize>,
+    },
+    /// Every vertex of the class could move to a lower color, such that the class
+    /// is not needed.
+    Removable,
+}
+
+/// Explanation of a single color class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassExplanation {
+    pub color: usize,
+    pub vertices: Vec<usize>,
+    pub reason: Reason,
+}
+
+/// Explains why the coloring uses each color from ```from``` on.
+/// A class is forced by a vertex whose neighbors span all lower colors. If such a
+/// vertex and its neighbors contain a clique of the size of the class, the clique
+/// is reported, otherwise the vertex with its neighbors in the lower colors.
+pub fn explain_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+    from: usize,
+) -> Vec<ClassExplanation> {
+    let colors = coloring.iter().max().map_or(0, |&c| c + 1);
+    let mut classes = vec![Vec::new(); colors];
+    for v in graph.vertices() {
+        classes[coloring[v]].push(v);
+    }
+
+    classes
+        .into_iter()
+        .enumerate()
+        .skip(from)
+        .map(|(color, vertices)| ClassExplanation {
+            reason: reason(graph, coloring, color, &vertices),
+            color,
+            vertices,
+        })
+        .collect()
+}
+
+fn reason<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+    color: usize,
+    class: &[usize],
+) -> Reason {
+    let mut blocked = None;
+
+    for &v in class {
+        // One neighbor per lower color
+        let mut witnesses = vec![None; color];
+        for u in graph.neighbors(v) {
+            if coloring[u] < color {
+                witnesses[coloring[u]].get_or_insert(u);
+            }
+        }
+        if witnesses.contains(&None) {
+            continue;
+        }
+
+        let clique = lower_clique(graph, coloring, v);
+        if clique.len() > color {
+            return Reason::Clique(clique);
+        }
+        if blocked.is_none() {
+            let witnesses = witnesses.into_iter().map(Option::unwrap).collect();
+            blocked = Some(Reason::Blocked {
+                vertex: v,
+                witnesses,
+            });
+        }
+    }
+
+    blocked.unwrap_or(Reason::Removable)
+}
+
+/// Clique grown greedily from ```v``` by its neighbors of lower colors, preferring
+/// higher colors and degrees.
+fn lower_clique<G: StaticGraph + ?Sized>(graph: &G, coloring: &[usize], v: usize) -> Vec<usize> {
+    let mut candidates = graph
+        .neighbors(v)
+        .filter(|&u| coloring[u] < coloring[v])
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|&u| std::cmp::Reverse((coloring[u], graph.neighbors(u).count())));
+
+    let mut clique = vec![v];
+    for u in candidates {
+        if clique.iter().all(|&w| graph.has_edge(u, w)) {
+            clique.push(u);
+        }
+    }
+    clique.sort_unstable();
+
+    clique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn explain_classes() {
+        // Triangle 0 1 2 with a pendant path 2 3 4
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        g.add_edge(3, 4);
+
+        let explanation = explain_coloring(&g, &[0, 1, 2, 0, 3], 2);
+
+        assert_eq!(explanation.len(), 2);
+        assert_eq!(explanation[0].reason, Reason::Clique(vec![0, 1, 2]));
+        assert_eq!(explanation[1].vertices, vec![4]);
+        assert_eq!(explanation[1].reason, Reason::Removable);
+
+        // Path with a third color due to a bad order
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        let explanation = explain_coloring(&g, &[0, 1, 2, 0], 2);
+
+        assert_eq!(
+            explanation[0].reason,
+            Reason::Blocked {
+                vertex: 2,
+                witnesses: vec![3, 1]
+            }
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use std::cmp::Ordering;
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{num_colors, sdo_coloring};
+use crate::graph::StaticGraph;
+
+/// Tolerance of the floating point comparisons.
+pub(crate) const EPS: f64 = 1e-9;
+
+/// Branch and bound nodes to spend on one exact search for an independent set.
+const PRICING_NODES: usize = 20_000;
+
+/// Bounds on the fractional chromatic number, the smallest total weight of
+/// independent sets covering every vertex with weight at least one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractionalChromatic {
+    /// Proven lower bound on the fractional chromatic number.
+    pub lower: f64,
+    /// Weight of ```cover```, an upper bound on the fractional chromatic number.
+    pub upper: f64,
+    /// Independent sets with their weights, covering every vertex at least once.
+    pub cover: Vec<(Vec<usize>, f64)>,
+}
+
+impl FractionalChromatic {
+    /// Whether the bounds agree up to rounding errors.
+    pub fn is_exact(&self) -> bool {
+        self.upper - self.lower < 1e-6
+    }
+
+    /// Lower bound on the chromatic number, the rounded up ```lower```.
+    pub fn chromatic_lower_bound(&self) -> usize {
+        (self.lower - 1e-6).ceil().max(0.0) as usize
+    }
+}
+
+/// Approximates the fractional chromatic number by column generation in at most
+/// ```rounds``` rounds.
+///
+/// The linear program covering the vertices with weighted independent sets starts
+/// from the color classes of ```sdo_coloring``` and is solved by the simplex
+/// method. Its dual assigns weights to the vertices, and an independent set
+/// heavier than one improves the program. Such sets are searched greedily and,
+/// failing that, by a budgeted branch and bound. Dividing the dual value by the
+/// weight of the heaviest independent set, or a clique cover bound on it, gives
+/// the lower bound. On triangle free graphs it is often far above the clique
+/// bound of two.
+pub fn fractional_chromatic<G: StaticGraph + ?Sized>(
+    graph: &G,
+    rounds: usize,
+) -> FractionalChromatic {
+    let n = graph.num_vertices();
+    let coloring = sdo_coloring(graph);
+
+    let mut sets = vec![Vec::new(); num_colors(&coloring)];
+    for (v, &c) in coloring.iter().enumerate() {
+        sets[c].push(v);
+    }
+
+    let mut lower = greedy_clique(graph).len() as f64;
+    let mut lp = cover_lp(n, &sets);
+
+    for _ in 0..rounds {
+        let weights = &lp.weights;
+
+        let set = match greedy_independent_set(graph, weights) {
+            (set, w) if w > 1.0 + EPS => set,
+            _ => {
+                let mut pricing = Pricing::new(graph, weights);
+                pricing.run();
+
+                if pricing.nodes <= PRICING_NODES {
+                    lower = lower.max(lp.value / pricing.best.max(1.0));
+                } else {
+                    lower =
+                        lower.max(lp.value / clique_cover_bound(graph, weights, &pricing.order));
+                }
+                if pricing.best <= 1.0 + EPS {
+                    break;
+                }
+
+                maximal(graph, pricing.best_set)
+            }
+        };
+
+        sets.push(set);
+        lp = cover_lp(n, &sets);
+    }
+
+    let cover = sets
+        .into_iter()
+        .zip(lp.cover)
+        .filter(|&(_, x)| x > EPS)
+        .collect();
+
+    FractionalChromatic {
+        lower: lower.min(lp.value),
+        upper: lp.value,
+        cover,
+    }
+}
+
+/// Optimal solution of the covering program restricted to some independent sets.
+pub(crate) struct CoverLp {
+    pub value: f64,
+    /// Dual weight of every vertex.
+    pub weights: Vec<f64>,
+    /// Weight of every independent set.
+    pub cover: Vec<f64>,
+}
+
+/// Solves the dual of the covering program, maximizing the total weight of the
+/// vertices such that no set is heavier than one. The origin is feasible, so the
+/// simplex method needs no first phase. Bland's rule prevents cycling.
+pub(crate) fn cover_lp(n: usize, sets: &[Vec<usize>]) -> CoverLp {
+    let m = sets.len();
+    let rhs = n + m;
+
+    // One row per set, with a slack variable per row
+    let mut tableau = vec![vec![0.0; rhs + 1]; m];
+    for (i, set) in sets.iter().enumerate() {
+        for &v in set {
+            tableau[i][v] = 1.0;
+        }
+        tableau[i][n + i] = 1.0;
+        tableau[i][rhs] = 1.0;
+    }
+    let mut objective = vec![0.0; rhs + 1];
+    for c in objective.iter_mut().take(n) {
+        *c = -1.0;
+    }
+    let mut basis = (n..n + m).collect::<Vec<_>>();
+
+    while let Some(col) = (0..rhs).find(|&j| objective[j] < -EPS) {
+        let row = (0..m)
+            .filter(|&i| tableau[i][col] > EPS)
+            .min_by(|&a, &b| {
+                let ra = tableau[a][rhs] / tableau[a][col];
+                let rb = tableau[b][rhs] / tableau[b][col];
+                ra.partial_cmp(&rb)
+                    .unwrap_or(Ordering::Equal)
+                    .then(basis[a].cmp(&basis[b]))
+            })
+            .expect("every vertex is in some set");
+
+        let pivot = tableau[row][col];
+        for x in tableau[row].iter_mut() {
+            *x /= pivot;
+        }
+        let pivot_row = tableau[row].clone();
+        for (i, r) in tableau.iter_mut().enumerate() {
+            let factor = r[col];
+            if i != row && factor != 0.0 {
+                for (x, p) in r.iter_mut().zip(&pivot_row) {
+                    *x -= factor * p;
+                }
+            }
+        }
+        let factor = objective[col];
+        for (x, p) in objective.iter_mut().zip(&pivot_row) {
+            *x -= factor * p;
+        }
+        basis[row] = col;
+    }
+
+    let mut weights = vec![0.0; n];
+    for (i, &b) in basis.iter().enumerate() {
+        if b < n {
+            weights[b] = tableau[i][rhs];
+        }
+    }
+
+    CoverLp {
+        value: objective[rhs],
+        weights,
+        cover: objective[n..rhs].to_vec(),
+    }
+}
+
+/// Heaviest of the independent sets built greedily by decreasing weight and by
+/// decreasing weight per degree, with its weight.
+pub(crate) fn greedy_independent_set<G: StaticGraph + ?Sized>(
+    graph: &G,
+    weights: &[f64],
+) -> (Vec<usize>, f64) {
+    let degree = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).count() as f64 + 1.0)
+        .collect::<Vec<_>>();
+
+    let keys: [&dyn Fn(usize) -> f64; 2] = [&|v| weights[v], &|v| weights[v] / degree[v]];
+    keys.iter()
+        .map(|key| {
+            let mut order = graph.vertices().collect::<Vec<_>>();
+            order.sort_by(|&a, &b| key(b).partial_cmp(&key(a)).unwrap_or(Ordering::Equal));
+
+            let set = maximal(graph, order);
+            let weight = set.iter().map(|&v| weights[v]).sum::<f64>();
+            (set, weight)
+        })
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+        .unwrap()
+}
+
+/// Extends the first vertices of ```order``` to a maximal independent set,
+/// adding every vertex not adjacent to the previous ones.
+pub(crate) fn maximal<G: StaticGraph + ?Sized>(graph: &G, order: Vec<usize>) -> Vec<usize> {
+    let mut blocked = vec![false; graph.num_vertices()];
+    let mut set = Vec::new();
+
+    for v in order.into_iter().chain(graph.vertices()) {
+        if !blocked[v] {
+            blocked[v] = true;
+            for u in graph.neighbors(v) {
+                blocked[u] = true;
+            }
+            set.push(v);
+        }
+    }
+
+    set.sort_unstable();
+    set
+}
+
+/// Upper bound on the weight of an independent set among ```candidates```, given
+/// in order of decreasing weight. They are covered greedily by cliques, each of
+/// which contributes its heaviest vertex.
+fn clique_cover_bound<G: StaticGraph + ?Sized>(
+    graph: &G,
+    weights: &[f64],
+    candidates: &[usize],
+) -> f64 {
+    let mut cliques: Vec<Vec<usize>> = Vec::new();
+    for &v in candidates {
+        match cliques
+            .iter_mut()
+            .find(|c| c.iter().all(|&u| graph.has_edge(u, v)))
+        {
+            Some(c) => c.push(v),
+            None => cliques.push(vec![v]),
+        }
+    }
+
+    cliques.iter().map(|c| weights[c[0]]).sum()
+}
+
+/// Branch and bound search for a heaviest independent set among the vertices of
+/// positive weight.
+struct Pricing<'a, G: StaticGraph + ?Sized> {
+    graph: &'a G,
+    weights: &'a [f64],
+    /// Vertices of positive weight by decreasing weight.
+    order: Vec<usize>,
+    best: f64,
+    best_set: Vec<usize>,
+    nodes: usize,
+}
+
+impl<'a, G: StaticGraph + ?Sized> Pricing<'a, G> {
+    fn new(graph: &'a G, weights: &'a [f64]) -> Self {
+        let mut order = graph
+            .vertices()
+            .filter(|&v| weights[v] > EPS)
+            .collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            weights[b]
+                .partial_cmp(&weights[a])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Self {
+            graph,
+            weights,
+            order,
+            best: 0.0,
+            best_set: Vec::new(),
+            nodes: 0,
+        }
+    }
+
+    fn run(&mut self) {
+        let order = self.order.clone();
+        self.search(&mut Vec::new(), 0.0, &order);
+    }
+
+    fn search(&mut self, set: &mut Vec<usize>, weight: f64, candidates: &[usize]) {
+        self.nodes += 1;
+        if weight > self.best {
+            self.best = weight;
+            self.best_set = set.clone();
+        }
+        if self.nodes > PRICING_NODES
+            || weight + clique_cover_bound(self.graph, self.weights, candidates) <= self.best + EPS
+        {
+            return;
+        }
+
+        for (i, &v) in candidates.iter().enumerate() {
+            let rest = &candidates[i + 1..];
+            let remaining = rest.iter().map(|&u| self.weights[u]).sum::<f64>();
+            if weight + self.weights[v] + remaining <= self.best + EPS {
+                break;
+            }
+
+            let next = rest
+                .iter()
+                .cloned()
+                .filter(|&u| !self.graph.has_edge(u, v))
+                .collect::<Vec<_>>();
+            set.push(v);
+            self.search(set, weight + self.weights[v], &next);
+            set.pop();
+
+            if self.nodes > PRICING_NODES {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    fn check(graph: &AdjList, f: &FractionalChromatic) {
+        let mut covered = vec![0.0; graph.num_vertices()];
+        for (set, x) in &f.cover {
+            for (i, &u) in set.iter().enumerate() {
+                covered[u] += x;
+                assert!(set[i + 1..].iter().all(|&v| !graph.has_edge(u, v)));
+            }
+        }
+
+        assert!(covered.iter().all(|&c| c > 1.0 - 1e-6));
+        assert!((f.cover.iter().map(|s| s.1).sum::<f64>() - f.upper).abs() < 1e-6);
+        assert!(f.lower <= f.upper);
+    }
+
+    #[test]
+    fn fractional_known_values() {
+        let mut cycle = AdjList::new();
+        for i in 0..5 {
+            cycle.add_edge(i, (i + 1) % 5);
+        }
+
+        let f = fractional_chromatic(&cycle, 100);
+        check(&cycle, &f);
+        assert!(f.is_exact());
+        assert!((f.upper - 2.5).abs() < 1e-6);
+        assert_eq!(f.chromatic_lower_bound(), 3);
+
+        // Groetzsch graph, triangle free with fractional chromatic number 29 / 10
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 4) % 5);
+            g.add_edge(i + 5, 10);
+        }
+
+        let f = fractional_chromatic(&g, 100);
+        check(&g, &f);
+        assert!(f.is_exact());
+        assert!((f.lower - 2.9).abs() < 1e-6);
+
+        let k = AdjList::complete(6);
+        assert!((fractional_chromatic(&k, 100).lower - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fractional_random() {
+        for _ in 0..5 {
+            let g = AdjList::random(30, 0.3);
+            let f = fractional_chromatic(&g, 200);
+
+            check(&g, &f);
+            assert!(f.upper <= num_colors(&sdo_coloring(&g)) as f64 + 1e-6);
+            assert!(f.lower >= greedy_clique(&g).len() as f64 - 1e-6);
+        }
+    }
+}
@@ -0,0 +1,582 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::analysis;
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::Coloring;
+use crate::graph::{MaskedGraph, StaticGraph};
+
+/// Order in which the greedy coloring visits the vertices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Order {
+    /// Vertices by index.
+    Natural,
+    /// Breadth first, such that every vertex except the first of each connected
+    /// component has an already colored neighbor.
+    Connected,
+    /// Vertices by decreasing degree.
+    LargestFirst,
+    /// Reverse of repeatedly removing a vertex of minimum remaining degree, also
+    /// known as degeneracy ordering.
+    SmallestLast,
+    /// Dynamically the vertex with the most distinct colors among its neighbors,
+    /// ties broken by degree.
+    Saturation,
+    /// The given sequence, which has to contain every vertex once.
+    Given(Vec<usize>),
+}
+
+/// Rule choosing the color of a vertex among the colors free at its neighbors.
+/// A new color is only used if no existing color is free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Selection {
+    /// The smallest free color.
+    FirstFit,
+    /// The free color with the fewest vertices, which balances the class sizes.
+    LeastUsed,
+    /// The free color with the most vertices.
+    MostUsed,
+}
+
+/// Greedy coloring composed of an ordering and a selection rule.
+/// ```rs_coloring```, ```cs_coloring```, ```lf_coloring```, ```sl_coloring``` and
+/// ```sdo_coloring``` are instances with first-fit selection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Greedy {
+    pub order: Order,
+    pub selection: Selection,
+    /// Color the vertices of a greedily found clique first, each with its own color.
+    pub clique_seed: bool,
+}
+
+impl Default for Greedy {
+    fn default() -> Self {
+        Self {
+            order: Order::Natural,
+            selection: Selection::FirstFit,
+            clique_seed: false,
+        }
+    }
+}
+
+impl Greedy {
+    /// Greedy coloring with the given order and first-fit selection.
+    pub fn ordered(order: Order) -> Self {
+        Self {
+            order,
+            ..Self::default()
+        }
+    }
+
+    /// Colors the graph.
+    pub fn color<G: StaticGraph + ?Sized>(&self, graph: &G) -> Coloring {
+        let mut workspace = ColoringWorkspace::new();
+        self.run(graph, None, &[], &mut workspace);
+
+        std::mem::take(&mut workspace.coloring)
+    }
+
+    /// Colors the uncolored vertices of ```partial``` and keeps the colors of the
+    /// others, which may be any colors. Panics if two adjacent vertices have the
+    /// same color in ```partial```.
+    pub fn color_extending<G: StaticGraph + ?Sized>(
+        &self,
+        graph: &G,
+        partial: &[Option<usize>],
+    ) -> Coloring {
+        assert_eq!(partial.len(), graph.num_vertices(), "one entry per vertex");
+        assert!(
+            graph
+                .edges()
+                .all(|(u, v)| partial[u].is_none() || partial[u] != partial[v]),
+            "adjacent vertices precolored alike"
+        );
+
+        let mut workspace = ColoringWorkspace::new();
+        self.run(graph, None, partial, &mut workspace);
+
+        std::mem::take(&mut workspace.coloring)
+    }
+
+    /// Same as ```color``` but reuses the buffers of ```workspace```.
+    /// The coloring is stored in the workspace until its next use.
+    pub fn color_with<'w, G: StaticGraph + ?Sized>(
+        &self,
+        graph: &G,
+        workspace: &'w mut ColoringWorkspace,
+    ) -> &'w [usize] {
+        self.run(graph, None, &[], workspace);
+
+        &workspace.coloring
+    }
+
+    /// Same as ```color_with``` but colors the vertices in the order of ```sequence```,
+    /// which has to contain every vertex once.
+    pub fn color_sequence_with<'w, G: StaticGraph + ?Sized>(
+        &self,
+        graph: &G,
+        sequence: &[usize],
+        workspace: &'w mut ColoringWorkspace,
+    ) -> &'w [usize] {
+        self.run(graph, Some(sequence), &[], workspace);
+
+        &workspace.coloring
+    }
+
+    /// Colors the vertices in the order of ```given```, or of ```self.order``` if none,
+    /// except those with a color in ```precolored```, which may be empty.
+    fn run<G: StaticGraph + ?Sized>(
+        &self,
+        graph: &G,
+        given: Option<&[usize]>,
+        precolored: &[Option<usize>],
+        workspace: &mut ColoringWorkspace,
+    ) {
+        let n = graph.num_vertices();
+        let ColoringWorkspace {
+            colors,
+            sizes,
+            blocked,
+            sequence: buffer,
+            degrees,
+            visited,
+            saturation: sets,
+            coloring,
+        } = workspace;
+
+        colors.clear();
+        colors.resize(n, None);
+        sizes.clear();
+
+        let mut state = State {
+            colors,
+            sizes,
+            blocked,
+            selection: self.selection,
+        };
+
+        for (v, c) in precolored.iter().enumerate() {
+            if let Some(c) = *c {
+                state.assign(v, c);
+            }
+        }
+
+        // Every vertex of the clique sees all colors of those before it
+        if self.clique_seed {
+            for v in greedy_clique(graph) {
+                if state.colors[v].is_none() {
+                    state.color(graph, v);
+                }
+            }
+        }
+
+        match (given, &self.order) {
+            (Some(given), _) => state.color_all(graph, given),
+            (None, Order::Saturation) => saturation(graph, &mut state, degrees, sets),
+            (None, order) => {
+                sequence(graph, order, buffer, degrees, visited);
+                state.color_all(graph, buffer);
+            }
+        }
+
+        coloring.clear();
+        coloring.extend(state.colors.iter().map(|c| c.unwrap()));
+    }
+}
+
+/// Scratch buffers of the greedy executor.
+/// Repeated colorings of a graph with the same workspace reuse the buffers of the
+/// first run, such as the partial coloring, blocked colors and ordering.
+/// Clique seeding and the smallest last order still allocate their own.
+#[derive(Debug, Clone, Default)]
+pub struct ColoringWorkspace {
+    colors: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+    blocked: Vec<u64>,
+    sequence: Vec<usize>,
+    degrees: Vec<usize>,
+    visited: Vec<bool>,
+    saturation: Vec<HashSet<usize>>,
+    coloring: Coloring,
+}
+
+impl ColoringWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the last coloring computed in the workspace.
+    pub fn coloring(&self) -> &[usize] {
+        &self.coloring
+    }
+
+    /// Returns the number of colors of the last coloring.
+    /// Greedy colorings use the colors ```0..k```, extended ones may leave some of
+    /// them out.
+    pub fn num_colors(&self) -> usize {
+        self.sizes.len()
+    }
+}
+
+/// Partial coloring built by the greedy executor.
+struct State<'a> {
+    colors: &'a mut Vec<Option<usize>>,
+    sizes: &'a mut Vec<usize>,
+    /// Colors blocked at the current vertex, one bit per color
+    blocked: &'a mut Vec<u64>,
+    selection: Selection,
+}
+
+impl<'a> State<'a> {
+    fn assign(&mut self, v: usize, c: usize) {
+        if c >= self.sizes.len() {
+            self.sizes.resize(c + 1, 0);
+        }
+        self.sizes[c] += 1;
+        self.colors[v] = Some(c);
+    }
+
+    /// Colors the uncolored vertices of ```sequence``` in order.
+    fn color_all<G: StaticGraph + ?Sized>(&mut self, graph: &G, sequence: &[usize]) {
+        for &v in sequence {
+            if self.colors[v].is_none() {
+                self.color(graph, v);
+            }
+        }
+    }
+
+    /// Colors ```v``` according to the selection rule, returns the color.
+    fn color<G: StaticGraph + ?Sized>(&mut self, graph: &G, v: usize) -> usize {
+        let k = self.sizes.len();
+
+        self.blocked.clear();
+        self.blocked.resize(k / 64 + 1, 0);
+        for u in graph.neighbors(v) {
+            if let Some(c) = self.colors[u] {
+                self.blocked[c / 64] |= 1 << (c % 64);
+            }
+        }
+
+        let c = match self.selection {
+            // The first zero bit, a new color if all are blocked
+            Selection::FirstFit => {
+                let (i, word) = self
+                    .blocked
+                    .iter()
+                    .enumerate()
+                    .find(|&(_, &word)| word != !0)
+                    .unwrap();
+                i * 64 + word.trailing_ones() as usize
+            }
+            selection => {
+                let blocked = &self.blocked;
+                let free = (0..k).filter(|&c| blocked[c / 64] & (1 << (c % 64)) == 0);
+                match selection {
+                    Selection::LeastUsed => free.min_by_key(|&c| self.sizes[c]),
+                    _ => free.min_by_key(|&c| std::cmp::Reverse(self.sizes[c])),
+                }
+                .unwrap_or(k)
+            }
+        };
+
+        self.assign(v, c);
+        c
+    }
+}
+
+/// Colors the uncolored vertices of ```partial``` by saturation degree ordering and
+/// keeps the colors of the others, such as registers assigned in advance.
+/// Panics if two adjacent vertices have the same color in ```partial```.
+pub fn precoloring_extension<G: StaticGraph + ?Sized>(
+    graph: &G,
+    partial: &[Option<usize>],
+) -> Coloring {
+    Greedy::ordered(Order::Saturation).color_extending(graph, partial)
+}
+
+/// Colors every vertex with a color of its list in ```allowed```, or returns ```None```
+/// if the heuristic runs out of colors at some vertex. Pinned vertices have a
+/// single color in their list.
+///
+/// Repeatedly colors the vertex with the fewest colors of its list left free by its
+/// neighbors, ties broken by degree, with the free color that is free for the fewest
+/// of its uncolored neighbors.
+pub fn greedy_list_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    allowed: &[HashSet<usize>],
+) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    assert_eq!(allowed.len(), n, "one list per vertex required");
+
+    let degree = (0..n)
+        .map(|v| graph.neighbors(v).count())
+        .collect::<Vec<_>>();
+    let mut free = allowed.to_vec();
+    let mut coloring = vec![None; n];
+
+    while let Some(v) = (0..n)
+        .filter(|&v| coloring[v].is_none())
+        .min_by_key(|&v| (free[v].len(), Reverse(degree[v]), v))
+    {
+        let c = *free[v].iter().min_by_key(|&c| {
+            let taken = graph
+                .neighbors(v)
+                .filter(|&u| coloring[u].is_none() && free[u].contains(c))
+                .count();
+            (taken, *c)
+        })?;
+
+        coloring[v] = Some(c);
+        for u in graph.neighbors(v) {
+            free[u].remove(&c);
+        }
+    }
+
+    coloring.into_iter().collect()
+}
+
+/// Writes the static sequence of vertices of the order to ```sequence```.
+fn sequence<G: StaticGraph + ?Sized>(
+    graph: &G,
+    order: &Order,
+    sequence: &mut Vec<usize>,
+    degrees: &mut Vec<usize>,
+    visited: &mut Vec<bool>,
+) {
+    sequence.clear();
+
+    match *order {
+        Order::Natural => sequence.extend(graph.vertices()),
+        Order::Connected => {
+            visited.clear();
+            visited.resize(graph.num_vertices(), false);
+
+            for first in graph.vertices() {
+                if visited[first] {
+                    continue;
+                }
+
+                let mut i = sequence.len();
+                visited[first] = true;
+                sequence.push(first);
+
+                while i < sequence.len() {
+                    for u in graph.neighbors(sequence[i]) {
+                        if !visited[u] {
+                            visited[u] = true;
+                            sequence.push(u);
+                        }
+                    }
+                    i += 1;
+                }
+            }
+        }
+        Order::LargestFirst => {
+            degrees.clear();
+            degrees.extend_from_slice(&analysis::degrees(graph));
+
+            // Ties by index, as a stable sort would, without its buffer
+            sequence.extend(graph.vertices());
+            sequence.sort_unstable_by_key(|&v| (std::cmp::Reverse(degrees[v]), v));
+        }
+        Order::SmallestLast => {
+            let mut masked = MaskedGraph::new(&graph);
+
+            while let Some(v) = masked.min_degree_vertex() {
+                masked.remove_vertex(v);
+                sequence.push(v);
+            }
+
+            sequence.reverse();
+        }
+        Order::Saturation => unreachable!("saturation order depends on the coloring"),
+        Order::Given(ref given) => sequence.extend_from_slice(given),
+    }
+}
+
+/// Colors the vertices in order of decreasing saturation degree.
+fn saturation<G: StaticGraph + ?Sized>(
+    graph: &G,
+    state: &mut State,
+    degree: &mut Vec<usize>,
+    saturation: &mut Vec<HashSet<usize>>,
+) {
+    let n = graph.num_vertices();
+    degree.clear();
+    degree.extend((0..n).map(|v| graph.neighbors(v).count()));
+
+    // Distinct colors among the neighbors of each vertex
+    saturation.resize_with(n, HashSet::new);
+    for set in saturation.iter_mut() {
+        set.clear();
+    }
+    for v in 0..n {
+        if let Some(c) = state.colors[v] {
+            for u in graph.neighbors(v) {
+                saturation[u].insert(c);
+            }
+        }
+    }
+
+    while let Some(v) = (0..n)
+        .filter(|&v| state.colors[v].is_none())
+        .max_by_key(|&v| (saturation[v].len(), degree[v], std::cmp::Reverse(v)))
+    {
+        let c = state.color(graph, v);
+        for u in graph.neighbors(v) {
+            saturation[u].insert(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn greedy_compositions() {
+        let g = AdjList::random(60, 0.3);
+
+        for order in [
+            Order::Natural,
+            Order::Connected,
+            Order::LargestFirst,
+            Order::SmallestLast,
+            Order::Saturation,
+            Order::Given((0..60).rev().collect()),
+        ]
+        .iter()
+        {
+            for &selection in &[
+                Selection::FirstFit,
+                Selection::LeastUsed,
+                Selection::MostUsed,
+            ] {
+                for &clique_seed in &[false, true] {
+                    let greedy = Greedy {
+                        order: order.clone(),
+                        selection,
+                        clique_seed,
+                    };
+
+                    let c = greedy.color(&g);
+
+                    assert!(check_coloring(&g, &c));
+                    assert!(num_colors(&c) <= g.max_degree() + 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_extends_precoloring() {
+        // Ends of a path pinned to different colors, a gap in the colors
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        let c = precoloring_extension(&g, &[Some(0), None, Some(3)]);
+        assert_eq!(c, vec![0, 1, 3]);
+
+        let g = AdjList::random(60, 0.3);
+        let partial = g
+            .vertices()
+            .map(|v| if v % 10 == 0 { Some(v / 10) } else { None })
+            .collect::<Vec<_>>();
+        for order in &[Order::Natural, Order::Saturation] {
+            let greedy = Greedy {
+                order: order.clone(),
+                selection: Selection::LeastUsed,
+                clique_seed: true,
+            };
+            let c = greedy.color_extending(&g, &partial);
+
+            assert!(check_coloring(&g, &c));
+            assert!(g.vertices().all(|v| partial[v].is_none_or(|p| c[v] == p)));
+        }
+    }
+
+    #[test]
+    fn greedy_list_coloring_lists() {
+        // Lists one larger than the degree always suffice
+        let g = AdjList::random(60, 0.3);
+        let allowed = g
+            .vertices()
+            .map(|v| (v..v + g.neighbors(v).count() + 1).collect())
+            .collect::<Vec<HashSet<_>>>();
+        let c = greedy_list_coloring(&g, &allowed).unwrap();
+        assert!(check_coloring(&g, &c));
+        assert!(g.vertices().all(|v| allowed[v].contains(&c[v])));
+
+        // K_{3,3} is not colorable from these lists of two colors
+        let mut g = AdjList::new();
+        for u in 0..3 {
+            for v in 3..6 {
+                g.add_edge(u, v);
+            }
+        }
+        let lists = [[0, 1], [0, 2], [1, 2], [0, 1], [0, 2], [1, 2]];
+        let allowed = lists
+            .iter()
+            .map(|l| l.iter().cloned().collect())
+            .collect::<Vec<_>>();
+        assert_eq!(greedy_list_coloring(&g, &allowed), None);
+    }
+
+    #[test]
+    fn greedy_workspace_reuse() {
+        let mut workspace = ColoringWorkspace::new();
+
+        for &n in &[40, 10, 60, 0] {
+            let g = AdjList::random(n, 0.3);
+
+            for order in [Order::Natural, Order::LargestFirst, Order::Saturation].iter() {
+                let greedy = Greedy::ordered(order.clone());
+                let expected = greedy.color(&g);
+
+                assert_eq!(greedy.color_with(&g, &mut workspace), &expected[..]);
+                assert_eq!(workspace.num_colors(), num_colors(&expected));
+            }
+
+            let sequence = (0..n).rev().collect::<Vec<_>>();
+            let expected = greedy_coloring(&g, sequence.iter().cloned());
+            assert_eq!(
+                greedy_coloring_with(&g, &sequence, &mut workspace),
+                &expected[..]
+            );
+        }
+    }
+
+    #[test]
+    fn greedy_least_used_balances() {
+        // Edge 0 - 1 and isolated vertices 2 and 3
+        let mut g = AdjList::with_capacity(4);
+        g.add_edge(0, 1);
+
+        let first_fit = Greedy::ordered(Order::Natural);
+        let least_used = Greedy {
+            selection: Selection::LeastUsed,
+            ..first_fit.clone()
+        };
+
+        assert_eq!(first_fit.color(&g), vec![0, 1, 0, 0]);
+        assert_eq!(least_used.color(&g), vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn greedy_clique_seed() {
+        let mut g = AdjList::complete(4);
+        g.add_edge(3, 4);
+
+        let greedy = Greedy {
+            order: Order::Given(vec![4, 3, 2, 1, 0]),
+            clique_seed: true,
+            ..Greedy::default()
+        };
+
+        // The clique is found starting at vertex 3 of highest degree
+        assert_eq!(greedy.color(&g), vec![1, 2, 3, 0, 1]);
+    }
+}
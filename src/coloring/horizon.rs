@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::coloring::recolor::recolor_partial;
+use crate::coloring::{align_colorings, num_colors, Coloring};
+use crate::graph::{Labeled, StaticGraph};
+
+/// Outcome of a single planning period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodReport {
+    /// Index of the period, counted from 0.
+    pub period: usize,
+    pub vertices: usize,
+    /// Number of colors of the chosen coloring.
+    pub colors: usize,
+    /// Number of colors of the solver on its own.
+    pub solver_colors: usize,
+    /// Vertices also present in the previous period.
+    pub carried: usize,
+    /// Carried vertices which changed their color.
+    pub changed: usize,
+    /// Whether the previous coloring was repaired, rather than replaced by the
+    /// solver's coloring.
+    pub repaired: bool,
+}
+
+/// Colors a sequence of related graphs, one per planning period, while changing
+/// the colors of as few vertices as possible between consecutive periods.
+/// Vertices are identified across periods by their labels.
+///
+/// The first period is colored by the solver, each later one starts from the colors
+/// of the previous one. Conflicts caused by
+/// the new graph are repaired with few changes as by ```recolor_min_change```, and
+/// new vertices are colored. The repair is kept unless it uses more than
+/// ```max_extra_colors``` colors beyond the solver's coloring of the period, which
+/// then replaces it with its colors renamed to agree with the previous period on as
+/// many vertices as possible.
+#[derive(Debug, Clone)]
+pub struct RollingHorizon<L> {
+    previous: HashMap<L, usize>,
+    max_extra_colors: usize,
+    reports: Vec<PeriodReport>,
+}
+
+impl<L: Hash + Eq + Clone> RollingHorizon<L> {
+    /// Driver keeping the repair as long as it needs no more colors than the solver.
+    pub fn new() -> Self {
+        Self {
+            previous: HashMap::new(),
+            max_extra_colors: 0,
+            reports: Vec::new(),
+        }
+    }
+
+    /// Number of colors the repair may use beyond the solver's coloring.
+    pub fn max_extra_colors(mut self, extra: usize) -> Self {
+        self.max_extra_colors = extra;
+        self
+    }
+
+    /// Colors the next period with ```solver``` and the colors of the previous one.
+    pub fn next_period<G, F>(&mut self, period: &Labeled<G, L>, solver: F) -> Coloring
+    where
+        G: StaticGraph,
+        F: FnOnce(&G) -> Coloring,
+    {
+        let graph = &period.graph;
+        let old = period
+            .labels
+            .iter()
+            .map(|l| self.previous.get(l).cloned())
+            .collect::<Vec<_>>();
+        let carried = (0..old.len())
+            .filter(|&v| old[v].is_some())
+            .collect::<Vec<_>>();
+
+        let fresh = solver(graph);
+        let solver_colors = num_colors(&fresh);
+        let repair = recolor_partial(graph, &old, usize::MAX).unwrap();
+
+        let repaired =
+            !carried.is_empty() && num_colors(&repair) <= solver_colors + self.max_extra_colors;
+        let coloring = if repaired {
+            repair
+        } else {
+            align(&fresh, &old, &carried)
+        };
+
+        self.reports.push(PeriodReport {
+            period: self.reports.len(),
+            vertices: graph.num_vertices(),
+            colors: num_colors(&coloring),
+            solver_colors,
+            carried: carried.len(),
+            changed: carried
+                .iter()
+                .filter(|&&v| old[v] != Some(coloring[v]))
+                .count(),
+            repaired,
+        });
+        self.previous = period
+            .labels
+            .iter()
+            .cloned()
+            .zip(coloring.clone())
+            .collect();
+
+        coloring
+    }
+
+    /// Reports of all periods colored so far.
+    pub fn reports(&self) -> &[PeriodReport] {
+        &self.reports
+    }
+}
+
+impl<L: Hash + Eq + Clone> Default for RollingHorizon<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Colors all periods in order with ```solver``` as described for ```RollingHorizon```.
+/// Returns the colorings and the reports of the periods.
+pub fn rolling_horizon_coloring<G, L, F>(
+    periods: &[Labeled<G, L>],
+    max_extra_colors: usize,
+    solver: F,
+) -> (Vec<Coloring>, Vec<PeriodReport>)
+where
+    G: StaticGraph,
+    L: Hash + Eq + Clone,
+    F: Fn(&G) -> Coloring,
+{
+    let mut horizon = RollingHorizon::new().max_extra_colors(max_extra_colors);
+    let colorings = periods
+        .iter()
+        .map(|period| horizon.next_period(period, &solver))
+        .collect();
+
+    (colorings, horizon.reports)
+}
+
+/// Renames the colors of ```fresh``` to agree with the old colors of the carried
+/// vertices as much as possible. Colors of no carried vertex get unused names.
+fn align(fresh: &[usize], old: &[Option<usize>], carried: &[usize]) -> Coloring {
+    let a = carried.iter().map(|&v| old[v].unwrap()).collect::<Vec<_>>();
+    let b = carried.iter().map(|&v| fresh[v]).collect::<Vec<_>>();
+    let relabel = align_colorings(&a, &b);
+
+    let seen = b.iter().cloned().collect::<HashSet<_>>();
+    let mut next = a
+        .iter()
+        .chain(seen.iter().map(|&c| &relabel[c]))
+        .max()
+        .map_or(0, |&c| c + 1);
+    let mut names = seen
+        .into_iter()
+        .map(|c| (c, relabel[c]))
+        .collect::<HashMap<_, _>>();
+
+    fresh
+        .iter()
+        .map(|&c| {
+            *names.entry(c).or_insert_with(|| {
+                next += 1;
+                next - 1
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, sdo_coloring};
+    use crate::graph::*;
+
+    #[test]
+    fn horizon_keeps_colors() {
+        // Path a - b - c, then a chord a - c and a new vertex d adjacent to b and c
+        let mut first = AdjList::new();
+        first.add_edge(0, 1);
+        first.add_edge(1, 2);
+        let first = Labeled::new(first, vec!["a", "b", "c"]);
+
+        let mut second = AdjList::new();
+        second.add_edge(0, 1);
+        second.add_edge(1, 2);
+        second.add_edge(2, 3);
+        second.add_edge(3, 1);
+        second.add_edge(0, 2);
+        let second = Labeled::new(second, vec!["d", "c", "b", "a"]);
+
+        let (colorings, reports) =
+            rolling_horizon_coloring(&[first.clone(), second.clone(), first], 1, sdo_coloring);
+
+        assert!(check_coloring(&second.graph, &colorings[1]));
+        assert_eq!(reports[1].carried, 3);
+        assert_eq!(reports[1].changed, 1);
+        assert!(reports[1].repaired);
+        assert_eq!((reports[2].changed, reports[2].colors), (0, 3));
+    }
+
+    #[test]
+    fn horizon_replaces_wasteful_repair() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        let period = Labeled::new(g, vec![0, 1, 2, 3]);
+
+        let mut horizon = RollingHorizon::new();
+        horizon.next_period(&period, |_| vec![0, 1, 2, 3]);
+        let c = horizon.next_period(&period, sdo_coloring);
+
+        assert!(check_coloring(&period.graph, &c));
+        assert_eq!(num_colors(&c), 2);
+        assert!(!horizon.reports()[1].repaired);
+        assert_eq!(horizon.reports()[1].changed, 2);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp::Reverse;
+
+use crate::coloring::Coloring;
+use crate::graph::Hypergraph;
+
+/// Whether no edge of the hypergraph has all its members colored alike.
+pub fn check_weak_coloring(hypergraph: &Hypergraph, coloring: &[usize]) -> bool {
+    coloring.len() == hypergraph.num_vertices()
+        && hypergraph
+            .edges()
+            .iter()
+            .all(|e| e.iter().any(|&v| coloring[v] != coloring[e[0]]))
+}
+
+/// Whether the members of every edge of the hypergraph have pairwise different colors.
+pub fn check_strong_coloring(hypergraph: &Hypergraph, coloring: &[usize]) -> bool {
+    let mut seen = Vec::new();
+
+    coloring.len() == hypergraph.num_vertices()
+        && hypergraph.edges().iter().all(|e| {
+            seen.clear();
+            seen.extend(e.iter().map(|&v| coloring[v]));
+            seen.sort_unstable();
+            seen.dedup();
+            seen.len() == e.len()
+        })
+}
+
+/// Colors the hypergraph such that no edge is monochromatic.
+///
+/// Vertices are colored in order of decreasing number of edges containing them.
+/// Each takes the smallest color which does not complete an edge whose other
+/// members all share that color. Strong colorings are obtained by coloring the
+/// ```Hypergraph::clique_expansion``` with any graph coloring method.
+pub fn weak_coloring(hypergraph: &Hypergraph) -> Coloring {
+    let n = hypergraph.num_vertices();
+    let incidence = hypergraph.incidence();
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by_key(|&v| (Reverse(incidence[v].len()), v));
+
+    let mut coloring: Vec<Option<usize>> = vec![None; n];
+    let mut blocked = Vec::new();
+
+    for v in order {
+        blocked.clear();
+        for &i in &incidence[v] {
+            // Color shared by all other members, if they are colored alike
+            let mut others = hypergraph.edges()[i].iter().filter(|&&u| u != v);
+            if let Some(c) = others.next().and_then(|&u| coloring[u]) {
+                if others.all(|&u| coloring[u] == Some(c)) {
+                    blocked.push(c);
+                }
+            }
+        }
+
+        coloring[v] = (0..).find(|c| !blocked.contains(c));
+    }
+
+    coloring.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn weak_and_strong() {
+        // Fano plane, not 2-colorable in the weak sense
+        let mut fano = Hypergraph::new(7);
+        for i in 0..7 {
+            fano.add_edge(&[i, (i + 1) % 7, (i + 3) % 7]);
+        }
+
+        let weak = weak_coloring(&fano);
+        let strong = sdo_coloring(&fano.clique_expansion::<AdjList>());
+
+        assert!(check_weak_coloring(&fano, &weak));
+        assert_eq!(num_colors(&weak), 3);
+        assert!(check_strong_coloring(&fano, &strong));
+        assert!(check_weak_coloring(&fano, &strong));
+        assert!(!check_strong_coloring(&fano, &weak));
+    }
+
+    #[test]
+    fn weak_large_edges() {
+        // Overlapping groups of five, two colors suffice
+        let mut h = Hypergraph::new(40);
+        for i in 0..36 {
+            h.add_edge(&[i, i + 1, i + 2, i + 3, i + 4]);
+        }
+
+        let c = weak_coloring(&h);
+
+        assert!(check_weak_coloring(&h, &c));
+        assert_eq!(num_colors(&c), 2);
+        assert!(!check_weak_coloring(&h, &vec![0; 40]));
+    }
+}
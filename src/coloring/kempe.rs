@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use crate::graph::StaticGraph;
+
+/// Returns the Kempe chain of ```v``` for the colors ```a``` and ```b```, the vertices
+/// reachable from ```v``` through vertices colored ```a``` or ```b```, starting with
+/// ```v```. Empty if ```v``` has neither color.
+///
+/// No vertex outside the chain colored ```a``` or ```b``` is adjacent to the chain,
+/// which is what makes ```kempe_swap``` safe.
+pub fn kempe_chain<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+    v: usize,
+    a: usize,
+    b: usize,
+) -> Vec<usize> {
+    if coloring[v] != a && coloring[v] != b {
+        return Vec::new();
+    }
+
+    chain(graph, coloring.len(), v, |u| {
+        coloring[u] == a || coloring[u] == b
+    })
+}
+
+/// Swaps the colors ```a``` and ```b``` on the Kempe chain of ```v``` and returns the
+/// recolored vertices. A valid coloring stays valid, and swapping again restores it.
+pub fn kempe_swap<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &mut [usize],
+    v: usize,
+    a: usize,
+    b: usize,
+) -> Vec<usize> {
+    let chain = kempe_chain(graph, coloring, v, a, b);
+    for &u in &chain {
+        coloring[u] = if coloring[u] == a { b } else { a };
+    }
+
+    chain
+}
+
+/// Returns the vertices reachable from ```start``` through vertices colored ```a```
+/// or ```b``` in a partial coloring. ```start``` itself may have any color.
+pub(crate) fn partial_kempe_chain<G: StaticGraph + ?Sized>(
+    graph: &G,
+    colors: &[Option<usize>],
+    start: usize,
+    a: usize,
+    b: usize,
+) -> Vec<usize> {
+    chain(graph, colors.len(), start, |u| {
+        colors[u] == Some(a) || colors[u] == Some(b)
+    })
+}
+
+fn chain<G: StaticGraph + ?Sized>(
+    graph: &G,
+    n: usize,
+    start: usize,
+    member: impl Fn(usize) -> bool,
+) -> Vec<usize> {
+    let mut visited = vec![false; n];
+    let mut chain = vec![start];
+    let mut queue = VecDeque::from(vec![start]);
+    visited[start] = true;
+
+    while let Some(v) = queue.pop_front() {
+        for u in graph.neighbors(v) {
+            if !visited[u] && member(u) {
+                visited[u] = true;
+                chain.push(u);
+                queue.push_back(u);
+            }
+        }
+    }
+
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, sdo_coloring};
+    use crate::graph::*;
+
+    fn assert_swaps_keep_coloring(g: &AdjList, coloring: &[usize]) {
+        assert!(check_coloring(g, coloring));
+
+        for v in g.vertices() {
+            for a in 0..3 {
+                for b in (0..3).filter(|&b| b != a) {
+                    let chain = kempe_chain(g, coloring, v, a, b);
+                    assert_eq!(chain.is_empty(), coloring[v] != a && coloring[v] != b);
+
+                    // Closed: no neighbor outside the chain has one of the colors
+                    for &u in &chain {
+                        assert!(g
+                            .neighbors(u)
+                            .all(|w| chain.contains(&w) || (coloring[w] != a && coloring[w] != b)));
+                    }
+
+                    let mut swapped = coloring.to_vec();
+                    assert_eq!(kempe_swap(g, &mut swapped, v, a, b), chain);
+                    assert!(check_coloring(g, &swapped));
+                    assert!(chain.iter().all(|&u| swapped[u] != coloring[u]));
+
+                    kempe_swap(g, &mut swapped, v, a, b);
+                    assert_eq!(swapped, coloring);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn kempe_swap_petersen() {
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i, i + 5);
+            g.add_edge(i + 5, (i + 2) % 5 + 5);
+        }
+
+        let coloring = sdo_coloring(&g);
+        assert_swaps_keep_coloring(&g, &coloring);
+        assert_swaps_keep_coloring(&g, &[0, 1, 0, 1, 2, 1, 2, 2, 0, 0]);
+    }
+
+    #[test]
+    fn kempe_swap_prism() {
+        let mut g = AdjList::new();
+        for i in 0..3 {
+            g.add_edge(i, (i + 1) % 3);
+            g.add_edge(i + 3, (i + 1) % 3 + 3);
+            g.add_edge(i, i + 3);
+        }
+
+        let coloring = vec![0, 1, 2, 1, 2, 0];
+        assert_swaps_keep_coloring(&g, &coloring);
+
+        // The chain of 0 and 1 covers the whole graph except the vertices colored 2
+        let mut chain = kempe_chain(&g, &coloring, 0, 0, 1);
+        chain.sort();
+        assert_eq!(chain, vec![0, 1, 3, 5]);
+    }
+}
@@ -0,0 +1,226 @@
+use rand::rngs::StdRng;
+use rand::{random, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+use crate::coloring::{compact_colors, kempe_chain, num_colors, Coloring};
+use crate::graph::StaticGraph;
+
+/// Temperature schedule of ```anneal_coloring```.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnealingSchedule {
+    /// Temperature of the first steps.
+    pub initial_temperature: f64,
+    /// Factor by which the temperature is multiplied after ```steps_per_temperature```
+    /// steps, below 1.
+    pub cooling: f64,
+    pub steps_per_temperature: usize,
+    /// Stop once the temperature falls below this.
+    pub min_temperature: f64,
+    /// Probability of a Kempe chain move rather than a single vertex move.
+    pub kempe_probability: f64,
+    /// Seed of the random number generator. A random seed is used if ```None```.
+    pub seed: Option<u64>,
+}
+
+impl AnnealingSchedule {
+    /// Checks that the temperatures are positive and the cooling factor is between
+    /// 0 and 1, such that the temperature falls below the minimum after finitely
+    /// many steps, and that the Kempe chain probability is a probability. Other
+    /// schedules are reported as ```InvalidInput``` errors.
+    pub fn validate(&self) -> io::Result<()> {
+        let invalid = |msg: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        let positive = |t: f64| t > 0.0 && t.is_finite();
+
+        if !positive(self.initial_temperature) {
+            return invalid("initial temperature must be positive");
+        }
+        if !positive(self.min_temperature) {
+            return invalid("minimum temperature must be positive");
+        }
+        if !(self.cooling > 0.0 && self.cooling < 1.0) {
+            return invalid("cooling factor must be between 0 and 1");
+        }
+        if !(0.0..=1.0).contains(&self.kempe_probability) {
+            return invalid("Kempe chain probability must be between 0 and 1");
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AnnealingSchedule {
+    fn default() -> Self {
+        Self {
+            initial_temperature: 10.0,
+            cooling: 0.95,
+            steps_per_temperature: 1000,
+            min_temperature: 0.01,
+            kempe_probability: 0.5,
+            seed: None,
+        }
+    }
+}
+
+/// Refines a valid coloring by simulated annealing and returns the coloring with
+/// the fewest colors encountered, never more than ```initial```.
+///
+/// Every move keeps the coloring valid. A Kempe chain move swaps two colors on the
+/// component of a random vertex in the subgraph of these colors, a single vertex
+/// move recolors a random vertex with a color none of its neighbors has. Moves are
+/// rated by the sum of squared class sizes, which grows as small classes empty into
+/// large ones. Improving moves are always accepted, others with probability
+/// ```exp(-loss / temperature)```.
+///
+/// Panics if the schedule does not pass ```AnnealingSchedule::validate```.
+pub fn anneal_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    initial: Coloring,
+    schedule: &AnnealingSchedule,
+) -> Coloring {
+    if let Err(e) = schedule.validate() {
+        panic!("invalid annealing schedule: {}", e);
+    }
+
+    let n = graph.num_vertices();
+    let mut best = initial;
+    compact_colors(&mut best);
+    let k = num_colors(&best);
+    if n == 0 || k < 2 {
+        return best;
+    }
+
+    let mut rng = StdRng::seed_from_u64(schedule.seed.unwrap_or_else(random));
+    let mut colors = best.clone();
+    let mut sizes = vec![0usize; k];
+    for &c in &best {
+        sizes[c] += 1;
+    }
+    let (mut used, mut fewest) = (k, k);
+
+    let mut temperature = schedule.initial_temperature;
+    while temperature >= schedule.min_temperature {
+        for _ in 0..schedule.steps_per_temperature {
+            let v = rng.gen_range(0, n);
+            let a = colors[v];
+
+            let (chain, b) = if rng.gen_bool(schedule.kempe_probability) {
+                let b = (a + rng.gen_range(1, k)) % k;
+                (kempe_chain(graph, &colors, v, a, b), b)
+            } else {
+                let mut free = vec![true; k];
+                for u in graph.neighbors(v) {
+                    free[colors[u]] = false;
+                }
+                let free = (0..k).filter(|&c| c != a && free[c]).collect::<Vec<_>>();
+                if free.is_empty() {
+                    continue;
+                }
+                (vec![v], free[rng.gen_range(0, free.len())])
+            };
+
+            let to_b = chain.iter().filter(|&&u| colors[u] == a).count();
+            let to_a = chain.len() - to_b;
+            let (new_a, new_b) = (sizes[a] - to_b + to_a, sizes[b] - to_a + to_b);
+            let gain = (new_a * new_a + new_b * new_b) as f64
+                - (sizes[a] * sizes[a] + sizes[b] * sizes[b]) as f64;
+            if gain < 0.0 && rng.gen::<f64>() >= (gain / temperature).exp() {
+                continue;
+            }
+
+            for &u in &chain {
+                colors[u] = if colors[u] == a { b } else { a };
+            }
+            used = used + (new_a > 0) as usize + (new_b > 0) as usize
+                - (sizes[a] > 0) as usize
+                - (sizes[b] > 0) as usize;
+            sizes[a] = new_a;
+            sizes[b] = new_b;
+
+            if used < fewest {
+                fewest = used;
+                best = colors.clone();
+                compact_colors(&mut best);
+            }
+        }
+
+        temperature *= schedule.cooling;
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, color};
+    use crate::graph::*;
+
+    #[test]
+    fn anneal_reduces_wasteful_coloring() {
+        // Even cycle colored with a color per vertex
+        let mut g = AdjList::new();
+        for v in 0..20 {
+            g.add_edge(v, (v + 1) % 20);
+        }
+        let schedule = AnnealingSchedule {
+            seed: Some(3),
+            ..AnnealingSchedule::default()
+        };
+
+        let c = anneal_coloring(&g, (0..20).collect(), &schedule);
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= 3);
+        assert_eq!(anneal_coloring(&g, (0..20).collect(), &schedule), c);
+    }
+
+    #[test]
+    fn anneal_never_worse() {
+        let g = AdjList::random(80, 0.4);
+        let initial = color(&g);
+        let schedule = AnnealingSchedule {
+            steps_per_temperature: 200,
+            ..AnnealingSchedule::default()
+        };
+
+        let c = anneal_coloring(&g, initial.clone(), &schedule);
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= num_colors(&initial));
+        assert!(anneal_coloring(&AdjList::new(), Vec::new(), &schedule).is_empty());
+    }
+
+    #[test]
+    fn anneal_schedule_validate() {
+        assert!(AnnealingSchedule::default().validate().is_ok());
+
+        for schedule in &[
+            AnnealingSchedule {
+                min_temperature: 0.0,
+                ..AnnealingSchedule::default()
+            },
+            AnnealingSchedule {
+                cooling: 1.0,
+                ..AnnealingSchedule::default()
+            },
+            AnnealingSchedule {
+                cooling: 0.0,
+                ..AnnealingSchedule::default()
+            },
+            AnnealingSchedule {
+                initial_temperature: f64::NAN,
+                ..AnnealingSchedule::default()
+            },
+            AnnealingSchedule {
+                kempe_probability: 1.5,
+                ..AnnealingSchedule::default()
+            },
+            AnnealingSchedule {
+                kempe_probability: f64::NAN,
+                ..AnnealingSchedule::default()
+            },
+        ] {
+            let err = schedule.validate().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+}
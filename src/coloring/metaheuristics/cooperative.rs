@@ -0,0 +1,150 @@
+use rand::{thread_rng, Rng};
+use std::time::{Duration, Instant};
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::metaheuristics::tabucol::tabu_moves;
+use crate::coloring::metaheuristics::{class_ordering, select_kernel, ConflictKernel, Monitor};
+use crate::coloring::presets::eliminate_classes;
+use crate::coloring::{
+    color_balanced, lf_coloring, num_colors, perturbation_restart_coloring_with, rs_coloring,
+    sdo_coloring, sl_coloring, Coloring,
+};
+use crate::graph::StaticGraph;
+
+/// Number of greedy constructions the solver rotates through.
+const CONSTRUCTIONS: usize = 5;
+
+/// Colors the graph by alternating construction and improvement until
+/// ```time_budget``` is used up.
+/// See ```cooperative_coloring_with``` for the method.
+pub fn cooperative_coloring<G: StaticGraph + ?Sized>(graph: &G, time_budget: Duration) -> Coloring {
+    cooperative_coloring_with(graph, time_budget, &mut Monitor::new())
+}
+
+/// Same as ```cooperative_coloring``` but reports every new incumbent to ```monitor```.
+///
+/// Starts from ```color_balanced```, then repeats rounds until the budget is used up
+/// or a clique shows the incumbent to be optimal. Each round constructs a coloring
+/// with the next of the saturation, smallest-last, largest-first, random sequence and
+/// perturbed incumbent orderings, and reduces it by moving whole color classes.
+/// A tabu search then tries to remove one color of the incumbent for a slice of
+/// the budget. Both steps replace the incumbent shared by all strategies whenever
+/// they improve on it.
+pub fn cooperative_coloring_with<G: StaticGraph + ?Sized>(
+    graph: &G,
+    time_budget: Duration,
+    monitor: &mut Monitor,
+) -> Coloring {
+    let start = Instant::now();
+    let deadline = start.checked_add(time_budget);
+    let slice = (time_budget / 20).clamp(Duration::from_millis(1), Duration::from_secs(1));
+
+    let mut rng = thread_rng();
+    let kernel = select_kernel(graph);
+    let lower = greedy_clique(graph).len();
+
+    let mut best = color_balanced(graph);
+    monitor.report(0, &best, 0);
+
+    let mut round = 0;
+    while num_colors(&best) > lower && deadline.is_none_or(|d| Instant::now() < d) {
+        round += 1;
+
+        let constructed = match round % CONSTRUCTIONS {
+            0 => sdo_coloring(graph),
+            1 => sl_coloring(graph),
+            2 => lf_coloring(graph),
+            3 => rs_coloring(graph),
+            _ => {
+                let ordering = class_ordering(&best, &mut rng);
+                perturbation_restart_coloring_with(graph, &ordering, 50, &mut rng)
+            }
+        };
+        let constructed = eliminate_classes(graph, constructed);
+        if num_colors(&constructed) < num_colors(&best) {
+            best = constructed;
+            monitor.report(round, &best, 0);
+        }
+
+        let now = Instant::now();
+        let until = match (now.checked_add(slice), deadline) {
+            (Some(end), Some(d)) => end.min(d),
+            (end, d) => end.or(d).unwrap_or(now),
+        };
+        let k = num_colors(&best) - 1;
+        if k >= lower {
+            if let Some(c) = tabu_search(&*kernel, &best, k, until, &mut rng) {
+                best = eliminate_classes(graph, c);
+                monitor.report(round, &best, 0);
+            }
+        }
+    }
+
+    monitor.finish();
+
+    best
+}
+
+/// Searches a coloring with ```k``` colors until ```deadline```, see ```tabu_moves```.
+fn tabu_search<R: Rng>(
+    kernel: &dyn ConflictKernel,
+    coloring: &[usize],
+    k: usize,
+    deadline: Instant,
+    rng: &mut R,
+) -> Option<Coloring> {
+    tabu_moves(
+        kernel,
+        coloring,
+        k,
+        |step| step % 128 == 0 && Instant::now() >= deadline,
+        rng,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::coloring::metaheuristics::ListKernel;
+    use crate::graph::*;
+
+    #[test]
+    fn cooperative_random() {
+        let g = AdjList::random(80, 0.5);
+        let mut reported = 0;
+
+        let c = cooperative_coloring_with(
+            &g,
+            Duration::from_millis(300),
+            &mut Monitor::new().on_improvement(|_| reported += 1),
+        );
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= g.max_degree() + 1);
+        assert!(reported >= 1);
+    }
+
+    #[test]
+    fn tabu_finds_optimum() {
+        // Complete tripartite graph colored wastefully
+        let mut g = AdjList::new();
+        for u in 0..30 {
+            for v in (u + 1..30).filter(|v| v % 3 != u % 3) {
+                g.add_edge(u, v);
+            }
+        }
+        let start = (0..30).map(|v| v % 6).collect::<Coloring>();
+
+        let c = tabu_search(
+            &ListKernel::new(&g),
+            &start,
+            3,
+            Instant::now() + Duration::from_secs(5),
+            &mut thread_rng(),
+        );
+
+        assert!(check_coloring(&g, &c.unwrap()));
+        assert!(cooperative_coloring(&AdjList::new(), Duration::from_millis(10)).is_empty());
+    }
+}
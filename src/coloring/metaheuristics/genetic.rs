@@ -0,0 +1,472 @@
+use rand::rngs::StdRng;
+use rand::{random, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::coloring::metaheuristics::Monitor;
+use crate::coloring::{
+    check_coloring, greedy_coloring_with, lf_coloring, sl_coloring, Coloring, ColoringWorkspace,
+};
+use crate::graph::StaticGraph;
+
+/// Crossover operators combining the vertex orderings of two parents.
+/// All operators take some positions from the first parent and fill the
+/// remaining positions with the missing vertices in the order of the second
+/// parent, which always results in a proper permutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crossover {
+    /// Take a prefix of random length from the first parent.
+    SinglePoint,
+    /// Take a random contiguous segment from the first parent.
+    TwoPoint,
+    /// Take every position from the first parent with probability 0.5.
+    Uniform,
+}
+
+/// Composition of the initial population.
+/// The given fractions of the population are seeded from the smallest-last and
+/// largest-first colorings respectively, the rest consists of random orderings.
+/// Seeded orderings list the vertices color class by color class, randomly
+/// shuffled within each class.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Initialization {
+    pub sl: f32,
+    pub lf: f32,
+}
+
+impl Default for Initialization {
+    fn default() -> Self {
+        Self { sl: 0.0, lf: 0.0 }
+    }
+}
+
+/// Parameters of the genetic algorithm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneticParams {
+    /// Number of individuals in each generation.
+    pub population: usize,
+    /// Maximal number of generations.
+    pub generations: usize,
+    /// Operator used to combine two parents.
+    pub crossover: Crossover,
+    /// Probability with which each position of an offspring is swapped with
+    /// the next one.
+    pub mutation_rate: f32,
+    /// Number of best individuals carried over unchanged into the next generation.
+    /// At least one offspring is created per generation.
+    pub elitism: usize,
+    /// Seed of the random number generator. A random seed is used if ```None```.
+    pub seed: Option<u64>,
+    /// Stop after the generation during which this much time has passed.
+    pub time_budget: Option<Duration>,
+    /// Composition of the initial population.
+    pub init: Initialization,
+}
+
+impl Default for GeneticParams {
+    fn default() -> Self {
+        Self {
+            population: 50,
+            generations: 50,
+            crossover: Crossover::SinglePoint,
+            mutation_rate: 0.2,
+            elitism: 25,
+            seed: None,
+            time_budget: None,
+            init: Initialization::default(),
+        }
+    }
+}
+
+/// Genetic algorithm evolving vertex orderings for the greedy coloring.
+/// The fitness of an ordering is the number of colors the greedy coloring
+/// uses with it. Parents are chosen among the better half of each generation.
+pub fn genetic_coloring<G: StaticGraph + ?Sized>(g: &G) -> Coloring {
+    genetic_coloring_with(g, &GeneticParams::default(), &mut Monitor::new())
+}
+
+/// Same as ```genetic_coloring``` but with the given parameters.
+/// The best coloring of each generation is reported to ```monitor```.
+pub fn genetic_coloring_with<G: StaticGraph + ?Sized>(
+    g: &G,
+    params: &GeneticParams,
+    monitor: &mut Monitor,
+) -> Coloring {
+    let start = Instant::now();
+
+    if g.num_vertices() == 0 {
+        return Coloring::new();
+    }
+
+    let mut population = Population::new(g, params, params.seed.unwrap_or_else(random));
+
+    for generation in 0..=params.generations {
+        if let Some((coloring, _)) = population.evaluate(g) {
+            monitor.report(generation, coloring, 0);
+        }
+
+        let out_of_time = params.time_budget.is_some_and(|b| start.elapsed() >= b);
+        if generation == params.generations || out_of_time {
+            break;
+        }
+
+        population.evolve(params);
+    }
+
+    monitor.finish();
+
+    population.into_best().0
+}
+
+/// Population of vertex orderings evolved by the genetic algorithm.
+pub(super) struct Population {
+    // Orderings with their number of colors, if already evaluated
+    orderings: Vec<(Vec<usize>, Option<usize>)>,
+    best: Option<(Coloring, usize)>,
+    rng: StdRng,
+    workspace: ColoringWorkspace,
+}
+
+impl Population {
+    /// Constructs the initial population for a graph with at least one vertex.
+    pub(super) fn new<G: StaticGraph + ?Sized>(g: &G, params: &GeneticParams, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let n = params.population.max(2);
+
+        let orderings = initial_orderings(g, n, &params.init, &mut rng)
+            .into_iter()
+            .map(|o| (o, None))
+            .collect();
+
+        Self {
+            orderings,
+            best: None,
+            rng,
+            workspace: ColoringWorkspace::new(),
+        }
+    }
+
+    /// Computes the number of colors of all orderings not yet evaluated.
+    /// Returns the best coloring and its number of colors if it improved.
+    pub(super) fn evaluate<G: StaticGraph + ?Sized>(
+        &mut self,
+        g: &G,
+    ) -> Option<&(Coloring, usize)> {
+        let mut improved = false;
+
+        for ordering in self.orderings.iter_mut().filter(|o| o.1.is_none()) {
+            let coloring = greedy_coloring_with(g, &ordering.0, &mut self.workspace);
+            assert!(check_coloring(g, coloring));
+            let num_col = self.workspace.num_colors();
+            ordering.1 = Some(num_col);
+
+            match self.best {
+                Some((_, b)) if b <= num_col => {}
+                Some((ref mut best, ref mut b)) => {
+                    best.copy_from_slice(self.workspace.coloring());
+                    *b = num_col;
+                    improved = true;
+                }
+                None => {
+                    self.best = Some((self.workspace.coloring().to_vec(), num_col));
+                    improved = true;
+                }
+            }
+        }
+
+        if improved {
+            self.best.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the population by the next generation.
+    /// All orderings must have been evaluated.
+    pub(super) fn evolve(&mut self, params: &GeneticParams) {
+        let n = self.orderings.len();
+        let parents = n / 2;
+        let elitism = params.elitism.min(n - 1);
+
+        self.orderings.sort_by_key(|o| o.1);
+
+        let mut next = self.orderings[..elitism].to_vec();
+        while next.len() < n {
+            let mom = self.rng.gen_range(0, parents);
+            let dad = self.rng.gen_range(0, parents);
+
+            let mut child = crossover(
+                params.crossover,
+                &self.orderings[mom].0,
+                &self.orderings[dad].0,
+                &mut self.rng,
+            );
+            mutate(&mut child, params.mutation_rate, &mut self.rng);
+
+            next.push((child, None));
+        }
+
+        self.orderings = next;
+    }
+
+    /// Returns copies of the ```k``` best evaluated orderings with their number of colors.
+    #[cfg(feature = "parallel")]
+    pub(super) fn emigrants(&mut self, k: usize) -> Vec<(Vec<usize>, usize)> {
+        self.orderings.sort_by_key(|o| o.1.unwrap_or(usize::MAX));
+
+        self.orderings
+            .iter()
+            .filter_map(|(o, c)| c.map(|c| (o.clone(), c)))
+            .take(k)
+            .collect()
+    }
+
+    /// Replaces the worst orderings of the population by the given ones.
+    #[cfg(feature = "parallel")]
+    pub(super) fn immigrate(&mut self, orderings: Vec<(Vec<usize>, usize)>) {
+        self.orderings.sort_by_key(|o| o.1.unwrap_or(usize::MAX));
+
+        let n = self.orderings.len();
+        for (i, (ordering, num_col)) in orderings.into_iter().take(n - 1).enumerate() {
+            self.orderings[n - 1 - i] = (ordering, Some(num_col));
+        }
+    }
+
+    /// Returns the best coloring found and its number of colors.
+    /// Panics if the population was never evaluated.
+    pub(super) fn into_best(self) -> (Coloring, usize) {
+        self.best.unwrap()
+    }
+}
+
+/// Build the initial population of orderings according to ```init```.
+fn initial_orderings<G: StaticGraph + ?Sized>(
+    g: &G,
+    n: usize,
+    init: &Initialization,
+    rng: &mut StdRng,
+) -> Vec<Vec<usize>> {
+    let n_sl = ((init.sl * n as f32).round() as usize).min(n);
+    let n_lf = ((init.lf * n as f32).round() as usize).min(n - n_sl);
+    let mut orderings = Vec::with_capacity(n);
+
+    // Smallest-last breaks ties arbitrarily, so each call may give a different coloring
+    for _ in 0..n_sl {
+        orderings.push(class_ordering(&sl_coloring(g), rng));
+    }
+
+    if n_lf > 0 {
+        let lf = lf_coloring(g);
+        for _ in 0..n_lf {
+            orderings.push(class_ordering(&lf, rng));
+        }
+    }
+
+    // Random initialization
+    while orderings.len() < n {
+        let mut permutation = (0..g.num_vertices()).collect::<Vec<usize>>();
+        permutation[..].shuffle(rng);
+
+        orderings.push(permutation);
+    }
+
+    orderings
+}
+
+/// Ordering of the vertices by color class, randomly shuffled within each class.
+/// Greedily coloring the vertices in this order never uses more colors than
+/// the given coloring.
+pub(crate) fn class_ordering<R: Rng + ?Sized>(coloring: &[usize], rng: &mut R) -> Vec<usize> {
+    let mut ordering = (0..coloring.len()).collect::<Vec<usize>>();
+    ordering[..].shuffle(rng);
+    ordering.sort_by_key(|&v| coloring[v]);
+
+    ordering
+}
+
+/// Combine the orderings of two parents into a new ordering.
+fn crossover(kind: Crossover, mom: &[usize], dad: &[usize], rng: &mut StdRng) -> Vec<usize> {
+    let n_vert = mom.len();
+
+    // Positions taken from mom
+    let mut keep = vec![false; n_vert];
+    match kind {
+        Crossover::SinglePoint => {
+            let split = rng.gen_range(0, n_vert);
+            keep[..split].iter_mut().for_each(|k| *k = true);
+        }
+        Crossover::TwoPoint => {
+            let a = rng.gen_range(0, n_vert);
+            let b = rng.gen_range(0, n_vert);
+            keep[a.min(b)..a.max(b)].iter_mut().for_each(|k| *k = true);
+        }
+        Crossover::Uniform => {
+            keep.iter_mut().for_each(|k| *k = rng.gen::<bool>());
+        }
+    }
+
+    let mut child = vec![0; n_vert];
+    let mut have = vec![false; n_vert];
+    for (i, _) in keep.iter().enumerate().filter(|(_, &k)| k) {
+        child[i] = mom[i];
+        have[mom[i]] = true;
+    }
+
+    // Take remaining elements in order specified by dad
+    // -> Leads to a proper permutation
+    let mut rest = dad.iter().filter(|&&v| !have[v]);
+    for (i, _) in keep.iter().enumerate().filter(|(_, &k)| !k) {
+        child[i] = *rest.next().unwrap();
+    }
+
+    child
+}
+
+/// Swap each position with the next one with probability ```rate```.
+fn mutate(ordering: &mut [usize], rate: f32, rng: &mut StdRng) {
+    for j in 1..ordering.len() {
+        if rng.gen::<f32>() < rate {
+            ordering.swap(j - 1, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{greedy_coloring, num_colors};
+    use crate::graph::*;
+
+    fn is_permutation(ordering: &[usize]) -> bool {
+        let mut sorted = ordering.to_vec();
+        sorted.sort();
+        sorted.into_iter().eq(0..ordering.len())
+    }
+
+    #[test]
+    fn genetic_random() {
+        let g = AdjList::random(50, 0.5);
+
+        let c = genetic_coloring(&g);
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= g.max_degree() + 1);
+    }
+
+    #[test]
+    fn genetic_empty() {
+        let g = AdjList::new();
+
+        let c = genetic_coloring(&g);
+
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn genetic_monitor() {
+        let g = AdjList::random(50, 0.5);
+        let mut improvements = Vec::new();
+
+        let c = {
+            let mut monitor = Monitor::new().on_improvement(|p| {
+                improvements.push((p.iteration, p.num_colors));
+            });
+            genetic_coloring_with(&g, &GeneticParams::default(), &mut monitor)
+        };
+
+        assert!(!improvements.is_empty());
+        assert!(improvements.windows(2).all(|w| w[0].1 > w[1].1));
+        assert_eq!(improvements.last().unwrap().1, num_colors(&c));
+    }
+
+    #[test]
+    fn genetic_seeded() {
+        let g = AdjList::random(50, 0.5);
+        let params = GeneticParams {
+            generations: 10,
+            seed: Some(42),
+            ..GeneticParams::default()
+        };
+
+        let c1 = genetic_coloring_with(&g, &params, &mut Monitor::new());
+        let c2 = genetic_coloring_with(&g, &params, &mut Monitor::new());
+
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn genetic_time_budget() {
+        let g = AdjList::random(50, 0.5);
+        let params = GeneticParams {
+            generations: usize::MAX,
+            time_budget: Some(Duration::from_millis(50)),
+            ..GeneticParams::default()
+        };
+
+        let c = genetic_coloring_with(&g, &params, &mut Monitor::new());
+
+        assert!(check_coloring(&g, &c));
+    }
+
+    #[test]
+    fn genetic_params() {
+        let g = AdjList::random(50, 0.5);
+
+        for &crossover in &[
+            Crossover::SinglePoint,
+            Crossover::TwoPoint,
+            Crossover::Uniform,
+        ] {
+            let params = GeneticParams {
+                population: 20,
+                generations: 10,
+                crossover,
+                mutation_rate: 0.05,
+                elitism: 2,
+                init: Initialization { sl: 0.25, lf: 0.25 },
+                ..GeneticParams::default()
+            };
+
+            let c = genetic_coloring_with(&g, &params, &mut Monitor::new());
+
+            assert!(check_coloring(&g, &c));
+            assert!(num_colors(&c) <= num_colors(&lf_coloring(&g)));
+        }
+    }
+
+    #[test]
+    fn crossover_permutation() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut mom = (0..30).collect::<Vec<usize>>();
+        let mut dad = mom.clone();
+
+        for &kind in &[
+            Crossover::SinglePoint,
+            Crossover::TwoPoint,
+            Crossover::Uniform,
+        ] {
+            for _ in 0..20 {
+                mom[..].shuffle(&mut rng);
+                dad[..].shuffle(&mut rng);
+
+                let mut child = crossover(kind, &mom, &dad, &mut rng);
+                assert!(is_permutation(&child));
+
+                mutate(&mut child, 0.5, &mut rng);
+                assert!(is_permutation(&child));
+            }
+        }
+    }
+
+    #[test]
+    fn class_ordering_colors() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let g = AdjList::random(50, 0.5);
+        let c = sl_coloring(&g);
+
+        let ordering = class_ordering(&c, &mut rng);
+        let c2 = greedy_coloring(&g, ordering.into_iter());
+
+        assert!(num_colors(&c2) <= num_colors(&c));
+    }
+}
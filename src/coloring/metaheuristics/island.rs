@@ -0,0 +1,208 @@
+use rand::random;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::coloring::metaheuristics::genetic::Population;
+use crate::coloring::metaheuristics::{GeneticParams, Monitor};
+use crate::coloring::Coloring;
+use crate::graph::StaticGraph;
+
+/// Parameters of the island model genetic algorithm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IslandParams {
+    /// Number of subpopulations, each evolved on its own thread.
+    pub islands: usize,
+    /// Number of generations between two migrations.
+    /// No migration takes place if this is ```0```.
+    pub migration_interval: usize,
+    /// Number of best individuals each island sends to its neighbor per migration.
+    pub migrants: usize,
+    /// Parameters of the genetic algorithm run on each island.
+    /// If a seed is given, island ```i``` uses ```seed + i```.
+    pub genetic: GeneticParams,
+}
+
+impl Default for IslandParams {
+    fn default() -> Self {
+        Self {
+            islands: num_cpus::get(),
+            migration_interval: 10,
+            migrants: 2,
+            genetic: GeneticParams::default(),
+        }
+    }
+}
+
+/// Island model genetic algorithm.
+/// Runs ```genetic_coloring``` on one subpopulation per processor. The islands are
+/// arranged in a ring and periodically send their best orderings to the next
+/// island, where they replace the worst orderings.
+pub fn island_coloring<G: StaticGraph + Sync + ?Sized>(g: &G) -> Coloring {
+    island_coloring_with(g, &IslandParams::default(), &mut Monitor::new())
+}
+
+/// Same as ```island_coloring``` but with the given parameters.
+/// Improvements found on any island are reported to ```monitor```.
+pub fn island_coloring_with<G: StaticGraph + Sync + ?Sized>(
+    g: &G,
+    params: &IslandParams,
+    monitor: &mut Monitor,
+) -> Coloring {
+    let start = Instant::now();
+    let islands = params.islands.max(1);
+    let seed = params.genetic.seed.unwrap_or_else(random);
+
+    if g.num_vertices() == 0 {
+        return Coloring::new();
+    }
+
+    // Communication channel between islands and main thread
+    let (tx_best, rx_best) = crossbeam::unbounded();
+
+    // Ring of migration channels, island 'i' receives on 'rx_migration[i]'
+    let (tx_migration, rx_migration): (Vec<_>, Vec<_>) =
+        (0..islands).map(|_| crossbeam::unbounded()).unzip();
+
+    let result = crossbeam::scope(|s| {
+        let mut handles = Vec::new();
+
+        for (i, rx_) in rx_migration.into_iter().enumerate() {
+            // Send migrants to the next island in the ring
+            let tx_ = tx_migration[(i + 1) % islands].clone();
+
+            // Send improvements over tx_best_ to main thread
+            let tx_best_ = tx_best.clone();
+
+            let genetic = &params.genetic;
+            let interval = params.migration_interval;
+            let migrants = params.migrants;
+
+            handles.push(s.spawn(move |_| {
+                let mut population = Population::new(g, genetic, seed.wrapping_add(i as u64));
+
+                for generation in 0..=genetic.generations {
+                    if let Some((coloring, _)) = population.evaluate(g) {
+                        tx_best_.send((generation, coloring.clone())).unwrap();
+                    }
+
+                    let out_of_time = genetic.time_budget.is_some_and(|b| start.elapsed() >= b);
+                    if generation == genetic.generations || out_of_time {
+                        break;
+                    }
+
+                    if interval > 0 && generation > 0 && generation % interval == 0 {
+                        tx_.send(population.emigrants(migrants)).unwrap_or(());
+
+                        // Neighbor may already have stopped, in which case we carry on alone
+                        if let Ok(immigrants) = rx_.recv() {
+                            population.immigrate(immigrants);
+                        }
+                    }
+
+                    population.evolve(genetic);
+                }
+
+                population.into_best()
+            }));
+        }
+
+        // Drop original senders such that the receivers notice when islands are done
+        drop(tx_best);
+        drop(tx_migration);
+
+        for (generation, coloring) in rx_best.iter() {
+            monitor.report(generation, &coloring, 0);
+        }
+
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .min_by_key(|&(_, num_col)| num_col)
+            .unwrap()
+            .0
+    })
+    .unwrap();
+
+    monitor.finish();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn island_random() {
+        let g = AdjList::random(50, 0.5);
+        let params = IslandParams {
+            islands: 4,
+            migration_interval: 5,
+            genetic: GeneticParams {
+                population: 20,
+                generations: 20,
+                elitism: 5,
+                ..GeneticParams::default()
+            },
+            ..IslandParams::default()
+        };
+
+        let c = island_coloring_with(&g, &params, &mut Monitor::new());
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= g.max_degree() + 1);
+    }
+
+    #[test]
+    fn island_seeded() {
+        let g = AdjList::random(50, 0.5);
+        let params = IslandParams {
+            islands: 3,
+            migration_interval: 2,
+            genetic: GeneticParams {
+                population: 10,
+                generations: 10,
+                seed: Some(7),
+                ..GeneticParams::default()
+            },
+            ..IslandParams::default()
+        };
+
+        let c1 = island_coloring_with(&g, &params, &mut Monitor::new());
+        let c2 = island_coloring_with(&g, &params, &mut Monitor::new());
+
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn island_monitor() {
+        let g = AdjList::random(50, 0.5);
+        let params = IslandParams {
+            islands: 2,
+            ..IslandParams::default()
+        };
+        let mut best = None;
+
+        let c = {
+            let mut monitor = Monitor::new().on_improvement(|p| best = Some(p.num_colors));
+            island_coloring_with(&g, &params, &mut monitor)
+        };
+
+        assert_eq!(best, Some(num_colors(&c)));
+    }
+
+    #[test]
+    fn island_single() {
+        let g = AdjList::random(30, 0.3);
+        let params = IslandParams {
+            islands: 1,
+            ..IslandParams::default()
+        };
+
+        let c = island_coloring_with(&g, &params, &mut Monitor::new());
+
+        assert!(check_coloring(&g, &c));
+    }
+}
@@ -0,0 +1,92 @@
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+use crate::coloring::{color_classes, dsatur_coloring, greedy_coloring, num_colors, Coloring};
+use crate::graph::StaticGraph;
+
+/// Improves ```dsatur_coloring``` by iterated greedy, known as IG, for ```iterations```
+/// rounds.
+///
+/// Every round concatenates the color classes of the current coloring in a new
+/// order and colors the vertices greedily in this order. As the vertices of a class
+/// are independent, the result never uses more colors than the current coloring,
+/// and reordering the classes often lets a small class dissolve into earlier ones.
+/// Classes are ordered by decreasing size, reversed or shuffled, chosen at random
+/// with weights 5, 5 and 3.
+pub fn iterated_greedy<G: StaticGraph + ?Sized>(graph: &G, iterations: usize) -> Coloring {
+    iterated_greedy_with(graph, iterations, &mut thread_rng())
+}
+
+/// Same as ```iterated_greedy``` but draws the class orders from ```rng```, such that
+/// a seeded generator reproduces the coloring.
+pub fn iterated_greedy_with<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    iterations: usize,
+    rng: &mut R,
+) -> Coloring {
+    iterated_greedy_from(graph, dsatur_coloring(graph), iterations, rng)
+}
+
+fn iterated_greedy_from<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    mut coloring: Coloring,
+    iterations: usize,
+    rng: &mut R,
+) -> Coloring {
+    for _ in 0..iterations {
+        let mut classes = color_classes(&coloring);
+        match rng.gen_range(0, 13) {
+            0..=4 => classes.sort_by_key(|class| usize::MAX - class.len()),
+            5..=9 => classes.reverse(),
+            _ => classes.shuffle(rng),
+        }
+
+        let next = greedy_coloring(graph, classes.into_iter().flatten());
+        debug_assert!(num_colors(&next) <= num_colors(&coloring));
+        coloring = next;
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn iterated_greedy_never_worse() {
+        let g = AdjList::random(100, 0.3);
+        let start = dsatur_coloring(&g);
+        let c = iterated_greedy(&g, 200);
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= num_colors(&start));
+        assert!(iterated_greedy(&AdjList::new(), 10).is_empty());
+    }
+
+    #[test]
+    fn iterated_greedy_seeded() {
+        let g = AdjList::random_with(100, |_, _| 0.3, &mut StdRng::seed_from_u64(2));
+        let seeded = |seed| iterated_greedy_with(&g, 50, &mut StdRng::seed_from_u64(seed));
+
+        assert_eq!(seeded(3), seeded(3));
+    }
+
+    #[test]
+    fn iterated_greedy_merges_classes() {
+        // Even cycle colored with a color per vertex
+        let mut g = AdjList::new();
+        for v in 0..20 {
+            g.add_edge(v, (v + 1) % 20);
+        }
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let c = iterated_greedy_from(&g, (0..20).collect(), 5, &mut rng);
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= 3);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::analysis::degrees;
+use crate::graph::StaticGraph;
+
+/// Density from which ```select_kernel``` prefers the bitset kernel.
+const DENSE: f64 = 0.5;
+
+/// Computations repeated in every iteration of the tabu searches. On dense graphs
+/// these dominate the runtime, implementors may offload them to an accelerator.
+/// ```ListKernel``` and ```BitsetKernel``` are implementations on the CPU for sparse
+/// and dense graphs.
+///
+/// The tabu searches keep the number of neighbors of every vertex in each color as
+/// computed by ```color_counts```, and update it by ```move_counts``` after every move.
+pub trait ConflictKernel {
+    /// Number of vertices of the graph.
+    fn num_vertices(&self) -> usize;
+
+    /// Writes the number of neighbors of every vertex in each of the colors
+    /// ```0..k``` to ```counts```, vertex ```v``` and color ```c``` at ```v * k + c```.
+    fn color_counts(&self, coloring: &[usize], k: usize, counts: &mut Vec<usize>);
+
+    /// Updates ```counts``` as computed by ```color_counts``` for vertex ```v``` moving
+    /// from color ```old```, or from no color below ```k```, to color ```new```.
+    fn move_counts(&self, v: usize, old: Option<usize>, new: usize, k: usize, counts: &mut [usize]);
+}
+
+/// Returns ```BitsetKernel``` for graphs with at least half of all possible edges
+/// and ```ListKernel``` otherwise.
+pub fn select_kernel<G: StaticGraph + ?Sized>(graph: &G) -> Box<dyn ConflictKernel> {
+    if is_dense(graph) {
+        Box::new(BitsetKernel::new(graph))
+    } else {
+        Box::new(ListKernel::new(graph))
+    }
+}
+
+fn is_dense<G: StaticGraph + ?Sized>(graph: &G) -> bool {
+    let n = graph.num_vertices();
+    let arcs = degrees(graph).iter().sum::<usize>();
+
+    n >= 2 && arcs as f64 >= DENSE * (n * (n - 1)) as f64
+}
+
+/// Adjacency lists. Every operation visits the neighbors of the vertices involved.
+#[derive(Debug, Clone)]
+pub struct ListKernel {
+    adj: Vec<Vec<usize>>,
+}
+
+impl ListKernel {
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let adj = (0..graph.num_vertices())
+            .map(|v| graph.neighbors(v).collect())
+            .collect();
+
+        Self { adj }
+    }
+}
+
+impl ConflictKernel for ListKernel {
+    fn num_vertices(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn color_counts(&self, coloring: &[usize], k: usize, counts: &mut Vec<usize>) {
+        counts.clear();
+        counts.resize(self.adj.len() * k, 0);
+        for (v, neighbors) in self.adj.iter().enumerate() {
+            for &u in neighbors.iter().filter(|&&u| coloring[u] < k) {
+                counts[v * k + coloring[u]] += 1;
+            }
+        }
+    }
+
+    fn move_counts(
+        &self,
+        v: usize,
+        old: Option<usize>,
+        new: usize,
+        k: usize,
+        counts: &mut [usize],
+    ) {
+        for &u in &self.adj[v] {
+            if let Some(old) = old {
+                counts[u * k + old] -= 1;
+            }
+            counts[u * k + new] += 1;
+        }
+    }
+}
+
+/// Adjacency matrix with rows of bits. Neighbors within a color class are counted
+/// by intersecting a row with the bitset of the class, 64 vertices at a time.
+#[derive(Debug, Clone)]
+pub struct BitsetKernel {
+    rows: Vec<Vec<u64>>,
+    words: usize,
+}
+
+impl BitsetKernel {
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let n = graph.num_vertices();
+        let words = n.div_ceil(64);
+
+        let rows = (0..n)
+            .map(|v| {
+                let mut row = vec![0; words];
+                for u in graph.neighbors(v) {
+                    row[u / 64] |= 1 << (u % 64);
+                }
+                row
+            })
+            .collect();
+
+        Self { rows, words }
+    }
+
+    /// Bitsets of the vertices in each color class.
+    fn classes(&self, coloring: &[usize]) -> Vec<Vec<u64>> {
+        let mut classes: Vec<Vec<u64>> = Vec::new();
+        for (v, &c) in coloring.iter().enumerate() {
+            if c >= classes.len() {
+                classes.resize(c + 1, vec![0; self.words]);
+            }
+            classes[c][v / 64] |= 1 << (v % 64);
+        }
+
+        classes
+    }
+
+    fn common(&self, v: usize, class: &[u64]) -> usize {
+        self.rows[v]
+            .iter()
+            .zip(class)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+}
+
+impl ConflictKernel for BitsetKernel {
+    fn num_vertices(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn color_counts(&self, coloring: &[usize], k: usize, counts: &mut Vec<usize>) {
+        let classes = self.classes(coloring);
+
+        counts.clear();
+        counts.resize(self.rows.len() * k, 0);
+        for v in 0..self.rows.len() {
+            for (c, class) in classes.iter().enumerate().take(k) {
+                counts[v * k + c] = self.common(v, class);
+            }
+        }
+    }
+
+    fn move_counts(
+        &self,
+        v: usize,
+        old: Option<usize>,
+        new: usize,
+        k: usize,
+        counts: &mut [usize],
+    ) {
+        for (i, &word) in self.rows[v].iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let u = i * 64 + word.trailing_zeros() as usize;
+                word &= word - 1;
+
+                if let Some(old) = old {
+                    counts[u * k + old] -= 1;
+                }
+                counts[u * k + new] += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn kernels_match_graph() {
+        let g = AdjList::random(150, 0.4);
+        let bitset = BitsetKernel::new(&g);
+        let list = ListKernel::new(&g);
+        for kernel in &[&bitset as &dyn ConflictKernel, &list] {
+            kernel_matches_graph(&g, *kernel);
+        }
+    }
+
+    #[test]
+    fn kernel_by_density() {
+        assert!(!is_dense(&AdjList::new()));
+        assert!(!is_dense(&AdjList::random(100, 0.1)));
+        assert!(is_dense(&AdjList::random(100, 0.9)));
+        assert_eq!(
+            select_kernel(&AdjList::random(100, 0.9)).num_vertices(),
+            100
+        );
+    }
+
+    fn kernel_matches_graph(g: &AdjList, kernel: &dyn ConflictKernel) {
+        let mut coloring = (0..150).map(|v| v % 7).collect::<Vec<_>>();
+
+        let mut counts = Vec::new();
+        kernel.color_counts(&coloring, 7, &mut counts);
+        for v in 0..150 {
+            for c in 0..7 {
+                let expected = g.neighbors(v).filter(|&u| coloring[u] == c).count();
+                assert_eq!(counts[v * 7 + c], expected);
+            }
+        }
+
+        // Vertex 10 starts without a color below k
+        coloring[10] = 9;
+        kernel.color_counts(&coloring, 7, &mut counts);
+        kernel.move_counts(3, Some(3), 5, 7, &mut counts);
+        kernel.move_counts(10, None, 0, 7, &mut counts);
+        coloring[3] = 5;
+        coloring[10] = 0;
+
+        let mut expected = Vec::new();
+        kernel.color_counts(&coloring, 7, &mut expected);
+        assert_eq!(counts, expected);
+    }
+}
@@ -0,0 +1,40 @@
+//! Metaheuristics which search for good colorings over many iterations
+//! rather than constructing a single coloring.
+//!
+//! Long running searches can be observed with a ```Monitor```, which is
+//! informed whenever the search finds a better coloring.
+//!
+//! The tabu searches count the conflicts of their moves with a ```ConflictKernel```,
+//! chosen by ```select_kernel``` from the density of the graph. ```tabucol_with```
+//! accepts other kernels, for example to offload the counting on very dense graphs.
+//!
+//! The cooperative solver behind ```color_best``` alternates greedy constructions
+//! with tabu search, sharing the best coloring between them. The same tabu search
+//! is available on its own as ```tabucol``` for a given number of colors.
+//! ```anneal_coloring``` refines an existing coloring by simulated annealing, and
+//! ```iterated_greedy``` by recoloring greedily with reordered color classes.
+//!
+//! The island model runs on multiple threads and requires the ```parallel``` feature.
+
+mod anneal;
+mod cooperative;
+mod genetic;
+#[cfg(feature = "parallel")]
+mod island;
+mod iterated_greedy;
+mod kernel;
+mod monitor;
+pub(crate) mod tabucol;
+
+pub use self::anneal::{anneal_coloring, AnnealingSchedule};
+pub use self::cooperative::{cooperative_coloring, cooperative_coloring_with};
+pub(crate) use self::genetic::class_ordering;
+pub use self::genetic::{
+    genetic_coloring, genetic_coloring_with, Crossover, GeneticParams, Initialization,
+};
+#[cfg(feature = "parallel")]
+pub use self::island::{island_coloring, island_coloring_with, IslandParams};
+pub use self::iterated_greedy::{iterated_greedy, iterated_greedy_with};
+pub use self::kernel::{select_kernel, BitsetKernel, ConflictKernel, ListKernel};
+pub use self::monitor::{Monitor, Progress};
+pub use self::tabucol::{tabu_coloring, tabucol, tabucol_with};
@@ -0,0 +1,259 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::coloring::{num_colors, Coloring};
+
+type Hook<'a> = Box<dyn FnMut(&Progress) + 'a>;
+
+/// State of a search at the time it found an improved coloring.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress<'a> {
+    /// Iteration (or generation) in which the coloring was found.
+    pub iteration: usize,
+    /// The improved coloring.
+    pub coloring: &'a Coloring,
+    /// Number of colors used by the coloring.
+    pub num_colors: usize,
+    /// Number of edges whose endpoints share a color.
+    pub conflicts: usize,
+    /// Time passed since the monitor was created.
+    pub elapsed: Duration,
+}
+
+/// Observes a metaheuristic and keeps track of the best coloring it found so far.
+///
+/// A coloring is better than another if it has fewer conflicts or, with equally
+/// many conflicts, uses fewer colors.
+/// Every improvement is passed to the hook registered with ```on_improvement```.
+/// If a snapshot file is configured with ```snapshot```, the best coloring is
+/// written to it at most once per interval, such that a crash during a long run
+/// does not lose the incumbent.
+pub struct Monitor<'a> {
+    start: Instant,
+    best: Option<(Coloring, usize, usize)>,
+    hook: Option<Hook<'a>>,
+    snapshot: Option<Snapshot>,
+    error: Option<io::Error>,
+}
+
+struct Snapshot {
+    path: PathBuf,
+    interval: Duration,
+    last_write: Option<Instant>,
+    pending: Option<(usize, Duration)>,
+}
+
+impl<'a> Monitor<'a> {
+    /// Constructs a monitor without hook and snapshot file.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            best: None,
+            hook: None,
+            snapshot: None,
+            error: None,
+        }
+    }
+
+    /// Calls ```hook``` whenever the search finds a better coloring.
+    pub fn on_improvement(mut self, hook: impl FnMut(&Progress) + 'a) -> Self {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Writes the best coloring to ```path``` at most every ```interval```.
+    /// The file is replaced atomically, so it always contains a complete coloring.
+    pub fn snapshot(mut self, path: impl AsRef<Path>, interval: Duration) -> Self {
+        self.snapshot = Some(Snapshot {
+            path: path.as_ref().to_path_buf(),
+            interval,
+            last_write: None,
+            pending: None,
+        });
+        self
+    }
+
+    /// Reports the current coloring of the search.
+    /// Returns ```true``` if it improves on all previously reported colorings.
+    pub fn report(&mut self, iteration: usize, coloring: &Coloring, conflicts: usize) -> bool {
+        let colors = num_colors(coloring);
+
+        if let Some((_, best_colors, best_conflicts)) = self.best {
+            if (conflicts, colors) >= (best_conflicts, best_colors) {
+                return false;
+            }
+        }
+
+        let elapsed = self.start.elapsed();
+        self.best = Some((coloring.clone(), colors, conflicts));
+
+        if let Some(ref mut hook) = self.hook {
+            hook(&Progress {
+                iteration,
+                coloring,
+                num_colors: colors,
+                conflicts,
+                elapsed,
+            });
+        }
+
+        if let Some(ref mut snapshot) = self.snapshot {
+            snapshot.pending = Some((iteration, elapsed));
+
+            let due = snapshot
+                .last_write
+                .is_none_or(|last| last.elapsed() >= snapshot.interval);
+            if due {
+                self.write_snapshot();
+            }
+        }
+
+        true
+    }
+
+    /// Writes any improvement not yet contained in the snapshot file.
+    /// Should be called once the search terminates.
+    pub fn finish(&mut self) {
+        self.write_snapshot();
+    }
+
+    /// Returns the best coloring reported so far.
+    pub fn best(&self) -> Option<&Coloring> {
+        self.best.as_ref().map(|(c, _, _)| c)
+    }
+
+    /// Returns the time passed since the monitor was created.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the last error encountered while writing a snapshot.
+    /// Failing to write a snapshot does not abort the search.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    fn write_snapshot(&mut self) {
+        let snapshot = match self.snapshot {
+            Some(ref mut snapshot) => snapshot,
+            None => return,
+        };
+
+        let (iteration, elapsed) = match snapshot.pending.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        let (coloring, colors, conflicts) = self.best.as_ref().unwrap();
+        let progress = Progress {
+            iteration,
+            coloring,
+            num_colors: *colors,
+            conflicts: *conflicts,
+            elapsed,
+        };
+
+        snapshot.last_write = Some(Instant::now());
+        if let Err(e) = write_progress(&snapshot.path, &progress) {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl<'a> Default for Monitor<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write the coloring to a temporary file next to ```path``` and move it into place.
+///
+/// The coloring is preceded by ```c``` comment lines describing the search state.
+/// Each vertex is then written as ```v vertex color``` with vertices in [1,n]
+/// (n inclusive) as in the DIMACS format.
+fn write_progress(path: &Path, progress: &Progress) -> io::Result<()> {
+    let mut tmp = OsString::from(path.as_os_str());
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    {
+        let mut out = BufWriter::new(File::create(&tmp)?);
+        writeln!(out, "c iteration {}", progress.iteration)?;
+        writeln!(out, "c colors {}", progress.num_colors)?;
+        writeln!(out, "c conflicts {}", progress.conflicts)?;
+        writeln!(out, "c elapsed {:.3}", progress.elapsed.as_secs_f64())?;
+
+        for (v, color) in progress.coloring.iter().enumerate() {
+            writeln!(out, "v {} {}", v + 1, color)?;
+        }
+
+        out.flush()?;
+    }
+
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn report_improvements() {
+        let mut seen = Vec::new();
+
+        {
+            let mut monitor =
+                Monitor::new().on_improvement(|p| seen.push((p.num_colors, p.conflicts)));
+
+            assert!(monitor.report(0, &vec![0, 1, 2], 0));
+            assert!(!monitor.report(1, &vec![0, 1, 2], 0));
+            assert!(monitor.report(2, &vec![0, 1, 1], 0));
+            assert!(!monitor.report(3, &vec![0, 0, 0], 1));
+
+            assert_eq!(monitor.best(), Some(&vec![0, 1, 1]));
+        }
+
+        assert_eq!(seen, vec![(3, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn report_fewer_conflicts() {
+        let mut monitor = Monitor::new();
+
+        assert!(monitor.report(0, &vec![0, 0, 0], 3));
+        assert!(monitor.report(1, &vec![0, 1, 2, 3], 0));
+        assert_eq!(monitor.best(), Some(&vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn snapshot_file() {
+        let path = env::temp_dir().join(format!("graml-snapshot-{}.sol", process::id()));
+
+        let mut monitor = Monitor::new().snapshot(&path, Duration::from_secs(3600));
+        monitor.report(0, &vec![0, 1, 2], 0);
+
+        // First improvement is written immediately
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("c colors 3"));
+        assert!(content.contains("v 3 2"));
+
+        // Later improvements wait for the interval or the end of the search
+        monitor.report(1, &vec![0, 1, 0], 0);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("c colors 3"));
+
+        monitor.finish();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("c iteration 1"));
+        assert!(content.contains("c colors 2"));
+        assert!(content.contains("v 3 0"));
+        assert!(monitor.take_error().is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+use rand::{thread_rng, Rng};
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::metaheuristics::{select_kernel, ConflictKernel};
+use crate::coloring::presets::eliminate_classes;
+use crate::coloring::{dsatur_coloring, lf_coloring, num_colors, sl_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Steps of tabu search per number of colors tried by ```tabu_coloring```.
+const ITERATIONS: usize = 20_000;
+
+/// Searches a coloring with ```k``` colors by tabu search for at most ```max_iters```
+/// moves, known as TabuCol. Returns ```None``` if none was found, which does not
+/// mean that none exists.
+///
+/// Starts from ```dsatur_coloring``` with the vertices of colors from ```k``` on
+/// moved to their least conflicting color, and minimizes the number of conflicting
+/// edges as described for ```tabu_moves```, with the kernel of ```select_kernel```.
+pub fn tabucol<G: StaticGraph + ?Sized>(graph: &G, k: usize, max_iters: usize) -> Option<Coloring> {
+    tabucol_with(graph, &*select_kernel(graph), k, max_iters)
+}
+
+/// Same as ```tabucol``` but evaluates the moves with ```kernel```, which has to be
+/// built from ```graph```.
+pub fn tabucol_with<G: StaticGraph + ?Sized, K: ConflictKernel + ?Sized>(
+    graph: &G,
+    kernel: &K,
+    k: usize,
+    max_iters: usize,
+) -> Option<Coloring> {
+    assert_eq!(kernel.num_vertices(), graph.num_vertices());
+    tabucol_from(kernel, &dsatur_coloring(graph), k, max_iters)
+}
+
+/// Colors the graph with as few colors as tabu search finds.
+///
+/// Starts from the best of the saturation, smallest-last and largest-first greedy
+/// colorings and binary searches the number of colors between the size of a
+/// greedily found clique and the greedy result. Every number of colors is tried by
+/// ```tabucol``` from the best coloring so far. As the search may miss colorings,
+/// the result is not necessarily the fewest colors tabu search could find with a
+/// linear scan.
+pub fn tabu_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let kernel = select_kernel(graph);
+    let mut best = [
+        dsatur_coloring(graph),
+        sl_coloring(graph),
+        lf_coloring(graph),
+    ]
+    .iter()
+    .map(|c| eliminate_classes(graph, c.clone()))
+    .min_by_key(num_colors)
+    .unwrap();
+
+    let (mut low, mut high) = (greedy_clique(graph).len(), num_colors(&best));
+    while low < high {
+        let k = low + (high - low) / 2;
+        match tabucol_from(&*kernel, &best, k, ITERATIONS) {
+            Some(c) => {
+                best = eliminate_classes(graph, c);
+                high = num_colors(&best);
+            }
+            None => low = k + 1,
+        }
+    }
+
+    best
+}
+
+fn tabucol_from<K: ConflictKernel + ?Sized>(
+    kernel: &K,
+    start: &[usize],
+    k: usize,
+    max_iters: usize,
+) -> Option<Coloring> {
+    if k == 0 {
+        return if kernel.num_vertices() == 0 {
+            Some(Vec::new())
+        } else {
+            None
+        };
+    }
+
+    tabu_moves(kernel, start, k, |step| step > max_iters, &mut thread_rng())
+}
+
+/// Searches a coloring with ```k``` colors until ```stop``` returns true for the
+/// number of the next step, starting from ```coloring``` with the vertices of higher
+/// colors moved to their least conflicting color below ```k```.
+///
+/// Every step moves a conflicting vertex to the color which reduces the conflicts
+/// the most. Moving a vertex back to a color it left recently is forbidden for a
+/// number of steps growing with the conflicts, unless it leads to fewer conflicts
+/// than ever before. The conflicts of every vertex in every color are kept up to
+/// date by ```kernel```.
+pub(crate) fn tabu_moves<K: ConflictKernel + ?Sized, R: Rng>(
+    kernel: &K,
+    coloring: &[usize],
+    k: usize,
+    mut stop: impl FnMut(usize) -> bool,
+    rng: &mut R,
+) -> Option<Coloring> {
+    let n = kernel.num_vertices();
+    let mut colors = coloring.to_vec();
+
+    // Conflicts of vertex v in color c at v * k + c
+    let mut gamma = Vec::new();
+    kernel.color_counts(&colors, k, &mut gamma);
+    let surplus = (0..n).filter(|&v| colors[v] >= k).collect::<Vec<_>>();
+    for v in surplus {
+        let c = (0..k).min_by_key(|&c| gamma[v * k + c]).unwrap();
+        colors[v] = c;
+        kernel.move_counts(v, None, c, k, &mut gamma);
+    }
+
+    let mut conflicts = (0..n).map(|v| gamma[v * k + colors[v]]).sum::<usize>() / 2;
+    let mut fewest = conflicts;
+    let mut tabu = vec![vec![0usize; k]; n];
+
+    let mut step = 0;
+    loop {
+        step += 1;
+        if conflicts == 0 {
+            return Some(colors);
+        }
+        if stop(step) {
+            return None;
+        }
+
+        // Best allowed move, ties broken uniformly
+        let mut chosen = None;
+        let mut ties = 0;
+        for v in (0..n).filter(|&v| gamma[v * k + colors[v]] > 0) {
+            let current = gamma[v * k + colors[v]] as isize;
+            for c in (0..k).filter(|&c| c != colors[v]) {
+                let delta = gamma[v * k + c] as isize - current;
+                let aspired = (conflicts as isize + delta) < fewest as isize;
+                if tabu[v][c] > step && !aspired {
+                    continue;
+                }
+
+                match chosen {
+                    Some((_, _, d)) if delta > d => {}
+                    Some((_, _, d)) if delta == d => {
+                        ties += 1;
+                        if rng.gen_range(0, ties) == 0 {
+                            chosen = Some((v, c, delta));
+                        }
+                    }
+                    _ => {
+                        chosen = Some((v, c, delta));
+                        ties = 1;
+                    }
+                }
+            }
+        }
+
+        let (v, c, delta) = match chosen {
+            Some(m) => m,
+            None => continue,
+        };
+
+        let old = colors[v];
+        colors[v] = c;
+        kernel.move_counts(v, Some(old), c, k, &mut gamma);
+        conflicts = (conflicts as isize + delta) as usize;
+        fewest = fewest.min(conflicts);
+        tabu[v][old] = step + conflicts * 6 / 10 + rng.gen_range(0, 10);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::coloring::metaheuristics::{BitsetKernel, ListKernel};
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn tabucol_k_coloring() {
+        // Complete tripartite graph
+        let mut g = AdjList::new();
+        for u in 0..30 {
+            for v in (u + 1..30).filter(|v| v % 3 != u % 3) {
+                g.add_edge(u, v);
+            }
+        }
+
+        let c = tabucol(&g, 3, 10_000).unwrap();
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 3);
+        assert_eq!(tabucol(&g, 2, 1_000), None);
+        assert_eq!(tabucol(&AdjList::new(), 0, 10), Some(Vec::new()));
+    }
+
+    #[test]
+    fn tabu_coloring_improves_greedy() {
+        let g = AdjList::random(60, 0.5);
+        let c = tabu_coloring(&g);
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= num_colors(&dsatur_coloring(&g)));
+        assert!(tabu_coloring(&AdjList::new()).is_empty());
+    }
+
+    #[test]
+    fn tabu_kernels_same_moves() {
+        let g = AdjList::random(70, 0.5);
+        let start = dsatur_coloring(&g);
+        let k = num_colors(&start) - 2;
+        let (list, bitset) = (ListKernel::new(&g), BitsetKernel::new(&g));
+
+        for seed in 0..5 {
+            let run = |kernel: &dyn ConflictKernel| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                tabu_moves(kernel, &start, k, |step| step > 2000, &mut rng)
+            };
+            assert_eq!(run(&list), run(&bitset));
+        }
+
+        let c = tabucol_with(&g, &bitset, num_colors(&start), 100).unwrap();
+        assert!(check_coloring(&g, &c));
+    }
+}
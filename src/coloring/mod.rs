@@ -1,61 +1,255 @@
-use rand::{ random, thread_rng, seq::SliceRandom };
+mod alignment;
+mod auto;
+mod availability;
+mod boundary;
+mod capacitated;
+mod certificate;
+mod column;
+mod community;
+mod conflict_graph;
+mod conflicts;
+mod consensus;
+mod constraints;
+mod decision;
+mod equitable;
+mod estimate;
+pub(crate) mod exact;
+mod explain;
+mod fractional;
+mod greedy;
+mod horizon;
+mod hypergraph;
+mod kempe;
+pub mod metaheuristics;
+mod online;
+#[cfg(test)]
+mod oracle;
+mod perturbation;
+mod planar;
+mod presets;
+mod recolor;
+pub mod reductions;
+mod sampling;
+pub mod sat;
+mod saturation;
+mod special;
+mod sum;
+mod tolerant;
+mod tournament;
+mod treewidth;
+mod weighted;
+mod whatif;
+mod zykov;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, VecDeque};
 
+use self::exact::greedy_clique;
 use crate::graph::StaticGraph;
 
+pub use self::alignment::{align_colorings, coloring_agreement, coloring_distance, rand_index};
+pub use self::auto::{color_auto, color_auto_with, DecisionTree};
+pub use self::availability::{availability_coloring, Availability};
+pub use self::boundary::merge_part_colorings;
+pub use self::capacitated::{capacitated_coloring, check_capacities};
+pub use self::certificate::Certificate;
+pub use self::column::column_gen_coloring;
+pub use self::community::community_coloring;
+#[cfg(feature = "parallel")]
+pub use self::community::community_coloring_parallel;
+#[cfg(feature = "parallel")]
+pub use self::conflict_graph::conflict_graph_parallel;
+pub use self::conflict_graph::{conflict_graph, overlaps, shares_resource};
+#[cfg(feature = "parallel")]
+pub use self::conflicts::num_conflicts_parallel;
+pub use self::conflicts::{conflicts, num_conflicts};
+pub use self::consensus::consensus_coloring;
+pub use self::constraints::{constrained_coloring, merge_same_color, Constraints, Merged};
+pub use self::decision::{k_coloring, k_coloring_within, KColoring};
+pub use self::equitable::{check_equitable, equitable_coloring};
+pub use self::estimate::{estimate_chromatic, ChromaticEstimate};
+pub use self::exact::{
+    chromatic_bounds, chromatic_number, exact_coloring, exact_coloring_with, ExactEngine,
+    ExactOptions,
+};
+pub use self::explain::{explain_coloring, ClassExplanation, Reason};
+pub use self::fractional::{fractional_chromatic, FractionalChromatic};
+pub use self::greedy::{
+    greedy_list_coloring, precoloring_extension, ColoringWorkspace, Greedy, Order, Selection,
+};
+pub use self::horizon::{rolling_horizon_coloring, PeriodReport, RollingHorizon};
+pub use self::hypergraph::{check_strong_coloring, check_weak_coloring, weak_coloring};
+pub use self::kempe::{kempe_chain, kempe_swap};
+pub use self::metaheuristics::{genetic_coloring, iterated_greedy, iterated_greedy_with};
+pub use self::online::{OnlineColorer, OnlineStats};
+pub use self::perturbation::{perturbation_restart_coloring, perturbation_restart_coloring_with};
+pub use self::planar::planar_coloring;
+pub use self::presets::{color_balanced, color_best, color_fast};
+pub use self::recolor::recolor_min_change;
+pub use self::sampling::sampled_coloring;
+pub use self::saturation::SaturationTracker;
+pub use self::special::{special_coloring, GraphClass};
+pub use self::sum::{coloring_cost, improve_sum, sum_coloring};
+pub use self::tolerant::{
+    min_conflicts_coloring, min_penalty_coloring, tolerant_coloring, TolerantColoring,
+};
+pub use self::tournament::round_robin;
+pub use self::treewidth::treewidth_coloring;
+pub use self::weighted::{improve_weighted, weighted_coloring, weighted_cost};
+#[cfg(feature = "parallel")]
+pub use self::whatif::rank_what_if_parallel;
+pub use self::whatif::{
+    rank_what_if, sample_elements, what_if_remove, what_if_remove_from, Element, WhatIf,
+};
+
 /// Coloring type.
 /// This maps from vertices to colors.
 pub type Coloring = Vec<usize>;
 
 /// Coloring heuristics implemented here.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColoringAlgo {
     RS,
     CS,
     LF,
     SL,
     SDO,
+    Genetic,
+    /// Iterated greedy, see ```iterated_greedy```.
+    IG,
+}
+
+/// Rounds of ```iterated_greedy``` run by ```ColoringAlgo::IG```.
+const IG_ITERATIONS: usize = 100;
+
+impl ColoringAlgo {
+    /// Colors the graph with this method.
+    pub fn color<G: StaticGraph + ?Sized>(self, graph: &G) -> Coloring {
+        match self {
+            ColoringAlgo::RS => rs_coloring(graph),
+            ColoringAlgo::CS => cs_coloring(graph),
+            ColoringAlgo::LF => lf_coloring(graph),
+            ColoringAlgo::SL => sl_coloring(graph),
+            ColoringAlgo::SDO => sdo_coloring(graph),
+            ColoringAlgo::Genetic => genetic_coloring(graph),
+            ColoringAlgo::IG => iterated_greedy(graph, IG_ITERATIONS),
+        }
+    }
+
+    /// Same as ```color```, but iterated greedy draws from ```rng```, such that a
+    /// seeded generator reproduces its coloring. The genetic algorithm keeps its
+    /// own generator, all other methods are deterministic.
+    pub fn color_with<G: StaticGraph + ?Sized, R: Rng>(self, graph: &G, rng: &mut R) -> Coloring {
+        match self {
+            ColoringAlgo::IG => iterated_greedy_with(graph, IG_ITERATIONS, rng),
+            _ => self.color(graph),
+        }
+    }
+
+    /// Short lowercase name of the method.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColoringAlgo::RS => "rs",
+            ColoringAlgo::CS => "cs",
+            ColoringAlgo::LF => "lf",
+            ColoringAlgo::SL => "sl",
+            ColoringAlgo::SDO => "sdo",
+            ColoringAlgo::Genetic => "genetic",
+            ColoringAlgo::IG => "ig",
+        }
+    }
+}
+
+/// Coloring together with the method which found it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColoringOutcome {
+    pub coloring: Coloring,
+    /// Name of the method, see ```ColoringAlgo::name``` and ```GraphClass::name```.
+    pub algorithm: &'static str,
+    pub num_colors: usize,
+    /// Whether the coloring is proven to use the fewest colors possible.
+    pub optimal: bool,
+}
+
+impl ColoringOutcome {
+    /// Outcome of a heuristic, optimal if it matches the ```lower``` bound on the
+    /// chromatic number.
+    pub fn new(algorithm: &'static str, coloring: Coloring, lower: usize) -> Self {
+        let colors = num_colors(&coloring);
+
+        ColoringOutcome {
+            coloring,
+            algorithm,
+            num_colors: colors,
+            optimal: colors <= lower,
+        }
+    }
 }
 
 /// Color the graph with all available methods and return the best coloring.
-pub fn color<G: StaticGraph>(graph: &G) -> Coloring {
-    let mut colorings = Vec::new();
-    colorings.push(rs_coloring(graph));
-    colorings.push(cs_coloring(graph));
-    colorings.push(lf_coloring(graph));
-    colorings.push(sl_coloring(graph));
-    colorings.push(sdo_coloring(graph));
-
-    colorings
-        .into_iter()
-        .map(|c| {
-            assert!(check_coloring(graph, &c));
-            c
-        })
-        .min_by_key(|c| num_colors(&c))
-        .unwrap()
+/// See ```color_detailed``` for the method.
+pub fn color<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    color_detailed(graph).coloring
+}
+
+/// Color the graph with all available methods and return the best coloring
+/// together with the method which found it. Ties are won by the earlier method.
+/// Graphs of a class detected by ```special_coloring``` are colored optimally
+/// without running the heuristics. Otherwise the coloring is marked optimal if it
+/// uses as many colors as a greedily found clique has vertices.
+pub fn color_detailed<G: StaticGraph + ?Sized>(graph: &G) -> ColoringOutcome {
+    if let Some((class, c)) = special_coloring(graph) {
+        return ColoringOutcome::new(class.name(), c, usize::MAX);
+    }
+
+    let lower = greedy_clique(graph).len();
+
+    [
+        ColoringAlgo::RS,
+        ColoringAlgo::CS,
+        ColoringAlgo::LF,
+        ColoringAlgo::SL,
+        ColoringAlgo::SDO,
+    ]
+    .iter()
+    .map(|a| ColoringOutcome::new(a.name(), a.color(graph), lower))
+    .inspect(|o| assert!(check_coloring(graph, &o.coloring)))
+    .min_by_key(|o| o.num_colors)
+    .unwrap()
 }
 
 /// Check whether coloring defines a color for all vertices that exist in the graph.
-pub fn compatible_coloring<G: StaticGraph>(graph: &G, coloring: &Coloring) -> bool {
+pub fn compatible_coloring<G: StaticGraph + ?Sized>(graph: &G, coloring: &[usize]) -> bool {
     graph.num_vertices() == coloring.len()
 }
 
 /// Check whether no adjacent vertices are in conflict.
 /// ```false``` indicates either a color conflict or no color defined for at least
 /// one of the vertices in the graph.
-pub fn check_coloring<G: StaticGraph>(graph: &G, coloring: &Coloring) -> bool {
+pub fn check_coloring<G: StaticGraph + ?Sized>(graph: &G, coloring: &[usize]) -> bool {
     if !compatible_coloring(graph, coloring) {
         return false;
     }
 
-    for (u, v) in graph.edges() {
-        if coloring[u] == coloring[v] {
-            return false;
+    conflicts(graph, coloring).next().is_none()
+}
+
+/// Check whether edges sharing a vertex have different colors, where ```colors[i]```
+/// is the color of the ```i```-th edge of ```graph.edges()```.
+pub fn check_edge_coloring<G: StaticGraph + ?Sized>(graph: &G, colors: &[usize]) -> bool {
+    let mut seen = HashSet::new();
+    let mut edges = 0;
+
+    for (i, (u, v)) in graph.edges().enumerate() {
+        edges += 1;
+        match colors.get(i) {
+            Some(&c) if seen.insert((u, c)) && seen.insert((v, c)) => {}
+            _ => return false,
         }
     }
 
-    true
+    edges == colors.len()
 }
 
 /// Returns the number of colors used in the coloring.
@@ -69,40 +263,67 @@ pub fn num_colors(coloring: &Coloring) -> usize {
     colors.len()
 }
 
+/// Returns the largest color index used in the coloring, ```None``` if it is empty.
+/// Exceeds ```num_colors - 1``` if some color below it is unused.
+pub fn peak_color(coloring: &[usize]) -> Option<usize> {
+    coloring.iter().cloned().max()
+}
+
+/// Relabels the colors to ```0..k``` for ```k``` colors used, keeping their relative order.
+pub fn compact_colors(coloring: &mut [usize]) {
+    let mut used = coloring.to_vec();
+    used.sort_unstable();
+    used.dedup();
+
+    for c in coloring.iter_mut() {
+        *c = used.binary_search(c).unwrap();
+    }
+}
+
+/// Returns the vertices of every color up to the peak color in increasing order,
+/// empty for unused colors.
+pub fn color_classes(coloring: &[usize]) -> Vec<Vec<usize>> {
+    let mut classes = vec![Vec::new(); peak_color(coloring).map_or(0, |c| c + 1)];
+    for (v, &c) in coloring.iter().enumerate() {
+        classes[c].push(v);
+    }
+
+    classes
+}
+
 /// Returns a 2-coloring of the graph if it exists, ```None``` otherwise.
 /// Can be used as a check for bipartiteness.
-pub fn two_coloring<G: StaticGraph>(graph: &G) -> Option<Coloring> {
+pub fn two_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Option<Coloring> {
     let mut c: Vec<Option<usize>> = vec![None; graph.num_vertices()];
     let mut q = VecDeque::new();
 
-    let first = 0;
-    q.push_back(first);
-    c[first] = Some(0);
+    // Search every connected component from its first vertex
+    for first in graph.vertices() {
+        if c[first].is_some() {
+            continue;
+        }
+
+        q.push_back(first);
+        c[first] = Some(0);
 
-    while let Some(v) = q.pop_front() {
-        let color = c[v].unwrap();
+        while let Some(v) = q.pop_front() {
+            let color = c[v].unwrap();
 
-        for u in graph.neighbors(v) {
-            if let Some(col) = c[u] {
-                // Conflict
-                if col == color {
-                    return None;
+            for u in graph.neighbors(v) {
+                if let Some(col) = c[u] {
+                    // Conflict
+                    if col == color {
+                        return None;
+                    }
+                } else {
+                    // Color neighbors opposite color and put in the frontier
+                    c[u] = Some(1 - color);
+                    q.push_back(u);
                 }
-            } else {
-                // Color neighbors opposite color and put in the frontier
-                c[u] = Some(1 - color);
-                q.push_back(u);
             }
         }
     }
 
-    // Set any isolated vertices to color '0'
-    for v in c.iter_mut() {
-        if v.is_none() {
-            *v = Some(0);
-        }
-    }
-
     let coloring: Option<Coloring> = c.into_iter().collect();
     assert!(coloring.is_some());
     let coloring = coloring.unwrap();
@@ -113,203 +334,82 @@ pub fn two_coloring<G: StaticGraph>(graph: &G) -> Option<Coloring> {
 /// Greedy coloring algorithm.
 /// Colors the vertices in the sequence provided by chosing the
 /// smallest color not in conflict.
-pub fn greedy_coloring<G: StaticGraph>(
+pub fn greedy_coloring<G: StaticGraph + ?Sized>(
     graph: &G,
     vertices: impl Iterator<Item = usize>,
 ) -> Coloring {
-    // Must be equal to 'vertices.count()'
-    // as 'vertices' must be permutation of 'graph.vertices'
-    let n = graph.num_vertices();
-    let mut c: Vec<Option<usize>> = vec![None; n];
-
-    let mut blocked_colors = vec![false; n];
-    for v in vertices {
-        for u in graph.neighbors(v) {
-            if let Some(color) = c[u] {
-                blocked_colors[color] = true;
-            }
-        }
-
-        for x in 0..n {
-            if !blocked_colors[x] {
-                c[v] = Some(x);
-                break;
-            }
-        }
-
-        blocked_colors = vec![false; n];
-    }
-
-    let coloring: Option<Coloring> = c.into_iter().collect();
-    assert!(coloring.is_some());
+    // 'vertices' must be permutation of 'graph.vertices'
+    Greedy::ordered(Order::Given(vertices.collect())).color(graph)
+}
 
-    coloring.unwrap()
+/// Same as ```greedy_coloring``` but reuses the buffers of ```workspace```.
+pub fn greedy_coloring_with<'w, G: StaticGraph + ?Sized>(
+    graph: &G,
+    vertices: &[usize],
+    workspace: &'w mut ColoringWorkspace,
+) -> &'w [usize] {
+    Greedy::default().color_sequence_with(graph, vertices, workspace)
 }
 
 /// Returns a random-sequence greedy coloring of the graph where the vertices have
 /// been colored in random order.
 /// There is no guarantee about the number of colors used.
-pub fn rs_coloring<G: StaticGraph>(graph: &G) -> Coloring {
-    // No sequence building stage for this algorithm
-    greedy_coloring(graph, graph.vertices())
+pub fn rs_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    Greedy::ordered(Order::Natural).color(graph)
 }
 
 /// Returns a connected-sequence greedy coloring of the graph where the vertices have
 /// been colored in an order such that each vertex (except the first) has atleast one
 /// neighbor that has already been colored.
-pub fn cs_coloring<G: StaticGraph>(graph: &G) -> Coloring {
-    // Sequence building stage
-    let mut visited = vec![false; graph.num_vertices()];
-    let mut vec: Vec<usize> = Vec::new();
-
-    let n = graph.vertices().count();
-
-    let first = graph.vertices().next().unwrap();
-    visited[first] = true;
-    vec.push(first);
-
-    let mut i = 0;
-    while i < vec.len() {
-        let v = vec[i];
-
-        for u in graph.neighbors(v) {
-            if !visited[u] {
-                vec.push(u);
-                visited[u] = true;
-            }
-        }
-        i += 1;
-    }
-
-    for v in 0..n {
-        if !visited[v] {
-            vec.push(v);
-        }
-    }
-
-    greedy_coloring(graph, vec.iter().cloned())
+pub fn cs_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    Greedy::ordered(Order::Connected).color(graph)
 }
 
 /// Returns a largest-first greedy coloring of the graph attained by greedily coloring
 /// the vertices in order of decreasing degree.
 /// There is no guarantee about the number of colors used.
-pub fn lf_coloring<G: StaticGraph>(graph: &G) -> Coloring {
-    // Sequence building stage
-    let mut vertices: Vec<(usize, usize)> = graph.vertices().map(|u| (u, 0)).collect();
-
-    for (v, d) in &mut vertices {
-        *d = graph.neighbors(*v).count();
-    }
-
-    vertices.sort_by(|a, b| b.1.cmp(&a.1));
-
-    greedy_coloring(graph, vertices.iter().map(|&(v, _)| v))
+pub fn lf_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    Greedy::ordered(Order::LargestFirst).color(graph)
 }
 
 /// Returns a smallest-last greedy coloring of the graph.
 /// This algorithm optimally colors trees, cycles and other types of graphs.
 /// For general graphs there is no guarantee about the number of colors used.
-pub fn sl_coloring<G: StaticGraph>(graph: &G) -> Coloring {
-    // Sequence building stage
-    // Inefficient implementation
-    let n = graph.num_vertices();
-    let mut k_set = vec![false; n];
-    let mut k = Vec::with_capacity(n);
-    let mut notk = graph.vertices().collect::<HashSet<usize>>();
-
-    while k.len() < n {
-        let mut min_d = std::usize::MAX;
-        let mut min_d_idx = 0;
-        for &v in notk.iter() {
-            // Only look at vertices not in k
-            if k_set[v] {
-                continue;
-            }
-
-            // Look for min degree of vertices not in k
-            let mut degree = 0;
-            for u in graph.neighbors(v) {
-                if !k_set[u] {
-                    degree += 1;
-                }
-            }
-            if degree < min_d {
-                min_d = degree;
-                min_d_idx = v;
-            }
-        }
-
-        k.push(min_d_idx);
-        k_set[min_d_idx] = true;
-        notk.remove(&min_d_idx);
-    }
-
-    // Greedy coloring with reversed order of k
-    greedy_coloring(graph, k.iter().rev().cloned())
+pub fn sl_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    Greedy::ordered(Order::SmallestLast).color(graph)
 }
 
 /// Returns a saturation degree ordered coloring of the graph.
 /// The SDO is defined by the number of distinct colors in the neighborhood -
 /// vertices with a high saturation degree are colored first.
 /// For general graphs there is no guarantee about the number of colors used.
-pub fn sdo_coloring<G: StaticGraph>(graph: &G) -> Coloring {
-    let n = graph.num_vertices();
-    let mut c = vec![None; n];
-
-    let mut left = graph.vertices().collect::<HashSet<usize>>();
-
-    while !left.is_empty() {
-        // Find vertex with highest saturation degree
-        let mut colors = HashSet::new();
-        let mut max_sd = 0;
-        let mut max_sd_idx = 0;
-        for &v in left.iter() {
-            for u in graph.neighbors(v) {
-                if let Some(color) = c[u] {
-                    colors.insert(color);
-                }
-            }
-
-            if colors.len() > max_sd
-                || (colors.len() == max_sd
-                    && graph.neighbors(v).count() > graph.neighbors(max_sd_idx).count())
-            {
-                max_sd = colors.len();
-                max_sd_idx = v;
-            }
-
-            colors.clear();
-        }
-
-        // Reacquire blocking colors for chosen vertex
-        for u in graph.neighbors(max_sd_idx) {
-            if let Some(color) = c[u] {
-                colors.insert(color);
-            }
-        }
+pub fn sdo_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    Greedy::ordered(Order::Saturation).color(graph)
+}
 
-        // Color vertex
-        for x in 0..n {
-            if !colors.contains(&x) {
-                c[max_sd_idx] = Some(x);
-                break;
-            }
-        }
+/// Returns the same coloring as ```sdo_coloring``` (DSATUR), but in
+/// ```O((n + m) log n)``` time instead of quadratic time.
+/// The saturation degrees are kept up to date as vertices are colored by a
+/// ```SaturationTracker```, which also orders the uncolored vertices.
+pub fn dsatur_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let mut tracker = SaturationTracker::new(graph);
 
-        colors.clear();
-        left.remove(&max_sd_idx);
+    while let Some(v) = tracker.most_saturated() {
+        let c = tracker.first_free(v);
+        tracker.color(graph, v, c);
     }
 
-    let coloring: Option<Coloring> = c.into_iter().collect();
-    assert!(coloring.is_some());
-
-    coloring.unwrap()
+    tracker.coloring().unwrap()
 }
 
 /// Perform provided coloring method on graph ```n``` times and return the coloring with
 /// minimal number of colors. This is useful for randomized coloring methods such as
 /// ```sl_coloring``` and ```sdo_coloring``` to get more robust results.
-pub fn repeat_coloring<G: StaticGraph>(g: &G, c: fn(&G) -> Coloring, n: usize) -> Coloring {
+pub fn repeat_coloring<G: StaticGraph + ?Sized>(
+    g: &G,
+    c: fn(&G) -> Coloring,
+    n: usize,
+) -> Coloring {
     let mut best_c = c(g);
     let mut nc_best_c = num_colors(&best_c);
 
@@ -325,11 +425,37 @@ pub fn repeat_coloring<G: StaticGraph>(g: &G, c: fn(&G) -> Coloring, n: usize) -
     best_c
 }
 
+/// Same as ```repeat_coloring``` for colorings computed into ```workspace```.
+/// The best coloring is kept in a single buffer allocated by the first run.
+pub fn repeat_coloring_with<G: StaticGraph + ?Sized>(
+    g: &G,
+    c: fn(&G, &mut ColoringWorkspace),
+    n: usize,
+    workspace: &mut ColoringWorkspace,
+) -> Coloring {
+    c(g, workspace);
+    let mut best_c = workspace.coloring().to_vec();
+    let mut nc_best_c = workspace.num_colors();
+
+    for _ in 1..n {
+        c(g, workspace);
+        let nc_new_c = workspace.num_colors();
+        if nc_new_c < nc_best_c {
+            best_c.copy_from_slice(workspace.coloring());
+            nc_best_c = nc_new_c;
+        }
+    }
+
+    best_c
+}
+
 /// Fixes a potentially wrong coloring by choosing the lowest available color
 /// for the vertex with lower saturation degree of any conflicting edge.
-pub fn fix_coloring<G: StaticGraph>(g: &G, c: &mut Coloring) {
-    for (u, v) in g.edges() {
-        // Conflict
+pub fn fix_coloring<G: StaticGraph + ?Sized>(g: &G, c: &mut Coloring) {
+    let conflicting = conflicts(g, c).collect::<Vec<_>>();
+
+    // Recoloring never creates a conflict but may resolve later ones
+    for (u, v) in conflicting {
         if c[u] == c[v] {
             let u_colors = g.neighbors(u).map(|x| c[x]).collect::<HashSet<usize>>();
             let v_colors = g.neighbors(v).map(|x| c[x]).collect::<HashSet<usize>>();
@@ -358,78 +484,6 @@ pub fn fix_coloring<G: StaticGraph>(g: &G, c: &mut Coloring) {
     }
 }
 
-pub fn genetic_coloring<G: StaticGraph>(g: &G) -> Coloring {
-    let n = 50;
-    let gen = 50;
-    let mutation_p = 0.2;
-    let n_vert = g.num_vertices();
-    let mut orderings: Vec<(Vec<usize>, usize)> = Vec::new();
-
-    // Random initialization
-    for _ in 0..n {
-        let mut permutation = (0..n_vert).collect::<Vec<usize>>();
-        permutation[..].shuffle(&mut thread_rng());
-
-        orderings.push((permutation, 0));
-    }
-
-    for _ in 0..gen {
-        // Compute num colors
-        for i in 0..n {
-            let coloring = greedy_coloring(g, orderings[i].0.iter().cloned());
-            assert!(check_coloring(g, &coloring));
-            let num_col = num_colors(&coloring);
-            orderings[i].1 = num_col;
-        }
-
-        orderings.sort_by(|a, b| {
-            a.1.cmp(&b.1)
-        });
-
-        for i in n / 2..n {
-            let mom = random::<usize>() % (n / 2);
-            let dad = random::<usize>() % (n / 2);
-
-            let split = random::<usize>() % n_vert;
-
-            // Take first 'split' elements from mom
-            let mut have = vec![false; n_vert];
-            for j in 0..split {
-                let v = orderings[mom].0[j];
-                orderings[i].0[j] = v;
-                have[v] = true;
-            }
-
-            // Take remaining elements in order specified by dad
-            // -> Leads to a proper permutation
-            let mut j = split;
-            let mut k = 0;
-            while j < n_vert && k < n_vert {
-                let v = orderings[dad].0[k];
-                if !have[v] {
-                    orderings[i].0[j] = v;
-                    have[v] = true;
-                    j += 1;
-                }
-
-                k += 1;
-            }
-
-            // Mutate
-            // -> Swap two vertices at random
-            for j in 0..n_vert-1 {
-                let p = random::<f32>();
-
-                if p < mutation_p {
-                    orderings[i].0.swap(j, j+1)
-                }
-            }
-        }
-    }
-
-    greedy_coloring(g, orderings.remove(0).0.iter().cloned())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,8 +531,8 @@ mod tests {
             }
         }
 
-        for u in 0..100 {
-            c[u] = u;
+        for (u, col) in c.iter_mut().enumerate() {
+            *col = u;
         }
 
         assert!(compatible_coloring(&g, &c));
@@ -495,13 +549,44 @@ mod tests {
         let n = 100;
         let mut c = vec![0; n];
 
-        for u in 0..100 {
-            c[u] = u % 11;
+        for (u, col) in c.iter_mut().enumerate() {
+            *col = u % 11;
         }
 
         assert_eq!(num_colors(&c), 11);
     }
 
+    #[test]
+    fn test_compact_colors() {
+        let mut c = vec![7, 2, 7, 9, 2];
+
+        assert_eq!(peak_color(&c), Some(9));
+        compact_colors(&mut c);
+
+        assert_eq!(c, vec![1, 0, 1, 2, 0]);
+        assert_eq!(peak_color(&c), Some(num_colors(&c) - 1));
+        assert_eq!(peak_color(&[]), None);
+
+        assert_eq!(
+            color_classes(&[2, 0, 2, 0]),
+            vec![vec![1, 3], vec![], vec![0, 2]]
+        );
+        assert!(color_classes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_color_detailed() {
+        let complete = color_detailed(&AdjList::complete(4));
+        assert_eq!((complete.algorithm, complete.optimal), ("complete", true));
+
+        let g = AdjList::random(50, 0.5);
+        let outcome = color_detailed(&g);
+
+        assert_eq!(outcome.num_colors, num_colors(&outcome.coloring));
+        assert!(check_coloring(&g, &outcome.coloring));
+        assert!(["rs", "cs", "lf", "sl", "sdo"].contains(&outcome.algorithm));
+    }
+
     #[test]
     fn rs_color() {
         let mut g = AdjList::new();
@@ -758,6 +843,18 @@ mod tests {
         assert_eq!(num_colors(&c), 2);
     }
 
+    #[test]
+    fn sdo_isolated() {
+        // Isolated vertices have lower degree than the already colored vertex 0
+        let mut g = AdjList::with_capacity(5);
+        g.add_edge(0, 1);
+
+        let c = sdo_coloring(&g);
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 2);
+    }
+
     #[test]
     fn sdo_random() {
         let g = AdjList::random(100, 0.5);
@@ -770,6 +867,26 @@ mod tests {
         assert!(num_colors(&c) <= g.max_degree() + 1);
     }
 
+    #[test]
+    fn dsatur_matches_sdo() {
+        for &p in &[0.05, 0.3, 0.8] {
+            let g = AdjList::random(120, p);
+            assert_eq!(dsatur_coloring(&g), sdo_coloring(&g));
+        }
+        assert!(dsatur_coloring(&AdjList::new()).is_empty());
+
+        let n = 20_000;
+        let mut g = AdjList::with_capacity(n);
+        for v in 0..n {
+            g.add_edge(v, (v + 1) % n);
+            g.add_edge(v, (v * 7919 + 13) % n);
+        }
+        let c = dsatur_coloring(&g);
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= g.max_degree() + 1);
+    }
+
     #[test]
     fn tree_coloring() {
         let mut g = AdjList::new();
@@ -0,0 +1,166 @@
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{color_fast, num_colors, Coloring};
+use crate::graph::{Graph, StaticGraph};
+
+/// Colors vertices as they arrive, each with its neighbors among the vertices seen
+/// so far. Every vertex immediately gets the smallest color not used by those
+/// neighbors (First-Fit), colors are never revised afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct OnlineColorer {
+    adj: Vec<Vec<usize>>,
+    coloring: Coloring,
+    num_colors: usize,
+    edges: usize,
+}
+
+/// Statistics comparing an online coloring with offline bounds on the same graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnlineStats {
+    pub vertices: usize,
+    pub edges: usize,
+    pub max_degree: usize,
+    /// Colors used by the online coloring.
+    pub colors: usize,
+    /// Size of a greedily found clique, a lower bound for any coloring.
+    pub lower_bound: usize,
+    /// Colors used by ```color_fast``` with knowledge of the whole graph.
+    pub offline_colors: usize,
+}
+
+impl OnlineStats {
+    /// Ratio of online colors to the clique lower bound, an upper bound on
+    /// the competitive ratio achieved on this graph.
+    pub fn competitive_ratio(&self) -> f64 {
+        if self.lower_bound == 0 {
+            1.0
+        } else {
+            self.colors as f64 / self.lower_bound as f64
+        }
+    }
+}
+
+impl OnlineColorer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the next vertex, adjacent to ```neighbors``` which must have arrived before.
+    /// Returns the color assigned to it. The vertex has index ```num_vertices()``` before the call.
+    pub fn add_vertex(&mut self, neighbors: &[usize]) -> usize {
+        let v = self.adj.len();
+        let mut blocked = vec![false; self.num_colors + 1];
+
+        let mut adj = neighbors.to_vec();
+        adj.sort_unstable();
+        adj.dedup();
+
+        for &u in &adj {
+            assert!(u < v, "neighbor {} has not arrived yet", u);
+            blocked[self.coloring[u]] = true;
+            self.adj[u].push(v);
+        }
+
+        let c = blocked.iter().position(|&b| !b).unwrap();
+        self.num_colors = self.num_colors.max(c + 1);
+        self.edges += adj.len();
+        self.coloring.push(c);
+        self.adj.push(adj);
+
+        c
+    }
+
+    /// Number of vertices colored so far.
+    pub fn num_vertices(&self) -> usize {
+        self.adj.len()
+    }
+
+    /// Number of colors used so far.
+    pub fn num_colors(&self) -> usize {
+        self.num_colors
+    }
+
+    /// Colors of all vertices so far, indexed by arrival.
+    pub fn coloring(&self) -> &Coloring {
+        &self.coloring
+    }
+
+    /// Returns the graph of all vertices so far.
+    pub fn graph(&self) -> Graph {
+        let mut g = Graph::with_capacity(self.adj.len());
+        for (v, adj) in self.adj.iter().enumerate() {
+            for &u in adj.iter().filter(|&&u| u < v) {
+                g.add_edge(u, v);
+            }
+        }
+
+        g
+    }
+
+    /// Compares the online coloring with offline colorings of the graph seen so far.
+    /// This colors the whole graph, it should not be called after every vertex.
+    pub fn stats(&self) -> OnlineStats {
+        let g = self.graph();
+
+        OnlineStats {
+            vertices: self.adj.len(),
+            edges: self.edges,
+            max_degree: self.adj.iter().map(Vec::len).max().unwrap_or(0),
+            colors: self.num_colors,
+            lower_bound: greedy_clique(&g).len(),
+            offline_colors: num_colors(&color_fast(&g)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+
+    #[test]
+    fn online_first_fit() {
+        let mut online = OnlineColorer::new();
+
+        assert_eq!(online.add_vertex(&[]), 0);
+        assert_eq!(online.add_vertex(&[0]), 1);
+        assert_eq!(online.add_vertex(&[0, 1]), 2);
+        assert_eq!(online.add_vertex(&[1, 1]), 0);
+
+        let stats = online.stats();
+
+        assert_eq!(stats.edges, 4);
+        assert_eq!(stats.colors, 3);
+        assert_eq!(stats.lower_bound, 3);
+        assert_eq!(stats.competitive_ratio(), 1.0);
+    }
+
+    #[test]
+    fn online_bad_order() {
+        // Bipartite graph presented such that First-Fit needs a color per pair
+        let mut online = OnlineColorer::new();
+        for i in 0..5 {
+            let earlier = (0..i)
+                .flat_map(|j| vec![2 * j, 2 * j + 1])
+                .collect::<Vec<_>>();
+            let a = earlier
+                .iter()
+                .cloned()
+                .filter(|u| u % 2 == 1)
+                .collect::<Vec<_>>();
+            let b = earlier
+                .iter()
+                .cloned()
+                .filter(|u| u % 2 == 0)
+                .collect::<Vec<_>>();
+
+            assert_eq!(online.add_vertex(&a), i);
+            assert_eq!(online.add_vertex(&b), i);
+        }
+
+        let stats = online.stats();
+
+        assert!(check_coloring(&online.graph(), online.coloring()));
+        assert_eq!(stats.colors, 5);
+        assert_eq!(stats.offline_colors, 2);
+    }
+}
@@ -0,0 +1,224 @@
+//! Exhaustive correctness checks of the coloring algorithms against a brute force
+//! chromatic number, over all graphs up to seven vertices.
+
+use std::collections::BTreeSet;
+
+use crate::coloring::metaheuristics::{genetic_coloring_with, GeneticParams, Monitor};
+use crate::coloring::reductions::with_reductions;
+use crate::coloring::*;
+use crate::graph::*;
+
+const MAX_VERTICES: usize = 7;
+
+/// Chromatic number by dynamic programming over vertex subsets.
+/// Every coloring of ```mask``` puts its lowest vertex into some independent set,
+/// the rest is colored recursively. Takes ```3^n``` steps.
+fn brute_force_chromatic(adj: &[u32]) -> usize {
+    let n = adj.len();
+    let full = (1u32 << n) - 1;
+
+    let independent = (0..=full)
+        .map(|s| (0..n).all(|v| s & (1 << v) == 0 || adj[v] & s == 0))
+        .collect::<Vec<_>>();
+
+    let mut chi = vec![0; full as usize + 1];
+    for mask in 1..=full {
+        let low = mask & mask.wrapping_neg();
+        let rest = mask ^ low;
+
+        // Independent sets containing 'low' within 'mask'
+        let mut best = usize::MAX;
+        let mut sub = rest;
+        loop {
+            let set = sub | low;
+            if independent[set as usize] {
+                best = best.min(chi[(mask ^ set) as usize] + 1);
+            }
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & rest;
+        }
+        chi[mask as usize] = best;
+    }
+
+    chi[full as usize]
+}
+
+/// Encodes the upper triangle of the adjacency matrix with vertices permuted.
+fn code(adj: &[u32], perm: &[usize]) -> u64 {
+    let n = perm.len();
+    let mut code = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            code = (code << 1) | u64::from(adj[perm[i]] >> perm[j] & 1);
+        }
+    }
+    code
+}
+
+/// Largest code over all orderings of the vertices by decreasing degree.
+/// Isomorphic graphs have the same degree sequence, hence the same canonical code.
+fn canonical(adj: &[u32]) -> u64 {
+    fn extend(adj: &[u32], degrees: &[u32], perm: &mut Vec<usize>, used: u32, best: &mut u64) {
+        let n = adj.len();
+        if perm.len() == n {
+            *best = (*best).max(code(adj, perm));
+            return;
+        }
+
+        let mut sorted = degrees.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        for v in 0..n {
+            if used & (1 << v) == 0 && degrees[v] == sorted[perm.len()] {
+                perm.push(v);
+                extend(adj, degrees, perm, used | (1 << v), best);
+                perm.pop();
+            }
+        }
+    }
+
+    let degrees = adj.iter().map(|a| a.count_ones()).collect::<Vec<_>>();
+    let mut best = 0;
+    extend(adj, &degrees, &mut Vec::new(), 0, &mut best);
+    best
+}
+
+fn decode(n: usize, mut code: u64) -> Vec<u32> {
+    let mut adj = vec![0; n];
+    for i in (0..n).rev() {
+        for j in (i + 1..n).rev() {
+            if code & 1 == 1 {
+                adj[i] |= 1 << j;
+                adj[j] |= 1 << i;
+            }
+            code >>= 1;
+        }
+    }
+    adj
+}
+
+/// All graphs on ```n``` vertices up to isomorphism, as adjacency bitmasks.
+/// Built by connecting a new vertex to every subset of the graphs on one vertex less.
+fn non_isomorphic(max: usize) -> Vec<Vec<Vec<u32>>> {
+    let mut levels = vec![vec![Vec::new()]];
+
+    for n in 1..=max {
+        let mut codes = BTreeSet::new();
+        for adj in &levels[n - 1] {
+            for subset in 0..1u32 << (n - 1) {
+                let mut next = adj.clone();
+                for (v, a) in next.iter_mut().enumerate() {
+                    *a |= (subset >> v & 1) << (n - 1);
+                }
+                next.push(subset);
+
+                codes.insert(canonical(&next));
+            }
+        }
+
+        levels.push(codes.into_iter().map(|c| decode(n, c)).collect());
+    }
+
+    levels
+}
+
+fn to_graph(adj: &[u32]) -> AdjList {
+    let mut g = AdjList::with_capacity(adj.len());
+    for (u, &a) in adj.iter().enumerate() {
+        for v in u + 1..adj.len() {
+            if a >> v & 1 == 1 {
+                g.add_edge(u, v);
+            }
+        }
+    }
+    g
+}
+
+#[test]
+fn oracle_graph_counts() {
+    let counts = non_isomorphic(MAX_VERTICES)
+        .iter()
+        .map(Vec::len)
+        .collect::<Vec<_>>();
+
+    // OEIS A000088
+    assert_eq!(counts, vec![1, 1, 2, 4, 11, 34, 156, 1044]);
+}
+
+#[test]
+fn oracle_small_graphs() {
+    let params = GeneticParams {
+        population: 6,
+        generations: 3,
+        seed: Some(0),
+        ..GeneticParams::default()
+    };
+
+    for adj in non_isomorphic(MAX_VERTICES).iter().flatten() {
+        let g = to_graph(adj);
+        let chi = brute_force_chromatic(adj);
+
+        let heuristics = [
+            rs_coloring(&g),
+            cs_coloring(&g),
+            lf_coloring(&g),
+            sl_coloring(&g),
+            sdo_coloring(&g),
+            color(&g),
+            color_fast(&g),
+            color_balanced(&g),
+            genetic_coloring_with(&g, &params, &mut Monitor::new()),
+        ];
+        for c in &heuristics {
+            assert!(check_coloring(&g, c), "invalid coloring of {:?}", adj);
+            assert_eq!(peak_color(c).map_or(0, |p| p + 1), num_colors(c));
+            assert!(
+                num_colors(c) >= chi,
+                "fewer than {} colors for {:?}",
+                chi,
+                adj
+            );
+        }
+
+        if let Some((_, c)) = special_coloring(&g) {
+            assert!(check_coloring(&g, &c), "invalid coloring of {:?}", adj);
+            assert_eq!(num_colors(&c), chi, "not optimal for {:?}", adj);
+        }
+
+        if let Some(c) = planar_coloring(&g) {
+            assert!(check_coloring(&g, &c) && num_colors(&c) >= chi);
+            assert_eq!(peak_color(&c).map_or(0, |p| p + 1), num_colors(&c));
+        }
+
+        let (lower, upper) = chromatic_bounds(&g);
+        assert!(lower <= chi && chi <= upper, "bounds wrong for {:?}", adj);
+
+        let zykov = ExactOptions {
+            engine: ExactEngine::Zykov,
+            ..ExactOptions::default()
+        };
+        let symmetric = ExactOptions {
+            symmetry_breaking: false,
+            ..ExactOptions::default()
+        };
+
+        for c in &[
+            exact_coloring(&g),
+            exact_coloring_with(&g, &zykov),
+            exact_coloring_with(&g, &symmetric),
+            treewidth_coloring(&g, MAX_VERTICES).unwrap(),
+            with_reductions(&g, exact_coloring),
+        ] {
+            assert!(check_coloring(&g, c), "invalid coloring of {:?}", adj);
+            assert_eq!(peak_color(c).map_or(0, |p| p + 1), num_colors(c));
+            assert_eq!(num_colors(c), chi, "not optimal for {:?}", adj);
+        }
+
+        assert_eq!(chromatic_number(&g), chi);
+        assert!(k_coloring(&g, chi).is_some_and(|c| check_coloring(&g, &c)));
+        assert!(chi == 0 || k_coloring(&g, chi - 1).is_none());
+        assert_eq!(two_coloring(&g).is_some(), chi <= 2);
+    }
+}
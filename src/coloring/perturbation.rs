@@ -0,0 +1,106 @@
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+use crate::coloring::metaheuristics::class_ordering;
+use crate::coloring::{greedy_coloring_with, Coloring, ColoringWorkspace};
+use crate::graph::StaticGraph;
+
+/// Rounds without improvement after which the perturbation strength doubles.
+const PATIENCE: usize = 10;
+
+/// Greedily colors the graph ```rounds``` times in slightly perturbed versions of
+/// ```base_ordering``` and returns the coloring with the fewest colors.
+/// ```base_ordering``` has to contain every vertex once.
+///
+/// Every round orders the vertices by the color classes of the current coloring,
+/// shuffled within each class, which never needs more colors, and then shuffles
+/// a few random windows of the ordering. Orderings using no more colors replace
+/// the current one. The number and size of the windows double whenever the
+/// search stagnates for a while, and reset after an improvement.
+pub fn perturbation_restart_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    base_ordering: &[usize],
+    rounds: usize,
+) -> Coloring {
+    perturbation_restart_coloring_with(graph, base_ordering, rounds, &mut thread_rng())
+}
+
+/// Same as ```perturbation_restart_coloring``` with the given random number generator.
+pub fn perturbation_restart_coloring_with<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    base_ordering: &[usize],
+    rounds: usize,
+    rng: &mut R,
+) -> Coloring {
+    let n = graph.num_vertices();
+    let mut workspace = ColoringWorkspace::new();
+
+    greedy_coloring_with(graph, base_ordering, &mut workspace);
+    let mut current = workspace.coloring().to_vec();
+    let mut best = current.clone();
+    let mut best_colors = workspace.num_colors();
+    let mut current_colors = best_colors;
+
+    let mut strength = 1;
+    let mut stagnation = 0;
+
+    for _ in 0..rounds {
+        if n < 2 {
+            break;
+        }
+
+        let mut ordering = class_ordering(&current, rng);
+        let window = (strength + 1).min(n);
+        for _ in 0..strength {
+            let start = rng.gen_range(0, n - window + 1);
+            ordering[start..start + window].shuffle(rng);
+        }
+
+        greedy_coloring_with(graph, &ordering, &mut workspace);
+        let colors = workspace.num_colors();
+
+        if colors <= current_colors {
+            current.copy_from_slice(workspace.coloring());
+            current_colors = colors;
+        }
+        if colors < best_colors {
+            best.copy_from_slice(workspace.coloring());
+            best_colors = colors;
+            strength = 1;
+            stagnation = 0;
+        } else {
+            stagnation += 1;
+            if stagnation == PATIENCE {
+                strength = (2 * strength).min(n);
+                stagnation = 0;
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn perturbation_improves_base() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..5 {
+            let g = AdjList::random(80, 0.3);
+            let base = (0..80).collect::<Vec<_>>();
+
+            let c = perturbation_restart_coloring_with(&g, &base, 200, &mut rng);
+
+            assert!(check_coloring(&g, &c));
+            assert!(num_colors(&c) <= num_colors(&greedy_coloring(&g, 0..80)));
+        }
+
+        assert!(perturbation_restart_coloring(&AdjList::new(), &[], 10).is_empty());
+    }
+}
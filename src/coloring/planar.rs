@@ -0,0 +1,189 @@
+use crate::coloring::kempe::partial_kempe_chain;
+use crate::coloring::Coloring;
+use crate::graph::{is_planar, StaticGraph};
+
+/// Colors a planar graph with at most five colors.
+/// Returns ```None``` if the graph is not planar.
+///
+/// Vertices are colored in reverse smallest-last order, such that each vertex has
+/// at most five colored neighbors. If these use all five colors, a Kempe chain
+/// swap between two of them frees a color, which always exists in planar graphs.
+///
+/// The planarity test and the bucketed smallest-last order take linear time, as
+/// does the coloring if no swap is needed. Each swap searches a Kempe chain in
+/// ```O(n)``` for up to ten pairs of colors, such that the coloring takes ```O(n^2)```
+/// in the worst case. It is not the linear-time five-coloring by contraction of
+/// low degree vertices.
+pub fn planar_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Option<Coloring> {
+    if !is_planar(graph) {
+        return None;
+    }
+
+    let n = graph.num_vertices();
+    let mut colors: Vec<Option<usize>> = vec![None; n];
+
+    for v in smallest_last(graph).into_iter().rev() {
+        let neighbors = graph
+            .neighbors(v)
+            .filter(|&u| colors[u].is_some())
+            .collect::<Vec<_>>();
+
+        let free = (0..5).find(|&c| neighbors.iter().all(|&u| colors[u] != Some(c)));
+
+        colors[v] = Some(match free {
+            Some(c) => c,
+            None => kempe_free(graph, &mut colors, &neighbors)
+                .expect("planar graphs admit a Kempe swap"),
+        });
+    }
+
+    Some(colors.into_iter().map(Option::unwrap).collect())
+}
+
+/// Frees a color among the five differently colored ```neighbors``` by swapping a
+/// Kempe chain. Returns the freed color.
+fn kempe_free<G: StaticGraph + ?Sized>(
+    graph: &G,
+    colors: &mut [Option<usize>],
+    neighbors: &[usize],
+) -> Option<usize> {
+    for (i, &x) in neighbors.iter().enumerate() {
+        for &y in &neighbors[i + 1..] {
+            let (a, b) = (colors[x].unwrap(), colors[y].unwrap());
+            let chain = partial_kempe_chain(graph, colors, x, a, b);
+
+            // Swapping is safe if the chain does not reach 'y', which keeps color 'b'
+            if !chain.contains(&y) {
+                for u in chain {
+                    colors[u] = Some(if colors[u] == Some(a) { b } else { a });
+                }
+                return Some(a);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the vertices in smallest-last order, i.e. repeatedly removing a vertex
+/// of minimum remaining degree. Uses buckets of vertices by degree.
+fn smallest_last<G: StaticGraph + ?Sized>(graph: &G) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut degree = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).count())
+        .collect::<Vec<_>>();
+    let mut buckets = vec![Vec::new(); degree.iter().cloned().max().unwrap_or(0) + 1];
+    for v in 0..n {
+        buckets[degree[v]].push(v);
+    }
+
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut d = 0;
+
+    while order.len() < n {
+        // Buckets may contain stale entries of vertices whose degree has decreased
+        let v = loop {
+            match buckets[d].pop() {
+                Some(v) if !removed[v] && degree[v] == d => break v,
+                Some(_) => continue,
+                None => d += 1,
+            }
+        };
+
+        removed[v] = true;
+        order.push(v);
+
+        for u in graph.neighbors(v) {
+            if !removed[u] {
+                degree[u] -= 1;
+                buckets[degree[u]].push(u);
+            }
+        }
+
+        d = d.saturating_sub(1);
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, num_colors};
+    use crate::graph::*;
+
+    #[test]
+    fn planar_icosahedron() {
+        // 5-regular, so every vertex may see five colored neighbors
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (0, 5),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 1),
+            (1, 6),
+            (2, 6),
+            (2, 7),
+            (3, 7),
+            (3, 8),
+            (4, 8),
+            (4, 9),
+            (5, 9),
+            (5, 10),
+            (1, 10),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 6),
+            (11, 6),
+            (11, 7),
+            (11, 8),
+            (11, 9),
+            (11, 10),
+        ];
+        let mut g = AdjList::new();
+        for &(u, v) in edges.iter() {
+            g.add_edge(u, v);
+        }
+
+        let c = planar_coloring(&g).unwrap();
+
+        assert!(check_coloring(&g, &c));
+        assert!(num_colors(&c) <= 5);
+    }
+
+    #[test]
+    fn planar_random_triangulations() {
+        for _ in 0..20 {
+            // Stack triangulation, inserting each vertex into a random face
+            let mut g = AdjList::complete(3);
+            let mut faces = vec![(0, 1, 2)];
+            for v in 3..300 {
+                let i = rand::random::<usize>() % faces.len();
+                let (a, b, c) = faces.swap_remove(i);
+                g.add_edge(v, a);
+                g.add_edge(v, b);
+                g.add_edge(v, c);
+                faces.extend(vec![(a, b, v), (b, c, v), (a, c, v)]);
+            }
+
+            let c = planar_coloring(&g).unwrap();
+
+            assert!(check_coloring(&g, &c));
+            assert!(num_colors(&c) <= 5);
+        }
+    }
+
+    #[test]
+    fn planar_rejects_nonplanar() {
+        assert!(planar_coloring(&AdjList::complete(5)).is_none());
+    }
+}
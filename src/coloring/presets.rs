@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::coloring::metaheuristics::cooperative_coloring;
+use crate::coloring::{
+    check_coloring, compact_colors, lf_coloring, num_colors, sdo_coloring, sl_coloring, Coloring,
+};
+use crate::graph::StaticGraph;
+
+/// Colors the graph with cheap greedy heuristics only.
+/// Returns the better of the largest-first and smallest-last colorings.
+pub fn color_fast<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    best_of(vec![lf_coloring(graph), sl_coloring(graph)])
+}
+
+/// Colors the graph with the greedy heuristics and saturation degree ordering,
+/// then tries to eliminate color classes by local search.
+pub fn color_balanced<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let c = best_of(vec![color_fast(graph), sdo_coloring(graph)]);
+
+    eliminate_classes(graph, c)
+}
+
+/// Colors the graph with the cooperative solver within ```time_budget```.
+/// It starts from ```color_balanced``` and alternates greedy constructions with
+/// local search, sharing the best coloring between them.
+pub fn color_best<G: StaticGraph + ?Sized>(graph: &G, time_budget: Duration) -> Coloring {
+    cooperative_coloring(graph, time_budget)
+}
+
+/// Returns the coloring using the fewest colors.
+fn best_of(colorings: Vec<Coloring>) -> Coloring {
+    colorings.into_iter().min_by_key(num_colors).unwrap()
+}
+
+/// Local search removing whole color classes.
+/// Tries to move every vertex of a color class into another class in which it has
+/// no neighbors, starting with the smallest class. Moves keep the coloring valid,
+/// such that the number of colors never increases. Stops once no class can be
+/// emptied and compacts the remaining colors.
+pub(crate) fn eliminate_classes<G: StaticGraph + ?Sized>(
+    graph: &G,
+    mut coloring: Coloring,
+) -> Coloring {
+    loop {
+        let mut classes: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (v, &c) in coloring.iter().enumerate() {
+            classes.entry(c).or_default().push(v);
+        }
+
+        let mut order = classes.keys().cloned().collect::<Vec<_>>();
+        order.sort_by_key(|c| (classes[c].len(), *c));
+
+        // Moves are only kept if the whole class could be emptied
+        let emptied = order.iter().find_map(|&c| {
+            let mut candidate = coloring.clone();
+
+            let moved = classes[&c].iter().all(|&v| {
+                let target = order
+                    .iter()
+                    .filter(|&&d| d != c)
+                    .find(|&&d| graph.neighbors(v).all(|u| candidate[u] != d));
+
+                match target {
+                    Some(&d) => {
+                        candidate[v] = d;
+                        true
+                    }
+                    None => false,
+                }
+            });
+
+            if moved {
+                Some(candidate)
+            } else {
+                None
+            }
+        });
+
+        match emptied {
+            Some(c) => coloring = c,
+            None => {
+                compact_colors(&mut coloring);
+                return coloring;
+            }
+        }
+
+        debug_assert!(check_coloring(graph, &coloring));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn presets_random() {
+        let g = AdjList::random_with(80, |_, _| 0.5, &mut StdRng::seed_from_u64(3));
+
+        let fast = color_fast(&g);
+        let balanced = color_balanced(&g);
+        let best = color_best(&g, Duration::from_millis(200));
+
+        assert!(check_coloring(&g, &fast));
+        assert!(check_coloring(&g, &balanced));
+        assert!(check_coloring(&g, &best));
+
+        // Balanced starts from the fast coloring if it is best and never adds colors
+        assert!(num_colors(&balanced) <= num_colors(&fast));
+        assert!(num_colors(&best) <= g.max_degree() + 1);
+    }
+
+    #[test]
+    fn presets_no_budget() {
+        let g = AdjList::random_with(50, |_, _| 0.3, &mut StdRng::seed_from_u64(4));
+
+        let c = color_best(&g, Duration::from_secs(0));
+
+        assert!(check_coloring(&g, &c));
+    }
+
+    #[test]
+    fn eliminate_wasteful_coloring() {
+        // Star colored with a distinct color per vertex
+        let mut g = AdjList::new();
+        for i in 1..10 {
+            g.add_edge(0, i);
+        }
+        let c = (0..10).collect::<Coloring>();
+
+        let c = eliminate_classes(&g, c);
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 2);
+    }
+
+    #[test]
+    fn eliminate_complete() {
+        let g = AdjMatrix::complete(10);
+        let c = (0..10).collect::<Coloring>();
+
+        let c = eliminate_classes(&g, c);
+
+        assert_eq!(num_colors(&c), 10);
+    }
+}
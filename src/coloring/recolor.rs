@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use crate::coloring::kempe::partial_kempe_chain;
+use crate::coloring::{check_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Repairs a coloring which became invalid through changes of the graph while
+/// changing the colors of at most ```max_changes``` vertices.
+/// ```old_coloring``` may be shorter than the graph, vertices without a color are
+/// colored as well but do not count as changes.
+///
+/// Conflicting edges are resolved by uncoloring a small vertex cover of them.
+/// The uncolored vertices are then given a color free among their neighbors, or
+/// one that is freed by swapping the fewest vertices along Kempe chains. Only if
+/// neither is within the budget a new color is used.
+/// Returns ```None``` if the repair needs more than ```max_changes``` changes.
+pub fn recolor_min_change<G: StaticGraph + ?Sized>(
+    graph: &G,
+    old_coloring: &[usize],
+    max_changes: usize,
+) -> Option<Coloring> {
+    let old = old_coloring.iter().map(|&c| Some(c)).collect::<Vec<_>>();
+
+    recolor_partial(graph, &old, max_changes)
+}
+
+/// Same as ```recolor_min_change``` but any vertex may lack an old color.
+pub(crate) fn recolor_partial<G: StaticGraph + ?Sized>(
+    graph: &G,
+    old_coloring: &[Option<usize>],
+    max_changes: usize,
+) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    let mut colors = (0..n)
+        .map(|v| old_coloring.get(v).cloned().flatten())
+        .collect::<Vec<_>>();
+
+    let changes = |colors: &[Option<usize>]| {
+        old_coloring
+            .iter()
+            .zip(colors)
+            .filter(|&(&old, &new)| old.is_some() && new != old)
+            .count()
+    };
+
+    // Greedy vertex cover of the conflicting edges
+    let mut conflicts = graph
+        .edges()
+        .filter(|&(u, v)| colors[u].is_some() && colors[u] == colors[v])
+        .collect::<Vec<_>>();
+
+    while !conflicts.is_empty() {
+        let mut count = vec![0; n];
+        for &(u, v) in &conflicts {
+            count[u] += 1;
+            count[v] += 1;
+        }
+
+        let w = (0..n).max_by_key(|&v| count[v]).unwrap();
+        colors[w] = None;
+        conflicts.retain(|&(u, v)| u != w && v != w);
+    }
+
+    if changes(&colors) > max_changes {
+        return None;
+    }
+
+    let palette = colors.iter().filter_map(|&c| c).collect::<HashSet<_>>();
+    let mut next = palette.iter().max().map_or(0, |&c| c + 1);
+    let mut palette = palette.into_iter().collect::<Vec<_>>();
+    palette.sort_unstable();
+
+    for v in 0..n {
+        if colors[v].is_some() {
+            continue;
+        }
+
+        let blocked = graph
+            .neighbors(v)
+            .filter_map(|u| colors[u])
+            .collect::<HashSet<_>>();
+
+        if let Some(&c) = palette.iter().find(|c| !blocked.contains(c)) {
+            colors[v] = Some(c);
+            continue;
+        }
+
+        let budget = max_changes - changes(&colors);
+        match cheapest_swap(graph, &colors, v, &palette, old_coloring) {
+            Some((c, chains, cost)) if cost <= budget => {
+                for u in chains {
+                    colors[u] = colors[u].map(|x| if x == c.0 { c.1 } else { c.0 });
+                }
+                colors[v] = Some(c.0);
+            }
+            _ => {
+                colors[v] = Some(next);
+                palette.push(next);
+                next += 1;
+            }
+        }
+    }
+
+    debug_assert!(changes(&colors) <= max_changes);
+    let coloring = colors.into_iter().map(Option::unwrap).collect::<Coloring>();
+    debug_assert!(check_coloring(graph, &coloring));
+
+    Some(coloring)
+}
+
+/// Finds the cheapest way to free a color for ```v``` by Kempe chain swaps.
+/// Color ```a``` is freed by swapping ```a``` and ```b``` in the chains through the
+/// neighbors colored ```a```, if none of them reaches a neighbor colored ```b```.
+/// Returns the pair of colors, the vertices to swap and the number of vertices
+/// which newly change with respect to ```old```.
+fn cheapest_swap<G: StaticGraph + ?Sized>(
+    graph: &G,
+    colors: &[Option<usize>],
+    v: usize,
+    palette: &[usize],
+    old: &[Option<usize>],
+) -> Option<((usize, usize), Vec<usize>, usize)> {
+    let mut best: Option<((usize, usize), Vec<usize>, usize)> = None;
+
+    for &a in palette {
+        for &b in palette.iter().filter(|&&b| b != a) {
+            let mut chains = HashSet::new();
+            for u in graph.neighbors(v).filter(|&u| colors[u] == Some(a)) {
+                if !chains.contains(&u) {
+                    chains.extend(partial_kempe_chain(graph, colors, u, a, b));
+                }
+            }
+
+            if graph
+                .neighbors(v)
+                .any(|u| colors[u] == Some(b) && chains.contains(&u))
+            {
+                continue;
+            }
+
+            // Vertices already changed may return to their old color
+            let cost = chains
+                .iter()
+                .map(|&u| {
+                    let swapped = if colors[u] == Some(a) { b } else { a };
+                    let old = old.get(u).cloned().flatten();
+                    let before = old.map_or(0, |c| (Some(c) != colors[u]) as isize);
+                    let after = old.map_or(0, |c| (c != swapped) as isize);
+                    after - before
+                })
+                .sum::<isize>()
+                .max(0) as usize;
+
+            if best.as_ref().is_none_or(|b| cost < b.2) {
+                best = Some(((a, b), chains.into_iter().collect(), cost));
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn recolor_single_conflict() {
+        let mut g = AdjList::new();
+        for i in 0..6 {
+            g.add_edge(i, (i + 1) % 6);
+        }
+        let old = vec![0, 1, 0, 1, 0, 1];
+
+        // Chord between two vertices of color 0
+        g.add_edge(0, 2);
+        let c = recolor_min_change(&g, &old, 1).unwrap();
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(coloring_distance(&old, &c), 1);
+        assert!(recolor_min_change(&g, &old, 0).is_none());
+    }
+
+    #[test]
+    fn recolor_kempe() {
+        // Edge 0 - 1 colored 0 1 and isolated 2 colored 1, the new vertex 3 sees both colors
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(3, 0);
+        g.add_edge(3, 2);
+        let old = vec![0, 1, 1];
+
+        // Swapping the chain of 2 alone frees color 1
+        let c = recolor_min_change(&g, &old, 1).unwrap();
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(c, vec![0, 1, 0, 1]);
+
+        // Without budget a new color is used
+        let c = recolor_min_change(&g, &old, 0).unwrap();
+
+        assert_eq!(c, vec![0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn recolor_random_additions() {
+        for _ in 0..10 {
+            let mut g = AdjList::random(50, 0.1);
+            let old = sl_coloring(&g);
+            g.add_edge(0, 1);
+            g.add_edge(2, 3);
+            g.add_edge(4, 5);
+
+            let c = recolor_min_change(&g, &old, 3).unwrap();
+
+            assert!(check_coloring(&g, &c));
+            assert!(c.iter().zip(&old).filter(|&(x, y)| x != y).count() <= 3);
+        }
+    }
+}
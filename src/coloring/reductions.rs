@@ -0,0 +1,268 @@
+//! Preprocessing rules shrinking a graph without changing its chromatic number.
+//!
+//! ```reduce``` repeatedly removes vertices which can always be colored once the
+//! rest of the graph is colored. The removals are recorded in an undo log, which
+//! ```Reduced::lift``` replays backwards to extend a coloring of the reduced graph
+//! to the original one. Lifting never uses more colors than the chromatic number
+//! or the colors of the reduced coloring, such that optimal colorings stay optimal.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{compact_colors, Coloring};
+use crate::graph::{EliminationGame, StaticGraph};
+
+/// A removal recorded in the undo log.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reduction {
+    /// ```vertex``` had degree below a lower bound on the chromatic number.
+    LowDegree {
+        vertex: usize,
+        neighbors: Vec<usize>,
+    },
+    /// ```vertex``` was simplicial, i.e. its neighbors formed a clique.
+    Simplicial {
+        vertex: usize,
+        neighbors: Vec<usize>,
+    },
+    /// ```vertex``` was not adjacent to ```by``` and all its neighbors were neighbors of ```by```.
+    Dominated { vertex: usize, by: usize },
+}
+
+/// Graph remaining after reductions together with the undo log.
+#[derive(Debug, Clone)]
+pub struct Reduced<G> {
+    /// The reduced graph, vertex ```i``` corresponds to ```kept[i]``` in the original graph.
+    pub graph: G,
+    pub kept: Vec<usize>,
+    /// Removals in the order they were applied.
+    pub log: Vec<Reduction>,
+    n: usize,
+}
+
+impl<G: StaticGraph> Reduced<G> {
+    /// Extends a coloring of the reduced graph to the original graph.
+    /// The colors are compacted first, such that removed vertices fill no gaps
+    /// between them with additional colors.
+    pub fn lift(&self, coloring: &[usize]) -> Coloring {
+        let mut coloring = coloring.to_vec();
+        compact_colors(&mut coloring);
+
+        let mut colors = vec![None; self.n];
+        for (i, &v) in self.kept.iter().enumerate() {
+            colors[v] = Some(coloring[i]);
+        }
+
+        // Neighbors present at the time of removal are colored before the vertex
+        for step in self.log.iter().rev() {
+            match *step {
+                Reduction::LowDegree {
+                    vertex,
+                    ref neighbors,
+                }
+                | Reduction::Simplicial {
+                    vertex,
+                    ref neighbors,
+                } => {
+                    let blocked = neighbors
+                        .iter()
+                        .filter_map(|&u| colors[u])
+                        .collect::<HashSet<_>>();
+                    colors[vertex] = (0..).find(|c| !blocked.contains(c));
+                }
+                Reduction::Dominated { vertex, by } => {
+                    colors[vertex] = colors[by];
+                }
+            }
+        }
+
+        colors.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Number of vertices removed from the original graph.
+    pub fn removed(&self) -> usize {
+        self.n - self.kept.len()
+    }
+}
+
+/// Applies all reduction rules until none of them applies anymore.
+///
+/// - Vertices with fewer neighbors than the size of a greedily found clique are removed.
+/// - Simplicial vertices are removed, their closed neighborhood is a clique.
+/// - A vertex whose neighborhood is contained in the neighborhood of a non-adjacent
+///   vertex is removed, it can take the color of the latter.
+pub fn reduce<G: StaticGraph>(graph: &G) -> Reduced<G> {
+    let n = graph.num_vertices();
+    let mut game = EliminationGame::new(graph);
+    let mut log = Vec::new();
+
+    let lower = greedy_clique(graph).len();
+
+    let mut queue = (0..n).collect::<VecDeque<_>>();
+    let mut queued = vec![true; n];
+
+    while let Some(v) = queue.pop_front() {
+        queued[v] = false;
+        if !game.is_alive(v) {
+            continue;
+        }
+
+        let step = if game.degree(v) < lower {
+            Some(Reduction::LowDegree {
+                vertex: v,
+                neighbors: game.remove(v),
+            })
+        } else if game.is_simplicial(v) {
+            Some(Reduction::Simplicial {
+                vertex: v,
+                neighbors: game.remove(v),
+            })
+        } else {
+            dominator(&game, v).map(|by| {
+                game.remove(v);
+                Reduction::Dominated { vertex: v, by }
+            })
+        };
+
+        if let Some(step) = step {
+            // Removal may enable reductions of the former neighbors
+            let neighbors = match step {
+                Reduction::LowDegree { ref neighbors, .. }
+                | Reduction::Simplicial { ref neighbors, .. } => neighbors.clone(),
+                Reduction::Dominated { .. } => {
+                    graph.neighbors(v).filter(|&u| game.is_alive(u)).collect()
+                }
+            };
+
+            for u in neighbors {
+                if !queued[u] {
+                    queued[u] = true;
+                    queue.push_back(u);
+                }
+            }
+            log.push(step);
+        }
+    }
+
+    let kept = game.alive().collect::<Vec<_>>();
+    let mut index = vec![0; n];
+    for (i, &v) in kept.iter().enumerate() {
+        index[v] = i;
+    }
+
+    let mut reduced = G::with_capacity(kept.len());
+    for &u in &kept {
+        for &v in game.neighbors(u) {
+            if u < v {
+                reduced.add_edge(index[u], index[v]);
+            }
+        }
+    }
+
+    Reduced {
+        graph: reduced,
+        kept,
+        log,
+        n,
+    }
+}
+
+/// Colors the graph by applying ```solver``` to the reduced graph and lifting the result.
+pub fn with_reductions<G, F>(graph: &G, solver: F) -> Coloring
+where
+    G: StaticGraph,
+    F: FnOnce(&G) -> Coloring,
+{
+    let reduced = reduce(graph);
+    let c = solver(&reduced.graph);
+
+    reduced.lift(&c)
+}
+
+/// Returns a vertex not adjacent to ```v``` whose neighborhood contains that of ```v```.
+fn dominator(game: &EliminationGame, v: usize) -> Option<usize> {
+    let neighbors = game.neighbors(v);
+
+    // Any candidate is a neighbor of each neighbor of 'v', the smallest suffices
+    let w = *neighbors.iter().min_by_key(|&&w| game.degree(w))?;
+
+    game.neighbors(w)
+        .iter()
+        .cloned()
+        .find(|&u| u != v && !neighbors.contains(&u) && neighbors.is_subset(game.neighbors(u)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn reduce_tree_completely() {
+        let mut g = AdjList::new();
+        for i in 0..31 {
+            g.add_edge(i, 2 * i + 1);
+            g.add_edge(i, 2 * i + 2);
+        }
+
+        let r = reduce(&g);
+        let c = r.lift(&[]);
+
+        assert_eq!(r.removed(), 63);
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 2);
+    }
+
+    #[test]
+    fn reduce_keeps_odd_cycle_core() {
+        // Wheel on an odd cycle with pendant paths
+        let mut g = AdjList::new();
+        for i in 0..7 {
+            g.add_edge(i, (i + 1) % 7);
+            g.add_edge(i, 7);
+        }
+        g.add_edge(0, 8);
+        g.add_edge(8, 9);
+
+        let r = reduce(&g);
+
+        assert_eq!(r.kept, (0..8).collect::<Vec<_>>());
+        assert_eq!(num_colors(&r.lift(&exact_coloring(&r.graph))), 4);
+
+        // Pendant vertices would take the unused colors between spread out ones
+        let spread = exact_coloring(&r.graph)
+            .iter()
+            .map(|c| 3 * c)
+            .collect::<Vec<_>>();
+        assert_eq!(num_colors(&r.lift(&spread)), 4);
+    }
+
+    #[test]
+    fn reduce_dominated() {
+        // Triangle free, 0 is not simplicial and its neighbors 2, 3, 4 are neighbors of 1
+        let mut g = AdjList::new();
+        for &v in &[2, 3, 4] {
+            g.add_edge(0, v);
+            g.add_edge(1, v);
+        }
+        g.add_edge(1, 5);
+
+        let r = reduce(&g);
+
+        assert!(r.log.contains(&Reduction::Dominated { vertex: 0, by: 1 }));
+        assert!(check_coloring(&g, &r.lift(&sdo_coloring(&r.graph))));
+    }
+
+    #[test]
+    fn reduce_preserves_chromatic_number() {
+        for _ in 0..20 {
+            let g = AdjList::random(16, 0.3);
+
+            let c = with_reductions(&g, exact_coloring);
+
+            assert!(check_coloring(&g, &c));
+            assert_eq!(num_colors(&c), chromatic_number(&g));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use rand::seq::index;
+use rand::Rng;
+
+use crate::coloring::{sdo_coloring, Coloring};
+use crate::graph::{AdjList, StaticGraph};
+
+/// Approximate coloring for graphs too large for the other algorithms.
+/// A skeleton of ```sample_size``` random vertices is colored with the saturation
+/// degree ordering on its induced subgraph. Every other vertex then independently
+/// takes the first color not used by its neighbors in the skeleton. This leaves
+/// conflicts between vertices outside of the skeleton, which are repaired in a
+/// single pass by recoloring the conflicting vertex first fit.
+///
+/// Every vertex is visited a constant number of times, trading quality for
+/// tractability. The result is a valid coloring.
+pub fn sampled_coloring<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    sample_size: usize,
+    rng: &mut R,
+) -> Coloring {
+    let n = graph.num_vertices();
+    let sample = index::sample(rng, n, sample_size.min(n)).into_vec();
+
+    // Position of every vertex in the skeleton
+    let mut position = vec![None; n];
+    for (i, &v) in sample.iter().enumerate() {
+        position[v] = Some(i);
+    }
+
+    let mut skeleton = AdjList::with_capacity(sample.len());
+    for (i, &v) in sample.iter().enumerate() {
+        for u in graph.neighbors(v) {
+            if let Some(j) = position[u] {
+                skeleton.add_edge(i, j);
+            }
+        }
+    }
+
+    let mut coloring = vec![0; n];
+    for (&v, c) in sample.iter().zip(sdo_coloring(&skeleton)) {
+        coloring[v] = c;
+    }
+
+    // Colors used by the neighbors of the current vertex are marked with its index
+    let mut used = Vec::new();
+    let mut first_fit = |v: usize, neighbors: &mut dyn Iterator<Item = usize>, c: &Coloring| {
+        for u in neighbors {
+            if c[u] >= used.len() {
+                used.resize(c[u] + 1, usize::MAX);
+            }
+            used[c[u]] = v;
+        }
+
+        (0..).find(|&x| used.get(x) != Some(&v)).unwrap()
+    };
+
+    // Extension by the skeleton colors only
+    for v in 0..n {
+        if position[v].is_none() {
+            let mut neighbors = graph.neighbors(v).filter(|&u| position[u].is_some());
+            coloring[v] = first_fit(v, &mut neighbors, &coloring);
+        }
+    }
+
+    // Repair, vertices recolored earlier are respected by later ones
+    for v in 0..n {
+        if position[v].is_none() && graph.neighbors(v).any(|u| coloring[u] == coloring[v]) {
+            coloring[v] = first_fit(v, &mut graph.neighbors(v), &coloring);
+        }
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, num_colors};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sampled_valid() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for &size in &[0, 1, 20, 200, 1000] {
+            let g = AdjList::random(200, 0.1);
+            let c = sampled_coloring(&g, size, &mut rng);
+
+            assert!(check_coloring(&g, &c));
+            assert!(num_colors(&c) <= g.max_degree() + 1);
+        }
+    }
+
+    #[test]
+    fn sampled_full_sample() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let g = AdjList::random(50, 0.3);
+        let c = sampled_coloring(&g, 50, &mut rng);
+
+        // The whole graph is the skeleton, no repair is needed
+        assert!(check_coloring(&g, &c));
+        assert!(sampled_coloring(&AdjList::new(), 10, &mut rng).is_empty());
+    }
+}
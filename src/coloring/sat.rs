@@ -0,0 +1,468 @@
+//! Exact k-colorability by reduction to propositional satisfiability.
+//!
+//! ```coloring_cnf``` encodes whether a graph can be colored with ```k``` colors as
+//! a formula in conjunctive normal form, which ```Cnf::solve``` decides with a small
+//! conflict driven clause learning solver. ```k_colorable``` combines both and
+//! decodes the model into a coloring. Formulas can also be written in the DIMACS
+//! format to certify a result with an external solver.
+//!
+//! Literals are numbered as in DIMACS: variable ```x``` is the literal ```x```, its
+//! negation ```-x```, and variables start at one.
+
+use std::io::{self, Write};
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::Coloring;
+use crate::graph::StaticGraph;
+
+/// Conflicts before the first restart, scaled by the Luby sequence.
+const RESTART_UNIT: usize = 100;
+
+/// Factor by which variable activities decay after every conflict.
+const ACTIVITY_DECAY: f64 = 0.95;
+
+/// Formula in conjunctive normal form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cnf {
+    pub num_vars: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+impl Cnf {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a clause, the disjunction of its literals. Grows the number of variables
+    /// to include the literals.
+    pub fn add_clause(&mut self, clause: &[i32]) {
+        assert!(!clause.contains(&0), "literals must not be zero");
+
+        for &l in clause {
+            self.num_vars = self.num_vars.max(l.unsigned_abs() as usize);
+        }
+        self.clauses.push(clause.to_vec());
+    }
+
+    /// Returns an assignment satisfying all clauses, where entry ```i``` is the value
+    /// of variable ```i + 1```, or ```None``` if the formula is unsatisfiable.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        Solver::new(self)?.solve()
+    }
+
+    /// Writes the formula in the DIMACS CNF format.
+    pub fn write_dimacs<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "p cnf {} {}", self.num_vars, self.clauses.len())?;
+        for clause in &self.clauses {
+            for l in clause {
+                write!(out, "{} ", l)?;
+            }
+            writeln!(out, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encodes whether the graph has a coloring with ```k``` colors. Variable
+/// ```v * k + c + 1``` states that vertex ```v``` has color ```c```.
+///
+/// Every vertex has at least one color and adjacent vertices do not share one. A
+/// vertex with several colors can take any of them. The vertices of a greedily
+/// found clique are fixed to the first colors, which rules out colorings differing
+/// only by a permutation of these colors.
+pub fn coloring_cnf<G: StaticGraph + ?Sized>(graph: &G, k: usize) -> Cnf {
+    let var = |v: usize, c: usize| (v * k + c + 1) as i32;
+    let mut cnf = Cnf {
+        num_vars: graph.num_vertices() * k,
+        clauses: Vec::new(),
+    };
+
+    for v in graph.vertices() {
+        cnf.add_clause(&(0..k).map(|c| var(v, c)).collect::<Vec<_>>());
+    }
+    for (u, v) in graph.edges() {
+        for c in 0..k {
+            cnf.add_clause(&[-var(u, c), -var(v, c)]);
+        }
+    }
+    for (c, v) in greedy_clique(graph).into_iter().enumerate().take(k) {
+        cnf.add_clause(&[var(v, c)]);
+    }
+
+    cnf
+}
+
+/// Returns a coloring with at most ```k``` colors, ```None``` if none exists.
+/// Decided by the SAT solver on ```coloring_cnf```, exponential in the worst case
+/// and meant for small graphs or certifying that a coloring is optimal.
+pub fn k_colorable<G: StaticGraph + ?Sized>(graph: &G, k: usize) -> Option<Coloring> {
+    if greedy_clique(graph).len() > k {
+        return None;
+    }
+
+    let model = coloring_cnf(graph, k).solve()?;
+    Some(
+        graph
+            .vertices()
+            .map(|v| (0..k).find(|&c| model[v * k + c]).unwrap())
+            .collect(),
+    )
+}
+
+/// Literals inside the solver are ```2 * var + negated``` with variables from zero.
+type Lit = usize;
+
+fn var(l: Lit) -> usize {
+    l / 2
+}
+
+fn from_dimacs(l: i32) -> Lit {
+    2 * (l.unsigned_abs() as usize - 1) + (l < 0) as usize
+}
+
+/// Conflict driven clause learning with two watched literals per clause, first
+/// unique implication point learning, activity based branching and Luby restarts.
+/// Learned clauses are kept for the whole search.
+struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// Clauses watching each literal, visited when the literal becomes false.
+    watches: Vec<Vec<usize>>,
+    value: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// Position in the trail at which each decision level starts.
+    levels: Vec<usize>,
+    head: usize,
+    activity: Vec<f64>,
+    increment: f64,
+    /// Value each variable last had, tried first when branching.
+    phase: Vec<bool>,
+    seen: Vec<bool>,
+}
+
+impl Solver {
+    /// Sets up the solver, ```None``` if the formula is trivially unsatisfiable.
+    fn new(cnf: &Cnf) -> Option<Self> {
+        let n = cnf.num_vars;
+        let mut solver = Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * n],
+            value: vec![None; n],
+            level: vec![0; n],
+            reason: vec![None; n],
+            trail: Vec::new(),
+            levels: Vec::new(),
+            head: 0,
+            activity: vec![0.0; n],
+            increment: 1.0,
+            phase: vec![false; n],
+            seen: vec![false; n],
+        };
+
+        for clause in &cnf.clauses {
+            let mut lits = clause.iter().map(|&l| from_dimacs(l)).collect::<Vec<_>>();
+            lits.sort_unstable();
+            lits.dedup();
+            if lits.windows(2).any(|w| w[0] ^ 1 == w[1]) {
+                continue;
+            }
+
+            match lits.len() {
+                0 => return None,
+                1 => match solver.lit_value(lits[0]) {
+                    Some(false) => return None,
+                    Some(true) => {}
+                    None => solver.assign(lits[0], None),
+                },
+                _ => {
+                    solver.attach(lits);
+                }
+            }
+        }
+
+        Some(solver)
+    }
+
+    fn solve(mut self) -> Option<Vec<bool>> {
+        let (mut conflicts, mut restart) = (0, 1);
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.levels.is_empty() {
+                    return None;
+                }
+
+                let (learnt, back) = self.analyze(conflict);
+                self.backtrack(back);
+                let implied = learnt[0];
+                if learnt.len() == 1 {
+                    self.assign(implied, None);
+                } else {
+                    let index = self.attach(learnt);
+                    self.assign(implied, Some(index));
+                }
+                self.increment /= ACTIVITY_DECAY;
+
+                conflicts += 1;
+                if conflicts >= RESTART_UNIT * luby(restart) {
+                    conflicts = 0;
+                    restart += 1;
+                    self.backtrack(0);
+                }
+            } else {
+                let next = (0..self.value.len())
+                    .filter(|&v| self.value[v].is_none())
+                    .max_by(|&a, &b| self.activity[a].partial_cmp(&self.activity[b]).unwrap());
+
+                match next {
+                    Some(v) => {
+                        self.levels.push(self.trail.len());
+                        self.assign(2 * v + !self.phase[v] as usize, None);
+                    }
+                    None => return Some(self.value.iter().map(|v| v.unwrap()).collect()),
+                }
+            }
+        }
+    }
+
+    fn lit_value(&self, l: Lit) -> Option<bool> {
+        self.value[var(l)].map(|v| v != (l & 1 == 1))
+    }
+
+    fn assign(&mut self, l: Lit, reason: Option<usize>) {
+        let v = var(l);
+        self.value[v] = Some(l & 1 == 0);
+        self.level[v] = self.levels.len();
+        self.reason[v] = reason;
+        self.trail.push(l);
+    }
+
+    /// Adds a clause of at least two literals watching its first two.
+    fn attach(&mut self, lits: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[lits[0]].push(index);
+        self.watches[lits[1]].push(index);
+        self.clauses.push(lits);
+
+        index
+    }
+
+    /// Assigns the literals implied by unit clauses. Returns a conflicting clause.
+    /// The implied literal of a reason is always its first.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.head < self.trail.len() {
+            let falsified = self.trail[self.head] ^ 1;
+            self.head += 1;
+
+            let mut watching = std::mem::take(&mut self.watches[falsified]);
+            let mut i = 0;
+            while i < watching.len() {
+                let index = watching[i];
+                if self.clauses[index][0] == falsified {
+                    self.clauses[index].swap(0, 1);
+                }
+
+                let first = self.clauses[index][0];
+                if self.lit_value(first) == Some(true) {
+                    i += 1;
+                    continue;
+                }
+
+                let replacement = (2..self.clauses[index].len())
+                    .find(|&j| self.lit_value(self.clauses[index][j]) != Some(false));
+                if let Some(j) = replacement {
+                    self.clauses[index].swap(1, j);
+                    self.watches[self.clauses[index][1]].push(index);
+                    watching.swap_remove(i);
+                    continue;
+                }
+
+                if self.lit_value(first) == Some(false) {
+                    self.watches[falsified] = watching;
+                    return Some(index);
+                }
+                self.assign(first, Some(index));
+                i += 1;
+            }
+            self.watches[falsified] = watching;
+        }
+
+        None
+    }
+
+    /// Learns a clause from a conflict at the current decision level. Returns the
+    /// clause, whose first literal is implied after backtracking to the returned level.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let current = self.levels.len();
+        let mut learnt = vec![0];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let (mut clause, mut skip) = (conflict, 0);
+
+        loop {
+            for j in skip..self.clauses[clause].len() {
+                let l = self.clauses[clause][j];
+                let v = var(l);
+                if self.seen[v] || self.level[v] == 0 {
+                    continue;
+                }
+
+                self.seen[v] = true;
+                self.bump(v);
+                if self.level[v] == current {
+                    pending += 1;
+                } else {
+                    learnt.push(l);
+                }
+            }
+
+            // Latest assigned literal of the current level in the clause
+            let l = loop {
+                index -= 1;
+                if self.seen[var(self.trail[index])] {
+                    break self.trail[index];
+                }
+            };
+            self.seen[var(l)] = false;
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = l ^ 1;
+                break;
+            }
+            clause = self.reason[var(l)].unwrap();
+            skip = 1;
+        }
+
+        for &l in &learnt[1..] {
+            self.seen[var(l)] = false;
+        }
+
+        // Watch the literal of the highest remaining level second
+        let mut back = 0;
+        if learnt.len() > 1 {
+            let j = (1..learnt.len())
+                .max_by_key(|&j| self.level[var(learnt[j])])
+                .unwrap();
+            learnt.swap(1, j);
+            back = self.level[var(learnt[1])];
+        }
+
+        (learnt, back)
+    }
+
+    fn bump(&mut self, v: usize) {
+        self.activity[v] += self.increment;
+        if self.activity[v] > 1e100 {
+            self.activity.iter_mut().for_each(|a| *a *= 1e-100);
+            self.increment *= 1e-100;
+        }
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.levels.len() <= level {
+            return;
+        }
+
+        for l in self.trail.drain(self.levels[level]..) {
+            self.phase[var(l)] = l & 1 == 0;
+            self.value[var(l)] = None;
+        }
+        self.levels.truncate(level);
+        self.head = self.trail.len();
+    }
+}
+
+/// Returns the ```i```-th element of the Luby sequence 1, 1, 2, 1, 1, 2, 4, ...
+/// counted from one.
+fn luby(mut i: usize) -> usize {
+    loop {
+        // Smallest complete block 2^k - 1 containing 'i'
+        let mut size = 1;
+        while size < i {
+            size = 2 * size + 1;
+        }
+        if size == i {
+            return size.div_ceil(2);
+        }
+        i -= size / 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, k_coloring, num_colors};
+    use crate::graph::*;
+
+    #[test]
+    fn sat_pigeonhole() {
+        // Four pigeons in three holes
+        let var = |p: i32, h: i32| 3 * p + h + 1;
+        let mut cnf = Cnf::new();
+        for p in 0..4 {
+            cnf.add_clause(&[var(p, 0), var(p, 1), var(p, 2)]);
+            for q in 0..p {
+                for h in 0..3 {
+                    cnf.add_clause(&[-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        assert_eq!(cnf.solve(), None);
+
+        // Three pigeons fit
+        cnf.clauses.retain(|c| c.iter().all(|&l| l.abs() <= 9));
+        let model = cnf.solve().unwrap();
+        assert!(cnf.clauses.iter().all(|c| c
+            .iter()
+            .any(|&l| model[l.unsigned_abs() as usize - 1] == (l > 0))));
+
+        let mut dimacs = Vec::new();
+        cnf.write_dimacs(&mut dimacs).unwrap();
+        assert!(String::from_utf8(dimacs).unwrap().starts_with("p cnf 12 "));
+        assert_eq!(luby(7), 4);
+        assert_eq!(luby(10), 2);
+    }
+
+    #[test]
+    fn k_colorable_petersen_groetzsch() {
+        let mut petersen = AdjList::new();
+        for i in 0..5 {
+            petersen.add_edge(i, (i + 1) % 5);
+            petersen.add_edge(i, i + 5);
+            petersen.add_edge(i + 5, (i + 2) % 5 + 5);
+        }
+        assert_eq!(k_colorable(&petersen, 2), None);
+        let c = k_colorable(&petersen, 3).unwrap();
+        assert!(check_coloring(&petersen, &c));
+
+        // Triangle free with chromatic number 4
+        let mut groetzsch = AdjList::new();
+        for i in 0..5 {
+            groetzsch.add_edge(i, (i + 1) % 5);
+            groetzsch.add_edge(i + 5, (i + 1) % 5);
+            groetzsch.add_edge(i + 5, (i + 4) % 5);
+            groetzsch.add_edge(i + 5, 10);
+        }
+        assert_eq!(k_colorable(&groetzsch, 3), None);
+        let c = k_colorable(&groetzsch, 4).unwrap();
+        assert!(check_coloring(&groetzsch, &c));
+        assert_eq!(num_colors(&c), 4);
+
+        assert_eq!(k_colorable(&AdjList::complete(5), 4), None);
+        assert_eq!(k_colorable(&AdjList::new(), 0), Some(Vec::new()));
+    }
+
+    #[test]
+    fn k_colorable_matches_search() {
+        for _ in 0..10 {
+            let g = AdjList::random(25, 0.4);
+            for k in 3..7 {
+                let c = k_colorable(&g, k);
+                assert_eq!(c.is_some(), k_coloring(&g, k).is_some());
+                assert!(c.is_none_or(|c| check_coloring(&g, &c) && num_colors(&c) <= k));
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+use crate::analysis::degrees;
+use crate::coloring::Coloring;
+use crate::graph::StaticGraph;
+
+/// Saturation degrees of a partially colored graph, kept up to date as vertices
+/// are colored and uncolored, as needed by DSATUR and backtracking searches.
+///
+/// For every vertex the colored neighbors of each color are counted and a bitset
+/// marks the colors present among them, such that the saturation degree, whether a
+/// color is free and the smallest free color are answered without visiting the
+/// neighbors. The uncolored vertices are kept ordered by saturation degree, ties
+/// broken by degree and then by the smaller index. Coloring or uncoloring a vertex
+/// of degree ```d``` takes ```O(d log n)``` time.
+///
+/// The tracker does not hold the graph, the same graph has to be passed to every
+/// call.
+#[derive(Debug, Clone, Default)]
+pub struct SaturationTracker {
+    colors: Vec<Option<usize>>,
+    degree: Vec<usize>,
+    /// Colored neighbors of every vertex per color
+    counts: Vec<Vec<u32>>,
+    /// Colors among the neighbors of every vertex, one bit per color
+    present: Vec<Vec<u64>>,
+    saturation: Vec<usize>,
+    /// Uncolored vertices by saturation degree, degree and index
+    queue: BTreeSet<(usize, usize, Reverse<usize>)>,
+}
+
+impl SaturationTracker {
+    /// Tracker of the graph without any colored vertex.
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let n = graph.num_vertices();
+        let degree = degrees(graph).into_owned();
+
+        Self {
+            colors: vec![None; n],
+            queue: (0..n).map(|v| (0, degree[v], Reverse(v))).collect(),
+            degree,
+            counts: vec![Vec::new(); n],
+            present: vec![Vec::new(); n],
+            saturation: vec![0; n],
+        }
+    }
+
+    /// Returns the color of ```v```, if any.
+    pub fn color_of(&self, v: usize) -> Option<usize> {
+        self.colors[v]
+    }
+
+    /// Returns the number of distinct colors among the neighbors of ```v```.
+    pub fn saturation(&self, v: usize) -> usize {
+        self.saturation[v]
+    }
+
+    /// Whether no neighbor of ```v``` has color ```c```.
+    pub fn is_free(&self, v: usize, c: usize) -> bool {
+        self.present[v]
+            .get(c / 64)
+            .is_none_or(|word| word & (1 << (c % 64)) == 0)
+    }
+
+    /// Returns the smallest color of no neighbor of ```v```.
+    pub fn first_free(&self, v: usize) -> usize {
+        self.present[v]
+            .iter()
+            .position(|&word| word != !0)
+            .map_or(self.present[v].len() * 64, |i| {
+                i * 64 + self.present[v][i].trailing_ones() as usize
+            })
+    }
+
+    /// Returns the uncolored vertex of the highest saturation degree, ties broken
+    /// by degree and then by the smaller index.
+    pub fn most_saturated(&self) -> Option<usize> {
+        self.queue.iter().next_back().map(|&(_, _, Reverse(v))| v)
+    }
+
+    /// Returns the number of uncolored vertices.
+    pub fn num_uncolored(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the partial coloring.
+    pub fn colors(&self) -> &[Option<usize>] {
+        &self.colors
+    }
+
+    /// Returns the coloring once every vertex is colored.
+    pub fn coloring(&self) -> Option<Coloring> {
+        self.colors.iter().cloned().collect()
+    }
+
+    /// Colors the uncolored vertex ```v``` with ```c```, which may conflict with its
+    /// neighbors. Panics if ```v``` is already colored.
+    pub fn color<G: StaticGraph + ?Sized>(&mut self, graph: &G, v: usize, c: usize) {
+        assert!(self.colors[v].is_none(), "vertex {} already colored", v);
+        self.queue.remove(&self.key(v));
+        self.colors[v] = Some(c);
+
+        for u in graph.neighbors(v) {
+            if self.counts[u].len() <= c {
+                self.counts[u].resize(c + 1, 0);
+                self.present[u].resize(c / 64 + 1, 0);
+            }
+            self.counts[u][c] += 1;
+            if self.counts[u][c] == 1 {
+                self.update(u, |t| {
+                    t.present[u][c / 64] |= 1 << (c % 64);
+                    t.saturation[u] += 1;
+                });
+            }
+        }
+    }
+
+    /// Removes the color of ```v``` and returns it, if any.
+    pub fn uncolor<G: StaticGraph + ?Sized>(&mut self, graph: &G, v: usize) -> Option<usize> {
+        let c = self.colors[v].take()?;
+        self.queue.insert(self.key(v));
+
+        for u in graph.neighbors(v) {
+            self.counts[u][c] -= 1;
+            if self.counts[u][c] == 0 {
+                self.update(u, |t| {
+                    t.present[u][c / 64] &= !(1 << (c % 64));
+                    t.saturation[u] -= 1;
+                });
+            }
+        }
+
+        Some(c)
+    }
+
+    fn key(&self, v: usize) -> (usize, usize, Reverse<usize>) {
+        (self.saturation[v], self.degree[v], Reverse(v))
+    }
+
+    /// Applies ```change``` to the saturation of ```v```, moving it in the queue.
+    fn update(&mut self, v: usize, change: impl FnOnce(&mut Self)) {
+        let queued = self.colors[v].is_none() && self.queue.remove(&self.key(v));
+        change(self);
+        if queued {
+            self.queue.insert(self.key(v));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn saturation_tracker_matches_recount() {
+        let g = AdjList::random(80, 0.3);
+        let mut tracker = SaturationTracker::new(&g);
+        let mut rng = thread_rng();
+
+        for _ in 0..2000 {
+            let v = rng.gen_range(0, 80);
+            match tracker.color_of(v) {
+                Some(_) => assert!(tracker.uncolor(&g, v).is_some()),
+                None => tracker.color(&g, v, rng.gen_range(0, 70)),
+            }
+
+            let colors = tracker.colors();
+            for v in 0..80 {
+                let mut seen = g.neighbors(v).filter_map(|u| colors[u]).collect::<Vec<_>>();
+                seen.sort_unstable();
+                seen.dedup();
+
+                assert_eq!(tracker.saturation(v), seen.len());
+                assert_eq!(
+                    tracker.first_free(v),
+                    (0..).find(|c| !seen.contains(c)).unwrap()
+                );
+                assert!(seen.iter().all(|&c| !tracker.is_free(v, c)));
+            }
+
+            let expected = (0..80)
+                .filter(|&v| colors[v].is_none())
+                .max_by_key(|&v| (tracker.saturation(v), g.neighbors(v).count(), Reverse(v)));
+            assert_eq!(tracker.most_saturated(), expected);
+        }
+    }
+
+    #[test]
+    fn saturation_tracker_dsatur() {
+        // Path 0 - 1 - 2, the middle vertex has the highest degree
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        let mut tracker = SaturationTracker::new(&g);
+
+        assert_eq!(tracker.most_saturated(), Some(1));
+        tracker.color(&g, 1, 0);
+        assert_eq!(tracker.most_saturated(), Some(0));
+        assert_eq!(tracker.first_free(0), 1);
+        assert_eq!(tracker.coloring(), None);
+
+        tracker.color(&g, 0, 1);
+        tracker.color(&g, 2, 1);
+        assert_eq!(tracker.num_uncolored(), 0);
+        assert_eq!(tracker.coloring(), Some(vec![1, 0, 1]));
+        assert_eq!(tracker.uncolor(&g, 1), Some(0));
+        assert_eq!(tracker.saturation(0), 0);
+    }
+}
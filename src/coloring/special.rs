@@ -0,0 +1,308 @@
+use crate::coloring::{two_coloring, Coloring};
+use crate::graph::{cotree, StaticGraph};
+
+/// Class of graphs which can be colored optimally in polynomial time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphClass {
+    /// No edges, one color suffices.
+    Edgeless,
+    /// All vertices pairwise adjacent, every vertex needs its own color.
+    Complete,
+    /// No cycles, two colors suffice.
+    Forest,
+    /// A single cycle through all vertices, three colors if its length is odd.
+    Cycle,
+    /// No odd cycles.
+    Bipartite,
+    /// Every cycle of length at least four has a chord.
+    Chordal,
+    /// Vertices partitioned into independent sets with all edges between them.
+    CompleteMultipartite,
+    /// No induced path on four vertices.
+    Cograph,
+}
+
+impl GraphClass {
+    /// Short lowercase name of the class.
+    pub fn name(self) -> &'static str {
+        match self {
+            GraphClass::Edgeless => "edgeless",
+            GraphClass::Complete => "complete",
+            GraphClass::Forest => "forest",
+            GraphClass::Cycle => "cycle",
+            GraphClass::Bipartite => "bipartite",
+            GraphClass::Chordal => "chordal",
+            GraphClass::CompleteMultipartite => "multipartite",
+            GraphClass::Cograph => "cograph",
+        }
+    }
+}
+
+/// Detects whether the graph belongs to one of the classes in ```GraphClass``` and
+/// returns the class together with an optimal coloring. ```None``` means the graph
+/// is in none of these classes, not that it is hard to color.
+/// Classes are tried in the order of the enum, such that e.g. a path is reported
+/// as a forest although it is chordal as well. Linear time except for the chordality
+/// and cograph tests, which are quadratic in the number of vertices.
+pub fn special_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Option<(GraphClass, Coloring)> {
+    let n = graph.num_vertices();
+    let degrees = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).count())
+        .collect::<Vec<_>>();
+    let m = degrees.iter().sum::<usize>() / 2;
+
+    if m == 0 {
+        return Some((GraphClass::Edgeless, vec![0; n]));
+    }
+    if 2 * m == n * (n - 1) {
+        return Some((GraphClass::Complete, (0..n).collect()));
+    }
+
+    let components = num_components(graph);
+    let cycle = components == 1 && degrees.iter().all(|&d| d == 2);
+
+    if let Some(c) = two_coloring(graph) {
+        let class = if m + components == n {
+            GraphClass::Forest
+        } else if cycle {
+            GraphClass::Cycle
+        } else {
+            GraphClass::Bipartite
+        };
+        return Some((class, c));
+    }
+    if cycle {
+        return Some((GraphClass::Cycle, odd_cycle_coloring(graph)));
+    }
+    if let Some(c) = chordal_coloring(graph) {
+        return Some((GraphClass::Chordal, c));
+    }
+
+    if let Some(c) = multipartite_coloring(graph, &degrees) {
+        return Some((GraphClass::CompleteMultipartite, c));
+    }
+
+    let tree = cotree(graph)?;
+    let mut coloring = vec![0; n];
+    tree.color(&mut coloring);
+
+    Some((GraphClass::Cograph, coloring))
+}
+
+fn num_components<G: StaticGraph + ?Sized>(graph: &G) -> usize {
+    let mut visited = vec![false; graph.num_vertices()];
+    let mut stack = Vec::new();
+    let mut count = 0;
+
+    for first in graph.vertices() {
+        if visited[first] {
+            continue;
+        }
+
+        visited[first] = true;
+        stack.push(first);
+        while let Some(v) = stack.pop() {
+            for u in graph.neighbors(v) {
+                if !visited[u] {
+                    visited[u] = true;
+                    stack.push(u);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    count
+}
+
+/// Colors a cycle of odd length alternately along the cycle, the last vertex
+/// takes the third color.
+fn odd_cycle_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let n = graph.num_vertices();
+    let mut coloring = vec![0; n];
+    let (mut prev, mut v) = (0, 0);
+
+    for i in 0..n {
+        coloring[v] = if i == n - 1 { 2 } else { i % 2 };
+
+        let next = graph.neighbors(v).find(|&u| u != prev).unwrap();
+        prev = v;
+        v = next;
+    }
+
+    coloring
+}
+
+/// Colors a chordal graph optimally, ```None``` if it is not chordal.
+///
+/// Maximum cardinality search visits the vertex with the most visited neighbors
+/// next. The graph is chordal if and only if the visited neighbors of every vertex
+/// form a clique, in which case first fit in visiting order uses as many colors as
+/// the largest clique.
+fn chordal_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    let mut weight = vec![0; n];
+    let mut position = vec![usize::MAX; n];
+    let mut coloring = vec![0; n];
+    let mut earlier = Vec::new();
+    let mut used = Vec::new();
+
+    for i in 0..n {
+        let v = (0..n)
+            .filter(|&v| position[v] == usize::MAX)
+            .max_by_key(|&v| (weight[v], std::cmp::Reverse(v)))
+            .unwrap();
+        position[v] = i;
+
+        earlier.clear();
+        for u in graph.neighbors(v) {
+            if position[u] == usize::MAX {
+                weight[u] += 1;
+            } else {
+                earlier.push(u);
+            }
+        }
+
+        // Earlier neighbors form a clique if they all neighbor the latest of them
+        if let Some(&parent) = earlier.iter().max_by_key(|&&u| position[u]) {
+            if earlier
+                .iter()
+                .any(|&u| u != parent && !graph.has_edge(u, parent))
+            {
+                return None;
+            }
+        }
+
+        used.clear();
+        used.extend(earlier.iter().map(|&u| coloring[u]));
+        coloring[v] = (0..).find(|c| !used.contains(c)).unwrap();
+    }
+
+    Some(coloring)
+}
+
+/// Colors a complete multipartite graph with one color per part, ```None``` if the
+/// graph is not complete multipartite. The parts are the classes of the
+/// non-adjacency relation, which has to be transitive.
+fn multipartite_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    degrees: &[usize],
+) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    let mut part = vec![usize::MAX; n];
+    let mut sizes = Vec::new();
+    let mut adjacent = vec![false; n];
+
+    for v in 0..n {
+        if part[v] != usize::MAX {
+            continue;
+        }
+
+        for u in graph.neighbors(v) {
+            adjacent[u] = true;
+        }
+
+        let p = sizes.len();
+        let mut size = 0;
+        for u in 0..n {
+            if !adjacent[u] {
+                if part[u] != usize::MAX {
+                    return None;
+                }
+                part[u] = p;
+                size += 1;
+            }
+        }
+        sizes.push(size);
+
+        for u in graph.neighbors(v) {
+            adjacent[u] = false;
+        }
+    }
+
+    // Non-adjacent to exactly the other vertices of its part
+    let valid = (0..n).all(|v| {
+        degrees[v] == n - sizes[part[v]] && graph.neighbors(v).all(|u| part[u] != part[v])
+    });
+
+    if valid {
+        Some(part)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    fn class_of(g: &AdjList) -> Option<GraphClass> {
+        let (class, c) = special_coloring(g)?;
+        assert!(check_coloring(g, &c));
+        assert_eq!(num_colors(&c), chromatic_number(g));
+
+        Some(class)
+    }
+
+    #[test]
+    fn special_classes() {
+        let mut cycle = AdjList::new();
+        let mut even = AdjList::new();
+        let mut tree = AdjList::new();
+        for i in 0..7 {
+            cycle.add_edge(i, (i + 1) % 7);
+            even.add_edge(i, (i + 1) % 8);
+            tree.add_edge(i / 2, i + 1);
+        }
+        even.add_edge(7, 0);
+
+        // Triangles glued along edges
+        let mut chordal = AdjList::new();
+        for i in 0..6 {
+            chordal.add_edge(i, i + 1);
+            chordal.add_edge(i, i + 2);
+        }
+
+        // Parts {0, 1, 2}, {3, 4} and {5}
+        let mut multipartite = AdjList::with_capacity(6);
+        for (u, v) in AdjList::complete(6).edges() {
+            if !((u < 3 && v < 3) || (u, v) == (3, 4)) {
+                multipartite.add_edge(u, v);
+            }
+        }
+
+        assert_eq!(
+            class_of(&AdjList::with_capacity(4)),
+            Some(GraphClass::Edgeless)
+        );
+        assert_eq!(class_of(&AdjList::complete(5)), Some(GraphClass::Complete));
+        assert_eq!(class_of(&tree), Some(GraphClass::Forest));
+        assert_eq!(class_of(&cycle), Some(GraphClass::Cycle));
+        assert_eq!(class_of(&even), Some(GraphClass::Cycle));
+        even.add_edge(0, 3);
+        assert_eq!(class_of(&even), Some(GraphClass::Bipartite));
+        assert_eq!(class_of(&chordal), Some(GraphClass::Chordal));
+        assert_eq!(
+            class_of(&multipartite),
+            Some(GraphClass::CompleteMultipartite)
+        );
+
+        // Disjoint union of a triangle and a four cycle
+        let mut cograph = AdjList::complete(3);
+        for i in 0..4 {
+            cograph.add_edge(3 + i, 3 + (i + 1) % 4);
+        }
+        assert_eq!(class_of(&cograph), Some(GraphClass::Cograph));
+
+        // Wheel on a five cycle
+        let mut wheel = AdjList::new();
+        for i in 0..5 {
+            wheel.add_edge(i, (i + 1) % 5);
+            wheel.add_edge(i, 5);
+        }
+        assert_eq!(class_of(&wheel), None);
+    }
+}
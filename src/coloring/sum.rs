@@ -0,0 +1,150 @@
+use std::cmp::Reverse;
+
+use crate::coloring::{color_classes, lf_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Returns the sum of the colors of all vertices counted from one, the chromatic
+/// sum of the coloring. With colors as finishing rounds of jobs, this is the
+/// total completion time.
+pub fn coloring_cost(coloring: &[usize]) -> usize {
+    coloring.iter().map(|&c| c + 1).sum()
+}
+
+/// Colors the graph such that the ```coloring_cost``` is small, as when scheduling
+/// conflicting jobs of equal length in rounds to minimize the average completion
+/// time. This may take more colors than necessary.
+///
+/// The small colors should have large classes, so classes are built one after the
+/// other as maximal independent sets, repeatedly taking the vertex of smallest
+/// degree among the remaining candidates. The result and a largest first coloring
+/// are improved with ```improve_sum``` and the cheaper one is returned.
+pub fn sum_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let mut independent = independent_set_coloring(graph);
+    let mut lf = lf_coloring(graph);
+    improve_sum(graph, &mut independent);
+    improve_sum(graph, &mut lf);
+
+    if coloring_cost(&lf) < coloring_cost(&independent) {
+        lf
+    } else {
+        independent
+    }
+}
+
+/// Lowers the ```coloring_cost``` of a valid coloring without introducing conflicts
+/// until neither step changes it: every vertex takes the smallest color of no
+/// neighbor if it is smaller than its own, and the colors are relabeled such that
+/// larger classes have smaller colors.
+pub fn improve_sum<G: StaticGraph + ?Sized>(graph: &G, coloring: &mut Coloring) {
+    let mut taken = Vec::new();
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for v in 0..coloring.len() {
+            taken.clear();
+            taken.resize(coloring[v], false);
+            for u in graph.neighbors(v) {
+                if let Some(t) = taken.get_mut(coloring[u]) {
+                    *t = true;
+                }
+            }
+
+            if let Some(c) = taken.iter().position(|&t| !t) {
+                coloring[v] = c;
+                changed = true;
+            }
+        }
+
+        let classes = color_classes(coloring);
+        let mut order = (0..classes.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&c| (Reverse(classes[c].len()), c));
+        for (new, &old) in order.iter().enumerate() {
+            for &v in &classes[old] {
+                coloring[v] = new;
+            }
+        }
+        changed |= order.iter().enumerate().any(|(new, &old)| new != old);
+    }
+}
+
+/// Colors the graph by taking maximal independent sets as classes, each built by
+/// repeatedly taking the candidate of fewest neighbors among the candidates.
+fn independent_set_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let n = graph.num_vertices();
+    let mut coloring = vec![usize::MAX; n];
+    let mut candidate = vec![false; n];
+    let mut degree = vec![0; n];
+    let mut remaining = n;
+    let mut c = 0;
+
+    while remaining > 0 {
+        for (t, &color) in candidate.iter_mut().zip(&coloring) {
+            *t = color == usize::MAX;
+        }
+        for (v, d) in degree.iter_mut().enumerate() {
+            *d = graph.neighbors(v).filter(|&u| candidate[u]).count();
+        }
+
+        while let Some(v) = (0..n)
+            .filter(|&v| candidate[v])
+            .min_by_key(|&v| (degree[v], v))
+        {
+            coloring[v] = c;
+            remaining -= 1;
+
+            candidate[v] = false;
+            for u in graph.neighbors(v) {
+                if candidate[u] {
+                    candidate[u] = false;
+                    for w in graph.neighbors(u) {
+                        degree[w] -= 1;
+                    }
+                }
+            }
+        }
+
+        c += 1;
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn sum_star() {
+        // All leaves share the first color
+        let mut g = AdjList::new();
+        for v in 1..6 {
+            g.add_edge(0, v);
+        }
+
+        let c = sum_coloring(&g);
+        assert_eq!(c, vec![1, 0, 0, 0, 0, 0]);
+        assert_eq!(coloring_cost(&c), 7);
+        assert_eq!(coloring_cost(&[]), 0);
+    }
+
+    #[test]
+    fn sum_random() {
+        for _ in 0..10 {
+            let g = AdjList::random(80, 0.2);
+
+            let c = sum_coloring(&g);
+            assert!(check_coloring(&g, &c));
+            assert!(coloring_cost(&c) <= coloring_cost(&lf_coloring(&g)));
+
+            let mut sl = sl_coloring(&g);
+            let before = coloring_cost(&sl);
+            improve_sum(&g, &mut sl);
+            assert!(check_coloring(&g, &sl));
+            assert!(coloring_cost(&sl) <= before);
+        }
+    }
+}
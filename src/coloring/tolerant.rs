@@ -0,0 +1,278 @@
+use rand::Rng;
+
+use crate::coloring::{color_fast, conflicts, num_colors, Coloring};
+use crate::graph::{EdgeWeights, StaticGraph};
+
+/// Coloring which may violate some edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TolerantColoring {
+    pub coloring: Coloring,
+    /// Edges whose endpoints share a color.
+    pub conflicts: Vec<(usize, usize)>,
+    /// Total weight of the conflicting edges, infinite if a hard edge conflicts.
+    pub penalty: f64,
+}
+
+/// Penalty compared by the number of violated hard edges first.
+/// Soft edges of positive weight are also counted, as rounding may leave a
+/// small sum of their weights after all of them were resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+struct Cost {
+    hard: usize,
+    soft: f64,
+    soft_edges: usize,
+}
+
+/// Colors the graph with ```k``` colors minimizing the number of conflicting edges.
+/// See ```min_penalty_coloring``` for the method, with all edges weighted equally.
+pub fn min_conflicts_coloring<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    k: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> TolerantColoring {
+    min_penalty_coloring(graph, &EdgeWeights::default(), k, iterations, rng)
+}
+
+/// Colors the graph with ```k``` colors minimizing the total weight of conflicting edges.
+/// Conflicts on hard edges of infinite weight are avoided before anything else.
+///
+/// Starts from ```color_fast``` with surplus colors folded into ```0..k``` and runs
+/// ```iterations``` steps of min-conflicts local search. Each step moves a random
+/// conflicting vertex to the color of least penalty, or with small probability to
+/// a random color to escape local minima.
+/// Returns the coloring with the least penalty encountered.
+pub fn min_penalty_coloring<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    weights: &EdgeWeights,
+    k: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> TolerantColoring {
+    min_penalty_search(graph, weights, k, |step| step >= iterations, rng)
+}
+
+/// Same as ```min_penalty_coloring```, but runs until ```stop``` returns true for
+/// the number of steps taken so far.
+pub(crate) fn min_penalty_search<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    weights: &EdgeWeights,
+    k: usize,
+    mut stop: impl FnMut(usize) -> bool,
+    rng: &mut R,
+) -> TolerantColoring {
+    assert!(k > 0 || graph.num_vertices() == 0, "no colors to use");
+
+    let n = graph.num_vertices();
+    let adj = graph
+        .vertices()
+        .map(|v| {
+            graph
+                .neighbors(v)
+                .map(|u| (u, weights.get(u, v)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut colors = color_fast(graph)
+        .into_iter()
+        .map(|c| c % k.max(1))
+        .collect::<Coloring>();
+
+    // Penalty of each vertex in each color from its neighbors
+    let mut gamma = vec![vec![Cost::default(); k]; n];
+    for v in 0..n {
+        for &(u, w) in &adj[v] {
+            add(&mut gamma[v][colors[u]], w, 1);
+        }
+    }
+
+    let mut total = Cost {
+        hard: (0..n).map(|v| gamma[v][colors[v]].hard).sum::<usize>() / 2,
+        soft: (0..n).map(|v| gamma[v][colors[v]].soft).sum::<f64>() / 2.0,
+        soft_edges: (0..n)
+            .map(|v| gamma[v][colors[v]].soft_edges)
+            .sum::<usize>()
+            / 2,
+    };
+    let mut best = (total, colors.clone());
+
+    for step in 0.. {
+        if stop(step) {
+            break;
+        }
+
+        let conflicting = (0..n)
+            .filter(|&v| gamma[v][colors[v]].hard > 0 || gamma[v][colors[v]].soft_edges > 0)
+            .collect::<Vec<_>>();
+        if conflicting.is_empty() {
+            break;
+        }
+
+        let v = conflicting[rng.gen_range(0, conflicting.len())];
+        let old = colors[v];
+
+        let new = if rng.gen::<f32>() < 0.1 {
+            rng.gen_range(0, k)
+        } else {
+            let candidates = (0..k).filter(|&c| c != old).collect::<Vec<_>>();
+            let min = candidates
+                .iter()
+                .map(|&c| gamma[v][c])
+                .fold(None, |m: Option<Cost>, c| match m {
+                    Some(m) if m <= c => Some(m),
+                    _ => Some(c),
+                });
+            let candidates = candidates
+                .into_iter()
+                .filter(|&c| Some(gamma[v][c]) == min)
+                .collect::<Vec<_>>();
+            match candidates.len() {
+                0 => old,
+                len => candidates[rng.gen_range(0, len)],
+            }
+        };
+
+        total.hard = total.hard + gamma[v][new].hard - gamma[v][old].hard;
+        total.soft += gamma[v][new].soft - gamma[v][old].soft;
+        total.soft_edges = total.soft_edges + gamma[v][new].soft_edges - gamma[v][old].soft_edges;
+        colors[v] = new;
+        for &(u, w) in &adj[v] {
+            add(&mut gamma[u][old], w, -1);
+            add(&mut gamma[u][new], w, 1);
+        }
+
+        if total < best.0 {
+            best = (total, colors.clone());
+        }
+    }
+
+    tolerant(graph, weights, best.1)
+}
+
+/// Adds or subtracts an edge weight to a penalty.
+fn add(cost: &mut Cost, weight: f64, sign: isize) {
+    if weight == f64::INFINITY {
+        cost.hard = (cost.hard as isize + sign) as usize;
+    } else if weight > 0.0 {
+        cost.soft += sign as f64 * weight;
+        cost.soft_edges = (cost.soft_edges as isize + sign) as usize;
+    }
+}
+
+/// Colors the graph with as few colors as possible while allowing at most
+/// ```max_conflicts``` conflicting edges.
+/// Starting with the colors of a valid ```color_fast``` coloring, the number of colors
+/// is decreased as long as ```min_conflicts_coloring``` stays within the budget.
+pub fn tolerant_coloring<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    max_conflicts: usize,
+    iterations: usize,
+    rng: &mut R,
+) -> TolerantColoring {
+    let mut best = tolerant(graph, &EdgeWeights::default(), color_fast(graph));
+
+    for k in (1..num_colors(&best.coloring)).rev() {
+        let candidate = min_conflicts_coloring(graph, k, iterations, rng);
+        if candidate.conflicts.len() > max_conflicts {
+            break;
+        }
+        best = candidate;
+    }
+
+    best
+}
+
+fn tolerant<G: StaticGraph + ?Sized>(
+    graph: &G,
+    weights: &EdgeWeights,
+    coloring: Coloring,
+) -> TolerantColoring {
+    let conflicts = conflicts(graph, &coloring).collect::<Vec<_>>();
+    let penalty = conflicts.iter().map(|&(u, v)| weights.get(u, v)).sum();
+
+    TolerantColoring {
+        coloring,
+        conflicts,
+        penalty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn min_conflicts_complete() {
+        let g = AdjList::complete(6);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // Best is three pairs sharing a color
+        let t = min_conflicts_coloring(&g, 3, 1000, &mut rng);
+
+        assert_eq!(t.conflicts.len(), 3);
+        assert_eq!(num_colors(&t.coloring), 3);
+    }
+
+    #[test]
+    fn min_conflicts_feasible() {
+        let mut g = AdjList::new();
+        for i in 0..10 {
+            g.add_edge(i, (i + 1) % 10);
+        }
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let t = min_conflicts_coloring(&g, 2, 1000, &mut rng);
+
+        assert!(t.conflicts.is_empty());
+        assert!(check_coloring(&g, &t.coloring));
+    }
+
+    #[test]
+    fn tolerant_budget() {
+        let g = AdjList::random_with(40, |_, _| 0.4, &mut StdRng::seed_from_u64(1));
+
+        // With the same seed both try the same colorings until the strict one stops
+        let strict = tolerant_coloring(&g, 0, 2000, &mut StdRng::seed_from_u64(0));
+        let loose = tolerant_coloring(&g, 10, 2000, &mut StdRng::seed_from_u64(0));
+
+        assert!(check_coloring(&g, &strict.coloring));
+        assert!(loose.conflicts.len() <= 10);
+        assert!(num_colors(&loose.coloring) <= num_colors(&strict.coloring));
+        for &(u, v) in &loose.conflicts {
+            assert_eq!(loose.coloring[u], loose.coloring[v]);
+        }
+    }
+
+    #[test]
+    fn min_penalty_weighted() {
+        // K4 with two colors, conflicts on the cheap edges 0-1 and 2-3 only
+        let g = AdjList::complete(4);
+        let mut weights = EdgeWeights::new(f64::INFINITY);
+        weights.set(0, 1, 1.0);
+        weights.set(2, 3, 2.0);
+        weights.set(0, 2, 5.0);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let t = min_penalty_coloring(&g, &weights, 2, 1000, &mut rng);
+
+        assert_eq!(t.penalty, 3.0);
+        assert_eq!(t.conflicts, vec![(0, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn min_penalty_hard_infeasible() {
+        let g = AdjList::complete(3);
+        let weights = EdgeWeights::new(f64::INFINITY);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let t = min_penalty_coloring(&g, &weights, 2, 100, &mut rng);
+
+        assert_eq!(t.conflicts.len(), 1);
+        assert_eq!(t.penalty, f64::INFINITY);
+    }
+}
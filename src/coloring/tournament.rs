@@ -0,0 +1,68 @@
+/// Schedules a round robin tournament in which every pair of ```teams``` meets once.
+/// Returns the rounds, each a list of pairings with the smaller team first.
+///
+/// The rounds are the color classes of an optimal edge coloring of the complete
+/// graph, constructed by the circle method: one team stays fixed while the others
+/// rotate around it. With an odd number of teams a dummy team is added, whose
+/// opponent in a round has a bye and does not appear in that round. There are
+/// ```teams - 1``` rounds for an even number of teams and ```teams``` rounds otherwise.
+pub fn round_robin(teams: usize) -> Vec<Vec<(usize, usize)>> {
+    if teams < 2 {
+        return Vec::new();
+    }
+
+    // Team 'm - 1' is fixed, and the dummy team if 'teams' is odd
+    let m = teams + teams % 2;
+    let pair = |a: usize, b: usize| (a.min(b), a.max(b));
+
+    (0..m - 1)
+        .map(|r| {
+            let mut round = vec![pair(r, m - 1)];
+            for k in 1..m / 2 {
+                round.push(pair((r + k) % (m - 1), (r + m - 1 - k) % (m - 1)));
+            }
+
+            round.retain(|&(_, b)| b < teams);
+            round
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_edge_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn round_robin_factorization() {
+        for teams in 0..12 {
+            let rounds = round_robin(teams);
+
+            // Every pairing is an edge of the complete graph, its round the color
+            let mut g = AdjList::with_capacity(teams);
+            let mut colors = Vec::new();
+            for (r, round) in rounds.iter().enumerate() {
+                for &(a, b) in round {
+                    g.add_edge(a, b);
+                    colors.push((a, b, r));
+                }
+            }
+            let colors = g
+                .edges()
+                .map(|(u, v)| {
+                    let e = (u.min(v), u.max(v));
+                    colors.iter().find(|c| (c.0, c.1) == e).unwrap().2
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(g.edges().count(), teams * teams.saturating_sub(1) / 2);
+            assert!(check_edge_coloring(&g, &colors));
+
+            if teams > 1 {
+                assert_eq!(rounds.len(), teams - 1 + teams % 2);
+                assert!(rounds.iter().all(|r| r.len() == teams / 2));
+            }
+        }
+    }
+}
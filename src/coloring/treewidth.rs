@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use crate::analysis::degeneracy;
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{num_colors, sdo_coloring, Coloring};
+use crate::graph::{tree_decomposition, Elimination, StaticGraph, TreeDecomposition};
+
+/// Optimal coloring by dynamic programming over a tree decomposition.
+/// Returns ```None``` if the heuristic decomposition has a width larger than
+/// ```max_width```. The running time is exponential only in the width, which makes
+/// this the method of choice for sparse graphs with small treewidth. Widths up
+/// to about 12 are feasible.
+pub fn treewidth_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    max_width: usize,
+) -> Option<Coloring> {
+    // The treewidth is at least the degeneracy, which is much cheaper to compute
+    if degeneracy(graph) > max_width {
+        return None;
+    }
+
+    let td = [Elimination::MinFill, Elimination::MinDegree]
+        .iter()
+        .map(|&h| tree_decomposition(graph, h))
+        .min_by_key(|td| td.width)
+        .unwrap();
+
+    if td.width > max_width {
+        return None;
+    }
+
+    let upper = sdo_coloring(graph);
+    let lower = greedy_clique(graph).len().max(1);
+
+    // A graph of treewidth 'w' is 'w + 1' colorable, so 'k' stays small
+    for k in lower..num_colors(&upper) {
+        if let Some(c) = k_coloring(graph, &td, k) {
+            return Some(c);
+        }
+    }
+
+    Some(upper)
+}
+
+/// Returns a ```k```-coloring if one exists.
+/// For every vertex ```v``` in elimination order, the table of ```v``` maps each
+/// coloring of its separator that extends to all vertices eliminated below ```v``` to
+/// a color of ```v``` achieving this. Separator colorings are stored with colors
+/// renamed in order of first appearance, as colorings differing only in the names of
+/// colors behave the same.
+fn k_coloring<G: StaticGraph + ?Sized>(
+    graph: &G,
+    td: &TreeDecomposition,
+    k: usize,
+) -> Option<Coloring> {
+    let n = graph.num_vertices();
+    let mut children = vec![Vec::new(); n];
+    for v in 0..n {
+        if let Some(p) = td.parent[v] {
+            children[p].push(v);
+        }
+    }
+
+    let mut tables: Vec<HashMap<Vec<u8>, u8>> = vec![HashMap::new(); n];
+
+    for &v in &td.order {
+        let mut table = HashMap::new();
+        let mut labels = vec![0; td.separators[v].len()];
+
+        extend(
+            graph,
+            td,
+            &children,
+            &tables,
+            v,
+            k,
+            0,
+            0,
+            &mut labels,
+            &mut table,
+        );
+
+        if table.is_empty() {
+            return None;
+        }
+        tables[v] = table;
+    }
+
+    // Assign actual colors top down, separators are colored before their vertex
+    let mut coloring = vec![0; n];
+    for &v in td.order.iter().rev() {
+        let separator = td.separators[v]
+            .iter()
+            .map(|&u| coloring[u])
+            .collect::<Vec<_>>();
+        let (key, names) = canonical(&separator);
+
+        let label = tables[v][&key] as usize;
+        coloring[v] = match names.get(label) {
+            Some(&c) => c,
+            None => (0..k).find(|c| !separator.contains(c)).unwrap(),
+        };
+    }
+
+    Some(coloring)
+}
+
+/// Enumerates the colorings of the separator of ```v``` from position ```i``` on,
+/// with ```used``` colors assigned so far, and records those admitting a color for ```v```.
+#[allow(clippy::too_many_arguments)]
+fn extend<G: StaticGraph + ?Sized>(
+    graph: &G,
+    td: &TreeDecomposition,
+    children: &[Vec<usize>],
+    tables: &[HashMap<Vec<u8>, u8>],
+    v: usize,
+    k: usize,
+    i: usize,
+    used: usize,
+    labels: &mut Vec<u8>,
+    table: &mut HashMap<Vec<u8>, u8>,
+) {
+    let separator = &td.separators[v];
+
+    if i == separator.len() {
+        let label = (0..(used + 1).min(k)).find(|&l| {
+            let blocked = separator
+                .iter()
+                .zip(labels.iter())
+                .any(|(&u, &lu)| lu as usize == l && graph.has_edge(u, v));
+
+            !blocked
+                && children[v].iter().all(|&c| {
+                    let restricted = td.separators[c]
+                        .iter()
+                        .map(|&u| {
+                            if u == v {
+                                l
+                            } else {
+                                labels[separator.binary_search(&u).unwrap()] as usize
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    tables[c].contains_key(&canonical(&restricted).0)
+                })
+        });
+
+        if let Some(l) = label {
+            table.insert(labels.clone(), l as u8);
+        }
+        return;
+    }
+
+    // New colors are only introduced in order, which avoids renamed duplicates
+    for l in 0..(used + 1).min(k) {
+        let conflict =
+            (0..i).any(|j| labels[j] as usize == l && graph.has_edge(separator[i], separator[j]));
+        if conflict {
+            continue;
+        }
+
+        labels[i] = l as u8;
+        extend(
+            graph,
+            td,
+            children,
+            tables,
+            v,
+            k,
+            i + 1,
+            used.max(l + 1),
+            labels,
+            table,
+        );
+    }
+}
+
+/// Renames colors in order of first appearance.
+/// Returns the renamed coloring and the original name of each new color.
+fn canonical(colors: &[usize]) -> (Vec<u8>, Vec<usize>) {
+    let mut names = Vec::new();
+
+    let key = colors
+        .iter()
+        .map(|&c| match names.iter().position(|&n| n == c) {
+            Some(l) => l as u8,
+            None => {
+                names.push(c);
+                (names.len() - 1) as u8
+            }
+        })
+        .collect();
+
+    (key, names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, chromatic_number};
+    use crate::graph::*;
+
+    #[test]
+    fn treewidth_matches_exact() {
+        for _ in 0..10 {
+            let g = AdjList::random(16, 0.25);
+
+            let c = treewidth_coloring(&g, 16).unwrap();
+
+            assert!(check_coloring(&g, &c));
+            assert_eq!(num_colors(&c), chromatic_number(&g));
+        }
+    }
+
+    #[test]
+    fn treewidth_odd_cycles() {
+        // Two odd cycles sharing a vertex, width 2 but 3 colors needed
+        let mut g = AdjList::new();
+        for i in 0..9 {
+            g.add_edge(i, (i + 1) % 9);
+        }
+        for i in 9..13 {
+            g.add_edge(i, i + 1);
+        }
+        g.add_edge(13, 0);
+        g.add_edge(0, 9);
+
+        let c = treewidth_coloring(&g, 2).unwrap();
+
+        assert!(check_coloring(&g, &c));
+        assert_eq!(num_colors(&c), 3);
+    }
+
+    #[test]
+    fn treewidth_too_wide() {
+        let g = AdjList::complete(10);
+
+        assert!(treewidth_coloring(&g, 5).is_none());
+        assert_eq!(num_colors(&treewidth_coloring(&g, 9).unwrap()), 10);
+    }
+}
@@ -0,0 +1,149 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::coloring::{compact_colors, peak_color, Coloring};
+use crate::graph::StaticGraph;
+
+/// Returns the sum over all color classes of the largest weight of a vertex in
+/// the class, the length of a schedule running the classes one after the other.
+pub fn weighted_cost(coloring: &[usize], weights: &[usize]) -> usize {
+    let mut max = vec![0; peak_color(coloring).map_or(0, |c| c + 1)];
+    for (&c, &w) in coloring.iter().zip(weights) {
+        max[c] = max[c].max(w);
+    }
+
+    max.iter().sum()
+}
+
+/// Colors the graph such that the ```weighted_cost``` is small, as when scheduling
+/// jobs of different durations in rounds, where conflicting jobs may not share a
+/// round and every round lasts as long as its longest job. This may take more
+/// colors than necessary, heavy vertices are best kept together in few classes.
+///
+/// Vertices are placed in order of decreasing weight, ties broken by decreasing
+/// degree, into the first class without a neighbor, which does not raise the
+/// maximum of the class. The result is improved with ```improve_weighted```.
+pub fn weighted_coloring<G: StaticGraph + ?Sized>(graph: &G, weights: &[usize]) -> Coloring {
+    let n = graph.num_vertices();
+    assert_eq!(weights.len(), n, "one weight per vertex required");
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by_key(|&v| (Reverse(weights[v]), Reverse(graph.neighbors(v).count()), v));
+
+    let mut coloring = vec![usize::MAX; n];
+    let mut blocked = Vec::new();
+    for v in order {
+        for u in graph.neighbors(v) {
+            if let Some(b) = blocked.get_mut(coloring[u]) {
+                *b = true;
+            }
+        }
+
+        let c = blocked.iter().position(|&b| !b).unwrap_or_else(|| {
+            blocked.push(false);
+            blocked.len() - 1
+        });
+        coloring[v] = c;
+        blocked.iter_mut().for_each(|b| *b = false);
+    }
+
+    improve_weighted(graph, weights, &mut coloring);
+    coloring
+}
+
+/// Moves single vertices between color classes as long as this lowers the
+/// ```weighted_cost``` of the coloring, and relabels the colors to ```0..k```. A
+/// vertex is moved if it is the only heaviest vertex of its class, to the class
+/// without a neighbor whose maximum rises the least.
+pub fn improve_weighted<G: StaticGraph + ?Sized>(
+    graph: &G,
+    weights: &[usize],
+    coloring: &mut Coloring,
+) {
+    let k = peak_color(coloring).map_or(0, |c| c + 1);
+
+    // Number of vertices of every weight in every class
+    let mut classes = vec![BTreeMap::new(); k];
+    for (&c, &w) in coloring.iter().zip(weights) {
+        *classes[c].entry(w).or_insert(0) += 1;
+    }
+
+    let max = |class: &BTreeMap<usize, usize>| class.keys().next_back().cloned().unwrap_or(0);
+
+    let mut blocked = vec![false; k];
+    let mut improved = true;
+    while improved {
+        improved = false;
+
+        for v in 0..coloring.len() {
+            let (a, w) = (coloring[v], weights[v]);
+            if max(&classes[a]) != w || classes[a][&w] > 1 {
+                continue;
+            }
+            let gain = w - classes[a].keys().rev().nth(1).cloned().unwrap_or(0);
+
+            for u in graph.neighbors(v) {
+                blocked[coloring[u]] = true;
+            }
+            // Every move strictly lowers the cost, such that this ends
+            let best = (0..k)
+                .filter(|&b| b != a && !blocked[b] && !classes[b].is_empty())
+                .map(|b| (w.saturating_sub(max(&classes[b])), b))
+                .min()
+                .filter(|&(rise, _)| rise < gain);
+            blocked.iter_mut().for_each(|b| *b = false);
+
+            if let Some((_, b)) = best {
+                classes[a].remove(&w);
+                *classes[b].entry(w).or_insert(0) += 1;
+                coloring[v] = b;
+                improved = true;
+            }
+        }
+    }
+
+    compact_colors(coloring);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn weighted_path() {
+        // Keeping both heavy ends in one class takes a third color but saves time
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        let weights = [5, 1, 1, 5];
+
+        let c = weighted_coloring(&g, &weights);
+        assert!(check_coloring(&g, &c));
+        assert_eq!(weighted_cost(&c, &weights), 7);
+        assert_eq!(weighted_cost(&[0, 1, 0, 1], &weights), 10);
+        assert_eq!(weighted_cost(&[], &[]), 0);
+    }
+
+    #[test]
+    fn weighted_improves() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let g = AdjList::random(100, 0.2);
+            let weights = (0..100).map(|_| rng.gen_range(1, 50)).collect::<Vec<_>>();
+
+            let c = weighted_coloring(&g, &weights);
+            assert!(check_coloring(&g, &c));
+
+            let mut lf = lf_coloring(&g);
+            let before = weighted_cost(&lf, &weights);
+            improve_weighted(&g, &weights, &mut lf);
+            assert!(check_coloring(&g, &lf));
+            assert!(weighted_cost(&lf, &weights) <= before);
+            assert_eq!(peak_color(&lf), Some(num_colors(&lf) - 1));
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::coloring::presets::eliminate_classes;
+use crate::coloring::{num_colors, Coloring, ColoringAlgo};
+use crate::graph::{MaskedGraph, StaticGraph};
+
+/// Vertex or edge of a graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Element {
+    Vertex(usize),
+    Edge(usize, usize),
+}
+
+/// Effect of removing an element on the number of colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhatIf {
+    pub element: Element,
+    /// Number of colors with the element.
+    pub before: usize,
+    /// Number of colors without the element.
+    pub after: usize,
+    /// Coloring without the element, in which a removed vertex is isolated.
+    pub coloring: Coloring,
+}
+
+impl WhatIf {
+    /// Number of colors saved by removing the element.
+    pub fn saved(&self) -> usize {
+        self.before - self.after
+    }
+}
+
+/// Colors the graph with ```algo``` and reports how many colors removing the
+/// element saves. See ```what_if_remove_from```.
+pub fn what_if_remove<G: StaticGraph>(graph: &G, element: Element, algo: ColoringAlgo) -> WhatIf {
+    what_if_remove_from(graph, &algo.color(graph), element, algo)
+}
+
+/// Same as ```what_if_remove``` but starts from an existing ```coloring``` of the
+/// whole graph, which can be shared by many queries.
+///
+/// The element is masked out of the graph without copying it, a removed vertex
+/// stays in the view as an isolated vertex. The coloring stays
+/// valid without the element, and is reduced by moving whole color classes. It is
+/// compared with a fresh coloring by ```algo``` reduced the same way, such that the
+/// number of colors never increases.
+pub fn what_if_remove_from<G: StaticGraph>(
+    graph: &G,
+    coloring: &[usize],
+    element: Element,
+    algo: ColoringAlgo,
+) -> WhatIf {
+    let mut masked = MaskedGraph::new(graph);
+    match element {
+        Element::Vertex(v) => masked.remove_vertex(v),
+        Element::Edge(u, v) => masked.remove_edge(u, v),
+    }
+
+    let warm = eliminate_classes(&masked, coloring.to_vec());
+
+    let fresh = eliminate_classes(&masked, algo.color(&masked));
+
+    let best = if num_colors(&fresh) < num_colors(&warm) {
+        fresh
+    } else {
+        warm
+    };
+
+    WhatIf {
+        element,
+        before: num_colors(&coloring.to_vec()),
+        after: num_colors(&best),
+        coloring: best,
+    }
+}
+
+/// Returns all vertices followed by all edges of the graph, or a uniform sample of
+/// ```count``` of them.
+pub fn sample_elements<G: StaticGraph + ?Sized, R: Rng>(
+    graph: &G,
+    count: Option<usize>,
+    rng: &mut R,
+) -> Vec<Element> {
+    let all = graph
+        .vertices()
+        .map(Element::Vertex)
+        .chain(graph.edges().map(|(u, v)| Element::Edge(u, v)))
+        .collect::<Vec<_>>();
+
+    match count {
+        Some(count) if count < all.len() => all.choose_multiple(rng, count).cloned().collect(),
+        _ => all,
+    }
+}
+
+/// Evaluates removing each of the elements and ranks them by the number of colors
+/// saved, highest first and otherwise in the given order.
+///
+/// All evaluations start from one coloring of the whole graph by ```algo```, see
+/// ```what_if_remove_from```. Repeated elements and vertices with the same
+/// neighbors, whose removal has the same effect, are evaluated only once.
+pub fn rank_what_if<G: StaticGraph>(
+    graph: &G,
+    elements: &[Element],
+    algo: ColoringAlgo,
+) -> Vec<WhatIf> {
+    let coloring = algo.color(graph);
+    let (unique, representatives) = deduplicate(graph, elements);
+
+    let results = unique
+        .iter()
+        .map(|&e| what_if_remove_from(graph, &coloring, e, algo))
+        .collect::<Vec<_>>();
+
+    rank(elements, representatives, &results)
+}
+
+/// Same as ```rank_what_if``` but evaluates the elements on one thread per processor.
+/// Requires the ```parallel``` feature.
+#[cfg(feature = "parallel")]
+pub fn rank_what_if_parallel<G: StaticGraph + Sync>(
+    graph: &G,
+    elements: &[Element],
+    algo: ColoringAlgo,
+) -> Vec<WhatIf> {
+    let coloring = algo.color(graph);
+    let (unique, representatives) = deduplicate(graph, elements);
+    let threads = num_cpus::get().max(1);
+
+    let mut results = crossbeam::scope(|s| {
+        let handles = (0..threads)
+            .map(|t| {
+                let (unique, coloring) = (&unique, &coloring);
+
+                s.spawn(move |_| {
+                    (t..unique.len())
+                        .step_by(threads)
+                        .map(|i| (i, what_if_remove_from(graph, coloring, unique[i], algo)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .unwrap();
+    results.sort_by_key(|&(i, _)| i);
+    let results = results.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+
+    rank(elements, representatives, &results)
+}
+
+/// Returns the elements to evaluate and for every given element the index of the
+/// evaluated one with the same effect.
+fn deduplicate<G: StaticGraph + ?Sized>(
+    graph: &G,
+    elements: &[Element],
+) -> (Vec<Element>, Vec<usize>) {
+    let mut unique = Vec::new();
+    let mut index = HashMap::new();
+    let mut twins = HashMap::new();
+
+    let representatives = elements
+        .iter()
+        .map(|&element| {
+            let key = match element {
+                Element::Edge(u, v) => Element::Edge(u.min(v), u.max(v)),
+                vertex => vertex,
+            };
+            if let Some(&i) = index.get(&key) {
+                return i;
+            }
+
+            let i = match element {
+                Element::Vertex(v) => {
+                    let neighbors = graph.neighbors(v).collect::<HashSet<_>>();
+                    let mut neighbors = neighbors.into_iter().collect::<Vec<_>>();
+                    neighbors.sort_unstable();
+
+                    *twins.entry(neighbors).or_insert_with(|| {
+                        unique.push(key);
+                        unique.len() - 1
+                    })
+                }
+                Element::Edge(..) => {
+                    unique.push(key);
+                    unique.len() - 1
+                }
+            };
+            index.insert(key, i);
+
+            i
+        })
+        .collect();
+
+    (unique, representatives)
+}
+
+/// Assigns every element the result of its representative and sorts them.
+fn rank(elements: &[Element], representatives: Vec<usize>, results: &[WhatIf]) -> Vec<WhatIf> {
+    let mut ranked = elements
+        .iter()
+        .zip(representatives)
+        .map(|(&element, i)| {
+            let mut result = results[i].clone();
+
+            // A twin is isolated instead of the representative by swapping colors
+            if let (Element::Vertex(v), Element::Vertex(u)) = (element, result.element) {
+                result.coloring.swap(u, v);
+            }
+            result.element = element;
+
+            result
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.saved()));
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn what_if_saves_color() {
+        // Odd cycle needs three colors, a path two
+        let mut g = AdjList::new();
+        for v in 0..7 {
+            g.add_edge(v, (v + 1) % 7);
+        }
+
+        let edge = what_if_remove(&g, Element::Edge(3, 4), ColoringAlgo::SDO);
+        assert_eq!((edge.before, edge.after, edge.saved()), (3, 2, 1));
+        let mut path = MaskedGraph::new(&g);
+        path.remove_edge(3, 4);
+        assert!(check_coloring(&path, &edge.coloring));
+
+        let k4 = AdjList::complete(4);
+        let vertex = what_if_remove(&k4, Element::Vertex(2), ColoringAlgo::LF);
+        assert_eq!(vertex.after, 3);
+        assert_eq!(vertex.coloring.len(), 4);
+    }
+
+    #[test]
+    fn rank_conflicts() {
+        // Odd cycle with two pendant twins at vertex 0
+        let mut g = AdjList::new();
+        for v in 0..5 {
+            g.add_edge(v, (v + 1) % 5);
+        }
+        g.add_edge(0, 5);
+        g.add_edge(0, 6);
+
+        let elements = sample_elements(&g, None, &mut rand::thread_rng());
+        assert_eq!(elements.len(), 14);
+
+        let ranked = rank_what_if(&g, &elements, ColoringAlgo::SDO);
+        assert_eq!(ranked.len(), elements.len());
+        assert_eq!(ranked.iter().filter(|r| r.saved() == 1).count(), 10);
+        assert_eq!(ranked[..10].iter().map(WhatIf::saved).sum::<usize>(), 10);
+
+        for r in &ranked {
+            let mut masked = MaskedGraph::new(&g);
+            match r.element {
+                Element::Vertex(v) => masked.remove_vertex(v),
+                Element::Edge(u, v) => masked.remove_edge(u, v),
+            }
+            assert!(check_coloring(&masked, &r.coloring));
+        }
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            rank_what_if_parallel(&g, &elements, ColoringAlgo::SDO),
+            ranked
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use crate::coloring::exact::greedy_clique;
+use crate::coloring::{num_colors, sdo_coloring, Coloring};
+use crate::graph::StaticGraph;
+
+/// Bytes of the keys of explored graphs remembered to avoid repeated searches.
+/// Every key takes about ```n * (n / 64 + 1)``` words, such that the number of
+/// remembered graphs shrinks with their size.
+const MEMO_BYTES: usize = 64 << 20;
+
+/// Optimal coloring by Zykov branching.
+/// Every coloring either gives two non-adjacent vertices the same color, which
+/// contracts them, or different colors, which adds an edge between them. The
+/// search branches on such pairs until the graph is complete, its number of
+/// vertices then being the number of colors. Branches whose greedily found
+/// clique is no smaller than the best coloring are cut, and graphs already
+/// explored are skipped.
+pub(crate) fn zykov_coloring<G: StaticGraph + ?Sized>(graph: &G) -> Coloring {
+    let best = sdo_coloring(graph);
+    let lower = greedy_clique(graph).len();
+
+    if num_colors(&best) <= lower {
+        return best;
+    }
+
+    let n = graph.num_vertices();
+    let mut root = State::new(n);
+    for (u, v) in graph.edges() {
+        root.connect(u, v);
+    }
+
+    let mut search = Search {
+        upper: num_colors(&best),
+        best,
+        lower,
+        seen: HashSet::new(),
+        seen_bytes: 0,
+    };
+    search.branch(root);
+
+    search.best
+}
+
+/// Graph obtained by contractions and edge additions, stored as bitsets.
+#[derive(Clone)]
+struct State {
+    adj: Vec<Vec<u64>>,
+    /// Vertex each vertex was contracted into, itself if still present.
+    parent: Vec<usize>,
+}
+
+impl State {
+    fn new(n: usize) -> Self {
+        Self {
+            adj: vec![vec![0; n.div_ceil(64)]; n],
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.adj[u][v / 64] & (1 << (v % 64)) != 0
+    }
+
+    fn connect(&mut self, u: usize, v: usize) {
+        self.adj[u][v / 64] |= 1 << (v % 64);
+        self.adj[v][u / 64] |= 1 << (u % 64);
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn live(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.parent.len()).filter(move |&v| self.parent[v] == v)
+    }
+
+    /// Merges ```v``` into ```u```, which inherits all its neighbors.
+    fn contract(&mut self, u: usize, v: usize) {
+        let neighbors = self.neighbors(v);
+        for w in neighbors {
+            self.adj[w][v / 64] &= !(1 << (v % 64));
+            self.connect(u, w);
+        }
+        self.adj[v].iter_mut().for_each(|w| *w = 0);
+        self.parent[v] = u;
+    }
+
+    fn neighbors(&self, v: usize) -> Vec<usize> {
+        let mut neighbors = Vec::new();
+        for (i, &word) in self.adj[v].iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                neighbors.push(i * 64 + word.trailing_zeros() as usize);
+                word &= word - 1;
+            }
+        }
+
+        neighbors
+    }
+
+    /// Non-adjacent pair to branch on: a vertex of maximum degree with a
+    /// non-neighbor, and the non-neighbor sharing the most neighbors with it.
+    fn pair(&self) -> Option<(usize, usize)> {
+        let n = self.live().count();
+        let u = self
+            .live()
+            .filter(|&v| self.degree(v) + 1 < n)
+            .max_by_key(|&v| self.degree(v))?;
+        let common = |v: usize| -> u32 {
+            self.adj[u]
+                .iter()
+                .zip(&self.adj[v])
+                .map(|(a, b)| (a & b).count_ones())
+                .sum()
+        };
+
+        self.live()
+            .filter(|&v| v != u && !self.has_edge(u, v))
+            .max_by_key(|&v| common(v))
+            .map(|v| (u, v))
+    }
+
+    /// Size of a clique grown greedily by decreasing degree.
+    fn clique(&self) -> usize {
+        let mut order = self.live().collect::<Vec<_>>();
+        order.sort_by_key(|&v| std::cmp::Reverse(self.degree(v)));
+
+        let mut clique: Vec<usize> = Vec::new();
+        for v in order {
+            if clique.iter().all(|&u| self.has_edge(u, v)) {
+                clique.push(v);
+            }
+        }
+
+        clique.len()
+    }
+
+    /// Coloring of the original vertices in which every remaining vertex has
+    /// its own color, proper once the graph is complete.
+    fn coloring(&self) -> Coloring {
+        let mut color = vec![0; self.parent.len()];
+        for (c, v) in self.live().enumerate() {
+            color[v] = c;
+        }
+
+        (0..self.parent.len())
+            .map(|mut v| {
+                while self.parent[v] != v {
+                    v = self.parent[v];
+                }
+                color[v]
+            })
+            .collect()
+    }
+
+    /// Identifies the graph by its remaining vertices and their neighbors.
+    fn key(&self) -> Vec<u64> {
+        let mut key = Vec::new();
+        for v in self.live() {
+            key.push(v as u64);
+            key.extend(&self.adj[v]);
+        }
+
+        key
+    }
+}
+
+struct Search {
+    best: Coloring,
+    upper: usize,
+    lower: usize,
+    seen: HashSet<Vec<u64>>,
+    seen_bytes: usize,
+}
+
+impl Search {
+    /// Searches the colorings of ```state```.
+    /// Returns whether an optimal coloring was found.
+    fn branch(&mut self, state: State) -> bool {
+        if self.upper <= self.lower || state.clique() >= self.upper {
+            return self.upper <= self.lower;
+        }
+        let key = state.key();
+        if self.seen.contains(&key) {
+            return false;
+        }
+        let bytes = key.len() * std::mem::size_of::<u64>();
+        if self.seen_bytes + bytes <= MEMO_BYTES {
+            self.seen_bytes += bytes;
+            self.seen.insert(key);
+        }
+
+        let (u, v) = match state.pair() {
+            Some(pair) => pair,
+            None => {
+                self.best = state.coloring();
+                self.upper = num_colors(&self.best);
+                return self.upper <= self.lower;
+            }
+        };
+
+        let mut same = state.clone();
+        same.contract(u, v);
+        if self.branch(same) {
+            return true;
+        }
+
+        let mut different = state;
+        different.connect(u, v);
+        self.branch(different)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, exact_coloring};
+    use crate::graph::*;
+
+    #[test]
+    fn zykov_matches_exact() {
+        for p in &[0.3, 0.6, 0.9] {
+            let g = AdjList::random(14, *p);
+            let c = zykov_coloring(&g);
+
+            assert!(check_coloring(&g, &c));
+            assert_eq!(num_colors(&c), num_colors(&exact_coloring(&g)));
+        }
+
+        // Groetzsch graph, triangle free with chromatic number 4
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 1) % 5);
+            g.add_edge(i + 5, (i + 4) % 5);
+            g.add_edge(i + 5, 10);
+        }
+        assert_eq!(num_colors(&zykov_coloring(&g)), 4);
+        assert!(zykov_coloring(&AdjList::new()).is_empty());
+    }
+}
@@ -0,0 +1,480 @@
+//! Experimental coloring of a graph split over several processes.
+//!
+//! The vertices are split into contiguous blocks by a ```Partition```, and every
+//! worker process holds only the adjacency of its block as a ```Part```, which it
+//! can read from a DIMACS file without loading the rest of the graph. Workers
+//! connect to a coordinator over TCP, which relays the colors of the boundary
+//! vertices between them and assembles the final coloring.
+//!
+//! Coloring is speculative as described by Gebremedhin and Manne: in every round
+//! each worker colors its uncolored vertices first fit, using the colors of remote
+//! neighbors from the previous round, and sends the colors of its boundary vertices.
+//! Adjacent vertices of different workers colored in the same round may conflict,
+//! in which case the vertex with the larger index is uncolored and colored again in
+//! the next round. The rounds end once no worker colored a vertex.
+//!
+//! Messages are JSON objects, one per line. A worker sends ```hello``` with its
+//! index, the number of vertices and the remote vertices it needs colors of, then
+//! alternates between sending ```colors``` and receiving ```ghosts```, and finally
+//! sends ```result``` with the colors of its block. Every round sends all boundary
+//! colors, which keeps the protocol simple rather than small.
+//!
+//! The coordinator is given the partition and rejects workers assuming another
+//! one. Reading or writing a message fails after ```TIMEOUT```, such that a
+//! stalled peer does not block the others forever.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::coloring::Coloring;
+use crate::graph::dimacs::{read_dimacs, DimacsItem};
+use crate::graph::StaticGraph;
+
+/// Color of a vertex which is not colored yet.
+const UNCOLORED: usize = usize::MAX;
+
+/// Longest wait for a message, long enough for all workers to be started by hand.
+pub const TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Split of the vertices into contiguous blocks of almost equal size, one per worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    pub vertices: usize,
+    pub workers: usize,
+}
+
+impl Partition {
+    pub fn new(vertices: usize, workers: usize) -> Self {
+        assert!(workers > 0, "at least one worker is needed");
+        Self { vertices, workers }
+    }
+
+    /// Returns the vertices of ```worker```.
+    pub fn range(&self, worker: usize) -> Range<usize> {
+        self.start(worker)..self.start(worker + 1)
+    }
+
+    /// Returns the worker owning ```v```.
+    pub fn owner(&self, v: usize) -> usize {
+        (((v as u128 + 1) * self.workers as u128 - 1) / self.vertices as u128) as usize
+    }
+
+    fn start(&self, worker: usize) -> usize {
+        (worker as u128 * self.vertices as u128 / self.workers as u128) as usize
+    }
+}
+
+/// Vertices of one worker with their neighbors, given by their global index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub partition: Partition,
+    pub worker: usize,
+    adj: Vec<Vec<usize>>,
+}
+
+impl Part {
+    /// Keeps the edges incident to the vertices of ```worker```.
+    pub fn from_edges(
+        partition: Partition,
+        worker: usize,
+        edges: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        let mut part = Self::empty(partition, worker);
+        for (u, v) in edges {
+            part.insert(u, v);
+        }
+        part.finish();
+
+        part
+    }
+
+    /// Takes the part of ```worker``` from a graph held in memory.
+    pub fn from_graph<G: StaticGraph + ?Sized>(graph: &G, workers: usize, worker: usize) -> Self {
+        Self::from_edges(
+            Partition::new(graph.num_vertices(), workers),
+            worker,
+            graph.edges(),
+        )
+    }
+
+    /// Reads the part of ```worker``` from a graph in DIMACS format, storing only
+    /// the edges incident to its vertices.
+    pub fn read_dimacs(reader: impl BufRead, workers: usize, worker: usize) -> io::Result<Self> {
+        let mut part = None;
+        read_dimacs(reader, usize::MAX, |item| match item {
+            DimacsItem::Comment(_) => {}
+            DimacsItem::Size(n) => part = Some(Self::empty(Partition::new(n, workers), worker)),
+            DimacsItem::Edge(u, v) => part.as_mut().unwrap().insert(u, v),
+        })?;
+
+        let mut part = part.unwrap();
+        part.finish();
+
+        Ok(part)
+    }
+
+    fn empty(partition: Partition, worker: usize) -> Self {
+        Self {
+            partition,
+            worker,
+            adj: vec![Vec::new(); partition.range(worker).len()],
+        }
+    }
+
+    fn insert(&mut self, u: usize, v: usize) {
+        let range = self.vertices();
+        if u != v && range.contains(&u) {
+            self.adj[u - range.start].push(v);
+        }
+        if u != v && range.contains(&v) {
+            self.adj[v - range.start].push(u);
+        }
+    }
+
+    /// Removes parallel edges.
+    fn finish(&mut self) {
+        for neighbors in &mut self.adj {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+    }
+
+    /// Returns the vertices of this part.
+    pub fn vertices(&self) -> Range<usize> {
+        self.partition.range(self.worker)
+    }
+
+    /// Returns the neighbors of ```v```, which must belong to this part.
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.adj[v - self.vertices().start]
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Message {
+    Hello {
+        worker: usize,
+        workers: usize,
+        vertices: usize,
+        ghosts: Vec<usize>,
+    },
+    /// Colors of the boundary vertices and the number of vertices colored this round.
+    Colors {
+        colors: Vec<(usize, usize)>,
+        colored: usize,
+    },
+    /// Colors of the requested remote vertices, in the order of ```hello```.
+    Ghosts {
+        colors: Vec<usize>,
+        done: bool,
+    },
+    Result {
+        colors: Vec<usize>,
+    },
+}
+
+/// Line based JSON messages over a TCP stream.
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    fn send(&mut self, message: &Message) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, message)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+
+    fn receive(&mut self) -> io::Result<Message> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed",
+            ));
+        }
+
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+/// Accepts the workers of ```partition``` on ```listener```, relays boundary colors
+/// between them until the coloring is valid and returns the assembled coloring.
+///
+/// Fails with ```InvalidData``` if a worker violates the protocol or assumes a
+/// different partition.
+pub fn coordinate(listener: &TcpListener, partition: Partition) -> io::Result<Coloring> {
+    let workers = partition.workers;
+    let mut peers = (0..workers).map(|_| None).collect::<Vec<_>>();
+
+    for _ in 0..workers {
+        let mut connection = Connection::new(listener.accept()?.0)?;
+        match connection.receive()? {
+            Message::Hello {
+                worker,
+                workers: w,
+                vertices,
+                ghosts,
+            } if w == workers && worker < workers && peers[worker].is_none() => {
+                if vertices != partition.vertices {
+                    return Err(invalid("worker assumes a different number of vertices"));
+                }
+                if ghosts
+                    .iter()
+                    .any(|&v| v >= vertices || partition.owner(v) == worker)
+                {
+                    return Err(invalid("ghost outside of the graph or of the worker"));
+                }
+                peers[worker] = Some((connection, ghosts));
+            }
+            _ => return Err(invalid("expected a hello with a new worker index")),
+        }
+    }
+
+    let mut peers = peers.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+    let mut boundary = HashMap::new();
+
+    loop {
+        let mut colored = 0;
+        for (worker, (connection, _)) in peers.iter_mut().enumerate() {
+            match connection.receive()? {
+                Message::Colors { colors, colored: c } => {
+                    if colors.iter().any(|&(v, _)| partition.owner(v) != worker) {
+                        return Err(invalid("colors of a vertex of another worker"));
+                    }
+                    boundary.extend(colors);
+                    colored += c;
+                }
+                _ => return Err(invalid("expected colors")),
+            }
+        }
+
+        let done = colored == 0;
+        for (connection, ghosts) in &mut peers {
+            let colors = ghosts
+                .iter()
+                .map(|v| boundary.get(v).cloned().unwrap_or(UNCOLORED))
+                .collect();
+            connection.send(&Message::Ghosts { colors, done })?;
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    let mut coloring = vec![UNCOLORED; partition.vertices];
+    for (worker, (connection, _)) in peers.iter_mut().enumerate() {
+        let range = partition.range(worker);
+        match connection.receive()? {
+            Message::Result { colors } if colors.len() == range.len() => {
+                coloring[range].copy_from_slice(&colors);
+            }
+            _ => return Err(invalid("expected the result of the worker")),
+        }
+    }
+
+    Ok(coloring)
+}
+
+/// Colors ```part``` in cooperation with the other workers through the coordinator
+/// at ```addr```. Returns the colors of the vertices of the part.
+pub fn work(addr: impl ToSocketAddrs, part: &Part) -> io::Result<Coloring> {
+    let mut connection = Connection::new(TcpStream::connect(addr)?)?;
+    let range = part.vertices();
+    let partition = part.partition;
+    let remote = |u: &usize| !range.contains(u);
+
+    let mut ghosts = range
+        .clone()
+        .flat_map(|v| part.neighbors(v).iter().cloned().filter(remote))
+        .collect::<Vec<_>>();
+    ghosts.sort_unstable();
+    ghosts.dedup();
+    let ghost_index = ghosts
+        .iter()
+        .enumerate()
+        .map(|(i, &u)| (u, i))
+        .collect::<HashMap<_, _>>();
+    let boundary = range
+        .clone()
+        .filter(|&v| part.neighbors(v).iter().any(remote))
+        .collect::<Vec<_>>();
+
+    connection.send(&Message::Hello {
+        worker: part.worker,
+        workers: partition.workers,
+        vertices: partition.vertices,
+        ghosts: ghosts.clone(),
+    })?;
+
+    let mut colors = vec![UNCOLORED; range.len()];
+    let mut ghost_colors = vec![UNCOLORED; ghosts.len()];
+    let mut used = Vec::new();
+
+    loop {
+        let mut colored = 0;
+        for v in range.clone() {
+            if colors[v - range.start] != UNCOLORED {
+                continue;
+            }
+
+            used.clear();
+            for &u in part.neighbors(v) {
+                let c = if remote(&u) {
+                    ghost_colors[ghost_index[&u]]
+                } else {
+                    colors[u - range.start]
+                };
+                if c != UNCOLORED {
+                    if c >= used.len() {
+                        used.resize(c + 1, false);
+                    }
+                    used[c] = true;
+                }
+            }
+            colors[v - range.start] = (0..).find(|&c| !used.get(c).unwrap_or(&false)).unwrap();
+            colored += 1;
+        }
+
+        connection.send(&Message::Colors {
+            colors: boundary
+                .iter()
+                .map(|&v| (v, colors[v - range.start]))
+                .collect(),
+            colored,
+        })?;
+
+        match connection.receive()? {
+            Message::Ghosts { colors: c, done } if c.len() == ghosts.len() => {
+                ghost_colors = c;
+                if done {
+                    break;
+                }
+            }
+            _ => return Err(invalid("expected the colors of the ghosts")),
+        }
+
+        // Of two conflicting vertices the larger one is colored again
+        for &v in &boundary {
+            let c = colors[v - range.start];
+            if part
+                .neighbors(v)
+                .iter()
+                .any(|u| remote(u) && *u < v && ghost_colors[ghost_index[u]] == c)
+            {
+                colors[v - range.start] = UNCOLORED;
+            }
+        }
+    }
+
+    connection.send(&Message::Result {
+        colors: colors.clone(),
+    })?;
+
+    Ok(colors)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+    use std::thread;
+
+    #[test]
+    fn distributed_coloring_valid() {
+        let g = AdjList::random(200, 0.1);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let workers = (0..4)
+            .map(|w| {
+                let part = Part::from_graph(&g, 4, w);
+                thread::spawn(move || work(addr, &part).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let coloring = coordinate(&listener, Partition::new(200, 4)).unwrap();
+
+        assert!(check_coloring(&g, &coloring));
+        let parts = workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(parts, coloring);
+    }
+
+    #[test]
+    fn distributed_part_from_dimacs() {
+        let g = Graph::random(30, 0.3);
+        let mut dimacs = Vec::new();
+        write_dimacs(&g, &mut dimacs).unwrap();
+
+        let partition = Partition::new(30, 4);
+        assert_eq!(partition.range(1), 7..15);
+        assert!((0..30).all(|v| partition.range(partition.owner(v)).contains(&v)));
+
+        for w in 0..4 {
+            let part = Part::read_dimacs(&dimacs[..], 4, w).unwrap();
+            assert_eq!(part, Part::from_graph(&g, 4, w));
+        }
+    }
+
+    #[test]
+    fn distributed_rejects_garbage() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(b"{\"type\":\"colors\"}\n").unwrap();
+        });
+        let error = coordinate(&listener, Partition::new(10, 1)).unwrap_err();
+        client.join().unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn distributed_rejects_other_partition() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Would make the coordinator allocate the coloring of a huge graph
+        let client = thread::spawn(move || {
+            let mut connection = Connection::new(TcpStream::connect(addr).unwrap()).unwrap();
+            let hello = Message::Hello {
+                worker: 0,
+                workers: 1,
+                vertices: 1 << 60,
+                ghosts: Vec::new(),
+            };
+            connection.send(&hello).unwrap();
+        });
+        let error = coordinate(&listener, Partition::new(10, 1)).unwrap_err();
+        client.join().unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,363 @@
+//! Line based interactive exploration of a single instance.
+//!
+//! Reads commands from stdin, one per line, and keeps a current coloring which
+//! algorithms replace and manual moves modify. Manual moves may introduce
+//! conflicts, which ```conflicts``` lists grouped into connected regions.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use graml::analysis::{Cached, Features};
+use graml::coloring::*;
+use graml::graph::*;
+
+const HELP: &str = "\
+stats               instance features and bounds on the chromatic number
+run <algo>          color with rs, cs, lf, sl, sdo, genetic, ig, balanced
+                    or exact
+classes             size of each color class
+class <c>           vertices of color class c
+vertex <v>          color and neighbors of vertex v
+conflicts           conflicting edges grouped into connected regions
+set <v> <c>         recolor vertex v with color c
+kempe <v> <c>       swap colors of the Kempe chain of v and color c
+help                show this help
+quit                leave the explorer";
+
+/// Loads the graph and runs the read-eval-print loop on stdin until ```quit```
+/// or end of input.
+pub fn explore(path: &str) -> io::Result<()> {
+    let (graph, meta) = load_graph_with_meta(path)?;
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    writeln!(out, "{} loaded, type 'help' for commands", path)?;
+    repl(Session::new(graph, meta), stdin.lock(), &mut out)
+}
+
+/// Executes the commands read from ```input``` line by line, each after a prompt,
+/// until ```quit``` or end of input.
+fn repl<R: BufRead, W: Write>(mut session: Session, input: R, out: &mut W) -> io::Result<()> {
+    write!(out, "> ")?;
+    out.flush()?;
+
+    for line in input.lines() {
+        if !session.execute(&line?, out)? {
+            break;
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Instance under exploration together with the current coloring.
+struct Session {
+    graph: Cached<Graph>,
+    meta: GraphMeta,
+    coloring: Option<Coloring>,
+}
+
+impl Session {
+    fn new(graph: Graph, meta: GraphMeta) -> Self {
+        Session {
+            graph: Cached::cached(graph),
+            meta,
+            coloring: None,
+        }
+    }
+
+    /// Executes one command line. Returns ```false``` if the session should end.
+    fn execute<W: Write>(&mut self, line: &str, out: &mut W) -> io::Result<bool> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let numbers = words
+            .iter()
+            .skip(1)
+            .map(|w| w.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>();
+
+        match (words.first().cloned(), numbers.as_deref()) {
+            (None, _) => {}
+            (Some("quit"), _) | (Some("exit"), _) => return Ok(false),
+            (Some("help"), _) => writeln!(out, "{}", HELP)?,
+            (Some("stats"), _) => self.stats(out)?,
+            (Some("run"), _) if words.len() == 2 => self.run(words[1], out)?,
+            (Some("classes"), Some([])) => self.classes(out)?,
+            (Some("class"), Some(&[c])) => self.class(c, out)?,
+            (Some("vertex"), Some(&[v])) => self.vertex(v, out)?,
+            (Some("conflicts"), Some([])) => self.conflicts(out)?,
+            (Some("set"), Some(&[v, c])) => self.set(v, c, out)?,
+            (Some("kempe"), Some(&[v, c])) => self.kempe(v, c, out)?,
+            (Some(other), _) => writeln!(out, "invalid command '{}', try 'help'", other)?,
+        }
+
+        Ok(true)
+    }
+
+    fn stats<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let f = Features::new(&self.graph);
+        let (lower, upper) = chromatic_bounds(&self.graph);
+
+        writeln!(out, "{:<12}{:>10}", "vertices", f.vertices)?;
+        writeln!(out, "{:<12}{:>10}", "edges", f.edges)?;
+        writeln!(out, "{:<12}{:>10.3}", "density", f.density)?;
+        writeln!(out, "{:<12}{:>10}", "max degree", f.max_degree)?;
+        writeln!(out, "{:<12}{:>10.3}", "avg degree", f.avg_degree)?;
+        writeln!(out, "{:<12}{:>10}", "degeneracy", f.degeneracy)?;
+        writeln!(out, "{:<12}{:>10.3}", "assortativ.", f.assortativity)?;
+        writeln!(out, "{:<12}{:>10}", "communities", f.communities)?;
+        writeln!(out, "{:<12}{:>10.3}", "modularity", f.modularity)?;
+        writeln!(out, "{:<12}{:>10}", "lower bound", lower)?;
+        writeln!(out, "{:<12}{:>10}", "upper bound", upper)?;
+
+        if let Some(k) = self.meta.chromatic {
+            writeln!(out, "{:<12}{:>10}", "best known", k)?;
+        }
+        if let Some(seed) = self.meta.seed {
+            writeln!(out, "{:<12}{:>10}", "seed", seed)?;
+        }
+        if let Some(source) = &self.meta.source {
+            writeln!(out, "{:<12}{}", "source", source)?;
+        }
+        Ok(())
+    }
+
+    fn run<W: Write>(&mut self, algo: &str, out: &mut W) -> io::Result<()> {
+        let g = &self.graph;
+        let start = Instant::now();
+        let c = match algo {
+            "rs" => rs_coloring(g),
+            "cs" => cs_coloring(g),
+            "lf" => lf_coloring(g),
+            "sl" => sl_coloring(g),
+            "sdo" => sdo_coloring(g),
+            "genetic" => genetic_coloring(g),
+            "ig" => iterated_greedy(g, 100),
+            "balanced" => color_balanced(g),
+            "exact" => exact_coloring(g),
+            _ => return writeln!(out, "unknown algorithm '{}'", algo),
+        };
+
+        writeln!(
+            out,
+            "{} colors in {:.3}s",
+            num_colors(&c),
+            start.elapsed().as_secs_f64()
+        )?;
+        self.coloring = Some(c);
+
+        Ok(())
+    }
+
+    fn classes<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let c = match self.current(out)? {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        for (color, members) in color_classes(c) {
+            writeln!(out, "{:<12}{:>10}", color, members.len())?;
+        }
+
+        Ok(())
+    }
+
+    fn class<W: Write>(&self, color: usize, out: &mut W) -> io::Result<()> {
+        let c = match self.current(out)? {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        let members = color_classes(c).remove(&color).unwrap_or_default();
+        writeln!(out, "{}", join(&members))
+    }
+
+    fn vertex<W: Write>(&self, v: usize, out: &mut W) -> io::Result<()> {
+        if v >= self.graph.num_vertices() {
+            return writeln!(out, "no vertex {}", v);
+        }
+
+        let mut neighbors = self.graph.neighbors(v).collect::<Vec<_>>();
+        neighbors.sort_unstable();
+
+        match self.coloring {
+            Some(ref c) => {
+                writeln!(out, "color {}", c[v])?;
+                for u in neighbors {
+                    let mark = if c[u] == c[v] { " conflict" } else { "" };
+                    writeln!(out, "  {:<8} color {}{}", u, c[u], mark)?;
+                }
+                Ok(())
+            }
+            None => writeln!(out, "neighbors {}", join(&neighbors)),
+        }
+    }
+
+    fn conflicts<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let c = match self.current(out)? {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        // Connected components of the subgraph of conflicting edges
+        let n = self.graph.num_vertices();
+        let conflicting = |v: usize| self.graph.neighbors(v).filter(move |&u| c[u] == c[v]);
+        let mut visited = vec![false; n];
+        let mut regions = 0;
+
+        for s in 0..n {
+            if visited[s] || conflicting(s).next().is_none() {
+                continue;
+            }
+
+            visited[s] = true;
+            let mut region = vec![s];
+            let mut queue = VecDeque::from(vec![s]);
+            while let Some(v) = queue.pop_front() {
+                for u in conflicting(v) {
+                    if !visited[u] {
+                        visited[u] = true;
+                        region.push(u);
+                        queue.push_back(u);
+                    }
+                }
+            }
+
+            region.sort_unstable();
+            regions += 1;
+            writeln!(out, "region {}: {}", regions, join(&region))?;
+        }
+
+        if regions == 0 {
+            writeln!(out, "no conflicts")?;
+        }
+
+        Ok(())
+    }
+
+    fn set<W: Write>(&mut self, v: usize, color: usize, out: &mut W) -> io::Result<()> {
+        if v >= self.graph.num_vertices() {
+            return writeln!(out, "no vertex {}", v);
+        }
+
+        let c = match self.coloring {
+            Some(ref mut c) => c,
+            None => return writeln!(out, "no coloring, use 'run' first"),
+        };
+        c[v] = color;
+
+        let conflicts = self.graph.neighbors(v).filter(|&u| c[u] == color).count();
+        writeln!(
+            out,
+            "{} colors, {} conflicts at {}",
+            num_colors(c),
+            conflicts,
+            v
+        )
+    }
+
+    fn kempe<W: Write>(&mut self, v: usize, color: usize, out: &mut W) -> io::Result<()> {
+        if v >= self.graph.num_vertices() {
+            return writeln!(out, "no vertex {}", v);
+        }
+
+        let c = match self.coloring {
+            Some(ref mut c) => c,
+            None => return writeln!(out, "no coloring, use 'run' first"),
+        };
+
+        let a = c[v];
+        if a == color {
+            return writeln!(out, "vertex {} already has color {}", v, color);
+        }
+
+        let chain = kempe_swap(&self.graph, c, v, a, color);
+        writeln!(
+            out,
+            "swapped {} vertices, {} colors",
+            chain.len(),
+            num_colors(c)
+        )
+    }
+
+    /// Returns the current coloring or explains that there is none.
+    fn current<W: Write>(&self, out: &mut W) -> io::Result<Option<&Coloring>> {
+        if self.coloring.is_none() {
+            writeln!(out, "no coloring, use 'run' first")?;
+        }
+
+        Ok(self.coloring.as_ref())
+    }
+}
+
+/// Groups vertices by color, in increasing order of colors.
+fn color_classes(coloring: &[usize]) -> BTreeMap<usize, Vec<usize>> {
+    let mut classes = BTreeMap::new();
+    for (v, &c) in coloring.iter().enumerate() {
+        classes.entry(c).or_insert_with(Vec::new).push(v);
+    }
+
+    classes
+}
+
+fn join(vertices: &[usize]) -> String {
+    vertices
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Runs the commands on a path of four vertices and returns the output.
+    fn session(commands: &str) -> String {
+        let mut g = Graph::new();
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+
+        let mut out = Vec::new();
+        repl(
+            Session::new(g, GraphMeta::default()),
+            Cursor::new(commands),
+            &mut out,
+        )
+        .unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn explore_kempe() {
+        let out = session("run lf\nset 2 2\nkempe 0 0\nvertex 0\nquit\nrun sl\n");
+        let lines = out.lines().collect::<Vec<_>>();
+
+        // Largest first colors the path 1 0 1 0, the chain of 0 ends before 2
+        assert_eq!(lines[1], "> 3 colors, 0 conflicts at 2");
+        assert_eq!(lines[2], "> swapped 2 vertices, 3 colors");
+        assert_eq!(&lines[3..], &["> color 0", "  1        color 1", "> "]);
+    }
+
+    #[test]
+    fn explore_errors() {
+        let out = session("kempe 0 1\nrun lf\nkempe 9 0\nkempe 0 1\nfoo\nclass 0\n");
+        let lines = out
+            .lines()
+            .map(|l| l.trim_start_matches("> "))
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "no coloring, use 'run' first");
+        assert_eq!(lines[2], "no vertex 9");
+        assert_eq!(lines[3], "vertex 0 already has color 1");
+        assert_eq!(lines[4], "invalid command 'foo', try 'help'");
+        assert_eq!(lines[5], "1 3");
+    }
+}
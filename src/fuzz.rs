@@ -0,0 +1,93 @@
+//! Entry points for fuzzing the loaders and the coloring pipeline.
+//!
+//! The functions accept arbitrary bytes and must neither panic nor hang on any
+//! input, except for the coloring checks which panic on an invalid coloring.
+//! The ```cargo-fuzz``` targets in ```fuzz/``` call them.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io;
+
+use crate::coloring::reductions::with_reductions;
+use crate::coloring::*;
+use crate::graph::dimacs::parse_dimacs_limited;
+use crate::graph::graph6::parse_graph6_limited;
+use crate::graph::{AdjList, Graph, StaticGraph};
+
+/// Vertices accepted by the fuzz entry points, larger graphs are only slower.
+const MAX_VERTICES: usize = 1 << 12;
+
+/// Parses DIMACS input from raw bytes. Graphs with more than 4096 vertices are
+/// rejected, such that a single line can not request arbitrary amounts of memory.
+pub fn parse_dimacs_bytes(data: &[u8]) -> io::Result<Graph> {
+    parse_dimacs_limited(data, MAX_VERTICES)
+}
+
+/// Parses a graph6 line from raw bytes, rejecting graphs with more than 4096
+/// vertices as ```parse_dimacs_bytes``` does.
+pub fn parse_graph6_bytes(data: &[u8]) -> io::Result<Graph> {
+    let line = std::str::from_utf8(data)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8"))?;
+    parse_graph6_limited(line, MAX_VERTICES)
+}
+
+/// Builds a graph from raw bytes and colors it with every algorithm.
+/// The first byte is the number of vertices modulo 64, every following pair of
+/// bytes an edge. Panics if any of the algorithms returns an invalid coloring.
+/// Returns the coloring with the fewest colors.
+pub fn color_from_edge_bytes(data: &[u8]) -> Coloring {
+    let (n, edges) = match data.split_first() {
+        Some((&n, edges)) => (usize::from(n) % 64, edges),
+        None => return Coloring::new(),
+    };
+
+    let mut g = AdjList::with_capacity(n);
+    if n > 0 {
+        for pair in edges.chunks_exact(2) {
+            g.add_edge(usize::from(pair[0]) % n, usize::from(pair[1]) % n);
+        }
+    }
+
+    let mut colorings = vec![
+        color(&g),
+        color_balanced(&g),
+        sampled_coloring(&g, n / 2, &mut StdRng::seed_from_u64(0)),
+        with_reductions(&g, sdo_coloring),
+    ];
+    colorings.extend(planar_coloring(&g));
+    colorings.extend(two_coloring(&g));
+    colorings.extend(treewidth_coloring(&g, 4));
+    if n <= 16 {
+        colorings.push(exact_coloring(&g));
+    }
+
+    for c in &colorings {
+        assert!(check_coloring(&g, c), "invalid coloring of {:?}", data);
+    }
+
+    colorings.into_iter().min_by_key(num_colors).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn fuzz_random_bytes() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..300 {
+            let len = rng.gen_range(0, 200);
+            let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+
+            let _ = parse_dimacs_bytes(&data);
+            let _ = parse_graph6_bytes(&data);
+            color_from_edge_bytes(&data);
+        }
+
+        assert!(parse_dimacs_bytes(b"p edge 99999999999999 0").is_err());
+        assert!(parse_graph6_bytes(b"~~~~~~~~").is_err());
+        assert!(parse_graph6_bytes(b"~?~?").is_err());
+    }
+}
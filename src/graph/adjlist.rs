@@ -17,6 +17,21 @@ impl AdjList {
     pub fn new() -> Self {
         Self { adj: vec![], n: 0 }
     }
+
+    /// Graph from the neighbors of every vertex, which must be symmetric and
+    /// without duplicates.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn from_adjacency(adj: Vec<Vec<usize>>) -> Self {
+        let n = adj.len();
+
+        Self { adj, n }
+    }
+}
+
+impl Default for AdjList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StaticGraph for AdjList {
@@ -31,7 +46,7 @@ impl StaticGraph for AdjList {
     }
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self {
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
         let mut g = Self::new();
         for (u, v) in graph.edges() {
             g.add_edge(u, v);
@@ -81,7 +96,7 @@ impl StaticGraph for AdjList {
     }
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a> {
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
         Box::new(
             self.adj
                 .iter()
@@ -100,7 +115,7 @@ impl StaticGraph for AdjList {
     }
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a> {
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
         if v >= self.adj.len() {
             Box::new(std::iter::empty())
         } else {
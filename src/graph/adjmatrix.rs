@@ -15,6 +15,14 @@ pub struct AdjMatrix {
 }
 
 impl AdjMatrix {
+    /// Graph from its symmetric ```n``` by ```n``` matrix, the row of vertex ```v```
+    /// at ```adj[v * n..(v + 1) * n]```.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn from_parts(adj: Vec<bool>, n: usize) -> Self {
+        debug_assert_eq!(adj.len(), n * n);
+        Self { adj, n }
+    }
+
     /// Get index into adjacency array from edge.
     fn get_idx(&self, u: usize, v: usize) -> usize {
         v * self.n + u
@@ -31,7 +39,7 @@ impl StaticGraph for AdjMatrix {
     }
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self {
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
         let mut g = Self::with_capacity(graph.vertices().count());
         for (u, v) in graph.edges() {
             g.add_edge(u, v);
@@ -68,7 +76,7 @@ impl StaticGraph for AdjMatrix {
     }
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a> {
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
         let n = self.n;
         Box::new(
             self.adj
@@ -95,7 +103,7 @@ impl StaticGraph for AdjMatrix {
     }
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a> {
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
         if v < self.n {
             Box::new(
                 self.adj[(v * self.n)..((v + 1) * self.n)]
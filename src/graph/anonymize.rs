@@ -0,0 +1,130 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::io::{self, BufRead, Write};
+
+use crate::coloring::Coloring;
+use crate::graph::StaticGraph;
+
+/// Copy of a graph with randomly permuted vertices, isomorphic to the original
+/// but without its vertex order, labels or comments. Vertex ```v``` of the
+/// original graph is vertex ```mapping[v]``` of ```graph```.
+#[derive(Debug, Clone)]
+pub struct Anonymized<G> {
+    pub graph: G,
+    pub mapping: Vec<usize>,
+}
+
+/// Relabels the vertices of the graph with a permutation determined by ```seed```.
+/// Edges are added in sorted order of the new vertices, such that writing the
+/// graph reveals nothing about the original order. Labels of a ```Labeled```
+/// graph are stripped by anonymizing its ```graph```.
+pub fn anonymize<G: StaticGraph, H: StaticGraph + ?Sized>(graph: &H, seed: u64) -> Anonymized<G> {
+    let n = graph.num_vertices();
+    let mut mapping = (0..n).collect::<Vec<_>>();
+    mapping.shuffle(&mut StdRng::seed_from_u64(seed));
+
+    let mut edges = graph
+        .edges()
+        .map(|(u, v)| {
+            let (a, b) = (mapping[u], mapping[v]);
+            (a.min(b), a.max(b))
+        })
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    let mut anonymized = G::with_capacity(n);
+    for (u, v) in edges {
+        anonymized.add_edge(u, v);
+    }
+
+    Anonymized {
+        graph: anonymized,
+        mapping,
+    }
+}
+
+impl<G> Anonymized<G> {
+    /// Translates a coloring of the anonymized graph to the original vertices.
+    pub fn restore(&self, coloring: &[usize]) -> Coloring {
+        self.mapping.iter().map(|&v| coloring[v]).collect()
+    }
+
+    /// Writes the mapping as one line ```m original anonymized``` per vertex, with
+    /// vertices numbered from 1 as in the DIMACS format. Keep it private, it undoes
+    /// the anonymization.
+    pub fn write_mapping<W: Write>(&self, mut out: W) -> io::Result<()> {
+        for (v, &a) in self.mapping.iter().enumerate() {
+            writeln!(out, "m {} {}", v + 1, a + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a mapping written by ```Anonymized::write_mapping```.
+/// Anything but a permutation is reported as an ```InvalidData``` error.
+pub fn parse_mapping(reader: impl BufRead) -> io::Result<Vec<usize>> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let mut pairs = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut splits = line.split_whitespace();
+
+        match splits.next() {
+            None | Some("c") => continue,
+            Some("m") => {
+                let mut vertex = || {
+                    splits
+                        .next()
+                        .and_then(|s| s.parse::<usize>().ok())
+                        .and_then(|v| v.checked_sub(1))
+                        .ok_or_else(|| invalid("expected a vertex"))
+                };
+                pairs.push((vertex()?, vertex()?));
+            }
+            Some(_) => return Err(invalid(&format!("unexpected line '{}'", line))),
+        }
+    }
+
+    let n = pairs.len();
+    let mut mapping = vec![None; n];
+    let mut used = vec![false; n];
+    for (v, a) in pairs {
+        if v >= n || a >= n || mapping[v].is_some() || used[a] {
+            return Err(invalid("mapping is not a permutation"));
+        }
+        mapping[v] = Some(a);
+        used[a] = true;
+    }
+
+    Ok(mapping.into_iter().map(Option::unwrap).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, sdo_coloring};
+    use crate::graph::*;
+
+    #[test]
+    fn anonymize_isomorphic() {
+        let g = AdjList::random(40, 0.2);
+
+        let a: Anonymized<AdjList> = anonymize(&g, 7);
+        let b: Anonymized<AdjList> = anonymize(&g, 7);
+
+        assert_eq!(a.mapping, b.mapping);
+        assert_eq!(a.graph.edges().count(), g.edges().count());
+        assert!(g
+            .edges()
+            .all(|(u, v)| a.graph.has_edge(a.mapping[u], a.mapping[v])));
+        assert!(check_coloring(&g, &a.restore(&sdo_coloring(&a.graph))));
+
+        let mut out = Vec::new();
+        a.write_mapping(&mut out).unwrap();
+        assert_eq!(parse_mapping(&out[..]).unwrap(), a.mapping);
+        assert!(parse_mapping(&b"m 1 2\nm 2 2\n"[..]).is_err());
+    }
+}
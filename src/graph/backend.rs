@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{AdjList, AdjMatrix, Csr, Csr32, Hybrid, StaticGraph};
+
+/// Graph of any datastructure behind a trait object, such that graphs of
+/// different types can be kept in one collection. Algorithms taking
+/// ```G: StaticGraph + ?Sized``` accept ```&*graph```, and the boxed graph is itself a
+/// ```StaticGraph```, constructed as an ```AdjList```.
+pub type DynGraph = Box<dyn StaticGraph + Send + Sync>;
+
+/// Graph datastructures selectable at runtime, e.g. per instance of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Backend {
+    AdjList,
+    AdjMatrix,
+    Csr,
+    Csr32,
+    Hybrid,
+}
+
+/// Largest number of vertices for which ```Backend::choose``` picks an adjacency
+/// matrix, which takes a byte per pair of vertices.
+const MATRIX_VERTICES: usize = 4096;
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [
+        Backend::AdjList,
+        Backend::AdjMatrix,
+        Backend::Csr,
+        Backend::Csr32,
+        Backend::Hybrid,
+    ];
+
+    /// Backend suited for a graph of the given size: an adjacency matrix for dense
+    /// graphs of up to a few thousand vertices, where the blocked colors dominate
+    /// the greedy colorings, and the compressed sparse row format otherwise.
+    pub fn choose(vertices: usize, edges: usize) -> Self {
+        let pairs = vertices.saturating_sub(1) as f64 * vertices as f64 / 2.0;
+
+        if vertices <= MATRIX_VERTICES && edges as f64 >= pairs / 2.0 {
+            Backend::AdjMatrix
+        } else if vertices <= u32::MAX as usize {
+            Backend::Csr32
+        } else {
+            Backend::Csr
+        }
+    }
+
+    /// Copies the graph into this datastructure.
+    pub fn build<G: StaticGraph + ?Sized>(self, graph: &G) -> DynGraph {
+        match self {
+            Backend::AdjList => Box::new(AdjList::from_graph(graph)),
+            Backend::AdjMatrix => Box::new(AdjMatrix::from_graph(graph)),
+            Backend::Csr => Box::new(Csr::from_graph(graph)),
+            Backend::Csr32 => Box::new(Csr32::from_graph(graph)),
+            Backend::Hybrid => Box::new(Hybrid::from_graph(graph)),
+        }
+    }
+
+    /// Short lowercase name of the backend.
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::AdjList => "adjlist",
+            Backend::AdjMatrix => "adjmatrix",
+            Backend::Csr => "csr",
+            Backend::Csr32 => "csr32",
+            Backend::Hybrid => "hybrid",
+        }
+    }
+
+    /// Backend of the given ```name```.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().cloned().find(|b| b.name() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+
+    #[test]
+    fn backends_in_one_vec() {
+        let g = AdjList::random(50, 0.3);
+        let graphs = Backend::ALL
+            .iter()
+            .map(|b| b.build(&g))
+            .collect::<Vec<DynGraph>>();
+
+        for graph in &graphs {
+            assert_eq!(graph.edges().count(), g.edges().count());
+            assert_eq!(lf_coloring(&**graph), lf_coloring(&g));
+            assert_eq!(ColoringAlgo::SDO.color(graph), sdo_coloring(&g));
+        }
+
+        assert_eq!(Backend::choose(100, 4000), Backend::AdjMatrix);
+        assert_eq!(Backend::choose(100_000, 400_000), Backend::Csr32);
+        assert_eq!(Backend::from_name("csr32"), Some(Backend::Csr32));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::graph::StaticGraph;
+
+/// Decomposition of a cograph, the graphs without an induced path on four vertices.
+/// It is the modular decomposition of the graph, which for cographs only consists
+/// of disjoint unions and joins.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cotree {
+    Vertex(usize),
+    /// Disjoint union, no edges between vertices of different children.
+    Union(Vec<Cotree>),
+    /// Join, all edges between vertices of different children.
+    Join(Vec<Cotree>),
+}
+
+impl Cotree {
+    /// Colors the vertices of the cotree with the fewest colors possible. Children
+    /// of a union share colors, children of a join use disjoint ranges of colors.
+    /// Returns the number of colors used.
+    pub fn color(&self, coloring: &mut [usize]) -> usize {
+        self.color_from(coloring, 0)
+    }
+
+    fn color_from(&self, coloring: &mut [usize], offset: usize) -> usize {
+        match self {
+            Cotree::Vertex(v) => {
+                coloring[*v] = offset;
+                1
+            }
+            Cotree::Union(children) => children
+                .iter()
+                .map(|c| c.color_from(coloring, offset))
+                .max()
+                .unwrap_or(0),
+            Cotree::Join(children) => children
+                .iter()
+                .fold(0, |used, c| used + c.color_from(coloring, offset + used)),
+        }
+    }
+}
+
+/// Returns the cotree of the graph if it is a cograph, ```None``` otherwise.
+///
+/// A graph with at least two vertices is a cograph if and only if it or its
+/// complement is disconnected and all components are cographs. The components of
+/// the complement are found without building it, by keeping the unvisited vertices
+/// in a list and moving those not adjacent to the current vertex into the search.
+/// Each level of the cotree takes linear time, quadratic in total in the worst case.
+pub fn cotree<G: StaticGraph + ?Sized>(graph: &G) -> Option<Cotree> {
+    let n = graph.num_vertices();
+    if n == 0 {
+        return Some(Cotree::Union(Vec::new()));
+    }
+
+    let mut decomposition = Decomposition {
+        graph,
+        mark: vec![0; n],
+        seen: vec![0; n],
+        tick: 0,
+    };
+
+    decomposition.split((0..n).collect())
+}
+
+/// Whether the graph contains no induced path on four vertices.
+pub fn is_cograph<G: StaticGraph + ?Sized>(graph: &G) -> bool {
+    cotree(graph).is_some()
+}
+
+struct Decomposition<'a, G: ?Sized> {
+    graph: &'a G,
+    /// Vertices marked with the current tick, meaning depends on the search.
+    mark: Vec<usize>,
+    seen: Vec<usize>,
+    tick: usize,
+}
+
+impl<'a, G: StaticGraph + ?Sized> Decomposition<'a, G> {
+    fn split(&mut self, set: Vec<usize>) -> Option<Cotree> {
+        if set.len() == 1 {
+            return Some(Cotree::Vertex(set[0]));
+        }
+
+        let parts = self.components(&set);
+        let (parts, join) = if parts.len() > 1 {
+            (parts, false)
+        } else {
+            let parts = self.co_components(&set);
+            if parts.len() == 1 {
+                return None;
+            }
+            (parts, true)
+        };
+
+        let children = parts
+            .into_iter()
+            .map(|p| self.split(p))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(if join {
+            Cotree::Join(children)
+        } else {
+            Cotree::Union(children)
+        })
+    }
+
+    /// Connected components of the subgraph induced by ```set```.
+    fn components(&mut self, set: &[usize]) -> Vec<Vec<usize>> {
+        self.tick += 1;
+        let inside = self.tick;
+        for &v in set {
+            self.mark[v] = inside;
+        }
+
+        let mut parts = Vec::new();
+        for &first in set {
+            if self.seen[first] == inside {
+                continue;
+            }
+
+            self.seen[first] = inside;
+            let mut part = vec![first];
+            let mut i = 0;
+            while i < part.len() {
+                for u in self.graph.neighbors(part[i]) {
+                    if self.mark[u] == inside && self.seen[u] != inside {
+                        self.seen[u] = inside;
+                        part.push(u);
+                    }
+                }
+                i += 1;
+            }
+            parts.push(part);
+        }
+
+        parts
+    }
+
+    /// Connected components of the complement of the subgraph induced by ```set```.
+    fn co_components(&mut self, set: &[usize]) -> Vec<Vec<usize>> {
+        let mut unvisited = set.to_vec();
+        let mut parts = Vec::new();
+
+        while let Some(first) = unvisited.pop() {
+            let mut part = vec![first];
+            let mut i = 0;
+            while i < part.len() {
+                self.tick += 1;
+                let adjacent = self.tick;
+                for u in self.graph.neighbors(part[i]) {
+                    self.mark[u] = adjacent;
+                }
+
+                let mark = &self.mark;
+                unvisited.retain(|&u| {
+                    if mark[u] == adjacent {
+                        true
+                    } else {
+                        part.push(u);
+                        false
+                    }
+                });
+                i += 1;
+            }
+            parts.push(part);
+        }
+
+        parts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn cograph_recognition() {
+        let mut path = AdjList::new();
+        for i in 0..3 {
+            path.add_edge(i, i + 1);
+        }
+        assert!(!is_cograph(&path));
+
+        // Join of a triangle with the union of an edge and a vertex
+        let mut g = AdjList::complete(3);
+        g.add_edge(3, 4);
+        for u in 0..3 {
+            for v in 3..6 {
+                g.add_edge(u, v);
+            }
+        }
+
+        let tree = cotree(&g).unwrap();
+        let mut coloring = vec![usize::MAX; 6];
+
+        assert!(matches!(tree, Cotree::Join(ref c) if c.len() == 4));
+        assert_eq!(tree.color(&mut coloring), 5);
+        assert!(g.edges().all(|(u, v)| coloring[u] != coloring[v]));
+        assert!(is_cograph(&AdjList::complete(4)));
+        assert!(is_cograph(&AdjList::new()));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::graph::{Labeled, StaticGraph};
+
+/// Returns the quotient graph of a vertex partition.
+/// Vertex ```v``` is merged into vertex ```partition[v]``` of the result, which has
+/// ```max(partition) + 1``` vertices. Two merged vertices are adjacent if any of their
+/// members are, edges within a group are dropped.
+/// Contracting connected groups yields a minor of the graph.
+pub fn contract_vertices<G: StaticGraph>(graph: &G, partition: &[usize]) -> G {
+    assert_eq!(graph.num_vertices(), partition.len());
+
+    let n = partition.iter().max().map_or(0, |&p| p + 1);
+    let mut g = G::with_capacity(n);
+
+    for (u, v) in graph.edges() {
+        let (pu, pv) = (partition[u], partition[v]);
+        if pu != pv {
+            g.add_edge(pu, pv);
+        }
+    }
+
+    g
+}
+
+impl<G: StaticGraph, L: Clone> Labeled<G, L> {
+    /// Same as ```contract_vertices```, the label of a merged vertex lists the labels
+    /// of its members in order.
+    pub fn contract(&self, partition: &[usize]) -> Labeled<G, Vec<L>> {
+        let graph = contract_vertices(&self.graph, partition);
+
+        let mut labels = vec![Vec::new(); graph.num_vertices()];
+        for (v, &p) in partition.iter().enumerate() {
+            labels[p].push(self.labels[v].clone());
+        }
+
+        Labeled::new(graph, labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn contract_cycle() {
+        let mut g = AdjList::new();
+        for i in 0..6 {
+            g.add_edge(i, (i + 1) % 6);
+        }
+
+        let h = contract_vertices(&g, &[0, 0, 1, 1, 2, 2]);
+
+        assert_eq!(h.num_vertices(), 3);
+        assert_eq!(h.edges().count(), 3);
+    }
+
+    #[test]
+    fn contract_petersen_k5_minor() {
+        // Outer cycle 0..5, inner pentagram 5..10
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i, i + 5);
+            g.add_edge(i + 5, (i + 2) % 5 + 5);
+        }
+
+        // Merging each spoke gives a complete graph on five vertices
+        let h = contract_vertices(&g, &[0, 1, 2, 3, 4, 0, 1, 2, 3, 4]);
+
+        assert_eq!(h.edges().count(), 10);
+    }
+
+    #[test]
+    fn contract_labeled() {
+        let g: Labeled<AdjList, _> =
+            interval_graph(vec![("a", 0..2), ("b", 1..3), ("c", 5..6), ("d", 2..6)]);
+
+        let h = g.contract(&[0, 1, 0, 1]);
+
+        assert_eq!(h.labels, vec![vec!["a", "c"], vec!["b", "d"]]);
+        assert!(h.graph.has_edge(0, 1));
+        assert_eq!(h.graph.edges().count(), 1);
+    }
+}
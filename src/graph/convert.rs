@@ -0,0 +1,210 @@
+//! Conversion between graph types on several threads.
+//!
+//! ```convert_parallel``` splits the vertices into ranges, which threads take from
+//! a shared counter to balance uneven degrees. Each thread collects the sorted
+//! neighbors of its ranges into a ```NeighborChunk```, and the chunks are merged
+//! into the target by ```FromChunks::from_chunks```. Targets whose layout follows
+//! the chunks, such as ```Csr``` and ```AdjList```, merge by concatenation, and
+//! ```AdjMatrix``` fills the rows of each chunk on a thread of its own. Others add
+//! every edge on the calling thread.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::graph::{
+    AdjList, AdjMatrix, EdgeList, GrowableAdjMatrix, Hybrid, IndexedCsr, StaticGraph, VertexId,
+};
+
+/// Ranges of vertices per thread, such that a thread stuck on a range of high
+/// degree vertices does not hold up the others for long.
+const RANGES_PER_THREAD: usize = 8;
+
+/// Sorted neighbors of a range of consecutive vertices in compressed sparse row
+/// format. The neighbors of ```vertices.start + i``` are
+/// ```targets[offsets[i]..offsets[i + 1]]```.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeighborChunk {
+    pub vertices: Range<usize>,
+    pub offsets: Vec<usize>,
+    pub targets: Vec<usize>,
+}
+
+impl NeighborChunk {
+    /// Collects the neighbors of the vertices of ```graph``` in the range.
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G, vertices: Range<usize>) -> Self {
+        let mut offsets = Vec::with_capacity(vertices.len() + 1);
+        let mut targets = Vec::new();
+
+        offsets.push(0);
+        for v in vertices.clone() {
+            let start = targets.len();
+            targets.extend(graph.neighbors(v));
+            targets[start..].sort_unstable();
+            offsets.push(targets.len());
+        }
+
+        Self {
+            vertices,
+            offsets,
+            targets,
+        }
+    }
+
+    /// Returns the sorted neighbors of ```v```, which must be in the range.
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        let i = v - self.vertices.start;
+        &self.targets[self.offsets[i]..self.offsets[i + 1]]
+    }
+}
+
+/// Graphs which can be assembled from the neighbors of all vertices in chunks.
+pub trait FromChunks: StaticGraph + Sized {
+    /// Constructs the graph with ```n``` vertices from chunks covering them in
+    /// order. By default every edge is added once.
+    fn from_chunks(n: usize, chunks: Vec<NeighborChunk>) -> Self {
+        let mut graph = Self::with_capacity(n);
+        for chunk in &chunks {
+            for u in chunk.vertices.clone() {
+                for &v in chunk.neighbors(u).iter().filter(|&&v| u < v) {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+impl<I: VertexId> FromChunks for IndexedCsr<I> {
+    fn from_chunks(n: usize, chunks: Vec<NeighborChunk>) -> Self {
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut targets = Vec::with_capacity(chunks.iter().map(|c| c.targets.len()).sum());
+
+        offsets.push(0);
+        for chunk in chunks {
+            let base = targets.len();
+            offsets.extend(chunk.offsets[1..].iter().map(|&o| base + o));
+            targets.extend(chunk.targets.into_iter().map(I::from_vertex));
+        }
+
+        Self::from_parts(offsets, targets)
+    }
+}
+
+impl FromChunks for AdjList {
+    fn from_chunks(n: usize, chunks: Vec<NeighborChunk>) -> Self {
+        let mut adj = Vec::with_capacity(n);
+        for chunk in &chunks {
+            adj.extend(chunk.vertices.clone().map(|v| chunk.neighbors(v).to_vec()));
+        }
+
+        AdjList::from_adjacency(adj)
+    }
+}
+
+impl FromChunks for AdjMatrix {
+    fn from_chunks(n: usize, chunks: Vec<NeighborChunk>) -> Self {
+        let mut adj = vec![false; n * n];
+
+        crossbeam::scope(|s| {
+            let mut rest = &mut adj[..];
+            for chunk in &chunks {
+                let (rows, tail) = rest.split_at_mut(chunk.vertices.len() * n);
+                rest = tail;
+
+                s.spawn(move |_| {
+                    for (v, row) in chunk.vertices.clone().zip(rows.chunks_mut(n)) {
+                        for &u in chunk.neighbors(v) {
+                            row[u] = true;
+                        }
+                    }
+                });
+            }
+        })
+        .unwrap();
+
+        AdjMatrix::from_parts(adj, n)
+    }
+}
+impl FromChunks for EdgeList {}
+impl FromChunks for GrowableAdjMatrix {}
+impl FromChunks for Hybrid {}
+
+/// Converts the graph into ```T``` on ```threads``` threads.
+/// Equivalent to ```T::from_graph```, but collects the neighbors of all vertices in
+/// parallel as described in the module documentation. Worthwhile for graphs with
+/// many millions of edges. Requires the ```parallel``` feature.
+pub fn convert_parallel<T: FromChunks>(
+    graph: &(impl StaticGraph + Sync + ?Sized),
+    threads: usize,
+) -> T {
+    let n = graph.num_vertices();
+    let threads = threads.max(1);
+    let size = n.div_ceil(threads * RANGES_PER_THREAD).max(1);
+    let ranges = n.div_ceil(size);
+    let next = AtomicUsize::new(0);
+
+    let mut chunks = crossbeam::scope(|s| {
+        let handles = (0..threads)
+            .map(|_| {
+                let next = &next;
+
+                s.spawn(move |_| {
+                    let mut chunks = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= ranges {
+                            return chunks;
+                        }
+                        chunks.push(NeighborChunk::new(graph, i * size..n.min((i + 1) * size)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .unwrap();
+    chunks.sort_by_key(|c| c.vertices.start);
+
+    T::from_chunks(n, chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Csr, Csr32};
+    use std::collections::BTreeSet;
+
+    fn edges<G: StaticGraph + ?Sized>(g: &G) -> BTreeSet<(usize, usize)> {
+        g.edges().map(|(u, v)| (u.min(v), u.max(v))).collect()
+    }
+
+    #[test]
+    fn convert_matches_from_graph() {
+        let mut g = AdjList::random(300, 0.1);
+        g.add_edge(310, 0);
+
+        for &threads in &[1, 3, 8] {
+            let csr: Csr = convert_parallel(&g, threads);
+            let list: AdjList = convert_parallel(&g, threads);
+            let matrix = convert_parallel::<AdjMatrix>(&g, threads);
+            let narrow: Csr32 = convert_parallel(&g, threads);
+
+            assert_eq!(csr.num_vertices(), 311);
+            assert_eq!(edges(&csr), edges(&g));
+            assert_eq!(edges(&list), edges(&g));
+            assert_eq!(edges(&narrow), edges(&g));
+            assert_eq!(edges(&matrix), edges(&Csr::from_graph(&g)));
+            assert!((0..311).all(|v| csr.neighbors(v).eq(Csr::from_graph(&g).neighbors(v))));
+        }
+
+        let empty: Csr = convert_parallel(&AdjList::new(), 4);
+        assert_eq!(empty.num_vertices(), 0);
+        let empty = convert_parallel::<AdjMatrix>(&AdjList::new(), 4);
+        assert_eq!(empty.num_vertices(), 0);
+    }
+}
@@ -0,0 +1,202 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::mem;
+
+use crate::graph::StaticGraph;
+
+/// Integer type storing vertex indices in the neighbor array of ```IndexedCsr```.
+/// Narrower types than ```usize``` save memory on graphs with few enough vertices.
+pub trait VertexId: Copy + Ord + Debug + Send + Sync {
+    /// Converts a vertex into the index type, if it fits.
+    fn try_from_vertex(v: usize) -> Option<Self>;
+
+    /// Converts a vertex into the index type.
+    /// Panics if the vertex does not fit into the type.
+    fn from_vertex(v: usize) -> Self {
+        Self::try_from_vertex(v).expect("vertex does not fit into the index type")
+    }
+
+    /// Converts the index back into a vertex.
+    fn vertex(self) -> usize;
+}
+
+impl VertexId for usize {
+    fn try_from_vertex(v: usize) -> Option<Self> {
+        Some(v)
+    }
+
+    fn vertex(self) -> usize {
+        self
+    }
+}
+
+impl VertexId for u32 {
+    fn try_from_vertex(v: usize) -> Option<Self> {
+        u32::try_from(v).ok()
+    }
+
+    fn vertex(self) -> usize {
+        self as usize
+    }
+}
+
+/// Graph in compressed sparse row format with the 64 bit indices of ```usize```
+/// on 64 bit machines.
+pub type Csr = IndexedCsr<usize>;
+
+/// Graph in compressed sparse row format with 32 bit indices, which halves the
+/// neighbor array for graphs of fewer than 2^32 vertices. Adding an edge between
+/// larger vertices panics.
+pub type Csr32 = IndexedCsr<u32>;
+
+/// Graph datastructure in compressed sparse row format.
+/// The neighbors of all vertices are stored sorted in one contiguous array, such
+/// that iterating neighbors is cache friendly and the overhead per vertex is a
+/// single offset. The graph is undirected and unweighted - only the connectivity
+/// pattern of the vertices is captured. Multiple edges and self edges are also disallowed.
+///
+/// Neighbors are stored as ```I```, see ```Csr``` and ```Csr32```. The neighbor array
+/// holds every edge twice and dominates the memory of all but the sparsest graphs,
+/// while the offsets stay ```usize``` as there may be more than 2^32 entries.
+///
+/// Meant to be built once with ```from_graph```. Adding an edge shifts all later
+/// entries and is linear in the size of the graph.
+#[derive(Debug, Clone)]
+pub struct IndexedCsr<I: VertexId> {
+    /// Neighbors of ```v``` are ```targets[offsets[v]..offsets[v + 1]]```.
+    offsets: Vec<usize>,
+    targets: Vec<I>,
+}
+
+impl<I: VertexId> IndexedCsr<I> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Graph from its arrays, the neighbors of every vertex sorted.
+    pub(crate) fn from_parts(offsets: Vec<usize>, targets: Vec<I>) -> Self {
+        debug_assert_eq!(offsets.last(), Some(&targets.len()));
+
+        Self { offsets, targets }
+    }
+
+    /// Returns the number of neighbors of ```v```.
+    pub fn degree(&self, v: usize) -> usize {
+        self.slice(v).len()
+    }
+
+    /// Returns the number of bytes of the offset and neighbor arrays.
+    pub fn memory(&self) -> usize {
+        self.offsets.len() * mem::size_of::<usize>() + self.targets.len() * mem::size_of::<I>()
+    }
+
+    fn slice(&self, v: usize) -> &[I] {
+        if v + 1 >= self.offsets.len() {
+            &[]
+        } else {
+            &self.targets[self.offsets[v]..self.offsets[v + 1]]
+        }
+    }
+
+    /// Inserts ```v``` into the sorted neighbors of ```u```.
+    fn insert(&mut self, u: usize, v: usize) {
+        let v = I::from_vertex(v);
+        let pos = self.offsets[u] + self.slice(u).binary_search(&v).unwrap_err();
+        self.targets.insert(pos, v);
+
+        for offset in &mut self.offsets[u + 1..] {
+            *offset += 1;
+        }
+    }
+}
+
+impl<I: VertexId> Default for IndexedCsr<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: VertexId> StaticGraph for IndexedCsr<I> {
+    /// Constructs a new graph with capacity for ```n``` vertices.
+    fn with_capacity(n: usize) -> Self {
+        Self {
+            offsets: vec![0; n + 1],
+            targets: Vec::new(),
+        }
+    }
+
+    /// Construct an instance of this type from another ```StaticGraph``` implementor
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let n = graph.num_vertices();
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut targets = Vec::new();
+
+        offsets.push(0);
+        for v in 0..n {
+            let start = targets.len();
+            targets.extend(graph.neighbors(v).map(I::from_vertex));
+            targets[start..].sort_unstable();
+            offsets.push(targets.len());
+        }
+
+        Self { offsets, targets }
+    }
+
+    /// Queries whether an edge exists in the graph.
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        // Vertices beyond the index type can not be neighbors
+        let v = match I::try_from_vertex(v) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        self.slice(u).binary_search(&v).is_ok()
+    }
+
+    /// Adds an edge to the graph.
+    /// ```add_edge(u,v)``` has the same effect as ```add_edge(v,u)```
+    /// as the graph captures undirected edges.
+    /// Adding an edge that already exists has no effect.
+    fn add_edge(&mut self, u: usize, v: usize) {
+        if u == v || self.has_edge(u, v) {
+            return;
+        }
+
+        while self.offsets.len() <= u.max(v) + 1 {
+            self.offsets.push(self.targets.len());
+        }
+
+        self.insert(u, v);
+        self.insert(v, u);
+    }
+
+    /// Returns an iterator over all the edges in the graph.
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
+        Box::new((0..self.num_vertices()).flat_map(move |u| {
+            self.slice(u)
+                .iter()
+                .map(|v| v.vertex())
+                .filter(move |&v| u < v)
+                .map(move |v| (u, v))
+        }))
+    }
+
+    /// Returns the number of vertices in the graph.
+    fn num_vertices(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns an iterator over all the neighboring vertices in the graph.
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
+        Box::new(self.slice(v).iter().map(|v| v.vertex()))
+    }
+
+    /// Returns the maximum degree of any node in the graph.
+    fn max_degree(&self) -> usize {
+        self.offsets
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .max()
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,221 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::graph::{Graph, GraphMeta, StaticGraph};
+
+/// Load a graph from file in DIMACS ```.col``` format, see ```parse_dimacs```.
+pub fn load_graph(name: impl AsRef<Path>) -> io::Result<Graph> {
+    parse_dimacs(BufReader::new(File::open(name)?))
+}
+
+/// Same as ```load_graph``` but also returns the metadata in the comment lines.
+pub fn load_graph_with_meta(name: impl AsRef<Path>) -> io::Result<(Graph, GraphMeta)> {
+    parse_dimacs_with_meta(BufReader::new(File::open(name)?))
+}
+
+/// Parses a graph in DIMACS ```.col``` format. ([Specification](http://lcs.ios.ac.cn/~caisw/Resource/about_DIMACS_graph_format.txt))
+///
+/// A line may start with ```c```, ```p``` or ```e```.
+/// ```c``` indicates a comment line and is ignored.
+/// ```p``` lists the parameters of the graph as such:
+/// ```p edge n m```
+/// where 'edge' is simply written out, indicating that the following lines will each encode an edge,
+/// n is the number of vertices in the graph and m is the number of edges.
+/// This line must appear before any ```e``` lines.
+/// Lastly, ```e``` lines appear as:
+/// ```e u v```
+/// where u and v are vertex ids in [1,n] (n inclusive).
+///
+/// Empty lines are skipped. Malformed lines and edges with vertices outside of
+/// [1,n] are reported as ```InvalidData``` errors.
+pub fn parse_dimacs(reader: impl BufRead) -> io::Result<Graph> {
+    parse_dimacs_limited(reader, usize::MAX)
+}
+
+/// Same as ```parse_dimacs``` but also returns the metadata in the comment lines,
+/// see ```GraphMeta```.
+pub fn parse_dimacs_with_meta(reader: impl BufRead) -> io::Result<(Graph, GraphMeta)> {
+    let mut graph = None;
+    let mut meta = GraphMeta::default();
+    read_dimacs(reader, usize::MAX, |item| match item {
+        DimacsItem::Comment(comment) => meta.add_comment(comment),
+        DimacsItem::Size(n) => graph = Some(Graph::with_capacity(n)),
+        DimacsItem::Edge(u, v) => graph.as_mut().unwrap().add_edge(u, v),
+    })?;
+
+    Ok((graph.unwrap(), meta))
+}
+
+/// Same as ```parse_dimacs``` but rejects graphs with more than ```max_vertices```
+/// vertices before allocating them.
+pub(crate) fn parse_dimacs_limited(reader: impl BufRead, max_vertices: usize) -> io::Result<Graph> {
+    let mut graph = None;
+    read_dimacs(reader, max_vertices, |item| match item {
+        DimacsItem::Comment(_) => {}
+        DimacsItem::Size(n) => graph = Some(Graph::with_capacity(n)),
+        DimacsItem::Edge(u, v) => graph.as_mut().unwrap().add_edge(u, v),
+    })?;
+
+    Ok(graph.unwrap())
+}
+
+/// Line of a DIMACS file, with vertices shifted to start at zero.
+pub(crate) enum DimacsItem<'a> {
+    /// Text of a comment line after the ```c```.
+    Comment(&'a str),
+    Size(usize),
+    Edge(usize, usize),
+}
+
+/// Passes the comments, the number of vertices and every edge of a graph in
+/// DIMACS format to ```visit``` in the order of the lines without storing the
+/// graph, validated as by ```parse_dimacs```.
+pub(crate) fn read_dimacs(
+    reader: impl BufRead,
+    max_vertices: usize,
+    mut visit: impl FnMut(DimacsItem<'_>),
+) -> io::Result<()> {
+    let mut size = None;
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut splits = line.split_whitespace();
+
+        match splits.next() {
+            None => continue,
+            Some("c") => visit(DimacsItem::Comment(&line.trim_start()[1..])),
+            Some("p") => {
+                let _format = splits.next();
+                let n = number(splits.next())?;
+                let _m = number(splits.next())?;
+
+                if size.is_some() {
+                    return Err(invalid("more than one 'p' line"));
+                }
+                if n > max_vertices {
+                    return Err(invalid("too many vertices"));
+                }
+
+                size = Some(n);
+                visit(DimacsItem::Size(n));
+            }
+            Some("e") => {
+                let u = number(splits.next())?;
+                let v = number(splits.next())?;
+
+                let n = size.ok_or_else(|| invalid("'e' line before 'p' line"))?;
+
+                // Shift everything down as vertices are in [1,n]
+                if u == 0 || v == 0 || u > n || v > n {
+                    return Err(invalid("vertex of 'e' line out of range"));
+                }
+
+                visit(DimacsItem::Edge(u - 1, v - 1));
+            }
+            Some(_) => return Err(invalid(&format!("unexpected line '{}'", line))),
+        }
+    }
+
+    size.map(|_| ()).ok_or_else(|| invalid("missing 'p' line"))
+}
+
+/// Writes the graph in DIMACS ```.col``` format, see ```parse_dimacs```, without
+/// comment lines. Edges are written in the order of ```graph.edges()```.
+pub fn write_dimacs<G: StaticGraph + ?Sized, W: Write>(graph: &G, out: W) -> io::Result<()> {
+    write_dimacs_with_meta(graph, &GraphMeta::default(), out)
+}
+
+/// Same as ```write_dimacs``` but starts with the metadata as comment lines, such
+/// that ```parse_dimacs_with_meta``` restores it.
+pub fn write_dimacs_with_meta<G: StaticGraph + ?Sized, W: Write>(
+    graph: &G,
+    meta: &GraphMeta,
+    mut out: W,
+) -> io::Result<()> {
+    meta.write_comments(&mut out)?;
+    writeln!(
+        out,
+        "p edge {} {}",
+        graph.num_vertices(),
+        graph.edges().count()
+    )?;
+    for (u, v) in graph.edges() {
+        writeln!(out, "e {} {}", u + 1, v + 1)?;
+    }
+
+    Ok(())
+}
+
+fn number(split: Option<&str>) -> io::Result<usize> {
+    split
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("expected a number"))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimacs_parse() {
+        let input = "c comment\n\np edge 4 2\ne 1 2\ne  4   2\n";
+        let g = parse_dimacs(input.as_bytes()).unwrap();
+
+        assert_eq!(g.num_vertices(), 4);
+        assert!(g.has_edge(0, 1) && g.has_edge(3, 1));
+        assert_eq!(g.edges().count(), 2);
+    }
+
+    #[test]
+    fn dimacs_roundtrip() {
+        let g = Graph::random(30, 0.3);
+        let mut out = Vec::new();
+        write_dimacs(&g, &mut out).unwrap();
+
+        let parsed = parse_dimacs(&out[..]).unwrap();
+        assert_eq!(parsed.num_vertices(), 30);
+        assert!(g.edges().all(|(u, v)| parsed.has_edge(u, v)));
+        assert_eq!(parsed.edges().count(), g.edges().count());
+    }
+
+    #[test]
+    fn dimacs_meta_roundtrip() {
+        let input =
+            "c FILE: myciel3.col\nc SOURCE: Mycielski\nc\np edge 3 1\nc chromatic: 2\ne 1 2\n";
+        let (g, meta) = parse_dimacs_with_meta(input.as_bytes()).unwrap();
+
+        assert_eq!(g.edges().count(), 1);
+        assert_eq!(meta.source.as_deref(), Some("Mycielski"));
+        assert_eq!(meta.chromatic, Some(2));
+        assert_eq!(meta.comments, vec!["FILE: myciel3.col", ""]);
+
+        let mut out = Vec::new();
+        write_dimacs_with_meta(&g, &meta, &mut out).unwrap();
+        assert_eq!(parse_dimacs_with_meta(&out[..]).unwrap().1, meta);
+    }
+
+    #[test]
+    fn dimacs_malformed() {
+        for input in &[
+            "",
+            "e 1 2\np edge 2 1",
+            "p edge 2 1\ne 0 1",
+            "p edge 2 1\ne 1 3",
+            "p edge 2 1\ne 1",
+            "p edge x 1",
+            "p edge 2 1\np edge 3 1",
+            "x",
+        ] {
+            let err = parse_dimacs(input.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+
+        assert!(parse_dimacs_limited("p edge 11 0".as_bytes(), 10).is_err());
+        assert!(parse_dimacs(&b"p edge 2 0\n\xff"[..]).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Experimental graph stored in a memory mapped file.
+//!
+//! ```DiskGraph``` keeps the arrays of the compressed sparse row format, see
+//! ```Csr```, in a file which the operating system pages in on access and evicts
+//! under memory pressure. Graphs larger than the main memory can therefore be
+//! loaded, iterated and colored, as long as the algorithm accesses them in a
+//! streaming fashion.
+//!
+//! # Performance
+//! Iterating the vertices in order and their neighbors reads the file sequentially
+//! and is close to the speed of ```Csr``` once the pages are cached. Random access,
+//! as by ```has_edge``` or by orders visiting the vertices out of order, may read a
+//! page from disk per access and is orders of magnitude slower when the graph does
+//! not fit into memory. Entries are stored as 64 bit integers independent of the
+//! platform, such that files are twice as large as needed on 32 bit machines.
+//!
+//! # Supported algorithms
+//! The graph is read-only, adding an edge panics. Algorithms building a copy of the
+//! graph or keeping per edge state need memory proportional to the graph and do not
+//! benefit. Suited are those with per vertex state only, such as ```rs_coloring```,
+//! which visits the vertices in order, ```lf_coloring``` and ```dsatur_coloring```,
+//! as well as ```check_coloring``` and ```max_degree```.
+//!
+//! # Format
+//! The file starts with the magic bytes ```GRAMLCSR```, followed by the number of
+//! vertices ```n``` and the number of entries ```m``` of the neighbor array. Then
+//! follow the ```n + 1``` offsets and the ```m``` neighbors, all little endian
+//! ```u64```. The neighbors of every vertex are sorted.
+
+use memmap2::{Mmap, MmapMut};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::graph::StaticGraph;
+
+const MAGIC: &[u8; 8] = b"GRAMLCSR";
+const HEADER: usize = 24;
+const WORD: usize = 8;
+
+/// Read-only graph in compressed sparse row format stored in a memory mapped file.
+/// See the module documentation for the supported algorithms.
+#[derive(Debug)]
+pub struct DiskGraph {
+    map: Mmap,
+    n: usize,
+}
+
+impl DiskGraph {
+    /// Writes the graph to a new file at ```path``` and maps it.
+    /// The graph is read twice in vertex order, once for the degrees and once for
+    /// the neighbors, such that only the neighbors of a single vertex are held in
+    /// memory. The source may thus itself be larger than the main memory, e.g. a
+    /// ```DiskGraph``` or a graph computed on the fly.
+    pub fn create<G: StaticGraph + ?Sized>(graph: &G, path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(&path)?);
+        write_csr(graph, &mut out)?;
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+
+        Self::open(path)
+    }
+
+    /// Maps a graph previously written by ```create```.
+    /// Reports files which are not in the format of the module documentation or
+    /// have neighbors outside of the graph as ```InvalidData``` errors, for which the
+    /// whole file is read once. The file must not be modified while it is mapped.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // Safety: the map is read-only and the file is not modified while mapped,
+        // as documented
+        let map = unsafe { Mmap::map(&file)? };
+
+        Self::from_map(map)
+    }
+
+    /// Returns the number of neighbors of ```v```.
+    pub fn degree(&self, v: usize) -> usize {
+        if v < self.n {
+            self.offset(v + 1) - self.offset(v)
+        } else {
+            0
+        }
+    }
+
+    fn from_map(map: Mmap) -> io::Result<Self> {
+        if map.len() < HEADER || &map[..8] != MAGIC {
+            return Err(invalid("not a graph file"));
+        }
+
+        let word = |i: usize| u64::from_le_bytes(map[i..i + WORD].try_into().unwrap()) as usize;
+        let (n, m) = (word(8), word(16));
+        let expected = n
+            .checked_add(m)
+            .and_then(|words| words.checked_add(1))
+            .and_then(|words| words.checked_mul(WORD))
+            .and_then(|bytes| bytes.checked_add(HEADER));
+        if expected != Some(map.len()) {
+            return Err(invalid("size of the graph file does not match its header"));
+        }
+
+        let graph = Self { map, n };
+        if graph.offset(0) != 0
+            || graph.offset(n) != m
+            || (0..n).any(|v| graph.offset(v) > graph.offset(v + 1))
+        {
+            return Err(invalid("offsets of the graph file are not increasing"));
+        }
+        if (0..m).any(|i| graph.target(i) >= n) {
+            return Err(invalid("neighbor outside of the graph"));
+        }
+
+        Ok(graph)
+    }
+
+    fn word(&self, i: usize) -> usize {
+        let start = HEADER + i * WORD;
+        u64::from_le_bytes(self.map[start..start + WORD].try_into().unwrap()) as usize
+    }
+
+    fn offset(&self, v: usize) -> usize {
+        self.word(v)
+    }
+
+    fn target(&self, i: usize) -> usize {
+        self.word(self.n + 1 + i)
+    }
+
+    /// Range of the neighbors of ```v``` in the neighbor array.
+    fn range(&self, v: usize) -> std::ops::Range<usize> {
+        if v < self.n {
+            self.offset(v)..self.offset(v + 1)
+        } else {
+            0..0
+        }
+    }
+
+    /// Graph copied into anonymous memory instead of a file.
+    fn in_memory<G: StaticGraph + ?Sized>(graph: &G) -> io::Result<Self> {
+        let n = graph.num_vertices();
+        let m = graph
+            .vertices()
+            .map(|v| graph.neighbors(v).count())
+            .sum::<usize>();
+        let mut map = MmapMut::map_anon(HEADER + (n + 1 + m) * WORD)?;
+        write_csr(graph, &mut map[..])?;
+
+        Self::from_map(map.make_read_only()?)
+    }
+}
+
+impl StaticGraph for DiskGraph {
+    /// Constructs a graph of ```n``` isolated vertices in anonymous memory.
+    fn with_capacity(n: usize) -> Self {
+        Self::in_memory(&crate::graph::Csr::with_capacity(n)).unwrap()
+    }
+
+    /// Copies the graph into anonymous memory, which is not backed by a file. Use
+    /// ```DiskGraph::create``` for graphs larger than the main memory.
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        Self::in_memory(graph).unwrap()
+    }
+
+    /// Queries whether an edge exists by binary search in the neighbors of ```u```.
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        let range = self.range(u);
+        let (mut low, mut high) = (range.start, range.end);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.target(mid) {
+                t if t < v => low = mid + 1,
+                t if t > v => high = mid,
+                _ => return true,
+            }
+        }
+
+        false
+    }
+
+    fn add_edge(&mut self, _: usize, _: usize) {
+        panic!("edges can not be added to a disk graph");
+    }
+
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
+        Box::new((0..self.n).flat_map(move |u| {
+            self.neighbors(u)
+                .filter(move |&v| u < v)
+                .map(move |v| (u, v))
+        }))
+    }
+
+    fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
+        Box::new(self.range(v).map(move |i| self.target(i)))
+    }
+
+    fn max_degree(&self) -> usize {
+        (0..self.n).map(|v| self.degree(v)).max().unwrap_or(0)
+    }
+}
+
+/// Writes the graph in the format of the module documentation.
+fn write_csr<G: StaticGraph + ?Sized, W: Write>(graph: &G, mut out: W) -> io::Result<()> {
+    let n = graph.num_vertices();
+    let degrees = || graph.vertices().map(|v| graph.neighbors(v).count());
+
+    out.write_all(MAGIC)?;
+    out.write_all(&(n as u64).to_le_bytes())?;
+    out.write_all(&(degrees().sum::<usize>() as u64).to_le_bytes())?;
+
+    let mut offset = 0;
+    out.write_all(&0u64.to_le_bytes())?;
+    for degree in degrees() {
+        offset += degree;
+        out.write_all(&(offset as u64).to_le_bytes())?;
+    }
+
+    let mut neighbors = Vec::new();
+    for v in graph.vertices() {
+        neighbors.clear();
+        neighbors.extend(graph.neighbors(v));
+        neighbors.sort_unstable();
+        for &u in &neighbors {
+            out.write_all(&(u as u64).to_le_bytes())?;
+        }
+    }
+
+    out.flush()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, lf_coloring, rs_coloring};
+    use crate::graph::*;
+    use std::{env, fs, process};
+
+    #[test]
+    fn disk_graph_roundtrip() {
+        let mut g = AdjList::random(200, 0.1);
+        g.add_edge(205, 3);
+        let path = env::temp_dir().join(format!("graml-disk-{}.csr", process::id()));
+
+        let disk = DiskGraph::create(&g, &path).unwrap();
+        let reopened = DiskGraph::open(&path).unwrap();
+        let csr = Csr::from_graph(&g);
+
+        assert_eq!(reopened.num_vertices(), 206);
+        assert_eq!(
+            reopened.edges().collect::<Vec<_>>(),
+            csr.edges().collect::<Vec<_>>()
+        );
+        assert!(g
+            .edges()
+            .all(|(u, v)| disk.has_edge(u, v) && disk.has_edge(v, u)));
+        assert!(!disk.has_edge(204, 3) && !disk.has_edge(300, 3));
+        assert_eq!(disk.max_degree(), g.max_degree());
+        assert_eq!(rs_coloring(&reopened), rs_coloring(&g));
+        assert!(check_coloring(&disk, &lf_coloring(&disk)));
+
+        let memory = DiskGraph::from_graph(&disk);
+        assert_eq!(memory.edges().count(), g.edges().count());
+        assert_eq!(DiskGraph::with_capacity(4).vertices().count(), 4);
+
+        drop((disk, reopened));
+        let mut bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+        assert_eq!(
+            DiskGraph::open(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        // Last neighbor beyond the 206 vertices
+        let last = bytes.len() - 8;
+        bytes[last..].copy_from_slice(&206u64.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+        assert_eq!(
+            DiskGraph::open(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
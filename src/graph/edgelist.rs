@@ -1,4 +1,6 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashSet;
+use std::hash::BuildHasherDefault;
 use std::iter::Iterator;
 
 use crate::graph::StaticGraph;
@@ -8,34 +10,45 @@ use crate::graph::StaticGraph;
 /// the vertices is captured. Multiple edges and self edges are also disallowed.
 ///
 /// Vertices and edges may not be removed.
+///
+/// The edges are hashed with fixed keys, such that iteration order only depends on
+/// the inserted edges. Algorithms give the same result on every run.
 #[derive(Debug, Clone)]
 pub struct EdgeList {
-    edges: HashSet<(usize, usize)>,
+    edges: Edges,
     n: usize,
 }
 
+type Edges = HashSet<(usize, usize), BuildHasherDefault<DefaultHasher>>;
+
 impl EdgeList {
     /// Constructs a new empty graph
     pub fn new() -> Self {
         Self {
-            edges: HashSet::new(),
+            edges: Edges::default(),
             n: 0,
         }
     }
 }
 
+impl Default for EdgeList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StaticGraph for EdgeList {
     /// Constructs a new graph with capacity for ```n``` vertices.
     fn with_capacity(n: usize) -> Self {
         // Only implemented for compatibility, not very much to do here
         Self {
-            edges: HashSet::new(),
+            edges: Edges::default(),
             n,
         }
     }
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self {
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
         let mut g = Self::new();
         for (u, v) in graph.edges() {
             g.add_edge(u, v);
@@ -71,7 +84,7 @@ impl StaticGraph for EdgeList {
     }
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a> {
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
         Box::new(self.edges.iter().cloned())
     }
 
@@ -81,7 +94,7 @@ impl StaticGraph for EdgeList {
     }
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a> {
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
         Box::new(
             self.edges().filter(move |(a, b)| *a == v || *b == v).map(
                 move |(a, b)| {
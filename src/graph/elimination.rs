@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use crate::graph::StaticGraph;
+
+/// Mutable view of a graph for vertex elimination.
+/// Vertices are removed by masking them out, such that the graph is copied only
+/// once. Eliminating a vertex additionally turns its remaining neighbors into a
+/// clique by adding fill edges.
+#[derive(Debug, Clone)]
+pub struct EliminationGame {
+    adj: Vec<HashSet<usize>>,
+    alive: Vec<bool>,
+    remaining: usize,
+}
+
+impl EliminationGame {
+    /// Starts the game on a graph with all vertices present.
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let adj = graph
+            .vertices()
+            .map(|v| graph.neighbors(v).collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+        let n = adj.len();
+
+        Self {
+            adj,
+            alive: vec![true; n],
+            remaining: n,
+        }
+    }
+
+    /// Number of vertices of the original graph.
+    pub fn num_vertices(&self) -> usize {
+        self.alive.len()
+    }
+
+    /// Number of vertices not yet removed.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    pub fn is_alive(&self, v: usize) -> bool {
+        self.alive[v]
+    }
+
+    /// Returns an iterator over the vertices not yet removed.
+    pub fn alive<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        (0..self.alive.len()).filter(move |&v| self.alive[v])
+    }
+
+    /// Returns the number of remaining neighbors of ```v```.
+    pub fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    /// Returns the remaining neighbors of ```v```, including fill edges.
+    pub fn neighbors(&self, v: usize) -> &HashSet<usize> {
+        &self.adj[v]
+    }
+
+    /// Whether the remaining neighbors of ```v``` form a clique.
+    pub fn is_simplicial(&self, v: usize) -> bool {
+        self.fill_in(v) == 0
+    }
+
+    /// Returns the number of fill edges eliminating ```v``` would add.
+    pub fn fill_in(&self, v: usize) -> usize {
+        let neighbors = self.adj[v].iter().collect::<Vec<_>>();
+        let mut missing = 0;
+
+        for (i, &a) in neighbors.iter().enumerate() {
+            for &b in &neighbors[i + 1..] {
+                if !self.adj[*a].contains(b) {
+                    missing += 1;
+                }
+            }
+        }
+
+        missing
+    }
+
+    /// Removes ```v``` without adding fill edges.
+    /// Returns its remaining neighbors at the time of removal, sorted.
+    pub fn remove(&mut self, v: usize) -> Vec<usize> {
+        assert!(self.alive[v], "vertex {} was already removed", v);
+
+        self.alive[v] = false;
+        self.remaining -= 1;
+
+        let mut neighbors = std::mem::take(&mut self.adj[v])
+            .into_iter()
+            .collect::<Vec<_>>();
+        neighbors.sort_unstable();
+
+        for &u in &neighbors {
+            self.adj[u].remove(&v);
+        }
+
+        neighbors
+    }
+
+    /// Eliminates ```v```, connecting its remaining neighbors to a clique.
+    /// Returns its remaining neighbors at the time of elimination, sorted.
+    pub fn eliminate(&mut self, v: usize) -> Vec<usize> {
+        let neighbors = self.remove(v);
+
+        for (i, &a) in neighbors.iter().enumerate() {
+            for &b in &neighbors[i + 1..] {
+                self.adj[a].insert(b);
+                self.adj[b].insert(a);
+            }
+        }
+
+        neighbors
+    }
+
+    /// Eliminates all remaining vertices, each time choosing a vertex minimizing
+    /// ```key```, ties broken by index. Returns the elimination ordering together with
+    /// the neighbors of each vertex at the time of its elimination.
+    pub fn eliminate_by<K: Ord>(
+        &mut self,
+        mut key: impl FnMut(&Self, usize) -> K,
+    ) -> Vec<(usize, Vec<usize>)> {
+        let mut order = Vec::with_capacity(self.remaining);
+
+        while self.remaining > 0 {
+            let v = self.alive().min_by_key(|&v| key(self, v)).unwrap();
+            let neighbors = self.eliminate(v);
+            order.push((v, neighbors));
+        }
+
+        order
+    }
+}
+
+/// Returns the simplicial vertices of the graph, whose neighbors form a clique.
+pub fn simplicial_vertices<G: StaticGraph + ?Sized>(graph: &G) -> Vec<usize> {
+    let game = EliminationGame::new(graph);
+
+    game.alive().filter(|&v| game.is_simplicial(v)).collect()
+}
+
+/// Returns a perfect elimination ordering if the graph is chordal.
+/// In such an ordering every vertex is simplicial among the vertices following it.
+/// Every chordal graph has a simplicial vertex, and stays chordal after removing it.
+pub fn perfect_elimination_ordering<G: StaticGraph + ?Sized>(graph: &G) -> Option<Vec<usize>> {
+    let mut game = EliminationGame::new(graph);
+    let mut order = Vec::with_capacity(game.num_vertices());
+
+    while game.remaining() > 0 {
+        let v = game.alive().find(|&v| game.is_simplicial(v))?;
+        game.remove(v);
+        order.push(v);
+    }
+
+    Some(order)
+}
+
+/// Whether every cycle of length at least four in the graph has a chord.
+pub fn is_chordal<G: StaticGraph + ?Sized>(graph: &G) -> bool {
+    perfect_elimination_ordering(graph).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn simplicial_path() {
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, i + 1);
+        }
+
+        assert_eq!(simplicial_vertices(&g), vec![0, 5]);
+        assert_eq!(simplicial_vertices(&AdjList::complete(4)), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn chordal_cycles() {
+        let mut g = AdjList::new();
+        for i in 0..6 {
+            g.add_edge(i, (i + 1) % 6);
+        }
+
+        assert!(!is_chordal(&g));
+
+        // Triangulating the cycle from vertex 0
+        for i in 2..5 {
+            g.add_edge(0, i);
+        }
+        let order = perfect_elimination_ordering(&g).unwrap();
+
+        assert_eq!(order.len(), 6);
+        assert!(is_chordal(&AdjList::complete(5)));
+    }
+
+    #[test]
+    fn eliminate_fill() {
+        let mut g = AdjList::new();
+        for i in 0..4 {
+            g.add_edge(i, (i + 1) % 4);
+        }
+        let mut game = EliminationGame::new(&g);
+
+        assert_eq!(game.fill_in(0), 1);
+        assert_eq!(game.eliminate(0), vec![1, 3]);
+        assert!(game.neighbors(1).contains(&3));
+        assert_eq!(game.remaining(), 3);
+        assert!(game.alive().all(|v| game.is_simplicial(v)));
+    }
+}
@@ -0,0 +1,338 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{MaskedGraph, StaticGraph};
+
+/// Parameters a generated instance was constructed with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Construction {
+    /// Leighton graph with ```cliques[j]``` cliques of size ```j + 2``` planted.
+    Leighton {
+        n: usize,
+        k: usize,
+        target_edges: usize,
+        cliques: Vec<usize>,
+    },
+    /// Flat graph whose edges are spread evenly over all pairs of color classes.
+    Flat {
+        n: usize,
+        k: usize,
+        target_edges: usize,
+    },
+}
+
+/// Generated graph with a planted coloring.
+#[derive(Debug, Clone)]
+pub struct Planted<G> {
+    pub graph: G,
+    /// Coloring with ```colors``` colors the graph was constructed around.
+    pub coloring: Vec<usize>,
+    /// Number of planted colors. Equals the chromatic number for Leighton graphs
+    /// and is an upper bound on it for flat graphs.
+    pub colors: usize,
+    pub construction: Construction,
+}
+
+impl<G: StaticGraph> Planted<G> {
+    /// Returns how many more colors than planted ```num_colors``` uses.
+    pub fn gap(&self, num_colors: usize) -> isize {
+        num_colors as isize - self.colors as isize
+    }
+}
+
+/// Constructs a graph from the stochastic block model.
+/// The vertices are partitioned into consecutive blocks of the given ```sizes```.
+/// Two vertices are connected with probability ```p_in``` if they lie in the same
+/// block and with probability ```p_out``` otherwise.
+/// With ```p_in = 0``` this plants a coloring with one color per block.
+pub fn stochastic_block_model<G: StaticGraph, R: Rng>(
+    sizes: &[usize],
+    p_in: f32,
+    p_out: f32,
+    rng: &mut R,
+) -> G {
+    let block = sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(b, &size)| std::iter::repeat_n(b, size))
+        .collect::<Vec<_>>();
+
+    G::random_with(
+        block.len(),
+        |u, v| if block[u] == block[v] { p_in } else { p_out },
+        rng,
+    )
+}
+
+/// Constructs a Leighton graph on ```n``` vertices with chromatic number ```k```.
+/// Vertices are randomly assigned to ```k``` classes and cliques of sizes ```2..=k```
+/// are planted, each using at most one vertex per class. Every clique size receives
+/// the same share of roughly ```target_edges``` edges, and at least one ```k```-clique is
+/// planted such that ```k``` colors are required. Requires ```2 <= k <= n```.
+pub fn leighton<G: StaticGraph, R: Rng>(
+    n: usize,
+    k: usize,
+    target_edges: usize,
+    rng: &mut R,
+) -> Planted<G> {
+    assert!(2 <= k && k <= n, "Leighton graphs require 2 <= k <= n");
+
+    let coloring = planted_classes(n, k, rng);
+    let mut classes = vec![Vec::new(); k];
+    for (v, &c) in coloring.iter().enumerate() {
+        classes[c].push(v);
+    }
+
+    let share = target_edges / (k - 1);
+    let cliques = (2..=k)
+        .map(|j| {
+            let count = share / (j * (j - 1) / 2);
+            if j == k {
+                count.max(1)
+            } else {
+                count
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut graph = G::with_capacity(n);
+    let mut order = (0..k).collect::<Vec<_>>();
+
+    for (j, &count) in (2..=k).zip(&cliques) {
+        for _ in 0..count {
+            order.shuffle(rng);
+            let clique = order[..j]
+                .iter()
+                .map(|&c| *classes[c].choose(rng).unwrap())
+                .collect::<Vec<_>>();
+
+            for (i, &u) in clique.iter().enumerate() {
+                for &v in &clique[i + 1..] {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+    }
+
+    Planted {
+        graph,
+        coloring,
+        colors: k,
+        construction: Construction::Leighton {
+            n,
+            k,
+            target_edges,
+            cliques,
+        },
+    }
+}
+
+/// Constructs a flat graph on ```n``` vertices with a planted ```k```-coloring.
+/// Every pair of color classes receives the same number of the roughly
+/// ```target_edges``` edges, and each edge joins the vertices of lowest degree in the
+/// two classes that are not yet adjacent. This keeps the degrees nearly uniform,
+/// which deprives greedy heuristics of any structure to exploit.
+/// Requires ```1 <= k <= n```.
+pub fn flat<G: StaticGraph, R: Rng>(
+    n: usize,
+    k: usize,
+    target_edges: usize,
+    rng: &mut R,
+) -> Planted<G> {
+    assert!(1 <= k && k <= n, "Flat graphs require 1 <= k <= n");
+
+    let coloring = planted_classes(n, k, rng);
+    let mut classes = vec![Vec::new(); k];
+    for (v, &c) in coloring.iter().enumerate() {
+        classes[c].push(v);
+    }
+
+    let mut graph = G::with_capacity(n);
+    let mut degree = vec![0; n];
+    let pairs = k * (k - 1) / 2;
+
+    for a in 0..k {
+        for b in a + 1..k {
+            let edges = (target_edges / pairs.max(1)).min(classes[a].len() * classes[b].len());
+
+            for _ in 0..edges {
+                // Random order breaks ties between vertices of equal degree
+                classes[a].shuffle(rng);
+                classes[b].shuffle(rng);
+
+                let candidate = classes[a]
+                    .iter()
+                    .flat_map(|&u| classes[b].iter().map(move |&v| (u, v)))
+                    .filter(|&(u, v)| !graph.has_edge(u, v))
+                    .min_by_key(|&(u, v)| (degree[u] + degree[v], degree[u].max(degree[v])));
+
+                if let Some((u, v)) = candidate {
+                    graph.add_edge(u, v);
+                    degree[u] += 1;
+                    degree[v] += 1;
+                }
+            }
+        }
+    }
+
+    Planted {
+        graph,
+        coloring,
+        colors: k,
+        construction: Construction::Flat { n, k, target_edges },
+    }
+}
+
+/// Randomizes the graph while keeping the degree of every vertex, which yields null
+/// models separating effects of the structure from those of the degree sequence.
+/// Returns the number of swaps performed.
+///
+/// Tries ```swaps``` double edge swaps, each replacing two random edges ```(a, b)```
+/// and ```(c, d)``` by ```(a, d)``` and ```(c, b)```. Swaps which would create a self
+/// edge or an edge already present are skipped. Edges are removed from the view
+/// and added to it, which copies a borrowed base graph on the first swap.
+pub fn rewire_preserving_degrees<G: StaticGraph, R: Rng>(
+    graph: &mut MaskedGraph<G>,
+    swaps: usize,
+    rng: &mut R,
+) -> usize {
+    let mut edges = graph.edges().collect::<Vec<_>>();
+    if edges.len() < 2 {
+        return 0;
+    }
+
+    let mut performed = 0;
+    for _ in 0..swaps {
+        let (i, j) = (rng.gen_range(0, edges.len()), rng.gen_range(0, edges.len()));
+        let (a, b) = edges[i];
+        let (c, d) = if rng.gen() {
+            edges[j]
+        } else {
+            (edges[j].1, edges[j].0)
+        };
+
+        // Also rejects two edges sharing a vertex, as one new edge exists already
+        if a == d || c == b || graph.has_edge(a, d) || graph.has_edge(c, b) {
+            continue;
+        }
+
+        graph.remove_edge(a, b);
+        graph.remove_edge(c, d);
+        graph.add_edge(a, d);
+        graph.add_edge(c, b);
+        edges[i] = (a, d);
+        edges[j] = (c, b);
+        performed += 1;
+    }
+
+    performed
+}
+
+/// Randomly assigns ```n``` vertices to ```k``` classes of equal size up to one.
+fn planted_classes<R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    let mut coloring = (0..n).map(|v| v % k).collect::<Vec<_>>();
+    coloring.shuffle(rng);
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{check_coloring, chromatic_number};
+    use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sbm_planted_partition() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let sizes = [10, 20, 5];
+
+        let g: AdjList = stochastic_block_model(&sizes, 0.0, 0.5, &mut rng);
+        let blocks = (0..35)
+            .map(|v| {
+                if v < 10 {
+                    0
+                } else if v < 30 {
+                    1
+                } else {
+                    2
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(g.num_vertices(), 35);
+        assert!(g.edges().count() > 0);
+        assert!(check_coloring(&g, &blocks));
+    }
+
+    #[test]
+    fn leighton_chromatic() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let p: Planted<AdjList> = leighton(20, 5, 60, &mut rng);
+
+        assert!(check_coloring(&p.graph, &p.coloring));
+        assert_eq!(chromatic_number(&p.graph), 5);
+        assert_eq!(p.gap(7), 2);
+
+        match p.construction {
+            Construction::Leighton { ref cliques, .. } => {
+                assert_eq!(cliques.len(), 4);
+                assert!(cliques[3] >= 1);
+            }
+            _ => panic!("Wrong construction"),
+        }
+    }
+
+    #[test]
+    fn flat_planted() {
+        let mut rng = StdRng::seed_from_u64(4);
+
+        let p: Planted<AdjList> = flat(30, 3, 120, &mut rng);
+        let degrees = p
+            .graph
+            .vertices()
+            .map(|v| p.graph.neighbors(v).count())
+            .collect::<Vec<_>>();
+
+        assert!(check_coloring(&p.graph, &p.coloring));
+        assert_eq!(p.graph.edges().count(), 120);
+        assert!(degrees.iter().max().unwrap() - degrees.iter().min().unwrap() <= 1);
+    }
+
+    #[test]
+    fn sbm_seeded() {
+        let a: AdjList = stochastic_block_model(&[15, 15], 0.8, 0.1, &mut StdRng::seed_from_u64(2));
+        let b: AdjList = stochastic_block_model(&[15, 15], 0.8, 0.1, &mut StdRng::seed_from_u64(2));
+
+        assert_eq!(a.edges().collect::<Vec<_>>(), b.edges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rewire_keeps_degrees() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let g = AdjList::random(60, 0.1);
+        let mut rewired = MaskedGraph::new(&g);
+
+        let performed = rewire_preserving_degrees(&mut rewired, 500, &mut rng);
+
+        assert!(performed > 100);
+        assert!(g
+            .vertices()
+            .all(|v| rewired.degree(v) == g.neighbors(v).count()));
+        assert!(g
+            .vertices()
+            .all(|v| rewired.neighbors(v).count() == rewired.degree(v)));
+        assert!(rewired.edges().any(|(u, v)| !g.has_edge(u, v)));
+
+        let mut star = AdjList::new();
+        star.add_edge(0, 1);
+        star.add_edge(0, 2);
+        assert_eq!(
+            rewire_preserving_degrees(&mut MaskedGraph::new(&star), 10, &mut rng),
+            0
+        );
+    }
+}
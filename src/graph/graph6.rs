@@ -0,0 +1,160 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::graph::{Graph, StaticGraph};
+
+/// Parses a single graph in graph6 format. ([Specification](https://users.cecs.anu.edu.au/~bdm/data/formats.txt))
+///
+/// The line starts with the number of vertices ```n```, followed by the upper
+/// triangle of the adjacency matrix in column order, packed into printable
+/// characters of six bits each. An optional ```>>graph6<<``` header is skipped.
+///
+/// Lines too short for the number of vertices are reported as ```InvalidData```
+/// errors before the graph is allocated.
+pub fn parse_graph6(line: &str) -> io::Result<Graph> {
+    parse_graph6_limited(line, usize::MAX)
+}
+
+/// Same as ```parse_graph6``` but rejects graphs with more than ```max_vertices```
+/// vertices before allocating them.
+pub(crate) fn parse_graph6_limited(line: &str, max_vertices: usize) -> io::Result<Graph> {
+    let line = line.trim_end();
+    let line = line.strip_prefix(">>graph6<<").unwrap_or(line);
+    let bytes = line.as_bytes();
+
+    if bytes.iter().any(|&b| !(63..=126).contains(&b)) {
+        return Err(invalid("graph6 line contains invalid characters"));
+    }
+
+    let (n, data) = match bytes {
+        [126, 126, rest @ ..] if rest.len() >= 6 => (decode(&rest[..6]), &rest[6..]),
+        [126, rest @ ..] if rest.len() >= 3 => (decode(&rest[..3]), &rest[3..]),
+        [b, rest @ ..] if *b != 126 => (usize::from(b - 63), rest),
+        _ => return Err(invalid("graph6 line is missing the number of vertices")),
+    };
+
+    if n > max_vertices {
+        return Err(invalid("too many vertices"));
+    }
+
+    let pairs = n
+        .checked_mul(n.saturating_sub(1))
+        .ok_or_else(|| invalid("too many vertices"))?
+        / 2;
+    if data.len().saturating_mul(6) < pairs {
+        return Err(invalid("graph6 line is too short"));
+    }
+
+    let mut g = Graph::with_capacity(n);
+    let mut k = 0;
+    for v in 1..n {
+        for u in 0..v {
+            let bit = (data[k / 6] - 63) >> (5 - k % 6) & 1;
+            if bit == 1 {
+                g.add_edge(u, v);
+            }
+            k += 1;
+        }
+    }
+
+    Ok(g)
+}
+
+/// Calls ```f``` on every graph of a file containing one graph6 graph per line.
+/// Reads from standard input if ```path``` is ```-```. Empty lines are skipped.
+/// Graphs are parsed one at a time, such that arbitrarily large streams can be
+/// processed.
+pub fn for_each_graph6_line(path: impl AsRef<Path>, mut f: impl FnMut(Graph)) -> io::Result<()> {
+    let reader: Box<dyn BufRead> = if path.as_ref() == Path::new("-") {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        f(parse_graph6(&line)?);
+    }
+
+    Ok(())
+}
+
+/// Decodes a big-endian number of six bit characters.
+fn decode(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &b| (acc << 6) | usize::from(b - 63))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn graph6_path() {
+        // Path on 5 vertices
+        let g = parse_graph6("DQc").unwrap();
+
+        assert_eq!(g.num_vertices(), 5);
+        assert_eq!(g.edges().count(), 4);
+        assert!(g.has_edge(0, 2));
+        assert!(g.has_edge(0, 4));
+        assert!(g.has_edge(1, 3));
+        assert!(g.has_edge(3, 4));
+    }
+
+    #[test]
+    fn graph6_complete() {
+        let g = parse_graph6(">>graph6<<C~").unwrap();
+
+        assert_eq!(g.num_vertices(), 4);
+        assert_eq!(g.edges().count(), 6);
+    }
+
+    #[test]
+    fn graph6_large_header() {
+        // 63 vertices require the long form of the vertex count
+        let mut line = vec![126, 63, 63, 126];
+        line.extend(vec![63; (63 * 62 / 2usize).div_ceil(6)]);
+        let g = parse_graph6(std::str::from_utf8(&line).unwrap()).unwrap();
+
+        assert_eq!(g.num_vertices(), 63);
+        assert_eq!(g.edges().count(), 0);
+    }
+
+    #[test]
+    fn graph6_invalid() {
+        assert!(parse_graph6("").is_err());
+        assert!(parse_graph6("D").is_err());
+        assert!(parse_graph6("D\u{7}c").is_err());
+
+        // The vertex count of the long header overflows the number of pairs
+        let err = parse_graph6("~~~~~~~~").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(parse_graph6_limited("~?@?", 10).is_err());
+    }
+
+    #[test]
+    fn graph6_stream() {
+        let path = env::temp_dir().join(format!("graml-stream-{}.g6", process::id()));
+        fs::write(&path, "A_\n\nC~\nDQc\n").unwrap();
+
+        let mut edges = Vec::new();
+        for_each_graph6_line(&path, |g| edges.push(g.edges().count())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(edges, vec![1, 6, 4]);
+    }
+}
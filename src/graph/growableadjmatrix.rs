@@ -63,6 +63,12 @@ impl GrowableAdjMatrix {
     }
 }
 
+impl Default for GrowableAdjMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StaticGraph for GrowableAdjMatrix {
     /// Constructs a new graph with capacity for ```n``` vertices.
     fn with_capacity(n: usize) -> Self {
@@ -76,7 +82,7 @@ impl StaticGraph for GrowableAdjMatrix {
     }
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self {
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
         let mut g = Self::with_capacity(graph.vertices().count());
         for (u, v) in graph.edges() {
             g.add_edge(u, v);
@@ -125,7 +131,7 @@ impl StaticGraph for GrowableAdjMatrix {
     }
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a> {
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
         Box::new(
             self.adj
                 .iter()
@@ -146,7 +152,7 @@ impl StaticGraph for GrowableAdjMatrix {
     }
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a> {
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
         Box::new(
             self.edges().filter(move |(a, b)| *a == v || *b == v).map(
                 move |(a, b)| {
@@ -25,6 +25,12 @@ impl Hybrid {
     }
 }
 
+impl Default for Hybrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StaticGraph for Hybrid {
     /// Constructs a new graph with capacity for ```n``` vertices.
     fn with_capacity(n: usize) -> Self {
@@ -35,7 +41,7 @@ impl StaticGraph for Hybrid {
     }
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self {
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
         Self {
             el: EdgeList::from_graph(graph),
             al: AdjList::from_graph(graph),
@@ -58,7 +64,7 @@ impl StaticGraph for Hybrid {
     }
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a> {
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
         // Faster in edge list
         self.el.edges()
     }
@@ -71,7 +77,7 @@ impl StaticGraph for Hybrid {
     }
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a> {
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
         // Faster in adjacency list
         self.al.neighbors(v)
     }
@@ -0,0 +1,102 @@
+use crate::graph::StaticGraph;
+
+/// Hypergraph whose edges are sets of any number of vertices, such as the exams
+/// taken by one student. Edges with fewer than two vertices are dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hypergraph {
+    n: usize,
+    edges: Vec<Vec<usize>>,
+}
+
+impl Hypergraph {
+    /// Constructs a hypergraph with ```n``` vertices and no edges.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds an edge containing ```members```, growing the vertex set if necessary.
+    pub fn add_edge(&mut self, members: &[usize]) {
+        let mut edge = members.to_vec();
+        edge.sort_unstable();
+        edge.dedup();
+
+        if let Some(&max) = edge.last() {
+            self.n = self.n.max(max + 1);
+        }
+        if edge.len() > 1 {
+            self.edges.push(edge);
+        }
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the edges, each with its members in increasing order.
+    pub fn edges(&self) -> &[Vec<usize>] {
+        &self.edges
+    }
+
+    /// Returns for every vertex the indices of the edges containing it.
+    pub fn incidence(&self) -> Vec<Vec<usize>> {
+        let mut incidence = vec![Vec::new(); self.n];
+        for (i, edge) in self.edges.iter().enumerate() {
+            for &v in edge {
+                incidence[v].push(i);
+            }
+        }
+
+        incidence
+    }
+
+    /// Returns the graph in which the members of every edge form a clique.
+    /// Its colorings are the strong colorings of the hypergraph, in which all
+    /// members of an edge have different colors.
+    pub fn clique_expansion<G: StaticGraph>(&self) -> G {
+        let mut graph = G::with_capacity(self.n);
+        for edge in &self.edges {
+            for (i, &u) in edge.iter().enumerate() {
+                for &v in &edge[i + 1..] {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the hypergraph with one edge per edge of the graph.
+    pub fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let mut h = Self::new(graph.num_vertices());
+        for (u, v) in graph.edges() {
+            h.add_edge(&[u, v]);
+        }
+
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn hypergraph_expansion() {
+        let mut h = Hypergraph::new(3);
+        h.add_edge(&[4, 1, 2, 1]);
+        h.add_edge(&[0, 1]);
+        h.add_edge(&[3]);
+
+        assert_eq!(h.num_vertices(), 5);
+        assert_eq!(h.edges(), &[vec![1, 2, 4], vec![0, 1]]);
+        assert_eq!(h.incidence()[1], vec![0, 1]);
+
+        let g: AdjList = h.clique_expansion();
+        assert_eq!(g.edges().count(), 4);
+        assert_eq!(Hypergraph::from_graph(&g).edges().len(), 4);
+    }
+}
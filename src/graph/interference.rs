@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::graph::{Labeled, StaticGraph};
+
+/// Builds the interference graph of a set of live ranges.
+/// Each live range is given by a label and the program points at which it is live.
+/// Two live ranges interfere, i.e. are connected by an edge, if they share a
+/// program point. Vertex ```i``` corresponds to the ```i```th live range.
+pub fn interference_graph<G, L, P, I>(ranges: impl IntoIterator<Item = (L, I)>) -> Labeled<G, L>
+where
+    G: StaticGraph,
+    P: Hash + Eq,
+    I: IntoIterator<Item = P>,
+{
+    let mut labels = Vec::new();
+    let mut live: HashMap<P, Vec<usize>> = HashMap::new();
+
+    for (v, (label, points)) in ranges.into_iter().enumerate() {
+        labels.push(label);
+
+        for p in points {
+            let at = live.entry(p).or_default();
+            // Points may be listed more than once
+            if at.last() != Some(&v) {
+                at.push(v);
+            }
+        }
+    }
+
+    let mut graph = G::with_capacity(labels.len());
+    for at in live.values() {
+        for (i, &u) in at.iter().enumerate() {
+            for &v in &at[i + 1..] {
+                graph.add_edge(u, v);
+            }
+        }
+    }
+
+    Labeled::new(graph, labels)
+}
+
+/// Builds the interference graph of live ranges given as half-open intervals of
+/// program points. Two intervals interfere if they overlap.
+/// Runs a sweep over the sorted interval boundaries instead of enumerating points.
+pub fn interval_graph<G, L>(intervals: impl IntoIterator<Item = (L, Range<usize>)>) -> Labeled<G, L>
+where
+    G: StaticGraph,
+{
+    let (labels, intervals): (Vec<_>, Vec<_>) = intervals.into_iter().unzip();
+    let mut graph = G::with_capacity(labels.len());
+
+    let mut order = (0..intervals.len())
+        .filter(|&v| !intervals[v].is_empty())
+        .collect::<Vec<_>>();
+    order.sort_by_key(|&v| intervals[v].start);
+
+    // Intervals containing the current start point
+    let mut active: Vec<usize> = Vec::new();
+    for v in order {
+        let start = intervals[v].start;
+        active.retain(|&u| intervals[u].end > start);
+
+        for &u in &active {
+            graph.add_edge(u, v);
+        }
+        active.push(v);
+    }
+
+    Labeled::new(graph, labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn interference_points() {
+        let ranges = vec![
+            ("a", vec![0, 1, 2]),
+            ("b", vec![2, 3]),
+            ("c", vec![4, 4]),
+            ("d", vec![1, 3]),
+        ];
+
+        let g: Labeled<AdjList, _> = interference_graph(ranges);
+
+        assert_eq!(g.graph.num_vertices(), 4);
+        assert_eq!(g.graph.edges().count(), 3);
+        assert!(g.graph.has_edge(0, 1));
+        assert!(g.graph.has_edge(0, 3));
+        assert!(g.graph.has_edge(1, 3));
+        assert_eq!(g.graph.neighbors(2).count(), 0);
+        assert_eq!(g.label(2), &"c");
+        assert_eq!(g.vertex(&"d"), Some(3));
+    }
+
+    #[test]
+    fn interval_matches_points() {
+        let intervals = vec![
+            (0, 0..5),
+            (1, 3..8),
+            (2, 5..6),
+            (3, 8..10),
+            (4, 2..2),
+            (5, 0..20),
+        ];
+        let points = intervals
+            .iter()
+            .map(|(l, r)| (*l, r.clone()))
+            .collect::<Vec<_>>();
+
+        let a: Labeled<AdjList, _> = interval_graph(intervals);
+        let b: Labeled<AdjList, _> = interference_graph(points);
+
+        let mut ea = a.graph.edges().collect::<Vec<_>>();
+        let mut eb = b.graph.edges().collect::<Vec<_>>();
+        ea.sort();
+        eb.sort();
+
+        assert_eq!(ea, eb);
+        assert_eq!(a.labels, b.labels);
+    }
+
+    #[test]
+    fn interference_assignment() {
+        let g: Labeled<AdjList, _> = interval_graph(vec![("x", 0..2), ("y", 1..3), ("z", 2..4)]);
+        let coloring = vec![0, 1, 0];
+
+        let assignment = g.assignment(&coloring).collect::<Vec<_>>();
+
+        assert_eq!(assignment, vec![(&"x", 0), (&"y", 1), (&"z", 0)]);
+    }
+}
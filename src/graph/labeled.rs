@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::graph::StaticGraph;
+
+/// Graph whose vertices carry labels, such as variable names or event ids.
+/// Vertex ```v``` of ```graph``` is labeled ```labels[v]```.
+#[derive(Debug, Clone)]
+pub struct Labeled<G, L> {
+    pub graph: G,
+    pub labels: Vec<L>,
+}
+
+impl<G: StaticGraph, L> Labeled<G, L> {
+    /// Attaches labels to the vertices of a graph.
+    /// Panics if the number of labels differs from the number of vertices.
+    pub fn new(graph: G, labels: Vec<L>) -> Self {
+        assert_eq!(graph.num_vertices(), labels.len());
+
+        Self { graph, labels }
+    }
+
+    /// Returns the label of vertex ```v```.
+    pub fn label(&self, v: usize) -> &L {
+        &self.labels[v]
+    }
+
+    /// Pairs the color of every vertex in ```coloring``` with its label.
+    pub fn assignment<'a>(
+        &'a self,
+        coloring: &'a [usize],
+    ) -> impl Iterator<Item = (&'a L, usize)> + 'a {
+        self.labels.iter().zip(coloring.iter().cloned())
+    }
+}
+
+impl<G, L: Hash + Eq> Labeled<G, L> {
+    /// Returns the vertex carrying ```label```, if any.
+    pub fn vertex(&self, label: &L) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    /// Returns a map from labels to vertices.
+    pub fn index(&self) -> HashMap<&L, usize> {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(v, l)| (l, v))
+            .collect()
+    }
+}
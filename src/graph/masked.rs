@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+
+use crate::graph::StaticGraph;
+
+/// View of a graph in which vertices and edges can be removed and restored without
+/// copying. Removed vertices keep their index and remain in ```vertices```, as
+/// isolated vertices, such that every algorithm can color the view. They are
+/// skipped by ```alive_vertices```. The degree of each vertex among the remaining ones is cached and
+/// updated on every removal and restoration.
+///
+/// The base graph is borrowed until an edge is added to the view, at which point
+/// it is copied.
+#[derive(Debug, Clone)]
+pub struct MaskedGraph<'a, G> {
+    base: Base<'a, G>,
+    mask: Vec<u64>,
+    /// Removed edges, with the smaller vertex first.
+    hidden: HashSet<(usize, usize)>,
+    degrees: Vec<usize>,
+    alive: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Base<'a, G> {
+    Borrowed(&'a G),
+    Owned(G),
+}
+
+impl<'a, G: StaticGraph> MaskedGraph<'a, G> {
+    /// Constructs a view of ```graph``` with all vertices present.
+    pub fn new(graph: &'a G) -> Self {
+        Self::with_base(Base::Borrowed(graph))
+    }
+
+    fn with_base(base: Base<'a, G>) -> Self {
+        let graph = match base {
+            Base::Borrowed(g) => g,
+            Base::Owned(ref g) => g,
+        };
+        let n = graph.num_vertices();
+
+        let mut mask = vec![!0; n.div_ceil(64)];
+        if n % 64 != 0 {
+            mask[n / 64] = (1 << (n % 64)) - 1;
+        }
+        let degrees = graph
+            .vertices()
+            .map(|v| graph.neighbors(v).count())
+            .collect();
+
+        Self {
+            base,
+            mask,
+            hidden: HashSet::new(),
+            degrees,
+            alive: n,
+        }
+    }
+
+    fn base(&self) -> &G {
+        match self.base {
+            Base::Borrowed(g) => g,
+            Base::Owned(ref g) => g,
+        }
+    }
+
+    /// Whether ```v``` is present in the view.
+    pub fn is_alive(&self, v: usize) -> bool {
+        v < self.degrees.len() && self.mask[v / 64] & (1 << (v % 64)) != 0
+    }
+
+    /// Number of vertices present in the view.
+    pub fn num_alive(&self) -> usize {
+        self.alive
+    }
+
+    /// Returns an iterator over the vertices present in the view.
+    pub fn alive_vertices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.degrees.len()).filter(move |&v| self.is_alive(v))
+    }
+
+    /// Returns the number of neighbors of ```v``` present in the view.
+    pub fn degree(&self, v: usize) -> usize {
+        if self.is_alive(v) {
+            self.degrees[v]
+        } else {
+            0
+        }
+    }
+
+    /// Removes ```v``` from the view. Has no effect if it was already removed.
+    pub fn remove_vertex(&mut self, v: usize) {
+        if !self.is_alive(v) {
+            return;
+        }
+
+        let neighbors = self.neighbors(v).collect::<Vec<_>>();
+        self.mask[v / 64] &= !(1 << (v % 64));
+        self.alive -= 1;
+
+        for u in neighbors {
+            self.degrees[u] -= 1;
+        }
+    }
+
+    /// Restores a previously removed vertex ```v```. Has no effect if it is present.
+    pub fn restore_vertex(&mut self, v: usize) {
+        if v >= self.degrees.len() || self.is_alive(v) {
+            return;
+        }
+
+        self.mask[v / 64] |= 1 << (v % 64);
+        self.alive += 1;
+
+        let neighbors = self.neighbors(v).collect::<Vec<_>>();
+        for &u in &neighbors {
+            self.degrees[u] += 1;
+        }
+        self.degrees[v] = neighbors.len();
+    }
+
+    /// Removes the edge between ```u``` and ```v``` from the view, independently of
+    /// the vertices. Has no effect if there is no such edge or it was already removed.
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        if u >= self.degrees.len() || v >= self.degrees.len() || !self.base().has_edge(u, v) {
+            return;
+        }
+
+        if self.hidden.insert((u.min(v), u.max(v))) && self.is_alive(u) && self.is_alive(v) {
+            self.degrees[u] -= 1;
+            self.degrees[v] -= 1;
+        }
+    }
+
+    /// Restores a previously removed edge. Has no effect if it was not removed.
+    pub fn restore_edge(&mut self, u: usize, v: usize) {
+        if self.hidden.remove(&(u.min(v), u.max(v))) && self.is_alive(u) && self.is_alive(v) {
+            self.degrees[u] += 1;
+            self.degrees[v] += 1;
+        }
+    }
+
+    fn is_hidden(&self, u: usize, v: usize) -> bool {
+        !self.hidden.is_empty() && self.hidden.contains(&(u.min(v), u.max(v)))
+    }
+
+    /// Returns the vertex of smallest degree present in the view, ties broken by index.
+    pub fn min_degree_vertex(&self) -> Option<usize> {
+        self.alive_vertices().min_by_key(|&v| self.degrees[v])
+    }
+}
+
+impl<'a, G: StaticGraph> StaticGraph for MaskedGraph<'a, G> {
+    /// Constructs a view of a new graph with capacity for ```n``` vertices.
+    fn with_capacity(n: usize) -> Self {
+        Self::with_base(Base::Owned(G::with_capacity(n)))
+    }
+
+    /// Constructs a view of a copy of another ```StaticGraph``` implementor.
+    fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+        Self::with_base(Base::Owned(G::from_graph(graph)))
+    }
+
+    /// Queries whether an edge between two present vertices exists.
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.is_alive(u) && self.is_alive(v) && self.base().has_edge(u, v) && !self.is_hidden(u, v)
+    }
+
+    /// Adds an edge to the underlying graph, copying it if it is borrowed.
+    /// Vertices beyond the current range are added as present.
+    /// The edge is only visible if both vertices are present. Adding a removed edge
+    /// restores it.
+    fn add_edge(&mut self, u: usize, v: usize) {
+        if let Base::Borrowed(g) = self.base {
+            self.base = Base::Owned(G::from_graph(g));
+        }
+
+        let new = !self.base().has_edge(u, v) || self.hidden.remove(&(u.min(v), u.max(v)));
+        if let Base::Owned(ref mut g) = self.base {
+            g.add_edge(u, v);
+        }
+
+        let n = self.base().num_vertices();
+        for w in self.degrees.len()..n {
+            if w / 64 == self.mask.len() {
+                self.mask.push(0);
+            }
+            self.mask[w / 64] |= 1 << (w % 64);
+            self.degrees.push(0);
+            self.alive += 1;
+        }
+
+        if new && self.is_alive(u) && self.is_alive(v) {
+            self.degrees[u] += 1;
+            self.degrees[v] += 1;
+        }
+    }
+
+    /// Returns an iterator over all the edges between present vertices.
+    fn edges<'b>(&'b self) -> Box<dyn Iterator<Item = (usize, usize)> + 'b> {
+        Box::new(
+            self.base().edges().filter(move |&(u, v)| {
+                self.is_alive(u) && self.is_alive(v) && !self.is_hidden(u, v)
+            }),
+        )
+    }
+
+    /// Returns the number of vertices of the underlying graph, including removed ones.
+    fn num_vertices(&self) -> usize {
+        self.degrees.len()
+    }
+
+    /// Returns an iterator over the present neighbors of ```v```, none if ```v``` is
+    /// removed.
+    fn neighbors<'b>(&'b self, v: usize) -> Box<dyn Iterator<Item = usize> + 'b> {
+        if !self.is_alive(v) {
+            return Box::new(std::iter::empty());
+        }
+
+        Box::new(
+            self.base()
+                .neighbors(v)
+                .filter(move |&u| self.is_alive(u) && !self.is_hidden(u, v)),
+        )
+    }
+
+    /// Returns the maximum degree among present vertices from the cache.
+    fn max_degree(&self) -> usize {
+        self.alive_vertices()
+            .map(|v| self.degrees[v])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn masked_remove_restore() {
+        let g = AdjList::complete(70);
+        let mut m = MaskedGraph::new(&g);
+
+        m.remove_vertex(3);
+        m.remove_vertex(65);
+        m.remove_vertex(65);
+
+        assert_eq!(m.num_alive(), 68);
+        assert_eq!(m.degree(0), 67);
+        assert_eq!(m.degree(3), 0);
+        assert!(!m.has_edge(3, 4));
+        assert_eq!(m.alive_vertices().count(), 68);
+        assert_eq!(m.vertices().count(), 70);
+        assert_eq!(m.neighbors(3).count(), 0);
+        assert_eq!(m.edges().count(), 68 * 67 / 2);
+
+        m.restore_vertex(3);
+
+        assert_eq!(m.degree(3), 68);
+        assert_eq!(m.degree(0), 68);
+        assert_eq!(m.max_degree(), 68);
+    }
+
+    #[test]
+    fn masked_degrees_match() {
+        let g = AdjList::random(100, 0.2);
+        let mut m = MaskedGraph::new(&g);
+
+        for v in (0..100).step_by(3) {
+            m.remove_vertex(v);
+        }
+        for v in (0..100).step_by(9) {
+            m.restore_vertex(v);
+        }
+
+        for v in m.vertices() {
+            assert_eq!(m.degree(v), m.neighbors(v).count());
+        }
+    }
+
+    #[test]
+    fn masked_remove_edges() {
+        let g = AdjList::complete(4);
+        let mut m = MaskedGraph::new(&g);
+
+        m.remove_edge(1, 0);
+        m.remove_vertex(2);
+        m.remove_edge(2, 3);
+
+        assert!(!m.has_edge(0, 1));
+        assert_eq!(m.edges().count(), 2);
+        assert_eq!(m.degree(0), 1);
+
+        m.restore_vertex(2);
+        assert_eq!(m.degree(2), 2);
+        assert_eq!(m.degree(3), 2);
+
+        m.restore_edge(0, 1);
+        m.restore_edge(2, 3);
+        for v in m.vertices() {
+            assert_eq!(m.degree(v), 3);
+        }
+    }
+
+    #[test]
+    fn masked_add_edge_copies() {
+        let g = AdjList::complete(3);
+        let mut m = MaskedGraph::new(&g);
+
+        m.remove_vertex(0);
+        m.add_edge(2, 4);
+
+        assert_eq!(m.num_vertices(), 5);
+        assert_eq!(m.num_alive(), 4);
+        assert_eq!(m.degree(2), 2);
+        assert_eq!(g.num_vertices(), 3);
+    }
+
+    #[test]
+    fn masked_colorable() {
+        use crate::coloring::{check_coloring, ColoringAlgo};
+
+        let g = AdjList::random(60, 0.3);
+        let mut m = MaskedGraph::new(&g);
+        m.remove_vertex(7);
+        m.remove_vertex(59);
+
+        for &algo in &[
+            ColoringAlgo::RS,
+            ColoringAlgo::CS,
+            ColoringAlgo::LF,
+            ColoringAlgo::SL,
+            ColoringAlgo::SDO,
+            ColoringAlgo::Genetic,
+            ColoringAlgo::IG,
+        ] {
+            let c = algo.color(&m);
+            assert_eq!(c.len(), 60);
+            assert!(check_coloring(&m, &c));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+/// Provenance of an instance, kept in the comment lines of its DIMACS file.
+///
+/// Comments of the form ```key: value``` with a known key are parsed into the
+/// fields: ```source```, ```chromatic``` (also ```chromatic number```, ```best known```
+/// or ```chi```) and ```seed```, with the key in any case. All other comments are
+/// kept in order in ```comments```, as are known keys whose value does not parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphMeta {
+    /// Where the instance comes from.
+    pub source: Option<String>,
+    /// Best known upper bound on the chromatic number.
+    pub chromatic: Option<usize>,
+    /// Seed the instance was generated from.
+    pub seed: Option<u64>,
+    /// Comment lines without the leading ```c```.
+    pub comments: Vec<String>,
+}
+
+impl GraphMeta {
+    /// Whether no metadata and no comments are present.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Adds the text of a comment line, parsing it if it has a known key.
+    pub fn add_comment(&mut self, comment: &str) {
+        let comment = comment.trim();
+
+        if let Some((key, value)) = comment.split_once(':') {
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "source" if !value.is_empty() => {
+                    self.source = Some(value.to_string());
+                    return;
+                }
+                "chromatic" | "chromatic number" | "best known" | "chi" => {
+                    if let Ok(k) = value.parse() {
+                        self.chromatic = Some(k);
+                        return;
+                    }
+                }
+                "seed" => {
+                    if let Ok(seed) = value.parse() {
+                        self.seed = Some(seed);
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.comments.push(comment.to_string());
+    }
+
+    /// Writes the metadata as DIMACS comment lines, the known keys first, such
+    /// that ```add_comment``` on every line restores it.
+    pub fn write_comments<W: Write>(&self, mut out: W) -> io::Result<()> {
+        if let Some(source) = &self.source {
+            writeln!(out, "c source: {}", source)?;
+        }
+        if let Some(k) = self.chromatic {
+            writeln!(out, "c chromatic: {}", k)?;
+        }
+        if let Some(seed) = self.seed {
+            writeln!(out, "c seed: {}", seed)?;
+        }
+        for comment in &self.comments {
+            if comment.is_empty() {
+                writeln!(out, "c")?;
+            } else {
+                writeln!(out, "c {}", comment)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_comments() {
+        let mut meta = GraphMeta::default();
+        assert!(meta.is_empty());
+
+        for line in &[
+            "FILE: myciel3.col",
+            "SOURCE: Michael Trick",
+            "Best Known: 4",
+            "seed: many",
+            "",
+        ] {
+            meta.add_comment(line);
+        }
+
+        assert_eq!(meta.source.as_deref(), Some("Michael Trick"));
+        assert_eq!(meta.chromatic, Some(4));
+        assert_eq!(meta.seed, None);
+        assert_eq!(meta.comments, vec!["FILE: myciel3.col", "seed: many", ""]);
+    }
+}
@@ -1,22 +1,96 @@
 mod adjlist;
 mod adjmatrix;
+mod anonymize;
+mod backend;
+mod cograph;
+mod contraction;
+#[cfg(feature = "parallel")]
+mod convert;
+mod csr;
+mod decomposition;
+pub(crate) mod dimacs;
+#[cfg(feature = "disk")]
+mod disk;
 mod edgelist;
+mod elimination;
+mod generators;
+pub(crate) mod graph6;
 mod growableadjmatrix;
 mod hybrid;
+mod hypergraph;
+mod interference;
+mod labeled;
+mod masked;
+mod meta;
+mod observed;
+mod planarity;
+mod products;
+mod shared;
+mod weighted;
 
-use rand::random;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use rand::{thread_rng, Rng};
+
+use crate::analysis::Invariants;
 
 pub use self::adjlist::AdjList;
 pub use self::adjmatrix::AdjMatrix;
+pub use self::anonymize::{anonymize, parse_mapping, Anonymized};
+pub use self::backend::{Backend, DynGraph};
+pub use self::cograph::{cotree, is_cograph, Cotree};
+pub use self::contraction::contract_vertices;
+#[cfg(feature = "parallel")]
+pub use self::convert::{convert_parallel, FromChunks, NeighborChunk};
+pub use self::csr::{Csr, Csr32, IndexedCsr, VertexId};
+pub use self::decomposition::{tree_decomposition, Elimination, TreeDecomposition};
+pub use self::dimacs::{
+    load_graph, load_graph_with_meta, parse_dimacs, parse_dimacs_with_meta, write_dimacs,
+    write_dimacs_with_meta,
+};
+#[cfg(feature = "disk")]
+pub use self::disk::DiskGraph;
 pub use self::edgelist::EdgeList;
+pub use self::elimination::{
+    is_chordal, perfect_elimination_ordering, simplicial_vertices, EliminationGame,
+};
+pub use self::generators::{
+    flat, leighton, rewire_preserving_degrees, stochastic_block_model, Construction, Planted,
+};
+pub use self::graph6::{for_each_graph6_line, parse_graph6};
 pub use self::growableadjmatrix::GrowableAdjMatrix;
 pub use self::hybrid::Hybrid;
+pub use self::hypergraph::Hypergraph;
+pub use self::interference::{interference_graph, interval_graph};
+pub use self::labeled::Labeled;
+pub use self::masked::MaskedGraph;
+pub use self::meta::GraphMeta;
+pub use self::observed::{DegreeCache, GraphObserver, Observed};
+pub use self::planarity::is_planar;
+pub use self::products::{cartesian_product, strong_product, tensor_product};
+pub use self::weighted::EdgeWeights;
 
 pub type Graph = AdjList;
 
+// All graph types can be shared between threads, e.g. by the parallel algorithms
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<AdjList>();
+    assert_send_sync::<AdjMatrix>();
+    assert_send_sync::<Csr>();
+    assert_send_sync::<Csr32>();
+    #[cfg(feature = "disk")]
+    assert_send_sync::<DiskGraph>();
+    assert_send_sync::<EdgeList>();
+    assert_send_sync::<GrowableAdjMatrix>();
+    assert_send_sync::<Hybrid>();
+    assert_send_sync::<MaskedGraph<Csr>>();
+    assert_send_sync::<Labeled<Csr, String>>();
+    assert_send_sync::<EliminationGame>();
+    assert_send_sync::<Observed<Csr, DegreeCache>>();
+    assert_send_sync::<std::sync::Arc<Csr>>();
+    assert_send_sync::<DynGraph>();
+};
+
 /// The trait to be implemented by any graph datastructure.
 /// This requires that graphs can be instantiated with a given capacity
 /// and adding edges must work properly if the vertices of the edge are within
@@ -27,12 +101,22 @@ pub type Graph = AdjList;
 /// Vertices should be in consecutive order. That is if ```add_edge(1,2)``` is called,
 /// the ```vertices``` iterator will yield ```[0, 1, 2]```. With ```0``` being an isolated
 /// vertex.
-pub trait StaticGraph: Sized {
+/// # Unsized graphs
+/// Algorithms only reading a graph accept ```&G``` with ```G: StaticGraph + ?Sized```.
+/// Constructors are only available for sized implementors.
+/// # Thread safety
+/// All graph types of this crate are ```Send + Sync```, such that one graph can be
+/// colored by several threads at once, e.g. shared through an ```Arc```.
+pub trait StaticGraph {
     /// Constructs a new graph with capacity for ```n``` vertices.
-    fn with_capacity(n: usize) -> Self;
+    fn with_capacity(n: usize) -> Self
+    where
+        Self: Sized;
 
     /// Construct an instance of this type from another ```StaticGraph``` implementor
-    fn from_graph<G: StaticGraph>(graph: &G) -> Self;
+    fn from_graph<G: StaticGraph + ?Sized>(graph: &G) -> Self
+    where
+        Self: Sized;
 
     /// Queries whether an edge exists in the graph.
     fn has_edge(&self, u: usize, v: usize) -> bool;
@@ -44,16 +128,16 @@ pub trait StaticGraph: Sized {
     fn add_edge(&mut self, u: usize, v: usize);
 
     /// Returns an iterator over all the edges in the graph.
-    fn edges<'a>(&'a self) -> Box<Iterator<Item = (usize, usize)> + 'a>;
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a>;
 
     /// Returns the number of vertices in the graph.
     fn num_vertices(&self) -> usize;
 
     /// Returns an iterator over all the neighboring vertices in the graph.
-    fn neighbors<'a>(&'a self, v: usize) -> Box<Iterator<Item = usize> + 'a>;
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a>;
 
     /// Returns an iterator over all the vertices in the graph.
-    fn vertices<'a>(&'a self) -> Box<Iterator<Item = usize> + 'a> {
+    fn vertices<'a>(&'a self) -> Box<dyn Iterator<Item = usize> + 'a> {
         let n = self.num_vertices();
         if n == 0 {
             Box::new(std::iter::empty())
@@ -64,12 +148,24 @@ pub trait StaticGraph: Sized {
 
     /// Constructs a random graph with ```n``` vertices where each undirected
     /// edge has probability ```p``` of occuring in the graph.
-    fn random(n: usize, p: f32) -> Self {
+    fn random(n: usize, p: f32) -> Self
+    where
+        Self: Sized,
+    {
+        Self::random_with(n, |_, _| p, &mut thread_rng())
+    }
+
+    /// Constructs a random graph with ```n``` vertices where the undirected edge
+    /// ```(u, v)``` with ```u < v``` has probability ```p(u, v)``` of occuring in the graph.
+    fn random_with<R: Rng>(n: usize, mut p: impl FnMut(usize, usize) -> f32, rng: &mut R) -> Self
+    where
+        Self: Sized,
+    {
         let mut g = Self::with_capacity(n);
 
         for u in 0..n {
             for v in u + 1..n {
-                if random::<f32>() < p {
+                if rng.gen::<f32>() < p(u, v) {
                     g.add_edge(u, v);
                 }
             }
@@ -80,7 +176,10 @@ pub trait StaticGraph: Sized {
 
     /// Constructs a complete graph of size ```n```.
     /// Every combination of vertices is connected by an edge.
-    fn complete(n: usize) -> Self {
+    fn complete(n: usize) -> Self
+    where
+        Self: Sized,
+    {
         let mut g = Self::with_capacity(n);
         for u in 0..n {
             for v in (u + 1)..n {
@@ -100,67 +199,20 @@ pub trait StaticGraph: Sized {
 
         max
     }
-}
-
-/// Load a graph from file in DIMACS ```.col``` format. ([Specification](http://lcs.ios.ac.cn/~caisw/Resource/about_DIMACS_graph_format.txt))
-///
-/// A line may start with ```c```, ```p``` or ```e```.
-/// ```c``` indicates a comment line and is ignored.
-/// ```p``` lists the parameters of the graph as such:
-/// ```p edge n m```
-/// where 'edge' is simply written out, indicating that the following lines will each encode an edge,
-/// n is the number of vertices in the graph and m is the number of edges.
-/// This line must appear before any ```e``` lines.
-/// Lastly, ```e``` lines appear as:
-/// ```e u v```
-/// where u and v are vertex ids in [1,n] (n inclusive).
-pub fn load_graph(name: impl AsRef<Path>) -> std::io::Result<Graph> {
-    let file = File::open(name)?;
-    let mut graph = None;
-
-    for line in BufReader::new(file).lines() {
-        // Should always be valid UTF-8
-        let line = line.unwrap();
-
-        if line.starts_with('c') {
-            continue;
-        }
-
-        if line.starts_with('p') {
-            let splits = line.split(' ').collect::<Vec<_>>();
-            //assert_eq!(splits[1], "edge".to_string());
-
-            let n = splits[2].parse::<usize>().unwrap();
-            let _m = splits[3].parse::<usize>().unwrap();
-
-            graph = Some(Graph::with_capacity(n));
-            continue;
-        }
 
-        if line.starts_with('e') {
-            let splits = line.split(' ').collect::<Vec<_>>();
-            let u = splits[1].parse::<usize>().unwrap();
-            let v = splits[2].parse::<usize>().unwrap();
-
-            // Shift everything down as vertices are in [1,n]
-            if let Some(ref mut graph) = graph {
-                graph.add_edge(u - 1, v - 1);
-            } else {
-                panic!("'e' line before 'p' line");
-            }
-
-            continue;
-        }
-
-        panic!("Unexpected line '{}'", line);
+    /// Returns the invariants cached with the graph, if it is wrapped in ```Cached```.
+    /// Algorithms needing degrees, degeneracy or a clique take them from here
+    /// instead of recomputing them on every call.
+    fn invariants(&self) -> Option<&Invariants> {
+        None
     }
-
-    Ok(graph.unwrap())
 }
 
 #[cfg(test)]
 mod tests {
     use crate::graph::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     use std::collections::HashSet;
 
     #[test]
@@ -217,6 +269,75 @@ mod tests {
         tester.run();
     }
 
+    /// Colorings must not depend on the instance of the graph.
+    fn assert_deterministic<G: StaticGraph>(base: &AdjList) {
+        use crate::coloring::*;
+
+        let runs = (0..3)
+            .map(|_| {
+                let g = G::from_graph(base);
+                let mut fixed = vec![0; g.num_vertices()];
+                fix_coloring(&g, &mut fixed);
+
+                (
+                    g.edges().collect::<Vec<_>>(),
+                    lf_coloring(&g),
+                    sl_coloring(&g),
+                    sdo_coloring(&g),
+                    color_balanced(&g),
+                    fixed,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert!(runs.iter().all(|run| *run == runs[0]));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let base = AdjList::random(60, 0.3);
+
+        assert_deterministic::<AdjList>(&base);
+        assert_deterministic::<AdjMatrix>(&base);
+        assert_deterministic::<Csr>(&base);
+        assert_deterministic::<Csr32>(&base);
+        assert_deterministic::<EdgeList>(&base);
+        assert_deterministic::<GrowableAdjMatrix>(&base);
+        assert_deterministic::<Hybrid>(&base);
+    }
+
+    #[test]
+    fn test_csr() {
+        let tester = GraphTester::<Csr>::new();
+        tester.run();
+    }
+
+    #[test]
+    fn test_csr32() {
+        let tester = GraphTester::<Csr32>::new();
+        tester.run();
+    }
+
+    #[test]
+    fn test_csr_csr32() {
+        let tester = GraphInteropTester::<Csr, Csr32>::new();
+        tester.run();
+
+        let g = Csr::random(100, 0.3);
+        let narrow = Csr32::from_graph(&g);
+        assert_eq!(
+            narrow.memory() - (g.num_vertices() + 1) * 8,
+            g.edges().count() * 2 * 4
+        );
+        assert!(!narrow.has_edge(0, 1 << 40));
+    }
+
+    #[test]
+    fn test_csr_al() {
+        let tester = GraphInteropTester::<Csr, AdjList>::new();
+        tester.run();
+    }
+
     #[test]
     fn test_hybrid_al() {
         let tester = GraphInteropTester::<Hybrid, AdjList>::new();
@@ -261,6 +382,7 @@ mod tests {
             self.random();
             self.random_full();
             self.random_empty();
+            self.random_with();
             self.complete();
         }
 
@@ -439,6 +561,16 @@ mod tests {
             assert_eq!(num_edges, 0);
         }
 
+        fn random_with(&self) {
+            let mut rng = StdRng::seed_from_u64(0);
+
+            // Only edges between vertices of equal parity
+            let g = G::random_with(50, |u, v| ((u + v + 1) % 2) as f32, &mut rng);
+
+            assert_eq!(g.edges().count(), 2 * (25 * 24) / 2);
+            assert!(g.edges().all(|(u, v)| u % 2 == v % 2));
+        }
+
         fn complete(&self) {
             let n = 50;
             let g = G::complete(n);
@@ -463,11 +595,11 @@ mod tests {
         }
 
         fn run(&self) {
-            self.from_graph();
-            self.from_graph_rev();
+            self.conversion();
+            self.conversion_rev();
         }
 
-        fn from_graph(&self) {
+        fn conversion(&self) {
             let g1 = G1::random(100, 0.5);
             let g2 = G2::from_graph(&g1);
 
@@ -477,7 +609,7 @@ mod tests {
             assert_eq!(edges1, edges2);
         }
 
-        fn from_graph_rev(&self) {
+        fn conversion_rev(&self) {
             let g1 = G2::random(100, 0.5);
             let g2 = G1::from_graph(&g1);
 
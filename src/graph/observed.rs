@@ -0,0 +1,240 @@
+use crate::analysis::Invariants;
+use crate::graph::StaticGraph;
+
+/// Receives notifications about mutations of an ```Observed``` graph.
+pub trait GraphObserver {
+    /// Called after the new edge ```(u, v)``` was added to the graph.
+    /// Not called for edges which already existed.
+    fn on_edge_added(&mut self, u: usize, v: usize);
+
+    /// Returns the invariants if the observer caches them.
+    fn invariants(&self) -> Option<&Invariants> {
+        None
+    }
+}
+
+impl GraphObserver for () {
+    fn on_edge_added(&mut self, _: usize, _: usize) {}
+}
+
+impl<A: GraphObserver, B: GraphObserver> GraphObserver for (A, B) {
+    fn on_edge_added(&mut self, u: usize, v: usize) {
+        self.0.on_edge_added(u, v);
+        self.1.on_edge_added(u, v);
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        self.0.invariants().or_else(|| self.1.invariants())
+    }
+}
+
+impl<O: GraphObserver + ?Sized> GraphObserver for Box<O> {
+    fn on_edge_added(&mut self, u: usize, v: usize) {
+        (**self).on_edge_added(u, v);
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        (**self).invariants()
+    }
+}
+
+impl<O: GraphObserver> GraphObserver for Vec<O> {
+    fn on_edge_added(&mut self, u: usize, v: usize) {
+        for observer in self {
+            observer.on_edge_added(u, v);
+        }
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        self.iter().find_map(|observer| observer.invariants())
+    }
+}
+
+/// Graph which notifies an observer of every mutation, such that derived
+/// structures stay in sync with the graph without recomputation.
+/// The observer only sees mutations made through the wrapper, it has to be
+/// initialized from ```graph``` when constructed.
+#[derive(Debug, Clone)]
+pub struct Observed<G, O> {
+    graph: G,
+    observer: O,
+}
+
+impl<G: StaticGraph, O: GraphObserver> Observed<G, O> {
+    /// Attaches ```observer``` to ```graph```.
+    pub fn new(graph: G, observer: O) -> Self {
+        Self { graph, observer }
+    }
+
+    /// The wrapped graph, mutable only through the wrapper.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// The observer of the graph.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Splits the wrapper into graph and observer.
+    pub fn into_parts(self) -> (G, O) {
+        (self.graph, self.observer)
+    }
+}
+
+impl<G: StaticGraph, O: GraphObserver + Default> StaticGraph for Observed<G, O> {
+    /// Constructs a new graph with capacity for ```n``` vertices and a default observer.
+    fn with_capacity(n: usize) -> Self {
+        Self::new(G::with_capacity(n), O::default())
+    }
+
+    /// Construct an instance of this type from another ```StaticGraph``` implementor.
+    /// The observer is notified of every edge.
+    fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+        let mut observed = Self::with_capacity(graph.num_vertices());
+        for (u, v) in graph.edges() {
+            observed.add_edge(u, v);
+        }
+
+        observed
+    }
+
+    /// Queries whether an edge exists in the graph.
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.graph.has_edge(u, v)
+    }
+
+    /// Adds an edge to the graph and notifies the observer if it is new.
+    fn add_edge(&mut self, u: usize, v: usize) {
+        if u == v || self.graph.has_edge(u, v) {
+            return;
+        }
+
+        self.graph.add_edge(u, v);
+        self.observer.on_edge_added(u, v);
+    }
+
+    /// Returns an iterator over all the edges in the graph.
+    fn edges<'a>(&'a self) -> Box<dyn Iterator<Item = (usize, usize)> + 'a> {
+        self.graph.edges()
+    }
+
+    /// Returns the number of vertices in the graph.
+    fn num_vertices(&self) -> usize {
+        self.graph.num_vertices()
+    }
+
+    /// Returns an iterator over all the neighboring vertices in the graph.
+    fn neighbors<'a>(&'a self, v: usize) -> Box<dyn Iterator<Item = usize> + 'a> {
+        self.graph.neighbors(v)
+    }
+
+    /// Returns the maximum degree of any node in the graph, from the cached
+    /// degrees if the observer caches invariants.
+    fn max_degree(&self) -> usize {
+        match self.observer.invariants() {
+            Some(invariants) => invariants.max_degree(self),
+            None => self.graph.max_degree(),
+        }
+    }
+
+    /// Returns the invariants cached by the observer, or else by the graph.
+    fn invariants(&self) -> Option<&Invariants> {
+        self.observer
+            .invariants()
+            .or_else(|| self.graph.invariants())
+    }
+}
+
+/// Observer caching the degree of every vertex.
+#[derive(Debug, Clone, Default)]
+pub struct DegreeCache {
+    degrees: Vec<usize>,
+    max: usize,
+}
+
+impl DegreeCache {
+    /// Constructs the cache for the current state of ```graph```.
+    pub fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let degrees = (0..graph.num_vertices())
+            .map(|v| graph.neighbors(v).count())
+            .collect::<Vec<_>>();
+        let max = degrees.iter().cloned().max().unwrap_or(0);
+
+        Self { degrees, max }
+    }
+
+    /// Returns the degree of ```v```.
+    pub fn degree(&self, v: usize) -> usize {
+        self.degrees.get(v).cloned().unwrap_or(0)
+    }
+
+    /// Returns the maximum degree.
+    pub fn max_degree(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the degrees of all vertices.
+    pub fn degrees(&self) -> &[usize] {
+        &self.degrees
+    }
+}
+
+impl GraphObserver for DegreeCache {
+    fn on_edge_added(&mut self, u: usize, v: usize) {
+        if self.degrees.len() <= u.max(v) {
+            self.degrees.resize(u.max(v) + 1, 0);
+        }
+
+        for &w in &[u, v] {
+            self.degrees[w] += 1;
+            self.max = self.max.max(self.degrees[w]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[derive(Default)]
+    struct Log(Vec<(usize, usize)>);
+
+    impl GraphObserver for Log {
+        fn on_edge_added(&mut self, u: usize, v: usize) {
+            self.0.push((u, v));
+        }
+    }
+
+    #[test]
+    fn observed_notifies_new_edges() {
+        let mut g = Observed::new(AdjList::with_capacity(3), Log::default());
+
+        g.add_edge(0, 1);
+        g.add_edge(1, 0);
+        g.add_edge(2, 2);
+        g.add_edge(1, 2);
+
+        assert_eq!(g.observer.0, vec![(0, 1), (1, 2)]);
+        assert_eq!(g.edges().count(), 2);
+    }
+
+    #[test]
+    fn degree_cache_in_sync() {
+        let base = AdjList::random(30, 0.2);
+        let cache = DegreeCache::new(&base);
+        let mut g = Observed::new(base, (cache, Log::default()));
+
+        for _ in 0..50 {
+            let other = AdjList::random(35, 0.05);
+            for (u, v) in other.edges() {
+                g.add_edge(u, v);
+            }
+        }
+
+        let degrees = DegreeCache::new(&g.graph);
+        assert_eq!(g.observer.0.degrees(), degrees.degrees());
+        assert_eq!(g.observer.0.max_degree(), g.graph.max_degree());
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::HashSet;
+
+use crate::graph::StaticGraph;
+
+/// Tests whether the graph is planar using the left-right planarity test
+/// as described by Brandes in "The Left-Right Planarity Test".
+///
+/// A depth first search orients the graph, after which the return edges of every
+/// tree edge are assigned to the left or right side subject to the constraints
+/// imposed by the nesting of their lowpoints. The graph is planar if this
+/// assignment succeeds. Runs in linear time and without recursion.
+pub fn is_planar<G: StaticGraph + ?Sized>(graph: &G) -> bool {
+    let n = graph.num_vertices();
+    let m = graph.edges().count();
+
+    // Euler's formula
+    if n > 2 && m > 3 * n - 6 {
+        return false;
+    }
+
+    let mut lr = LeftRight::new(graph);
+    lr.orient();
+    lr.test()
+}
+
+/// Interval of return edges on one side, from ```high``` down to ```low```.
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+/// Return edges which have to be on different sides.
+#[derive(Debug, Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+struct LeftRight {
+    adj: Vec<Vec<usize>>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<usize>>,
+    roots: Vec<usize>,
+
+    // Edges oriented by the depth first search
+    source: Vec<usize>,
+    target: Vec<usize>,
+    out: Vec<Vec<usize>>,
+
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting: Vec<usize>,
+    reference: Vec<Option<usize>>,
+    lowpt_edge: Vec<Option<usize>>,
+    stack_bottom: Vec<usize>,
+    stack: Vec<ConflictPair>,
+}
+
+impl LeftRight {
+    fn new<G: StaticGraph + ?Sized>(graph: &G) -> Self {
+        let n = graph.num_vertices();
+
+        Self {
+            adj: graph
+                .vertices()
+                .map(|v| graph.neighbors(v).collect())
+                .collect(),
+            height: vec![None; n],
+            parent_edge: vec![None; n],
+            roots: Vec::new(),
+            source: Vec::new(),
+            target: Vec::new(),
+            out: vec![Vec::new(); n],
+            lowpt: Vec::new(),
+            lowpt2: Vec::new(),
+            nesting: Vec::new(),
+            reference: Vec::new(),
+            lowpt_edge: Vec::new(),
+            stack_bottom: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Orients the edges along a depth first search and computes lowpoints and
+    /// nesting depths.
+    fn orient(&mut self) {
+        let mut oriented = HashSet::new();
+
+        for r in 0..self.adj.len() {
+            if self.height[r].is_some() {
+                continue;
+            }
+
+            self.height[r] = Some(0);
+            self.roots.push(r);
+
+            // Vertex and index of its next neighbor to visit
+            let mut dfs = vec![(r, 0)];
+
+            while let Some(&mut (v, ref mut i)) = dfs.last_mut() {
+                if *i == self.adj[v].len() {
+                    dfs.pop();
+                    if let Some(e) = self.parent_edge[v] {
+                        self.finish_edge(e);
+                    }
+                    continue;
+                }
+
+                let w = self.adj[v][*i];
+                *i += 1;
+
+                if !oriented.insert((v.min(w), v.max(w))) {
+                    continue;
+                }
+
+                let e = self.source.len();
+                let hv = self.height[v].unwrap();
+                self.source.push(v);
+                self.target.push(w);
+                self.out[v].push(e);
+                self.lowpt.push(hv);
+                self.lowpt2.push(hv);
+                self.nesting.push(0);
+
+                match self.height[w] {
+                    None => {
+                        // Tree edge, finished once the search returns from 'w'
+                        self.parent_edge[w] = Some(e);
+                        self.height[w] = Some(hv + 1);
+                        dfs.push((w, 0));
+                    }
+                    Some(hw) => {
+                        // Back edge
+                        self.lowpt[e] = hw;
+                        self.finish_edge(e);
+                    }
+                }
+            }
+        }
+
+        let m = self.source.len();
+        self.reference = vec![None; m];
+        self.lowpt_edge = vec![None; m];
+        self.stack_bottom = vec![0; m];
+    }
+
+    /// Computes the nesting depth of edge ```e``` and passes its lowpoints on to the
+    /// parent edge of its source.
+    fn finish_edge(&mut self, e: usize) {
+        let v = self.source[e];
+        let hv = self.height[v].unwrap();
+
+        self.nesting[e] = 2 * self.lowpt[e];
+        if self.lowpt2[e] < hv {
+            // Chordal
+            self.nesting[e] += 1;
+        }
+
+        if let Some(p) = self.parent_edge[v] {
+            if self.lowpt[e] < self.lowpt[p] {
+                self.lowpt2[p] = self.lowpt[p].min(self.lowpt2[e]);
+                self.lowpt[p] = self.lowpt[e];
+            } else if self.lowpt[e] > self.lowpt[p] {
+                self.lowpt2[p] = self.lowpt2[p].min(self.lowpt[e]);
+            } else {
+                self.lowpt2[p] = self.lowpt2[p].min(self.lowpt2[e]);
+            }
+        }
+    }
+
+    /// Runs the second depth first search, visiting outgoing edges in order of
+    /// nesting depth and collecting the side constraints on a stack.
+    fn test(&mut self) -> bool {
+        for v in 0..self.out.len() {
+            let nesting = &self.nesting;
+            self.out[v].sort_by_key(|&e| nesting[e]);
+        }
+
+        for r in self.roots.clone() {
+            let mut dfs = vec![(r, 0)];
+
+            while let Some(&mut (v, ref mut i)) = dfs.last_mut() {
+                if *i == self.out[v].len() {
+                    dfs.pop();
+                    if let Some(e) = self.parent_edge[v] {
+                        self.remove_back_edges(e);
+                        if !self.integrate(e) {
+                            return false;
+                        }
+                    }
+                    continue;
+                }
+
+                let ei = self.out[v][*i];
+                *i += 1;
+
+                self.stack_bottom[ei] = self.stack.len();
+                let w = self.target[ei];
+
+                if self.parent_edge[w] == Some(ei) {
+                    // Tree edge, integrated once the search returns from 'w'
+                    dfs.push((w, 0));
+                } else {
+                    // Back edge
+                    self.lowpt_edge[ei] = Some(ei);
+                    self.stack.push(ConflictPair {
+                        left: Interval::default(),
+                        right: Interval {
+                            low: Some(ei),
+                            high: Some(ei),
+                        },
+                    });
+
+                    if !self.integrate(ei) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Integrates the return edges of ```ei``` into the constraints of the parent edge
+    /// of its source.
+    fn integrate(&mut self, ei: usize) -> bool {
+        let v = self.source[ei];
+
+        if self.lowpt[ei] < self.height[v].unwrap() {
+            // Roots have no return edges, so 'v' has a parent edge
+            let e = self.parent_edge[v].unwrap();
+
+            if self.out[v][0] == ei {
+                self.lowpt_edge[e] = self.lowpt_edge[ei];
+            } else {
+                return self.add_constraints(ei, e);
+            }
+        }
+
+        true
+    }
+
+    fn add_constraints(&mut self, ei: usize, e: usize) -> bool {
+        let mut p = ConflictPair::default();
+
+        // Merge return edges of 'ei' into the right interval of 'p'
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+
+            if self.lowpt[q.right.low.unwrap()] > self.lowpt[e] {
+                if p.right.is_empty() {
+                    p.right = q.right;
+                } else {
+                    self.reference[p.right.low.unwrap()] = q.right.high;
+                }
+                p.right.low = q.right.low;
+            } else {
+                self.reference[q.right.low.unwrap()] = self.lowpt_edge[e];
+            }
+
+            if self.stack.len() == self.stack_bottom[ei] {
+                break;
+            }
+        }
+
+        // Merge conflicting return edges of previous siblings into the left interval
+        while let Some(&top) = self.stack.last() {
+            if !self.conflicting(top.left, ei) && !self.conflicting(top.right, ei) {
+                break;
+            }
+
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(q.right, ei) {
+                q.swap();
+            }
+            if self.conflicting(q.right, ei) {
+                return false;
+            }
+
+            if let Some(low) = p.right.low {
+                self.reference[low] = q.right.high;
+            }
+            if q.right.low.is_some() {
+                p.right.low = q.right.low;
+            }
+
+            if p.left.is_empty() {
+                p.left = q.left;
+            } else {
+                self.reference[p.left.low.unwrap()] = q.left.high;
+            }
+            p.left.low = q.left.low;
+        }
+
+        if !(p.left.is_empty() && p.right.is_empty()) {
+            self.stack.push(p);
+        }
+
+        true
+    }
+
+    /// Removes the back edges ending at the source of ```e```, whose subtree is done.
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.source[e];
+        let hu = self.height[u].unwrap();
+
+        while let Some(&top) = self.stack.last() {
+            if self.lowest(top) != hu {
+                break;
+            }
+            self.stack.pop();
+        }
+
+        if let Some(mut p) = self.stack.pop() {
+            // Trim left interval
+            while let Some(high) = p.left.high {
+                if self.target[high] != u {
+                    break;
+                }
+                p.left.high = self.reference[high];
+            }
+            if p.left.high.is_none() {
+                if let Some(low) = p.left.low {
+                    self.reference[low] = p.right.low;
+                    p.left.low = None;
+                }
+            }
+
+            // Trim right interval
+            while let Some(high) = p.right.high {
+                if self.target[high] != u {
+                    break;
+                }
+                p.right.high = self.reference[high];
+            }
+            if p.right.high.is_none() {
+                if let Some(low) = p.right.low {
+                    self.reference[low] = p.left.low;
+                    p.right.low = None;
+                }
+            }
+
+            self.stack.push(p);
+        }
+
+        // Side of 'e' is the side of a highest return edge
+        if self.lowpt[e] < hu {
+            if let Some(top) = self.stack.last() {
+                let (hl, hr) = (top.left.high, top.right.high);
+                self.reference[e] = match (hl, hr) {
+                    (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => hl,
+                    (Some(_), None) => hl,
+                    _ => hr,
+                };
+            }
+        }
+    }
+
+    /// Whether the interval contains a return edge higher than the lowpoint of ```b```.
+    fn conflicting(&self, interval: Interval, b: usize) -> bool {
+        !interval.is_empty() && interval.high.is_some_and(|h| self.lowpt[h] > self.lowpt[b])
+    }
+
+    /// Lowest lowpoint of the return edges in the pair.
+    fn lowest(&self, p: ConflictPair) -> usize {
+        match (p.left.low, p.right.low) {
+            (None, Some(r)) => self.lowpt[r],
+            (Some(l), None) => self.lowpt[l],
+            (Some(l), Some(r)) => self.lowpt[l].min(self.lowpt[r]),
+            (None, None) => usize::MAX,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    fn grid(w: usize, h: usize) -> AdjList {
+        let mut g = AdjList::new();
+        for y in 0..h {
+            for x in 0..w {
+                let v = y * w + x;
+                if x + 1 < w {
+                    g.add_edge(v, v + 1);
+                }
+                if y + 1 < h {
+                    g.add_edge(v, v + w);
+                }
+                if x + 1 < w && y + 1 < h {
+                    g.add_edge(v, v + w + 1);
+                }
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn planar_small() {
+        assert!(is_planar(&AdjList::new()));
+        assert!(is_planar(&AdjList::complete(4)));
+        assert!(!is_planar(&AdjList::complete(5)));
+    }
+
+    #[test]
+    fn planar_k33() {
+        let mut g = AdjList::new();
+        for u in 0..3 {
+            for v in 3..6 {
+                g.add_edge(u, v);
+            }
+        }
+
+        assert!(!is_planar(&g));
+
+        // Removing any edge makes it planar
+        let mut h = AdjList::new();
+        for (u, v) in g.edges().skip(1) {
+            h.add_edge(u, v);
+        }
+        assert!(is_planar(&h));
+    }
+
+    #[test]
+    fn planar_petersen() {
+        let mut g = AdjList::new();
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+            g.add_edge(i, i + 5);
+            g.add_edge(i + 5, (i + 2) % 5 + 5);
+        }
+
+        assert!(!is_planar(&g));
+    }
+
+    #[test]
+    fn planar_triangulated_grid() {
+        let mut g = grid(30, 30);
+
+        assert!(is_planar(&g));
+
+        // Edge across the grid creates a K5 minor together with the triangulation
+        g.add_edge(0, 899);
+        g.add_edge(29, 870);
+        assert!(!is_planar(&g));
+    }
+
+    #[test]
+    fn planar_long_path() {
+        // Deep search without recursion
+        let mut g = AdjList::new();
+        for i in 0..200_000 {
+            g.add_edge(i, i + 1);
+        }
+
+        assert!(is_planar(&g));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::graph::StaticGraph;
+
+/// Returns the Cartesian product of the graphs, in which ```(u, v)``` and ```(x, y)```
+/// are adjacent if ```u = x``` and ```v, y``` are adjacent in ```h```, or ```v = y```
+/// and ```u, x``` are adjacent in ```g```.
+/// Vertex ```(u, v)``` of the product is ```u * h.num_vertices() + v```. Its chromatic
+/// number is the maximum of the chromatic numbers of the factors.
+pub fn cartesian_product<G: StaticGraph, H: StaticGraph + ?Sized>(g: &G, h: &H) -> G {
+    product(g, h, true, false)
+}
+
+/// Returns the tensor product of the graphs, in which ```(u, v)``` and ```(x, y)```
+/// are adjacent if ```u, x``` are adjacent in ```g``` and ```v, y``` in ```h```.
+/// Vertices are numbered as in ```cartesian_product```. Its chromatic number is at
+/// most the minimum of the chromatic numbers of the factors.
+pub fn tensor_product<G: StaticGraph, H: StaticGraph + ?Sized>(g: &G, h: &H) -> G {
+    product(g, h, false, true)
+}
+
+/// Returns the strong product of the graphs, the union of the Cartesian and the
+/// tensor product. Vertices are numbered as in ```cartesian_product```.
+pub fn strong_product<G: StaticGraph, H: StaticGraph + ?Sized>(g: &G, h: &H) -> G {
+    product(g, h, true, true)
+}
+
+fn product<G: StaticGraph, H: StaticGraph + ?Sized>(
+    g: &G,
+    h: &H,
+    cartesian: bool,
+    tensor: bool,
+) -> G {
+    let m = h.num_vertices();
+    let vertex = |u: usize, v: usize| u * m + v;
+    let mut p = G::with_capacity(g.num_vertices() * m);
+
+    if cartesian {
+        for u in g.vertices() {
+            for (v, y) in h.edges() {
+                p.add_edge(vertex(u, v), vertex(u, y));
+            }
+        }
+        for (u, x) in g.edges() {
+            for v in h.vertices() {
+                p.add_edge(vertex(u, v), vertex(x, v));
+            }
+        }
+    }
+
+    if tensor {
+        for (u, x) in g.edges() {
+            for (v, y) in h.edges() {
+                p.add_edge(vertex(u, v), vertex(x, y));
+                p.add_edge(vertex(u, y), vertex(x, v));
+            }
+        }
+    }
+
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::chromatic_number;
+    use crate::graph::*;
+
+    #[test]
+    fn products() {
+        let mut cycle = AdjList::new();
+        for i in 0..5 {
+            cycle.add_edge(i, (i + 1) % 5);
+        }
+        let k3 = AdjList::complete(3);
+
+        let c: AdjList = cartesian_product(&cycle, &k3);
+        let t: AdjList = tensor_product(&cycle, &k3);
+        let s: AdjList = strong_product(&cycle, &k3);
+
+        assert_eq!(c.num_vertices(), 15);
+        assert_eq!(c.edges().count(), 5 * 3 + 3 * 5);
+        assert_eq!(t.edges().count(), 2 * 5 * 3);
+        assert_eq!(s.edges().count(), 30 + 30);
+
+        assert_eq!(chromatic_number(&c), 3);
+        assert_eq!(chromatic_number(&t), 3);
+        assert!(chromatic_number(&s) >= 6);
+    }
+}
@@ -0,0 +1,229 @@
+//! Graphs behind references and smart pointers.
+//!
+//! Algorithms take ```&G``` with ```G: StaticGraph + ?Sized```, such that a graph shared
+//! through an ```Arc``` can be colored from several threads without cloning it.
+//! The implementations here additionally allow pointers wherever a graph type is
+//! expected, e.g. as the base of a ```MaskedGraph```, and boxed graphs of different
+//! types in one collection.
+
+use std::sync::Arc;
+
+use crate::analysis::Invariants;
+use crate::graph::{AdjList, DynGraph, StaticGraph};
+
+/// Shared references are read-only views.
+/// Constructing a reference or adding edges through it panics.
+impl<G: StaticGraph + ?Sized> StaticGraph for &G {
+    fn with_capacity(_: usize) -> Self {
+        panic!("a graph reference can not be constructed");
+    }
+
+    fn from_graph<H: StaticGraph + ?Sized>(_: &H) -> Self {
+        panic!("a graph reference can not be constructed");
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        (**self).has_edge(u, v)
+    }
+
+    fn add_edge(&mut self, _: usize, _: usize) {
+        panic!("edges can not be added through a shared reference");
+    }
+
+    fn edges<'b>(&'b self) -> Box<dyn Iterator<Item = (usize, usize)> + 'b> {
+        (**self).edges()
+    }
+
+    fn num_vertices(&self) -> usize {
+        (**self).num_vertices()
+    }
+
+    fn neighbors<'b>(&'b self, v: usize) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).neighbors(v)
+    }
+
+    fn vertices<'b>(&'b self) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).vertices()
+    }
+
+    fn max_degree(&self) -> usize {
+        (**self).max_degree()
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        (**self).invariants()
+    }
+}
+
+impl<G: StaticGraph> StaticGraph for Box<G> {
+    fn with_capacity(n: usize) -> Self {
+        Box::new(G::with_capacity(n))
+    }
+
+    fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+        Box::new(G::from_graph(graph))
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        (**self).has_edge(u, v)
+    }
+
+    fn add_edge(&mut self, u: usize, v: usize) {
+        (**self).add_edge(u, v)
+    }
+
+    fn edges<'b>(&'b self) -> Box<dyn Iterator<Item = (usize, usize)> + 'b> {
+        (**self).edges()
+    }
+
+    fn num_vertices(&self) -> usize {
+        (**self).num_vertices()
+    }
+
+    fn neighbors<'b>(&'b self, v: usize) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).neighbors(v)
+    }
+
+    fn vertices<'b>(&'b self) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).vertices()
+    }
+
+    fn max_degree(&self) -> usize {
+        (**self).max_degree()
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        (**self).invariants()
+    }
+}
+
+/// Boxed graphs of any type, see ```DynGraph```. New graphs are ```AdjList```s.
+impl StaticGraph for DynGraph {
+    fn with_capacity(n: usize) -> Self {
+        Box::new(AdjList::with_capacity(n))
+    }
+
+    fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+        Box::new(AdjList::from_graph(graph))
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        (**self).has_edge(u, v)
+    }
+
+    fn add_edge(&mut self, u: usize, v: usize) {
+        (**self).add_edge(u, v)
+    }
+
+    fn edges<'b>(&'b self) -> Box<dyn Iterator<Item = (usize, usize)> + 'b> {
+        (**self).edges()
+    }
+
+    fn num_vertices(&self) -> usize {
+        (**self).num_vertices()
+    }
+
+    fn neighbors<'b>(&'b self, v: usize) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).neighbors(v)
+    }
+
+    fn vertices<'b>(&'b self) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).vertices()
+    }
+
+    fn max_degree(&self) -> usize {
+        (**self).max_degree()
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        (**self).invariants()
+    }
+}
+
+/// Adding an edge copies the graph if it is shared, see ```Arc::make_mut```.
+impl<G: StaticGraph + Clone> StaticGraph for Arc<G> {
+    fn with_capacity(n: usize) -> Self {
+        Arc::new(G::with_capacity(n))
+    }
+
+    fn from_graph<H: StaticGraph + ?Sized>(graph: &H) -> Self {
+        Arc::new(G::from_graph(graph))
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        (**self).has_edge(u, v)
+    }
+
+    fn add_edge(&mut self, u: usize, v: usize) {
+        Arc::make_mut(self).add_edge(u, v)
+    }
+
+    fn edges<'b>(&'b self) -> Box<dyn Iterator<Item = (usize, usize)> + 'b> {
+        (**self).edges()
+    }
+
+    fn num_vertices(&self) -> usize {
+        (**self).num_vertices()
+    }
+
+    fn neighbors<'b>(&'b self, v: usize) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).neighbors(v)
+    }
+
+    fn vertices<'b>(&'b self) -> Box<dyn Iterator<Item = usize> + 'b> {
+        (**self).vertices()
+    }
+
+    fn max_degree(&self) -> usize {
+        (**self).max_degree()
+    }
+
+    fn invariants(&self) -> Option<&Invariants> {
+        (**self).invariants()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+    use std::thread;
+
+    #[test]
+    fn shared_arc_threads() {
+        let g = Arc::new(Csr::random(100, 0.2));
+
+        let handles = (0..2)
+            .map(|i| {
+                let g = Arc::clone(&g);
+                thread::spawn(move || {
+                    if i == 0 {
+                        sl_coloring(&g)
+                    } else {
+                        sdo_coloring(&*g)
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for h in handles {
+            assert!(check_coloring(&g, &h.join().unwrap()));
+        }
+    }
+
+    #[test]
+    fn shared_pointers() {
+        let g = AdjList::complete(4);
+
+        assert_eq!(num_colors(&lf_coloring(&&g)), 4);
+        assert_eq!(num_colors(&lf_coloring(&Box::new(g.clone()))), 4);
+
+        let mut a = Arc::new(g.clone());
+        let b = Arc::clone(&a);
+        a.add_edge(3, 4);
+
+        assert_eq!(a.num_vertices(), 5);
+        assert_eq!(b.num_vertices(), 4);
+    }
+}
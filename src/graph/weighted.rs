@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+/// Weights of the edges of an undirected graph.
+/// Edges without an explicit weight have the default weight. An infinite weight
+/// marks an edge as a hard constraint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeWeights {
+    default: f64,
+    weights: HashMap<(usize, usize), f64>,
+}
+
+impl EdgeWeights {
+    /// Constructs weights giving every edge the weight ```default```.
+    pub fn new(default: f64) -> Self {
+        Self {
+            default,
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Sets the weight of the edge ```(u, v)```, which is the same edge as ```(v, u)```.
+    pub fn set(&mut self, u: usize, v: usize, weight: f64) {
+        self.weights.insert((u.min(v), u.max(v)), weight);
+    }
+
+    /// Returns the weight of the edge ```(u, v)```.
+    pub fn get(&self, u: usize, v: usize) -> f64 {
+        self.weights
+            .get(&(u.min(v), u.max(v)))
+            .cloned()
+            .unwrap_or(self.default)
+    }
+
+    /// Whether the edge ```(u, v)``` has infinite weight.
+    pub fn is_hard(&self, u: usize, v: usize) -> bool {
+        self.get(u, v) == f64::INFINITY
+    }
+}
+
+impl Default for EdgeWeights {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_undirected() {
+        let mut w = EdgeWeights::default();
+        w.set(3, 1, 2.5);
+        w.set(0, 2, f64::INFINITY);
+
+        assert_eq!(w.get(1, 3), 2.5);
+        assert_eq!(w.get(0, 1), 1.0);
+        assert!(w.is_hard(2, 0));
+        assert!(!w.is_hard(1, 3));
+    }
+}
@@ -0,0 +1,139 @@
+//! Reading and writing of graphs and colorings.
+//!
+//! Graphs are read in DIMACS ```.col``` or graph6 format. Graphs and colorings are
+//! exported for visualization tools, colored vertices are filled according to
+//! ```palette::color```. Graphs with colorings found for them are stored compactly
+//! by ```Pack```.
+
+mod pack;
+pub mod palette;
+mod svg;
+
+use std::fmt::Write;
+
+use crate::graph::StaticGraph;
+
+pub use self::pack::{Pack, PackedColoring};
+pub use self::svg::{render_svg, Layout};
+pub use crate::graph::{
+    for_each_graph6_line, load_graph, load_graph_with_meta, parse_dimacs, parse_dimacs_with_meta,
+    parse_graph6,
+};
+
+/// Returns the graph in Graphviz DOT format.
+/// If a coloring is given, vertices are filled with the color of their class.
+pub fn to_dot<G: StaticGraph + ?Sized>(graph: &G, coloring: Option<&[usize]>) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "graph G {{").unwrap();
+    if coloring.is_some() {
+        writeln!(out, "  node [style=filled];").unwrap();
+    }
+
+    for v in graph.vertices() {
+        match coloring {
+            Some(c) => writeln!(
+                out,
+                "  {} [fillcolor=\"{}\", label=\"{}:{}\"];",
+                v,
+                palette::color(c[v]),
+                v,
+                c[v]
+            )
+            .unwrap(),
+            None => writeln!(out, "  {};", v).unwrap(),
+        }
+    }
+
+    for (u, v) in graph.edges() {
+        writeln!(out, "  {} -- {};", u, v).unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+/// Returns the graph in GraphML format.
+/// If a coloring is given, every vertex carries its color index as ```color``` and
+/// the corresponding palette entry as ```fill``` attribute.
+pub fn to_graphml<G: StaticGraph + ?Sized>(graph: &G, coloring: Option<&[usize]>) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(
+        out,
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+    )
+    .unwrap();
+    if coloring.is_some() {
+        writeln!(
+            out,
+            "  <key id=\"color\" for=\"node\" attr.name=\"color\" attr.type=\"int\"/>"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "  <key id=\"fill\" for=\"node\" attr.name=\"fill\" attr.type=\"string\"/>"
+        )
+        .unwrap();
+    }
+    writeln!(out, "  <graph id=\"G\" edgedefault=\"undirected\">").unwrap();
+
+    for v in graph.vertices() {
+        match coloring {
+            Some(c) => {
+                writeln!(out, "    <node id=\"n{}\">", v).unwrap();
+                writeln!(out, "      <data key=\"color\">{}</data>", c[v]).unwrap();
+                writeln!(
+                    out,
+                    "      <data key=\"fill\">{}</data>",
+                    palette::color(c[v])
+                )
+                .unwrap();
+                writeln!(out, "    </node>").unwrap();
+            }
+            None => writeln!(out, "    <node id=\"n{}\"/>", v).unwrap(),
+        }
+    }
+
+    for (u, v) in graph.edges() {
+        writeln!(out, "    <edge source=\"n{}\" target=\"n{}\"/>", u, v).unwrap();
+    }
+
+    writeln!(out, "  </graph>").unwrap();
+    writeln!(out, "</graphml>").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn dot_colored() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 1);
+        let c = vec![0, 1];
+
+        let dot = to_dot(&g, Some(&c));
+
+        assert!(dot.starts_with("graph G {"));
+        assert!(dot.contains("0 -- 1;"));
+        assert!(dot.contains(&format!("1 [fillcolor=\"{}\"", palette::color(1))));
+    }
+
+    #[test]
+    fn graphml_uncolored() {
+        let mut g = AdjList::new();
+        g.add_edge(0, 2);
+
+        let xml = to_graphml(&g, None);
+
+        assert_eq!(xml.matches("<node ").count(), 3);
+        assert!(xml.contains("<edge source=\"n0\" target=\"n2\"/>"));
+        assert!(!xml.contains("<key"));
+    }
+}
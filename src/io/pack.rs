@@ -0,0 +1,413 @@
+//! Compact binary storage of a graph together with colorings found for it.
+//!
+//! A pack replaces the DIMACS file of an instance and the solution files of the
+//! runs on it by a single file, which loads in one pass without parsing text.
+//!
+//! # Format
+//! All numbers are unsigned LEB128 varints, strings are their length followed by
+//! their UTF-8 bytes. The file starts with the magic bytes ```GRAMLPAK``` and the
+//! version byte ```1```, followed by the name of the instance and the number of
+//! vertices ```n```. Then follow, for every vertex ```v```, the number of its
+//! neighbors larger than ```v``` and these neighbors in increasing order, each as
+//! the difference to the previous one, starting from ```v```. Every edge is thus
+//! stored once and dense graphs need about a byte per edge.
+//!
+//! The number of colorings follows, each with the name of the algorithm, a byte
+//! ```1``` followed by the seed or a byte ```0``` if there is none, the running time
+//! in nanoseconds and the ```n``` colors. Colorings of fewer than 128 colors take a
+//! byte per vertex.
+//!
+//! Colorings are exchanged with other tools as solution files in a line based
+//! format similar to DIMACS, with vertices numbered from 1:
+//! ```text
+//! c algorithm sdo
+//! c seed 42
+//! c time 0.125
+//! v 1 0
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::coloring::Coloring;
+use crate::graph::{Csr, StaticGraph};
+
+const MAGIC: &[u8; 8] = b"GRAMLPAK";
+const VERSION: u8 = 1;
+
+/// Graph of an instance with the colorings found for it.
+#[derive(Debug, Clone)]
+pub struct Pack {
+    pub name: String,
+    pub graph: Csr,
+    pub colorings: Vec<PackedColoring>,
+}
+
+/// Coloring of a packed graph with the run which found it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackedColoring {
+    pub algorithm: String,
+    pub seed: Option<u64>,
+    pub time: Duration,
+    pub coloring: Coloring,
+}
+
+impl Pack {
+    /// Pack of a copy of ```graph``` without colorings.
+    pub fn new<G: StaticGraph + ?Sized>(name: &str, graph: &G) -> Self {
+        Self {
+            name: name.to_string(),
+            graph: Csr::from_graph(graph),
+            colorings: Vec::new(),
+        }
+    }
+
+    /// Loads a pack from file, see ```read```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(&fs::read(path)?[..])
+    }
+
+    /// Writes the pack to a new file at ```path```.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()
+    }
+
+    /// Reads a pack in the format of the module documentation. Files in another
+    /// format are reported as ```InvalidData```, truncated ones as ```UnexpectedEof```
+    /// errors. The colorings are not checked to be valid. Reads byte by byte, such
+    /// that ```reader``` should be buffered.
+    pub fn read(mut reader: impl Read) -> io::Result<Self> {
+        let mut magic = [0; 9];
+        reader.read_exact(&mut magic)?;
+        if &magic[..8] != MAGIC || magic[8] != VERSION {
+            return Err(invalid("not a graph pack"));
+        }
+
+        let name = read_string(&mut reader)?;
+        let n = read_number(&mut reader)?;
+
+        // Neighbors larger than every vertex, which are sorted and come after the
+        // smaller ones, such that the rows of the graph fill in order
+        let mut offsets = vec![0];
+        let mut larger = Vec::new();
+        let mut degrees = Vec::new();
+        for v in 0..n {
+            degrees.push(0);
+            let mut u = v;
+            for _ in 0..read_number(&mut reader)? {
+                u = read_number(&mut reader)?
+                    .checked_add(u)
+                    .filter(|&w| w > u && w < n)
+                    .ok_or_else(|| invalid("neighbor out of order"))?;
+                larger.push(u);
+            }
+            offsets.push(larger.len());
+        }
+        for &u in &larger {
+            degrees[u] += 1;
+        }
+        for v in 0..n {
+            degrees[v] += offsets[v + 1] - offsets[v];
+        }
+
+        let mut starts = vec![0; n + 1];
+        for v in 0..n {
+            starts[v + 1] = starts[v] + degrees[v];
+        }
+        let mut fill = starts.clone();
+        let mut targets = vec![0; larger.len() * 2];
+        for v in 0..n {
+            for &u in &larger[offsets[v]..offsets[v + 1]] {
+                targets[fill[v]] = u;
+                targets[fill[u]] = v;
+                fill[v] += 1;
+                fill[u] += 1;
+            }
+        }
+        let graph = Csr::from_parts(starts, targets);
+
+        let mut colorings = Vec::new();
+        for _ in 0..read_number(&mut reader)? {
+            let algorithm = read_string(&mut reader)?;
+            let seed = match read_byte(&mut reader)? {
+                0 => None,
+                1 => Some(read_varint(&mut reader)?),
+                _ => return Err(invalid("malformed seed")),
+            };
+            let time = Duration::from_nanos(read_varint(&mut reader)?);
+            let coloring = (0..n)
+                .map(|_| read_number(&mut reader))
+                .collect::<io::Result<_>>()?;
+
+            colorings.push(PackedColoring {
+                algorithm,
+                seed,
+                time,
+                coloring,
+            });
+        }
+
+        Ok(Self {
+            name,
+            graph,
+            colorings,
+        })
+    }
+
+    /// Writes the pack in the format of the module documentation.
+    /// Panics if a coloring does not color every vertex of the graph.
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let n = self.graph.num_vertices();
+
+        out.write_all(MAGIC)?;
+        out.write_all(&[VERSION])?;
+        write_string(&mut out, &self.name)?;
+        write_varint(&mut out, n as u64)?;
+
+        let mut larger = Vec::new();
+        for v in 0..n {
+            larger.clear();
+            larger.extend(self.graph.neighbors(v).filter(|&u| u > v));
+            write_varint(&mut out, larger.len() as u64)?;
+            let mut previous = v;
+            for &u in &larger {
+                write_varint(&mut out, (u - previous) as u64)?;
+                previous = u;
+            }
+        }
+
+        write_varint(&mut out, self.colorings.len() as u64)?;
+        for packed in &self.colorings {
+            assert_eq!(packed.coloring.len(), n, "coloring of another graph");
+
+            write_string(&mut out, &packed.algorithm)?;
+            match packed.seed {
+                Some(seed) => {
+                    out.write_all(&[1])?;
+                    write_varint(&mut out, seed)?;
+                }
+                None => out.write_all(&[0])?,
+            }
+            write_varint(&mut out, packed.time.as_nanos() as u64)?;
+            for &color in &packed.coloring {
+                write_varint(&mut out, color as u64)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PackedColoring {
+    /// Loads a solution file, see ```parse```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(BufReader::new(File::open(path)?))
+    }
+
+    /// Parses a solution file in the format of the module documentation. Unknown
+    /// comments are ignored, malformed lines and vertices without a color are
+    /// reported as ```InvalidData``` errors. The coloring is only allocated once
+    /// every vertex up to the largest one has a line.
+    pub fn parse(reader: impl BufRead) -> io::Result<Self> {
+        let mut packed = Self::default();
+        let mut colors = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let mut splits = line.split_whitespace();
+
+            match (splits.next(), splits.next(), splits.next()) {
+                (None, _, _) => continue,
+                (Some("c"), Some("algorithm"), Some(algorithm)) => {
+                    packed.algorithm = algorithm.to_string()
+                }
+                (Some("c"), Some("seed"), Some(seed)) => {
+                    packed.seed = Some(seed.parse().map_err(|_| invalid("malformed seed"))?)
+                }
+                (Some("c"), Some("time"), Some(time)) => {
+                    packed.time = time
+                        .parse()
+                        .ok()
+                        .and_then(|t| Duration::try_from_secs_f64(t).ok())
+                        .ok_or_else(|| invalid("malformed time"))?
+                }
+                (Some("c"), _, _) => continue,
+                (Some("v"), Some(v), Some(color)) => {
+                    let v = v
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|v| v.checked_sub(1))
+                        .ok_or_else(|| invalid("malformed vertex"))?;
+                    let color = color.parse().map_err(|_| invalid("malformed color"))?;
+
+                    colors.push((v, color));
+                }
+                _ => return Err(invalid(&format!("unexpected line '{}'", line))),
+            }
+        }
+
+        let n = colors.iter().map(|&(v, _)| v + 1).max().unwrap_or(0);
+        if n > colors.len() {
+            return Err(invalid("vertex without color"));
+        }
+        packed.coloring = vec![usize::MAX; n];
+        for (v, color) in colors {
+            packed.coloring[v] = color;
+        }
+        if packed.coloring.contains(&usize::MAX) {
+            return Err(invalid("vertex without color"));
+        }
+
+        Ok(packed)
+    }
+
+    /// Writes the solution file in the format of the module documentation.
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "c algorithm {}", self.algorithm)?;
+        if let Some(seed) = self.seed {
+            writeln!(out, "c seed {}", seed)?;
+        }
+        writeln!(out, "c time {}", self.time.as_secs_f64())?;
+        for (v, color) in self.coloring.iter().enumerate() {
+            writeln!(out, "v {} {}", v + 1, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_varint<W: Write>(out: &mut W, mut x: u64) -> io::Result<()> {
+    while x >= 0x80 {
+        out.write_all(&[x as u8 | 0x80])?;
+        x >>= 7;
+    }
+    out.write_all(&[x as u8])
+}
+
+fn write_string<W: Write>(out: &mut W, s: &str) -> io::Result<()> {
+    write_varint(out, s.len() as u64)?;
+    out.write_all(s.as_bytes())
+}
+
+fn read_byte<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut x = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(reader)?;
+        if shift == 63 && byte > 1 {
+            break;
+        }
+        x |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Ok(x);
+        }
+    }
+
+    Err(invalid("number too large"))
+}
+
+fn read_number<R: Read>(reader: &mut R) -> io::Result<usize> {
+    let x = read_varint(reader)?;
+    if x > usize::MAX as u64 {
+        return Err(invalid("number too large"));
+    }
+
+    Ok(x as usize)
+}
+
+fn read_string<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_number(reader)?;
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid("malformed string"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::{rs_coloring, sdo_coloring};
+    use crate::graph::*;
+
+    #[test]
+    fn pack_roundtrip() {
+        let g = AdjList::random(100, 0.3);
+        let mut pack = Pack::new("random", &g);
+        pack.colorings.push(PackedColoring {
+            algorithm: "sdo".to_string(),
+            seed: None,
+            time: Duration::from_micros(1500),
+            coloring: sdo_coloring(&g),
+        });
+        pack.colorings.push(PackedColoring {
+            algorithm: "rs".to_string(),
+            seed: Some(u64::MAX),
+            time: Duration::from_secs(3),
+            coloring: rs_coloring(&g),
+        });
+
+        let mut bytes = Vec::new();
+        pack.write(&mut bytes).unwrap();
+        let mut dimacs = Vec::new();
+        write_dimacs(&g, &mut dimacs).unwrap();
+        assert!(bytes.len() < dimacs.len() / 4);
+
+        let read = Pack::read(&bytes[..]).unwrap();
+        assert_eq!(read.name, "random");
+        assert_eq!(read.colorings, pack.colorings);
+        assert_eq!(
+            read.graph.edges().collect::<Vec<_>>(),
+            g.edges().collect::<Vec<_>>()
+        );
+        assert!(g
+            .vertices()
+            .all(|v| read.graph.neighbors(v).eq(pack.graph.neighbors(v))));
+
+        // Truncated packs and other files are rejected
+        assert!(Pack::read(&bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(
+            Pack::read(&dimacs[..]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn solution_roundtrip() {
+        let packed = PackedColoring {
+            algorithm: "dsatur".to_string(),
+            seed: Some(7),
+            time: Duration::from_millis(250),
+            coloring: vec![0, 1, 0, 2],
+        };
+
+        let mut out = Vec::new();
+        packed.write(&mut out).unwrap();
+        assert_eq!(PackedColoring::parse(&out[..]).unwrap(), packed);
+
+        assert!(PackedColoring::parse(&b"v 1 0\nv 3 1\n"[..]).is_err());
+        assert!(PackedColoring::parse(&b"v 0 1\n"[..]).is_err());
+        assert!(PackedColoring::parse(&b"v 99999999999 0\n"[..]).is_err());
+        assert!(PackedColoring::parse(&b"v 2 0\nv 2 1\n"[..]).is_err());
+        for time in &["1e300", "NaN", "-1", "inf"] {
+            let line = format!("c time {}\nv 1 0\n", time);
+            let err = PackedColoring::parse(line.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
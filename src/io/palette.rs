@@ -0,0 +1,105 @@
+//! Mapping of color indices to visually distinct RGB colors.
+//!
+//! The first colors are taken from a curated categorical palette, further colors
+//! are generated by rotating the hue by the golden angle.
+
+use std::fmt;
+
+/// A color in RGB space, displayed as hex string ```#rrggbb```.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Returns the hex representation ```#rrggbb```.
+    pub fn hex(self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Categorical palette of well distinguishable colors.
+const CATEGORICAL: [Rgb; 12] = [
+    Rgb(0x1f, 0x77, 0xb4),
+    Rgb(0xff, 0x7f, 0x0e),
+    Rgb(0x2c, 0xa0, 0x2c),
+    Rgb(0xd6, 0x27, 0x28),
+    Rgb(0x94, 0x67, 0xbd),
+    Rgb(0x8c, 0x56, 0x4b),
+    Rgb(0xe3, 0x77, 0xc2),
+    Rgb(0x7f, 0x7f, 0x7f),
+    Rgb(0xbc, 0xbd, 0x22),
+    Rgb(0x17, 0xbe, 0xcf),
+    Rgb(0xff, 0xd9, 0x2f),
+    Rgb(0x1b, 0x1b, 0x6e),
+];
+
+/// Returns the RGB value for color index ```i```.
+/// Indices within the categorical palette map to curated colors, larger ones to
+/// hues spaced by the golden angle with alternating lightness.
+pub fn color(i: usize) -> Rgb {
+    if i < CATEGORICAL.len() {
+        return CATEGORICAL[i];
+    }
+
+    let j = i - CATEGORICAL.len();
+    let hue = (j as f64 * 137.507_764) % 360.0;
+    let lightness = [0.45, 0.6, 0.35][j % 3];
+
+    hsl_to_rgb(hue, 0.65, lightness)
+}
+
+/// Returns the RGB values of the first ```k``` color indices.
+pub fn palette(k: usize) -> Vec<Rgb> {
+    (0..k).map(color).collect()
+}
+
+/// Converts a color given by hue in degrees, saturation and lightness in ```[0, 1]```.
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> Rgb {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r, g, b) = match (h / 60.0) as usize {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+
+    Rgb(channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn hex_format() {
+        assert_eq!(Rgb(0, 15, 255).hex(), "#000fff");
+        assert_eq!(color(0).hex(), "#1f77b4");
+    }
+
+    #[test]
+    fn distinct_colors() {
+        let colors = palette(200).into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(colors.len(), 200);
+    }
+
+    #[test]
+    fn hsl_primaries() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), Rgb(255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), Rgb(0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), Rgb(0, 0, 255));
+    }
+}
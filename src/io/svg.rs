@@ -0,0 +1,221 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt::Write;
+
+use crate::graph::StaticGraph;
+use crate::io::palette;
+
+/// Side length of the rendered image in pixels.
+const SIZE: f64 = 600.0;
+/// Distance between the drawing area and the image border.
+const MARGIN: f64 = 20.0;
+/// Radius of a vertex.
+const RADIUS: f64 = 8.0;
+
+/// Placement of the vertices in the rendered image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// Vertices evenly spaced on a circle.
+    Circular,
+    /// Fruchterman-Reingold spring embedding starting from random positions.
+    ForceDirected { iterations: usize, seed: u64 },
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::ForceDirected {
+            iterations: 300,
+            seed: 0,
+        }
+    }
+}
+
+/// Renders the graph as standalone SVG image.
+/// Vertices are filled according to ```palette::color``` of their color and show
+/// their index on hover. Intended for graphs of up to a few hundred vertices.
+pub fn render_svg<G: StaticGraph + ?Sized>(
+    graph: &G,
+    coloring: &[usize],
+    layout: Layout,
+) -> String {
+    let positions = match layout {
+        Layout::Circular => circular(graph.num_vertices()),
+        Layout::ForceDirected { iterations, seed } => force_directed(graph, iterations, seed),
+    };
+
+    // Map unit square into the drawing area
+    let scale = |(x, y): (f64, f64)| {
+        (
+            MARGIN + x * (SIZE - 2.0 * MARGIN),
+            MARGIN + y * (SIZE - 2.0 * MARGIN),
+        )
+    };
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">",
+        SIZE
+    )
+    .unwrap();
+
+    for (u, v) in graph.edges() {
+        let (x1, y1) = scale(positions[u]);
+        let (x2, y2) = scale(positions[v]);
+        writeln!(
+            out,
+            "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#999\"/>",
+            x1, y1, x2, y2
+        )
+        .unwrap();
+    }
+
+    for v in graph.vertices() {
+        let (x, y) = scale(positions[v]);
+        writeln!(
+            out,
+            "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" stroke=\"#333\"><title>{}: {}</title></circle>",
+            x,
+            y,
+            RADIUS,
+            palette::color(coloring[v]),
+            v,
+            coloring[v]
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "</svg>").unwrap();
+
+    out
+}
+
+/// Positions in the unit square, evenly spaced on a circle.
+fn circular(n: usize) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|v| {
+            let angle = 2.0 * std::f64::consts::PI * v as f64 / n as f64;
+            (0.5 + 0.5 * angle.cos(), 0.5 + 0.5 * angle.sin())
+        })
+        .collect()
+}
+
+/// Positions in the unit square computed by the Fruchterman-Reingold algorithm.
+/// Vertices repel each other while edges act as springs. The maximum displacement
+/// per iteration decreases linearly to zero.
+fn force_directed<G: StaticGraph + ?Sized>(
+    graph: &G,
+    iterations: usize,
+    seed: u64,
+) -> Vec<(f64, f64)> {
+    let n = graph.num_vertices();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pos = (0..n)
+        .map(|_| (rng.gen::<f64>(), rng.gen::<f64>()))
+        .collect::<Vec<_>>();
+
+    if n < 2 {
+        return vec![(0.5, 0.5); n];
+    }
+
+    let edges = graph.edges().collect::<Vec<_>>();
+    let k = (1.0 / n as f64).sqrt();
+
+    for i in 0..iterations {
+        let temperature = 0.1 * (1.0 - i as f64 / iterations as f64);
+        let mut disp = vec![(0.0, 0.0); n];
+
+        for u in 0..n {
+            for v in u + 1..n {
+                let (dx, dy) = (pos[u].0 - pos[v].0, pos[u].1 - pos[v].1);
+                let d = (dx * dx + dy * dy).sqrt().max(1e-6);
+                let f = k * k / d;
+
+                disp[u].0 += dx / d * f;
+                disp[u].1 += dy / d * f;
+                disp[v].0 -= dx / d * f;
+                disp[v].1 -= dy / d * f;
+            }
+        }
+
+        for &(u, v) in &edges {
+            let (dx, dy) = (pos[u].0 - pos[v].0, pos[u].1 - pos[v].1);
+            let d = (dx * dx + dy * dy).sqrt().max(1e-6);
+            let f = d * d / k;
+
+            disp[u].0 -= dx / d * f;
+            disp[u].1 -= dy / d * f;
+            disp[v].0 += dx / d * f;
+            disp[v].1 += dy / d * f;
+        }
+
+        for v in 0..n {
+            let (dx, dy) = disp[v];
+            let d = (dx * dx + dy * dy).sqrt().max(1e-6);
+            let step = d.min(temperature);
+
+            pos[v].0 += dx / d * step;
+            pos[v].1 += dy / d * step;
+        }
+    }
+
+    normalize(pos)
+}
+
+/// Scales and translates positions to fill the unit square.
+fn normalize(pos: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    let min_x = pos.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let max_x = pos.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = pos.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max_y = pos.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+
+    let width = (max_x - min_x).max(1e-6);
+    let height = (max_y - min_y).max(1e-6);
+
+    pos.into_iter()
+        .map(|(x, y)| ((x - min_x) / width, (y - min_y) / height))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn svg_circular() {
+        let g = AdjList::complete(5);
+        let c = sl_coloring(&g);
+
+        let svg = render_svg(&g, &c, Layout::Circular);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert_eq!(svg.matches("<line").count(), 10);
+    }
+
+    #[test]
+    fn svg_force_directed() {
+        let g = AdjList::random(40, 0.1);
+        let c = sl_coloring(&g);
+
+        let a = render_svg(&g, &c, Layout::default());
+        let b = render_svg(&g, &c, Layout::default());
+
+        assert_eq!(a, b);
+        assert!(!a.contains("NaN"));
+    }
+
+    #[test]
+    fn layout_in_unit_square() {
+        let g = AdjList::random(30, 0.2);
+
+        let pos = force_directed(&g, 50, 1);
+
+        assert!(pos
+            .iter()
+            .all(|&(x, y)| (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y)));
+    }
+}
@@ -1,5 +1,15 @@
 extern crate itertools;
 extern crate rand;
 
+pub mod analysis;
+pub mod bipartite;
 pub mod coloring;
+#[cfg(feature = "distributed")]
+pub mod distributed;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
 pub mod graph;
+pub mod io;
+pub mod prelude;
+#[cfg(feature = "runner")]
+pub mod runner;
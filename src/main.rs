@@ -1,14 +1,235 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::BTreeMap;
 use std::env;
-use std::fs;
-use std::path::Path;
-use std::thread;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
+use graml::analysis::{Cached, Features};
 use graml::coloring::*;
+use graml::distributed::{self, Part, Partition};
 use graml::graph::*;
+use graml::io::{Pack, PackedColoring};
+use graml::runner::delta::Delta;
+use graml::runner::manifest::{InstanceRecord, Manifest, Source};
+use graml::runner::pareto::Experiment;
+use graml::runner::report::{BatchReport, InstanceResult};
+use graml::runner::run_parallel;
+
+mod explore;
+
+/// Heuristics compared by the binary, in column order.
+const ALGORITHMS: [ColoringAlgo; 6] = [
+    ColoringAlgo::RS,
+    ColoringAlgo::CS,
+    ColoringAlgo::LF,
+    ColoringAlgo::SL,
+    ColoringAlgo::SDO,
+    ColoringAlgo::IG,
+];
 
 fn main() {
-    let args = env::args().collect::<Vec<String>>();
+    let mut args = env::args().collect::<Vec<String>>();
+
+    // Optional output file for labeled training data of the algorithm selection
+    let training = args.iter().position(|a| a == "--training").map(|i| {
+        let path = args.get(i + 1).expect("--training requires a file").clone();
+        args.drain(i..i + 2);
+        path
+    });
+
+    // Optional HTML report with heatmaps of the comparison
+    let report = args.iter().position(|a| a == "--report").map(|i| {
+        let path = args.get(i + 1).expect("--report requires a file").clone();
+        args.drain(i..i + 2);
+        path
+    });
+
+    // Optional manifest recording how to reproduce the comparison
+    let manifest = args.iter().position(|a| a == "--manifest").map(|i| {
+        let path = args.get(i + 1).expect("--manifest requires a file").clone();
+        args.drain(i..i + 2);
+        path
+    });
+
+    // Graph datastructure of the comparison, chosen per graph with "auto"
+    let backend = args.iter().position(|a| a == "--backend").map(|i| {
+        let name = args.get(i + 1).expect("--backend requires a name").clone();
+        args.drain(i..i + 2);
+        parse_backend(&name).expect("unknown backend")
+    });
+    let backend = backend.unwrap_or(Some(Backend::AdjList));
+
+    // Seed of the random graphs of the comparison
+    let seed = args.iter().position(|a| a == "--seed").map(|i| {
+        let seed = args.get(i + 1).expect("--seed requires a number").clone();
+        args.drain(i..i + 2);
+        seed.parse::<u64>().expect("invalid seed")
+    });
+
+    // Comparison rerun from a manifest, reporting differing results
+    if let Some(i) = args.iter().position(|a| a == "--replay") {
+        let path = args.get(i + 1).expect("--replay requires a file").clone();
+
+        replay(&path).unwrap();
+        return;
+    }
+
+    // Comparison of a new version of a dataset, recoloring only changed instances
+    if let Some(i) = args.iter().position(|a| a == "--incremental") {
+        let old = args
+            .get(i + 1)
+            .expect("--incremental requires a manifest")
+            .clone();
+        let dir = args
+            .get(i + 2)
+            .expect("--incremental requires a directory")
+            .clone();
+
+        incremental(&old, &dir, manifest).unwrap();
+        return;
+    }
+
+    // Download of the benchmark instances into the cache
+    if args.get(1).map(String::as_str) == Some("fetch") {
+        let curated = args.iter().any(|a| a == "--curated");
+        args.retain(|a| a != "--curated");
+        if args.get(2).map(String::as_str) != Some("dimacs") {
+            panic!("Usage: fetch dimacs [directory] [--curated]");
+        }
+
+        fetch(args.get(3).map(String::as_str), curated);
+        return;
+    }
+
+    // Single file with a graph and the colorings found for it
+    if args.get(1).map(String::as_str) == Some("pack") {
+        if args.len() < 4 {
+            panic!("Usage: pack <out> <graph.col> [coloring.sol ...]");
+        }
+
+        pack(&args[2], &args[3], &args[4..]).unwrap();
+        return;
+    }
+
+    // Graph and solution files of a pack, written into a directory
+    if args.get(1).map(String::as_str) == Some("unpack") {
+        let path = args.get(2).expect("Usage: unpack <pack> [directory]");
+
+        unpack(path, args.get(3).map_or(".", String::as_str)).unwrap();
+        return;
+    }
+
+    // Check of a solution file against its graph, listing the conflicting edges
+    if args.get(1).map(String::as_str) == Some("verify") {
+        if args.len() < 4 {
+            panic!("Usage: verify <graph.col> <coloring.sol>");
+        }
+
+        verify(&args[2], &args[3]).unwrap();
+        return;
+    }
+
+    // Stream of small graphs in graph6 format, processed instead of the comparison
+    if let Some(i) = args.iter().position(|a| a == "--graph6") {
+        let path = args.get(i + 1).expect("--graph6 requires a file").clone();
+        let computation = match args.get(i + 2).map(String::as_str) {
+            None | Some("chromatic") => Computation::Chromatic,
+            Some("bounds") => Computation::Bounds,
+            Some(other) => panic!("Unknown computation '{}'", other),
+        };
+
+        graph6_stream(&path, computation);
+        return;
+    }
+
+    // Pareto front of the heuristics on a single graph
+    if let Some(i) = args.iter().position(|a| a == "--pareto") {
+        let path = args.get(i + 1).expect("--pareto requires a file").clone();
+        let previous = args.get(i + 2).filter(|a| *a != "--csv").cloned();
+        let csv = args.iter().any(|a| a == "--csv");
+
+        pareto(&path, previous.as_deref(), csv).unwrap();
+        return;
+    }
+
+    // Bound certificates next to every instance, resuming from existing ones
+    if let Some(i) = args.iter().position(|a| a == "--certify") {
+        let path = args.get(i + 1).expect("--certify requires a file").clone();
+        let seconds = args
+            .get(i + 2)
+            .map_or(10, |s| s.parse().expect("invalid seconds"));
+
+        certify(&path, Duration::from_secs(seconds)).unwrap();
+        return;
+    }
+
+    // Shareable copy of an instance with permuted vertices
+    if let Some(i) = args.iter().position(|a| a == "--anonymize") {
+        let path = args
+            .get(i + 1)
+            .expect("--anonymize requires a file")
+            .clone();
+        let out = args
+            .get(i + 2)
+            .expect("--anonymize requires an output file")
+            .clone();
+        let seed = args
+            .get(i + 3)
+            .map_or_else(rand::random, |s| s.parse().expect("invalid seed"));
+
+        anonymize_instance(&path, &out, seed).unwrap();
+        return;
+    }
+
+    // Coordinator of a distributed coloring, waiting for the workers to connect
+    if let Some(i) = args.iter().position(|a| a == "--coordinate") {
+        let addr = args
+            .get(i + 1)
+            .expect("--coordinate requires an address")
+            .clone();
+        let vertices = args
+            .get(i + 2)
+            .expect("--coordinate requires the number of vertices")
+            .parse()
+            .expect("invalid number of vertices");
+        let workers = args
+            .get(i + 3)
+            .expect("--coordinate requires the number of workers")
+            .parse()
+            .expect("invalid number of workers");
+        let out = args.get(i + 4).cloned();
+
+        let partition = Partition::new(vertices, workers);
+        coordinate(&addr, partition, out.as_deref()).unwrap();
+        return;
+    }
+
+    // Worker of a distributed coloring, reading only its part of the graph
+    if let Some(i) = args.iter().position(|a| a == "--work") {
+        let usage = "Usage: --work <address> <graph> <worker> <workers>";
+        let addr = args.get(i + 1).expect(usage);
+        let path = args.get(i + 2).expect(usage);
+        let worker = args.get(i + 3).and_then(|w| w.parse().ok()).expect(usage);
+        let workers = args.get(i + 4).and_then(|w| w.parse().ok()).expect(usage);
+
+        let file = std::io::BufReader::new(File::open(path).unwrap());
+        let part = Part::read_dimacs(file, workers, worker).unwrap();
+        distributed::work(addr.as_str(), &part).unwrap();
+        return;
+    }
+
+    // Interactive exploration of a single graph
+    if let Some(i) = args.iter().position(|a| a == "--explore") {
+        let path = args.get(i + 1).expect("--explore requires a file").clone();
+
+        explore::explore(&path).unwrap();
+        return;
+    }
 
+    let seed = seed.unwrap_or_else(rand::random);
     let graphs = if args.len() == 1 {
         // Run comparison on this many graphs
         let samples = 50;
@@ -20,7 +241,9 @@ fn main() {
         let p = 0.9;
 
         (0..samples)
-            .map(move |_| JobType::Random(n, p, format!("Random({},{})", n, p)))
+            .map(move |i| {
+                JobType::Random(n, p, seed.wrapping_add(i), format!("Random({},{})", n, p))
+            })
             .collect::<Vec<JobType>>()
     } else {
         let path = &args[1];
@@ -29,8 +252,7 @@ fn main() {
 
         if meta.is_dir() {
             // Handle all the graphs
-            for file in fs::read_dir(path).unwrap() {
-                let file = file.unwrap().path();
+            for file in col_files(path).unwrap() {
                 graphs.push(JobType::File(file.as_path().to_str().unwrap().to_string()));
             }
         } else {
@@ -40,125 +262,589 @@ fn main() {
         graphs
     };
 
-    parallel_coloring(graphs);
+    let records = parallel_coloring(graphs, seed, backend, training, report);
+
+    if let Some(path) = manifest {
+        let mut manifest = comparison_manifest(seed, backend);
+        manifest.instances = records;
+        manifest.save(path).unwrap();
+    }
 }
 
 #[derive(Debug, Clone)]
 enum JobType {
-    Random(usize, f32, String),
+    /// Random graph with the given number of vertices, edge probability and seed.
+    Random(usize, f32, u64, String),
     File(String),
 }
 
-fn parallel_coloring(graphs: Vec<JobType>) {
-    let samples = graphs.len();
+/// Backend of the given name, ```None``` for one chosen per graph.
+fn parse_backend(name: &str) -> Option<Option<Backend>> {
+    match name {
+        "auto" => Some(None),
+        name => Backend::from_name(name).map(Some),
+    }
+}
 
-    // Number of processors
-    let workers = num_cpus::get();
+/// Backend recorded in the manifest. Manifests of older versions name the type of
+/// the adjacency list.
+fn recorded_backend(manifest: &Manifest) -> Option<Backend> {
+    parse_backend(&manifest.backend).unwrap_or(Some(Backend::AdjList))
+}
+
+/// Manifest of a comparison without its instances.
+fn comparison_manifest(seed: u64, backend: Option<Backend>) -> Manifest {
+    let algorithms = ALGORITHMS.iter().map(|a| a.name().to_string()).collect();
 
-    // Communication channel between worker threads and main thread
-    let (tx_res, rx_res) = crossbeam::unbounded();
-    let (tx_job, rx_job) = crossbeam::unbounded();
+    Manifest::new(seed, algorithms, backend.map_or("auto", Backend::name))
+        .option("workers", num_cpus::get())
+}
 
-    for graph in graphs.into_iter() {
-        tx_job.send(graph).unwrap();
+/// Rerun the comparison recorded in the manifest and print where the results
+/// differ. Instance files must be unchanged.
+fn replay(path: &str) -> std::io::Result<()> {
+    let recorded = Manifest::load(path)?;
+    recorded.verify_instances()?;
+
+    let graphs = recorded
+        .instances
+        .iter()
+        .map(|instance| match &instance.source {
+            Source::Random { vertices, p, seed } => {
+                JobType::Random(*vertices, *p, *seed, instance.name.clone())
+            }
+            Source::File { path, .. } => JobType::File(path.clone()),
+        })
+        .collect();
+
+    let backend = recorded_backend(&recorded);
+    let mut replayed = comparison_manifest(recorded.seed, backend);
+    replayed.instances = parallel_coloring(graphs, recorded.seed, backend, None, None);
+
+    let differences = recorded.differences(&replayed);
+    println!();
+    if differences.is_empty() {
+        println!("Replay matches the manifest");
+    }
+    for difference in differences {
+        println!("{}", difference);
     }
 
-    // Drop original tx_job such that rx_.iter() will yield `None` when all jobs are done
-    drop(tx_job);
+    Ok(())
+}
 
-    for _ in 0..workers {
-        // Send results over tx_ to main thread
-        let tx_ = tx_res.clone();
+/// Computation applied to every graph of a graph6 stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Computation {
+    /// Exact chromatic number.
+    Chromatic,
+    /// Clique lower bound and heuristic upper bound.
+    Bounds,
+}
 
-        // Wait on rx_ for jobs by main thread
-        let rx_ = rx_job.clone();
+/// Apply the computation to every graph in the graph6 file in parallel and print
+/// aggregated statistics.
+fn graph6_stream(path: &str, computation: Computation) {
+    let mut graphs = 0;
+    let mut tight = 0;
+    let mut gap = 0;
+    // Number of graphs per (upper bound on the) chromatic number
+    let mut histogram = BTreeMap::new();
 
-        // Spawn workers
-        thread::spawn(move || {
-            for graph in rx_.iter() {
-                let name;
-                let g = match graph {
-                    JobType::Random(n, p, gname) => {
-                        name = gname;
-                        Graph::random(n, p)
-                    }
-                    JobType::File(ref gname) => {
-                        let file = Path::new(&gname);
-                        name = file.file_name().unwrap().to_str().unwrap().to_string();
-                        load_graph(file).unwrap()
-                    }
-                };
+    let read = run_parallel(
+        num_cpus::get(),
+        |emit| for_each_graph6_line(path, emit),
+        |g: Graph| match computation {
+            Computation::Chromatic => {
+                let chi = chromatic_number(&g);
+                (chi, chi)
+            }
+            Computation::Bounds => chromatic_bounds(&g),
+        },
+        |(lower, upper)| {
+            graphs += 1;
+            gap += upper - lower;
+            if lower == upper {
+                tight += 1;
+            }
+            *histogram.entry(upper).or_insert(0) += 1;
+        },
+    );
 
-                // Color graph
-                let c = all_colorings(&g);
+    read.unwrap();
 
-                // Send result back to main thread
-                tx_.send((c, name)).unwrap();
+    println!("{:<12}{:>10}", "graphs", graphs);
+    if computation == Computation::Bounds {
+        println!("{:<12}{:>10}", "tight", tight);
+        println!(
+            "{:<12}{:>10.3}",
+            "avg gap",
+            gap as f64 / graphs.max(1) as f64
+        );
+    }
+
+    println!();
+    for (colors, count) in histogram {
+        println!("{:<12}{:>10}", colors, count);
+    }
+}
+
+/// Run the heuristics on the graph and print their Pareto front of colors, balance
+/// and changes to the previous coloring, given as JSON array.
+fn pareto(path: &str, previous: Option<&str>, csv: bool) -> std::io::Result<()> {
+    let g = load_graph(path)?;
+    let previous = match previous {
+        Some(p) => Some(serde_json::from_reader::<_, Coloring>(File::open(p)?)?),
+        None => None,
+    };
+
+    let mut experiment = Experiment::new();
+    for &a in ALGORITHMS.iter() {
+        experiment = experiment.solver(a.name(), move |g: &Graph| a.color(g));
+    }
+    let report = experiment
+        .solver("balanced", color_balanced)
+        .run(&g, previous.as_deref());
+
+    let out = std::io::stdout();
+    if csv {
+        report.write_csv(out.lock())
+    } else {
+        report.write_json(out.lock())
+    }
+}
+
+/// Compare the heuristics on the instances in ```dir``` whose content is not in the
+/// manifest of an earlier run, reuse the results of the others and print how the
+/// results changed per heuristic. The merged results are written to ```manifest```.
+fn incremental(old: &str, dir: &str, manifest: Option<String>) -> std::io::Result<()> {
+    let old = Manifest::load(old)?;
+
+    let files = col_files(dir)?;
+    let mut new = comparison_manifest(old.seed, recorded_backend(&old));
+
+    // Previous results, or None for instances to be colored
+    let mut merged = Vec::new();
+    let mut graphs = Vec::new();
+    for file in files {
+        let name = file.file_name().unwrap().to_string_lossy().into_owned();
+        match old.reuse(&name, &Source::file(&file)?, &new.algorithms) {
+            Some(record) => merged.push(Some(record)),
+            None => {
+                merged.push(None);
+                graphs.push(JobType::File(file.to_string_lossy().into_owned()));
             }
-        });
+        }
     }
 
-    // Drop original tx_res such that rx_res.iter() will yield `None` when last tx_ is dropped
-    drop(tx_res);
+    let reused = merged.len() - graphs.len();
+    let colored = if graphs.is_empty() {
+        Vec::new()
+    } else {
+        parallel_coloring(graphs, old.seed, recorded_backend(&old), None, None)
+    };
+    let mut colored = colored.into_iter();
+
+    new.instances = merged
+        .into_iter()
+        .map(|record| record.or_else(|| colored.next()).unwrap())
+        .collect();
 
-    // Print results
-    let spacing = 8;
-    let width = 20;
     println!(
-        "{0:<1$}{3:>2$}{4:>2$}{5:>2$}{6:>2$}{7:>2$}\n",
-        "", width, spacing, "rs", "cs", "lf", "sl", "sdo"
+        "\n{} instances reused, {} colored\n",
+        reused,
+        new.instances.len() - reused
     );
+    Delta::new(&old, &new).write(std::io::stdout().lock())?;
+
+    match manifest {
+        Some(path) => new.save(path),
+        None => Ok(()),
+    }
+}
+
+/// Download the DIMACS instances into ```dir``` or the default cache directory.
+#[cfg(feature = "http")]
+fn fetch(dir: Option<&str>, curated: bool) {
+    use graml::runner::fetch::{default_cache, fetch_dimacs};
 
-    let mut sum = vec![0; 6];
+    let dir = dir
+        .map(std::path::PathBuf::from)
+        .or_else(default_cache)
+        .expect("no cache directory, pass one explicitly");
+    let paths = fetch_dimacs(&dir, curated).unwrap();
+
+    println!("{} instances in {}", paths.len(), dir.display());
+}
+
+#[cfg(not(feature = "http"))]
+fn fetch(_dir: Option<&str>, _curated: bool) {
+    panic!("Fetching requires the 'http' feature");
+}
+
+/// Returns the sorted paths of the ```.col``` files in ```dir```, skipping other
+/// files such as the checksums of fetched instances.
+fn col_files(dir: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = fs::read_dir(dir)?
+        .map(|f| f.map(|f| f.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    files.retain(|f| f.extension().is_some_and(|e| e == "col"));
+    files.sort();
+
+    Ok(files)
+}
+
+/// Improve the certificate of every instance in the file or directory for at most
+/// ```budget``` each and print the bounds. Existing certificates are verified first.
+fn certify(path: &str, budget: Duration) -> std::io::Result<()> {
+    let instances = if fs::metadata(path)?.is_dir() {
+        col_files(path)?
+    } else {
+        vec![Path::new(path).to_path_buf()]
+    };
+
+    for instance in instances {
+        let g = load_graph(&instance)?;
+        let mut certificate = match Certificate::load_for(&instance, &g)? {
+            Some(certificate) => certificate,
+            None => Certificate::new(&g),
+        };
+
+        let optimal = certificate.improve(&g, budget);
+        certificate.save(Certificate::path_for(&instance))?;
 
-    // Iterate over all values received by worker threads
-    for (n, name) in rx_res.iter() {
         println!(
-            "{0:<1$}{3:>2$}{4:>2$}{5:>2$}{6:>2$}{7:>2$}",
-            name, width, spacing, n[0], n[1], n[2], n[3], n[4]
+            "{:<20}{:>8}{:>8}{:>10}",
+            instance.file_name().unwrap().to_string_lossy(),
+            certificate.lower(),
+            certificate.upper(),
+            if optimal { "optimal" } else { "" }
         );
+    }
+
+    Ok(())
+}
 
-        sum[0] += n[0];
-        sum[1] += n[1];
-        sum[2] += n[2];
-        sum[3] += n[3];
-        sum[4] += n[4];
+/// Print the number of colors of the solution file at ```solution``` or its
+/// conflicting edges with the graph at ```graph```, numbered from 1 as in the files.
+/// Fails if the solution is not a valid coloring of the graph.
+fn verify(graph: &str, solution: &str) -> std::io::Result<()> {
+    let g = load_graph(graph)?;
+    let coloring = PackedColoring::load(solution)?.coloring;
+    let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+
+    if coloring.len() != g.num_vertices() {
+        return Err(invalid(format!(
+            "{} colors {} of {} vertices",
+            solution,
+            coloring.len(),
+            g.num_vertices()
+        )));
     }
 
+    let count = num_conflicts(&g, &coloring);
+    if count > 0 {
+        for (u, v) in conflicts(&g, &coloring).take(10) {
+            println!("conflict {} {} color {}", u + 1, v + 1, coloring[u]);
+        }
+        return Err(invalid(format!("{} conflicting edges", count)));
+    }
+
+    println!("valid coloring with {} colors", num_colors(&coloring));
+    Ok(())
+}
+
+/// Pack the graph at ```graph``` and the given solution files into ```out```.
+fn pack(out: &str, graph: &str, solutions: &[String]) -> std::io::Result<()> {
+    let g = load_graph(graph)?;
+    let name = Path::new(graph).file_stem().unwrap().to_string_lossy();
+    let mut pack = Pack::new(&name, &g);
+
+    for path in solutions {
+        let packed = PackedColoring::load(path)?;
+        if packed.coloring.len() != g.num_vertices() || !check_coloring(&g, &packed.coloring) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is not a coloring of {}", path, graph),
+            ));
+        }
+        pack.colorings.push(packed);
+    }
+
+    pack.save(out)?;
+    println!(
+        "Packed {} colorings into {} bytes",
+        pack.colorings.len(),
+        fs::metadata(out)?.len()
+    );
+
+    Ok(())
+}
+
+/// Write the graph of the pack at ```path``` to ```<name>.col``` in ```dir``` and
+/// every coloring to ```<name>.<index>.<algorithm>.sol```.
+fn unpack(path: &str, dir: &str) -> std::io::Result<()> {
+    let pack = Pack::load(path)?;
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir)?;
+
+    let mut out = BufWriter::new(File::create(dir.join(format!("{}.col", pack.name)))?);
+    write_dimacs(&pack.graph, &mut out)?;
+    out.flush()?;
+
+    for (i, packed) in pack.colorings.iter().enumerate() {
+        let file = format!("{}.{}.{}.sol", pack.name, i, packed.algorithm);
+        let mut out = BufWriter::new(File::create(dir.join(file))?);
+        packed.write(&mut out)?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Coordinate the workers of ```partition``` connecting to ```addr```, print the
+/// number of colors and optionally write the coloring to ```out``` as JSON.
+fn coordinate(addr: &str, partition: Partition, out: Option<&str>) -> std::io::Result<()> {
+    let listener = std::net::TcpListener::bind(addr)?;
+    let start = Instant::now();
+    let coloring = distributed::coordinate(&listener, partition)?;
+
     println!(
-        "\n{0:<1$}{3:>2$.2}{4:>2$.2}{5:>2$.2}{6:>2$.2}{7:>2$.2}",
-        "",
-        width,
-        spacing,
-        sum[0] as f32 / samples as f32,
-        sum[1] as f32 / samples as f32,
-        sum[2] as f32 / samples as f32,
-        sum[3] as f32 / samples as f32,
-        sum[4] as f32 / samples as f32,
+        "{} colors in {:.3}s",
+        num_colors(&coloring),
+        start.elapsed().as_secs_f64()
+    );
+    if let Some(out) = out {
+        serde_json::to_writer(BufWriter::new(File::create(out)?), &coloring)?;
+    }
+
+    Ok(())
+}
+
+/// Write the anonymized graph to ```out``` and the mapping back to the original
+/// vertices to ```out.map```, which is meant to stay local.
+fn anonymize_instance(path: &str, out: &str, seed: u64) -> std::io::Result<()> {
+    let anonymized: Anonymized<Graph> = anonymize(&load_graph(path)?, seed);
+
+    let mut graph = BufWriter::new(File::create(out)?);
+    write_dimacs(&anonymized.graph, &mut graph)?;
+    graph.flush()?;
+
+    let mut mapping = BufWriter::new(File::create(format!("{}.map", out))?);
+    anonymized.write_mapping(&mut mapping)?;
+    mapping.flush()
+}
+
+/// Compare the heuristics on all graphs and return the records of the instances
+/// in the order of ```graphs```. Every graph is colored in the given backend, or
+/// in the one chosen for its size if there is none.
+fn parallel_coloring(
+    graphs: Vec<JobType>,
+    seed: u64,
+    backend: Option<Backend>,
+    training: Option<String>,
+    report: Option<String>,
+) -> Vec<InstanceRecord> {
+    let samples = graphs.len();
+
+    // Print results
+    let spacing = 8;
+    let width = 20;
+    print!("{0:<1$}", "", width);
+    for a in ALGORITHMS.iter() {
+        print!("{0:>1$}", a.name(), spacing);
+    }
+    println!("\n");
+
+    let mut sum = [0; ALGORITHMS.len()];
+    let mut rows = Vec::new();
+    let mut records = Vec::new();
+
+    // Color all graphs on worker threads, results arrive as they are done
+    run_parallel(
+        num_cpus::get(),
+        |emit| graphs.into_iter().enumerate().for_each(emit),
+        |(index, graph)| {
+            let name;
+            let source;
+            // Seeds the randomized heuristics independently of the other instances
+            let mut rng = StdRng::seed_from_u64(match graph {
+                JobType::Random(_, _, seed, _) => seed,
+                JobType::File(ref gname) => instance_seed(seed, gname),
+            });
+            let (g, meta) = match graph {
+                JobType::Random(n, p, seed, gname) => {
+                    name = gname;
+                    source = Source::Random {
+                        vertices: n,
+                        p,
+                        seed,
+                    };
+                    let meta = GraphMeta {
+                        seed: Some(seed),
+                        ..GraphMeta::default()
+                    };
+                    let g = Graph::random_with(n, |_, _| p, &mut StdRng::seed_from_u64(seed));
+                    (g, meta)
+                }
+                JobType::File(ref gname) => {
+                    let file = Path::new(&gname);
+                    name = file.file_name().unwrap().to_str().unwrap().to_string();
+                    source = Source::file(file).unwrap();
+                    load_graph_with_meta(file).unwrap()
+                }
+            };
+            let g: DynGraph = match backend {
+                Some(Backend::AdjList) => Box::new(g),
+                Some(backend) => backend.build(&g),
+                None => Backend::choose(g.num_vertices(), g.edges().count()).build(&g),
+            };
+            // Bounds, orderings and features share the degrees and the clique
+            let g = Cached::cached(g);
+
+            // Color graph
+            let (c, times) = all_colorings(&g, &mut rng);
+            let features = Features::new(&g);
+
+            (c, times, name, features, meta, index, source)
+        },
+        |(outcomes, times, name, features, meta, index, source)| {
+            let n = outcomes.iter().map(|o| o.num_colors).collect::<Vec<_>>();
+            print!("{0:<1$}", name, width);
+            for (s, &n) in sum.iter_mut().zip(&n) {
+                print!("{0:>1$}", n, spacing);
+                *s += n;
+            }
+            println!();
+
+            records.push((
+                index,
+                InstanceRecord {
+                    name: name.clone(),
+                    source,
+                    colors: n.clone(),
+                },
+            ));
+            rows.push((name, features, meta, outcomes, times));
+        },
     );
+
+    print!("\n{0:<1$}", "", width);
+    for &s in &sum {
+        print!("{0:>1$.2}", s as f32 / samples as f32, spacing);
+    }
+    println!();
+
+    if let Some(path) = training {
+        write_training_data(&path, &rows).unwrap();
+    }
+
+    if let Some(path) = report {
+        write_report(&path, rows).unwrap();
+    }
+
+    records.sort_by_key(|&(index, _)| index);
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+/// Write the comparison as an HTML page with heatmaps of colors and times.
+fn write_report(path: &str, rows: Vec<Row>) -> std::io::Result<()> {
+    let mut report = BatchReport::new(ALGORITHMS.iter().map(|a| a.name().to_string()).collect());
+    for (name, features, meta, outcomes, times) in rows {
+        report.push(InstanceResult {
+            name,
+            features,
+            colors: outcomes.iter().map(|o| o.num_colors).collect(),
+            times,
+            meta,
+        });
+    }
+
+    report.write_html(BufWriter::new(File::create(path)?))
+}
+
+/// Write one CSV row per graph with its features, the number of colors each
+/// heuristic used and the best heuristic as label.
+fn write_training_data(path: &str, rows: &[Row]) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    let names = ALGORITHMS.iter().map(|a| a.name()).collect::<Vec<_>>();
+    writeln!(
+        out,
+        "name,vertices,edges,density,max_degree,avg_degree,degeneracy,\
+         assortativity,communities,modularity,{},best",
+        names.join(",")
+    )?;
+
+    for (name, f, _, outcomes, _) in rows {
+        // First heuristic with the fewest colors
+        let best = outcomes.iter().min_by_key(|o| o.num_colors).unwrap();
+
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            name,
+            f.vertices,
+            f.edges,
+            f.density,
+            f.max_degree,
+            f.avg_degree,
+            f.degeneracy,
+            f.assortativity,
+            f.communities,
+            f.modularity,
+            outcomes
+                .iter()
+                .map(|o| o.num_colors.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            best.algorithm
+        )?;
+    }
+
+    out.flush()
 }
 
-fn all_colorings<G: StaticGraph>(g: &G) -> Vec<usize> {
-    // Perform colorings
-    let c1 = rs_coloring(g);
-    let c2 = cs_coloring(g);
-    let c3 = lf_coloring(g);
-    let c4 = sl_coloring(g);
-    let c5 = sdo_coloring(g);
-
-    // Check colorings
-    assert!(check_coloring(g, &c1));
-    assert!(check_coloring(g, &c2));
-    assert!(check_coloring(g, &c3));
-    assert!(check_coloring(g, &c4));
-    assert!(check_coloring(g, &c5));
-
-    // Count number of colors used
-    let n1 = num_colors(&c1);
-    let n2 = num_colors(&c2);
-    let n3 = num_colors(&c3);
-    let n4 = num_colors(&c4);
-    let n5 = num_colors(&c5);
-
-    vec![n1, n2, n3, n4, n5]
+/// Name, features, metadata, outcomes and times of the heuristics of a graph.
+type Row = (
+    String,
+    Features,
+    GraphMeta,
+    Vec<ColoringOutcome>,
+    Vec<Duration>,
+);
+
+/// Derives the seed of the heuristics on the instance at ```path``` from the seed of
+/// the comparison and the file name, which identifies the instance in a manifest.
+fn instance_seed(seed: u64, path: &str) -> u64 {
+    let name = Path::new(path)
+        .file_name()
+        .map_or(path.into(), |n| n.to_string_lossy());
+
+    // FNV-1a, which unlike the standard hasher is fixed across releases
+    name.bytes().fold(0xcbf2_9ce4_8422_2325 ^ seed, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn all_colorings<G: StaticGraph + ?Sized>(
+    g: &G,
+    rng: &mut StdRng,
+) -> (Vec<ColoringOutcome>, Vec<Duration>) {
+    let (lower, _) = chromatic_bounds(g);
+
+    ALGORITHMS
+        .iter()
+        .map(|a| {
+            // Perform coloring
+            let start = Instant::now();
+            let c = a.color_with(g, rng);
+            let time = start.elapsed();
+
+            // Check coloring
+            assert!(check_coloring(g, &c));
+
+            (ColoringOutcome::new(a.name(), c, lower), time)
+        })
+        .unzip()
 }
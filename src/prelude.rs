@@ -0,0 +1,24 @@
+//! Commonly used types and functions.
+//!
+//! ```
+//! use graml::prelude::*;
+//!
+//! let g = Graph::random(50, 0.3);
+//! let c = color_balanced(&g);
+//!
+//! assert!(check_coloring(&g, &c));
+//! ```
+//!
+//! Fallible functions such as ```load_graph``` report errors as ```std::io::Error```.
+
+pub use crate::coloring::{
+    check_coloring, chromatic_number, color, color_auto, color_balanced, color_best, color_fast,
+    exact_coloring, greedy_coloring, lf_coloring, num_colors, rs_coloring, sdo_coloring,
+    sl_coloring, Coloring, ColoringAlgo, ColoringWorkspace, Greedy, Order, Selection,
+};
+pub use crate::graph::{
+    AdjList, AdjMatrix, Csr, EdgeList, EdgeWeights, Graph, GrowableAdjMatrix, Hybrid, Labeled,
+    MaskedGraph, StaticGraph,
+};
+pub use crate::io::{load_graph, parse_graph6};
+pub use std::io::{Error as IoError, Result as IoResult};
@@ -0,0 +1,210 @@
+//! Changes in the results between two runs over versions of a dataset.
+//!
+//! Instances are matched by name. Building the new run with ```Manifest::reuse```
+//! keeps the old results of instances whose content did not change, such that only
+//! changed instances have to be colored again.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::runner::manifest::{InstanceRecord, Manifest};
+
+/// Instance whose number of colors changed for some algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceChange {
+    pub name: String,
+    /// Number of colors of the common algorithms in the old and new run.
+    pub old: Vec<usize>,
+    pub new: Vec<usize>,
+}
+
+/// Differences between the results of an old and a new run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    /// Algorithms present in both runs.
+    pub algorithms: Vec<String>,
+    /// Number of instances on which each algorithm uses fewer colors.
+    pub improved: Vec<usize>,
+    /// Number of instances on which each algorithm uses more colors.
+    pub regressed: Vec<usize>,
+    pub changes: Vec<InstanceChange>,
+    /// Instances only in the new run.
+    pub added: Vec<String>,
+    /// Instances only in the old run.
+    pub removed: Vec<String>,
+}
+
+impl Delta {
+    pub fn new(old: &Manifest, new: &Manifest) -> Self {
+        let algorithms = old
+            .algorithms
+            .iter()
+            .filter(|a| new.algorithms.contains(a))
+            .cloned()
+            .collect::<Vec<_>>();
+        let columns = |manifest: &Manifest| -> Vec<usize> {
+            algorithms
+                .iter()
+                .map(|a| manifest.algorithms.iter().position(|b| a == b).unwrap())
+                .collect()
+        };
+        let (old_columns, new_columns) = (columns(old), columns(new));
+
+        let old_instances = by_name(old);
+        let new_instances = by_name(new);
+
+        let mut delta = Delta {
+            improved: vec![0; algorithms.len()],
+            regressed: vec![0; algorithms.len()],
+            algorithms,
+            changes: Vec::new(),
+            added: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        for (name, a) in &old_instances {
+            let b = match new_instances.get(name) {
+                Some(b) => b,
+                None => {
+                    delta.removed.push(name.to_string());
+                    continue;
+                }
+            };
+
+            let old_colors = old_columns.iter().map(|&i| a.colors[i]).collect::<Vec<_>>();
+            let new_colors = new_columns.iter().map(|&i| b.colors[i]).collect::<Vec<_>>();
+            for (i, (x, y)) in old_colors.iter().zip(&new_colors).enumerate() {
+                if y < x {
+                    delta.improved[i] += 1;
+                } else if y > x {
+                    delta.regressed[i] += 1;
+                }
+            }
+
+            if old_colors != new_colors {
+                delta.changes.push(InstanceChange {
+                    name: name.to_string(),
+                    old: old_colors,
+                    new: new_colors,
+                });
+            }
+        }
+        delta.added = new_instances
+            .keys()
+            .filter(|name| !old_instances.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+
+        delta
+    }
+
+    /// Writes the number of improved and regressed instances per algorithm,
+    /// followed by the changed, added and removed instances.
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let width = 20;
+        let spacing = 10;
+
+        writeln!(
+            out,
+            "{0:<1$}{3:>2$}{4:>2$}",
+            "", width, spacing, "improved", "regressed"
+        )?;
+        for (i, algorithm) in self.algorithms.iter().enumerate() {
+            writeln!(
+                out,
+                "{0:<1$}{3:>2$}{4:>2$}",
+                algorithm, width, spacing, self.improved[i], self.regressed[i]
+            )?;
+        }
+
+        if !self.changes.is_empty() {
+            writeln!(out)?;
+        }
+        for change in &self.changes {
+            writeln!(
+                out,
+                "{0:<1$}{2:?} -> {3:?}",
+                change.name, width, change.old, change.new
+            )?;
+        }
+        for name in &self.added {
+            writeln!(out, "added   {}", name)?;
+        }
+        for name in &self.removed {
+            writeln!(out, "removed {}", name)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn by_name(manifest: &Manifest) -> BTreeMap<&str, &InstanceRecord> {
+    manifest
+        .instances
+        .iter()
+        .map(|i| (i.name.as_str(), i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::manifest::Source;
+
+    fn record(name: &str, sha256: &str, colors: Vec<usize>) -> InstanceRecord {
+        InstanceRecord {
+            name: name.to_string(),
+            source: Source::File {
+                path: format!("old/{}", name),
+                sha256: sha256.to_string(),
+            },
+            colors,
+        }
+    }
+
+    #[test]
+    fn delta_per_algorithm() {
+        let algorithms = vec!["lf".to_string(), "sdo".to_string()];
+        let mut old = Manifest::new(0, algorithms.clone(), "AdjList");
+        old.instances = vec![
+            record("a", "1", vec![5, 4]),
+            record("b", "2", vec![7, 7]),
+            record("c", "3", vec![3, 3]),
+        ];
+
+        let mut new = Manifest::new(0, algorithms.clone(), "AdjList");
+        new.instances = vec![
+            old.reuse("a", &record("a", "1", vec![]).source, &algorithms)
+                .unwrap(),
+            record("b", "4", vec![6, 8]),
+            record("d", "5", vec![2, 2]),
+        ];
+        assert!(old
+            .reuse("b", &new.instances[1].source, &algorithms)
+            .is_none());
+
+        let delta = Delta::new(&old, &new);
+
+        assert_eq!(delta.improved, vec![1, 0]);
+        assert_eq!(delta.regressed, vec![0, 1]);
+        assert_eq!(delta.changes.len(), 1);
+        assert_eq!(delta.added, vec!["d".to_string()]);
+        assert_eq!(delta.removed, vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn reuse_by_algorithm() {
+        let mut old = Manifest::new(0, vec!["lf".to_string(), "sdo".to_string()], "AdjList");
+        old.instances = vec![record("a", "1", vec![5, 4])];
+        let source = record("a", "1", vec![]).source;
+
+        // Colors follow the new order, new algorithms need a new run
+        let reordered = ["sdo".to_string(), "lf".to_string()];
+        let added = ["lf".to_string(), "ig".to_string()];
+        assert_eq!(
+            old.reuse("a", &source, &reordered).unwrap().colors,
+            vec![4, 5]
+        );
+        assert!(old.reuse("a", &source, &added).is_none());
+    }
+}
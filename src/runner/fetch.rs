@@ -0,0 +1,345 @@
+//! Download of the DIMACS graph coloring benchmark instances into a local cache.
+//!
+//! Every download is checked to parse as a DIMACS graph with the number of
+//! vertices listed in the catalog. Instances with a SHA-256 checksum pinned in
+//! the catalog are verified against it on every fetch. Checksums of all other
+//! instances are kept in a ```SHA256SUMS``` file in the cache directory: cached
+//! instances are verified against it, and instances without a checksum have
+//! theirs recorded. Sharing the file pins the versions of these instances too.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::graph::{parse_dimacs, StaticGraph};
+use crate::runner::manifest::sha256_hex;
+
+/// Location of the DIMACS instances.
+pub const DIMACS_URL: &str = "https://mat.tepper.cmu.edu/COLOR/instances/";
+
+/// Name of the checksum file in the cache directory.
+const CHECKSUMS: &str = "SHA256SUMS";
+
+/// Largest instance accepted from the server.
+const MAX_BYTES: u64 = 64 << 20;
+
+/// Benchmark instance of the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instance {
+    /// File name without the ```.col``` extension.
+    pub name: &'static str,
+    pub vertices: usize,
+    /// Part of the small subset which is colored quickly by all algorithms.
+    pub curated: bool,
+    /// Hex encoded SHA-256 checksum of the file, if pinned in the catalog.
+    pub sha256: Option<&'static str>,
+}
+
+impl Instance {
+    /// Pins the checksum of the file, such that no other version is accepted.
+    pub const fn pinned(self, sha256: &'static str) -> Self {
+        Instance {
+            sha256: Some(sha256),
+            ..self
+        }
+    }
+}
+
+const fn instance(name: &'static str, vertices: usize, curated: bool) -> Instance {
+    Instance {
+        name,
+        vertices,
+        curated,
+        sha256: None,
+    }
+}
+
+/// The DIMACS graph coloring benchmark set.
+/// Checksums are pinned with ```Instance::pinned``` from the ```SHA256SUMS``` of a
+/// fetch whose instances were checked against the originals.
+pub const DIMACS: &[Instance] = &[
+    instance("myciel3", 11, true),
+    instance("myciel4", 23, true),
+    instance("myciel5", 47, true),
+    instance("myciel6", 95, false),
+    instance("myciel7", 191, false),
+    instance("queen5_5", 25, true),
+    instance("queen6_6", 36, true),
+    instance("queen7_7", 49, false),
+    instance("queen8_8", 64, false),
+    instance("queen8_12", 96, false),
+    instance("queen9_9", 81, false),
+    instance("queen10_10", 100, false),
+    instance("queen11_11", 121, false),
+    instance("queen12_12", 144, false),
+    instance("queen13_13", 169, false),
+    instance("queen14_14", 196, false),
+    instance("queen15_15", 225, false),
+    instance("queen16_16", 256, false),
+    instance("huck", 74, true),
+    instance("jean", 80, true),
+    instance("david", 87, true),
+    instance("anna", 138, true),
+    instance("homer", 561, false),
+    instance("games120", 120, true),
+    instance("miles250", 128, true),
+    instance("miles500", 128, false),
+    instance("miles750", 128, false),
+    instance("miles1000", 128, false),
+    instance("miles1500", 128, false),
+    instance("mulsol.i.1", 197, true),
+    instance("mulsol.i.2", 188, false),
+    instance("mulsol.i.3", 184, false),
+    instance("mulsol.i.4", 185, false),
+    instance("mulsol.i.5", 186, false),
+    instance("zeroin.i.1", 211, true),
+    instance("zeroin.i.2", 211, false),
+    instance("zeroin.i.3", 206, false),
+    instance("fpsol2.i.1", 496, false),
+    instance("fpsol2.i.2", 451, false),
+    instance("fpsol2.i.3", 425, false),
+    instance("inithx.i.1", 864, false),
+    instance("inithx.i.2", 645, false),
+    instance("inithx.i.3", 621, false),
+    instance("le450_5a", 450, true),
+    instance("le450_5b", 450, false),
+    instance("le450_5c", 450, false),
+    instance("le450_5d", 450, false),
+    instance("le450_15a", 450, false),
+    instance("le450_15b", 450, false),
+    instance("le450_15c", 450, false),
+    instance("le450_15d", 450, false),
+    instance("le450_25a", 450, false),
+    instance("le450_25b", 450, false),
+    instance("le450_25c", 450, false),
+    instance("le450_25d", 450, false),
+    instance("school1", 385, false),
+    instance("school1_nsh", 352, false),
+    instance("DSJC125.1", 125, true),
+    instance("DSJC125.5", 125, true),
+    instance("DSJC125.9", 125, false),
+    instance("DSJC250.1", 250, false),
+    instance("DSJC250.5", 250, false),
+    instance("DSJC250.9", 250, false),
+    instance("DSJC500.1", 500, false),
+    instance("DSJC500.5", 500, false),
+    instance("DSJC500.9", 500, false),
+    instance("DSJC1000.1", 1000, false),
+    instance("DSJC1000.5", 1000, false),
+    instance("DSJC1000.9", 1000, false),
+    instance("DSJR500.1", 500, false),
+    instance("DSJR500.1c", 500, false),
+    instance("DSJR500.5", 500, false),
+    instance("flat300_20_0", 300, false),
+    instance("flat300_26_0", 300, false),
+    instance("flat300_28_0", 300, false),
+    instance("flat1000_50_0", 1000, false),
+    instance("flat1000_60_0", 1000, false),
+    instance("flat1000_76_0", 1000, false),
+    instance("latin_square_10", 900, false),
+];
+
+/// Returns the default cache directory of the DIMACS instances,
+/// ```$XDG_CACHE_HOME/graml/dimacs``` or ```~/.cache/graml/dimacs```.
+pub fn default_cache() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+
+    Some(base.join("graml").join("dimacs"))
+}
+
+/// Downloads the DIMACS instances, or only the curated subset, into ```dir```.
+/// See ```fetch_instances```.
+pub fn fetch_dimacs(dir: impl AsRef<Path>, curated: bool) -> io::Result<Vec<PathBuf>> {
+    let instances = DIMACS
+        .iter()
+        .filter(|i| i.curated || !curated)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    fetch_instances(dir, &instances, DIMACS_URL)
+}
+
+/// Downloads the instances from ```url``` into ```dir``` unless already cached and
+/// returns their paths. Instances failing verification are reported as
+/// ```InvalidData``` errors and not stored.
+pub fn fetch_instances(
+    dir: impl AsRef<Path>,
+    instances: &[Instance],
+    url: &str,
+) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let sums = dir.join(CHECKSUMS);
+    let mut checksums = match fs::read_to_string(&sums) {
+        Ok(text) => parse_checksums(&text)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut paths = Vec::new();
+    for instance in instances {
+        let file = format!("{}.col", instance.name);
+        let path = dir.join(&file);
+
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => download(&format!("{}{}", url, file))?,
+            Err(e) => return Err(e),
+        };
+
+        // Pinned checksums take precedence over the recorded ones
+        let checksum = sha256_hex(&bytes);
+        let expected = match instance.sha256 {
+            Some(pinned) => Some(pinned.to_lowercase()),
+            None => checksums.get(&file).cloned(),
+        };
+        if expected.is_some_and(|c| c != checksum) {
+            return Err(invalid(&format!("checksum mismatch of {}", file)));
+        }
+        verify_instance(instance, &bytes)?;
+
+        if !path.exists() {
+            let tmp = dir.join(format!("{}.tmp", file));
+            fs::write(&tmp, &bytes)?;
+            fs::rename(&tmp, &path)?;
+        }
+        if instance.sha256.is_none() {
+            checksums.insert(file, checksum);
+            fs::write(&sums, write_checksums(&checksums))?;
+        }
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Checks that the instance parses as a DIMACS graph of the catalogued size.
+pub fn verify_instance(instance: &Instance, bytes: &[u8]) -> io::Result<()> {
+    let graph = parse_dimacs(bytes)?;
+
+    if graph.num_vertices() != instance.vertices {
+        return Err(invalid(&format!(
+            "{} has {} vertices instead of {}",
+            instance.name,
+            graph.num_vertices(),
+            instance.vertices
+        )));
+    }
+
+    Ok(())
+}
+
+fn download(url: &str) -> io::Result<Vec<u8>> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| io::Error::other(format!("{}: {}", url, e)))?;
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_BYTES)
+        .read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Parses lines of a checksum and a file name, as written by ```sha256sum```.
+fn parse_checksums(text: &str) -> io::Result<BTreeMap<String, String>> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut splits = line.split_whitespace();
+            match (splits.next(), splits.next()) {
+                (Some(sum), Some(file)) => Ok((file.to_string(), sum.to_lowercase())),
+                _ => Err(invalid(&format!("malformed checksum line '{}'", line))),
+            }
+        })
+        .collect()
+}
+
+fn write_checksums(checksums: &BTreeMap<String, String>) -> String {
+    checksums
+        .iter()
+        .map(|(file, sum)| format!("{}  {}\n", sum, file))
+        .collect()
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let text = format!("{}  a.col\n", sha256_hex(b"abc"));
+        assert_eq!(write_checksums(&parse_checksums(&text).unwrap()), text);
+        assert!(parse_checksums("abc\n").is_err());
+    }
+
+    #[test]
+    fn fetch_cached() {
+        let dir = env::temp_dir().join(format!("graml-fetch-{}", process::id()));
+        let triangle = instance("triangle", 3, true);
+        let square = instance("square", 4, true);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("triangle.col"),
+            "p edge 3 3\ne 1 2\ne 2 3\ne 3 1\n",
+        )
+        .unwrap();
+        fs::write(dir.join("square.col"), "p edge 3 0\n").unwrap();
+
+        // Cached instances are not downloaded, their checksums are recorded
+        let paths = fetch_instances(&dir, &[triangle], "http://invalid/").unwrap();
+        assert_eq!(paths, vec![dir.join("triangle.col")]);
+        assert!(fetch_instances(&dir, &[square], "http://invalid/").is_err());
+
+        fs::write(dir.join("triangle.col"), "p edge 3 1\ne 1 2\n").unwrap();
+        let changed = fetch_instances(&dir, &[triangle], "http://invalid/");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(changed.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn fetch_pinned() {
+        let dir = env::temp_dir().join(format!("graml-pinned-{}", process::id()));
+        let text = "p edge 3 3\ne 1 2\ne 2 3\ne 3 1\n";
+        // Checksum of "abc" instead of the file
+        let triangle = instance("triangle", 3, true)
+            .pinned("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("triangle.col"), text).unwrap();
+
+        // The pinned checksum is checked even if the recorded one matches
+        fs::write(
+            dir.join(CHECKSUMS),
+            format!("{}  triangle.col\n", sha256_hex(text.as_bytes())),
+        )
+        .unwrap();
+        let mismatch = fetch_instances(&dir, &[triangle], "http://invalid/");
+
+        let pinned =
+            triangle.pinned("4DBB0BDA6981C17E4179CB7BFF584C083BD89F505842253B3798BC9927EA64A2");
+        let matching = fetch_instances(&dir, &[pinned], "http://invalid/");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(mismatch.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(matching.unwrap(), vec![dir.join("triangle.col")]);
+    }
+}
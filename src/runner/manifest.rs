@@ -0,0 +1,304 @@
+//! Manifest of a comparison run, recording everything needed to reproduce it.
+//!
+//! Besides the results, a ```Manifest``` records the crate version and git commit,
+//! the seed and options of the run, the graph backend, the machine it ran on and
+//! the source of every instance: the seed of a random graph or the SHA-256 hash of
+//! a file. A replay reruns the instances and compares the results with
+//! ```differences```.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process::Command;
+
+/// Record of a comparison run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    /// Commit the binary was built from, if known.
+    pub git_hash: Option<String>,
+    /// Seed from which the seeds of the random instances are derived.
+    pub seed: u64,
+    pub algorithms: Vec<String>,
+    /// Further options of the run by name.
+    pub options: BTreeMap<String, String>,
+    /// Type of graph the instances were stored in.
+    pub backend: String,
+    pub hardware: Hardware,
+    pub instances: Vec<InstanceRecord>,
+}
+
+/// Machine a run was performed on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hardware {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub cpu_model: Option<String>,
+}
+
+/// Where an instance came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Source {
+    /// Random graph with edge probability ```p```, generated from ```seed```.
+    Random { vertices: usize, p: f32, seed: u64 },
+    /// Graph file with the SHA-256 hash of its contents.
+    File { path: String, sha256: String },
+}
+
+/// Instance of a run with the number of colors of every algorithm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceRecord {
+    pub name: String,
+    pub source: Source,
+    /// Number of colors in the order of the manifest's algorithms.
+    pub colors: Vec<usize>,
+}
+
+impl Manifest {
+    /// Manifest of a run on the current machine and build, without instances.
+    pub fn new(seed: u64, algorithms: Vec<String>, backend: &str) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: git_hash(),
+            seed,
+            algorithms,
+            options: BTreeMap::new(),
+            backend: backend.to_string(),
+            hardware: Hardware::detect(),
+            instances: Vec::new(),
+        }
+    }
+
+    /// Sets an option of the run.
+    pub fn option(mut self, name: &str, value: impl ToString) -> Self {
+        self.options.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Loads a manifest written by ```save```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Writes the manifest as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        writeln!(out)?;
+        out.flush()
+    }
+
+    /// Checks that every file instance still has its recorded hash, reporting
+    /// changed files as ```InvalidData``` errors.
+    pub fn verify_instances(&self) -> io::Result<()> {
+        for instance in &self.instances {
+            if let Source::File { path, sha256 } = &instance.source {
+                if file_hash(path)? != *sha256 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} changed since the run", path),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recorded results of a file instance with the same content as ```source```,
+    /// renamed to ```name```, with the colors of ```algorithms``` in their order.
+    /// ```None``` if no file instance had this content or an algorithm was not run.
+    pub fn reuse(
+        &self,
+        name: &str,
+        source: &Source,
+        algorithms: &[String],
+    ) -> Option<InstanceRecord> {
+        let hash = match source {
+            Source::File { sha256, .. } => sha256,
+            Source::Random { .. } => return None,
+        };
+
+        let instance = self
+            .instances
+            .iter()
+            .find(|i| matches!(&i.source, Source::File { sha256, .. } if sha256 == hash))?;
+        let colors = algorithms
+            .iter()
+            .map(|a| {
+                let column = self.algorithms.iter().position(|b| a == b)?;
+                instance.colors.get(column).cloned()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(InstanceRecord {
+            name: name.to_string(),
+            source: source.clone(),
+            colors,
+        })
+    }
+
+    /// Describes every difference in the instances and their results of a replay
+    /// of this run. Version, machine and options are not compared.
+    pub fn differences(&self, replay: &Manifest) -> Vec<String> {
+        let mut differences = Vec::new();
+
+        if self.algorithms != replay.algorithms {
+            differences.push(format!(
+                "algorithms {:?} instead of {:?}",
+                replay.algorithms, self.algorithms
+            ));
+        }
+        if self.instances.len() != replay.instances.len() {
+            differences.push(format!(
+                "{} instances instead of {}",
+                replay.instances.len(),
+                self.instances.len()
+            ));
+        }
+
+        for (i, (a, b)) in self.instances.iter().zip(&replay.instances).enumerate() {
+            if a.source != b.source {
+                differences.push(format!(
+                    "instance {} ({}) has a different source",
+                    i, a.name
+                ));
+            } else if a.colors != b.colors {
+                differences.push(format!(
+                    "instance {} ({}) colored with {:?} instead of {:?}",
+                    i, a.name, b.colors, a.colors
+                ));
+            }
+        }
+
+        differences
+    }
+}
+
+impl Hardware {
+    /// Describes the current machine.
+    pub fn detect() -> Self {
+        // Only available on Linux
+        let cpu_model = fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+            info.lines()
+                .find(|line| line.starts_with("model name"))
+                .and_then(|line| line.split(':').nth(1))
+                .map(|model| model.trim().to_string())
+        });
+
+        Self {
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            cpus: num_cpus::get(),
+            cpu_model,
+        }
+    }
+}
+
+impl Source {
+    /// Source of a graph file, hashing its contents.
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+
+        Ok(Source::File {
+            path: path.to_string_lossy().into_owned(),
+            sha256: file_hash(path)?,
+        })
+    }
+}
+
+/// Returns the SHA-256 hash of the bytes as lowercase hex string.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn file_hash(path: impl AsRef<Path>) -> io::Result<String> {
+    Ok(sha256_hex(&fs::read(path)?))
+}
+
+/// Commit of the source directory the crate was built from, if it is a git
+/// checkout and git is installed.
+fn git_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|hash| hash.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn manifest_roundtrip() {
+        let file = env::temp_dir().join(format!("graml-manifest-{}.col", process::id()));
+        let path = env::temp_dir().join(format!("graml-manifest-{}.json", process::id()));
+        fs::write(&file, "p edge 2 1\ne 1 2\n").unwrap();
+
+        let mut manifest = Manifest::new(7, vec!["sdo".to_string()], "AdjList").option("p", 0.5);
+        manifest.instances.push(InstanceRecord {
+            name: "edge".to_string(),
+            source: Source::file(&file).unwrap(),
+            colors: vec![2],
+        });
+        manifest.instances.push(InstanceRecord {
+            name: "random".to_string(),
+            source: Source::Random {
+                vertices: 10,
+                p: 0.5,
+                seed: 7,
+            },
+            colors: vec![4],
+        });
+        manifest.save(&path).unwrap();
+        let loaded = Manifest::load(&path).unwrap();
+
+        let verified = loaded.verify_instances();
+        fs::write(&file, "p edge 2 0\n").unwrap();
+        let changed = loaded.verify_instances();
+        fs::remove_file(&file).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, manifest);
+        assert!(verified.is_ok());
+        assert_eq!(changed.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn replay_differences() {
+        let mut manifest = Manifest::new(1, vec!["lf".to_string()], "AdjList");
+        manifest.instances.push(InstanceRecord {
+            name: "random".to_string(),
+            source: Source::Random {
+                vertices: 10,
+                p: 0.5,
+                seed: 1,
+            },
+            colors: vec![4],
+        });
+
+        let mut replay = manifest.clone();
+        assert!(manifest.differences(&replay).is_empty());
+
+        replay.instances[0].colors = vec![5];
+        assert_eq!(manifest.differences(&replay).len(), 1);
+    }
+}
@@ -0,0 +1,92 @@
+//! Running solvers on many instances or configurations.
+//!
+//! ```run_parallel``` distributes jobs over worker threads, ```tuning``` searches
+//! parameters of the metaheuristics and ```pareto``` compares solvers on several
+//! objectives. ```report``` renders the results of a batch comparison as HTML and
+//! ```manifest``` records what is needed to reproduce it. ```delta``` compares the
+//! results of two runs over versions of a dataset. ```pool``` keeps worker threads
+//! alive across calls for applications coloring many graphs.
+//! ```fetch``` downloads the DIMACS benchmark instances and requires the ```http```
+//! feature.
+
+pub mod delta;
+#[cfg(feature = "http")]
+pub mod fetch;
+pub mod manifest;
+pub mod pareto;
+pub mod pool;
+pub mod report;
+pub mod tuning;
+
+/// Applies ```work``` to every job on ```workers``` threads and passes the results to
+/// ```consume``` on the calling thread in order of completion.
+/// Jobs are emitted by ```produce``` on a separate thread into a bounded queue, such
+/// that it does not run far ahead of the workers. Returns the result of ```produce```.
+pub fn run_parallel<J, R, P>(
+    workers: usize,
+    produce: impl FnOnce(&mut dyn FnMut(J)) -> P + Send,
+    work: impl Fn(J) -> R + Sync,
+    mut consume: impl FnMut(R),
+) -> P
+where
+    J: Send,
+    R: Send,
+    P: Send,
+{
+    let workers = workers.max(1);
+    let work = &work;
+
+    crossbeam::scope(|s| {
+        let (tx_job, rx_job) = crossbeam::bounded(16 * workers);
+        let (tx_res, rx_res) = crossbeam::unbounded();
+
+        for _ in 0..workers {
+            let rx_ = rx_job.clone();
+            let tx_ = tx_res.clone();
+
+            s.spawn(move |_| {
+                for job in rx_.iter() {
+                    tx_.send(work(job)).unwrap();
+                }
+            });
+        }
+
+        // Results end once all workers are done, which happens once the producer is
+        drop(tx_res);
+        let producer = s.spawn(move |_| produce(&mut |job| tx_job.send(job).unwrap()));
+
+        for result in rx_res.iter() {
+            consume(result);
+        }
+
+        producer.join().unwrap()
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_all_jobs() {
+        let mut results = Vec::new();
+
+        let produced = run_parallel(
+            4,
+            |emit| {
+                for i in 0..1000 {
+                    emit(i);
+                }
+                1000
+            },
+            |i: usize| i * i,
+            |r| results.push(r),
+        );
+
+        results.sort_unstable();
+
+        assert_eq!(produced, 1000);
+        assert_eq!(results, (0..1000).map(|i| i * i).collect::<Vec<_>>());
+    }
+}
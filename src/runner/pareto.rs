@@ -0,0 +1,233 @@
+//! Multi-objective comparison of colorings.
+//!
+//! When colorings are schedules, the number of colors is not the only concern.
+//! An ```Experiment``` runs a set of solvers and scores each coloring by its number
+//! of colors, the imbalance of its class sizes and the number of vertices changed
+//! with respect to a previous coloring. The ```ParetoReport``` marks the colorings
+//! not dominated in all three objectives and can be exported as JSON or CSV.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::coloring::{coloring_distance, peak_color, Coloring};
+use crate::graph::StaticGraph;
+
+type Solver<'a, G> = Box<dyn Fn(&G) -> Coloring + 'a>;
+
+/// Named solvers compared on a graph.
+pub struct Experiment<'a, G> {
+    solvers: Vec<(String, Solver<'a, G>)>,
+}
+
+/// Objectives of a coloring, all to be minimized.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Objectives {
+    pub colors: usize,
+    /// Size of the largest class relative to the average class size, ```1``` if
+    /// all classes have the same size.
+    pub imbalance: f64,
+    /// Vertices whose color differs from the previous coloring after aligning the
+    /// color classes, ```0``` without a previous coloring.
+    pub changes: usize,
+    /// Largest color index, which bounds the palette needed if colors are used as
+    /// indices. Not compared by ```dominates```, it is ```colors - 1``` for compact
+    /// colorings.
+    pub peak: usize,
+}
+
+/// Coloring found by a solver together with its objectives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub solver: String,
+    pub objectives: Objectives,
+    /// Whether no other solution is at least as good in all objectives and better in one.
+    pub pareto: bool,
+    pub coloring: Coloring,
+}
+
+/// Solutions of all solvers of an experiment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParetoReport {
+    pub solutions: Vec<Solution>,
+}
+
+impl Objectives {
+    /// Computes the objectives of ```coloring``` relative to ```previous```.
+    pub fn new(coloring: &[usize], previous: Option<&[usize]>) -> Self {
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for &c in coloring {
+            *sizes.entry(c).or_default() += 1;
+        }
+
+        let colors = sizes.len();
+        let imbalance = match sizes.values().max() {
+            Some(&max) => max as f64 * colors as f64 / coloring.len() as f64,
+            None => 1.0,
+        };
+
+        Self {
+            colors,
+            imbalance,
+            changes: previous.map_or(0, |p| coloring_distance(p, coloring)),
+            peak: peak_color(coloring).unwrap_or(0),
+        }
+    }
+
+    /// Whether these objectives are at least as good as ```other``` in all objectives
+    /// and better in at least one.
+    pub fn dominates(&self, other: &Objectives) -> bool {
+        self.colors <= other.colors
+            && self.imbalance <= other.imbalance
+            && self.changes <= other.changes
+            && (self.colors < other.colors
+                || self.imbalance < other.imbalance
+                || self.changes < other.changes)
+    }
+}
+
+impl<'a, G: StaticGraph> Experiment<'a, G> {
+    pub fn new() -> Self {
+        Self {
+            solvers: Vec::new(),
+        }
+    }
+
+    /// Adds a solver to the experiment.
+    pub fn solver(mut self, name: &str, solver: impl Fn(&G) -> Coloring + 'a) -> Self {
+        self.solvers.push((name.to_string(), Box::new(solver)));
+        self
+    }
+
+    /// Runs all solvers on the graph. Changes are counted with respect to ```previous```,
+    /// which has to color the same vertices.
+    pub fn run(&self, graph: &G, previous: Option<&[usize]>) -> ParetoReport {
+        let mut solutions = self
+            .solvers
+            .iter()
+            .map(|(name, solver)| {
+                let coloring = solver(graph);
+
+                Solution {
+                    solver: name.clone(),
+                    objectives: Objectives::new(&coloring, previous),
+                    pareto: false,
+                    coloring,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..solutions.len() {
+            let o = solutions[i].objectives;
+            solutions[i].pareto = !solutions.iter().any(|s| s.objectives.dominates(&o));
+        }
+
+        ParetoReport { solutions }
+    }
+}
+
+impl<'a, G: StaticGraph> Default for Experiment<'a, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParetoReport {
+    /// Returns the solutions on the Pareto front.
+    pub fn front(&self) -> impl Iterator<Item = &Solution> {
+        self.solutions.iter().filter(|s| s.pareto)
+    }
+
+    /// Writes the report including the colorings as JSON.
+    pub fn write_json<W: Write>(&self, out: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(out, self).map_err(io::Error::from)
+    }
+
+    /// Writes one CSV row per solution with its objectives, without the colorings.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "solver,colors,peak,imbalance,changes,pareto")?;
+
+        for s in &self.solutions {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                s.solver,
+                s.objectives.colors,
+                s.objectives.peak,
+                s.objectives.imbalance,
+                s.objectives.changes,
+                s.pareto
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::*;
+    use crate::graph::*;
+
+    #[test]
+    fn objectives_balance() {
+        let o = Objectives::new(&[0, 0, 0, 1], Some(&[1, 1, 0, 0]));
+
+        assert_eq!(o.colors, 2);
+        assert_eq!(o.imbalance, 1.5);
+        assert_eq!(o.changes, 1);
+        assert_eq!(o.peak, 1);
+        assert!(!o.dominates(&o));
+    }
+
+    #[test]
+    fn pareto_front() {
+        // Star, the center alone in a class versus three classes of equal size
+        let mut g = AdjList::new();
+        for i in 1..6 {
+            g.add_edge(0, i);
+        }
+
+        let report = Experiment::new()
+            .solver("greedy", sl_coloring)
+            .solver("balanced", |_: &AdjList| vec![0, 1, 1, 2, 2, 2])
+            .solver("wasteful", |_: &AdjList| vec![0, 1, 2, 3, 4, 5])
+            .solver("same", sl_coloring)
+            .run(&g, None);
+
+        let front = report
+            .front()
+            .map(|s| s.solver.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(front, vec!["greedy", "balanced", "wasteful", "same"]);
+
+        let report = Experiment::new()
+            .solver("greedy", sl_coloring)
+            .solver("worse", |_: &AdjList| vec![0, 1, 2, 3, 1, 1])
+            .run(&g, None);
+
+        assert_eq!(report.front().count(), 1);
+    }
+
+    #[test]
+    fn pareto_export() {
+        let g = AdjList::complete(3);
+        let report = Experiment::new()
+            .solver("lf", lf_coloring)
+            .run(&g, Some(&[2, 1, 0]));
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "solver,colors,peak,imbalance,changes,pareto\nlf,3,2,1,0,true\n"
+        );
+        let parsed: ParetoReport = serde_json::from_slice(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -0,0 +1,163 @@
+use crossbeam::Sender;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::coloring::{Coloring, ColoringAlgo};
+use crate::graph::StaticGraph;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Handle owning a pool of worker threads, which are reused across calls instead
+/// of being started for every batch as by ```run_parallel```. Meant for services
+/// coloring many small graphs, where starting threads dominates the latency.
+///
+/// Dropping the runner shuts it down like ```shutdown```.
+#[derive(Debug)]
+pub struct Runner {
+    tasks: Option<Sender<Task>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Runner {
+    /// Starts a pool of ```threads``` worker threads, at least one.
+    pub fn new(threads: usize) -> Self {
+        let (tasks, queue) = crossbeam::unbounded::<Task>();
+
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let queue = queue.clone();
+                thread::Builder::new()
+                    .name(format!("graml-runner-{}", i))
+                    .spawn(move || {
+                        for task in queue.iter() {
+                            task();
+                        }
+                    })
+                    .expect("failed to start a worker thread")
+            })
+            .collect();
+
+        Self {
+            tasks: Some(tasks),
+            workers,
+        }
+    }
+
+    /// Starts a pool with one worker thread per processor.
+    pub fn with_cpus() -> Self {
+        Self::new(num_cpus::get())
+    }
+
+    /// Returns the number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Applies ```work``` to every job on the worker threads and returns the results
+    /// in the order of the jobs. A panic of ```work``` is resumed on the calling
+    /// thread once all jobs are done, and leaves the workers usable.
+    pub fn map<J, R>(&self, jobs: Vec<J>, work: impl Fn(J) -> R + Send + Sync + 'static) -> Vec<R>
+    where
+        J: Send + 'static,
+        R: Send + 'static,
+    {
+        let tasks = self.tasks.as_ref().unwrap();
+        let work = Arc::new(work);
+        let (tx, rx) = crossbeam::unbounded();
+
+        let count = jobs.len();
+        for (i, job) in jobs.into_iter().enumerate() {
+            let (work, tx) = (Arc::clone(&work), tx.clone());
+
+            tasks
+                .send(Box::new(move || {
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| work(job)));
+                    tx.send((i, result)).unwrap();
+                }))
+                .unwrap();
+        }
+
+        let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+        let mut panicked = None;
+        for (i, result) in rx.iter().take(count) {
+            match result {
+                Ok(result) => results[i] = Some(result),
+                Err(payload) => panicked = Some(payload),
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Colors every graph with ```algo``` on the worker threads and returns the
+    /// colorings in the order of the graphs. Graphs shared with the caller can be
+    /// passed as ```Arc```.
+    pub fn color_many<G>(&self, graphs: Vec<G>, algo: ColoringAlgo) -> Vec<Coloring>
+    where
+        G: StaticGraph + Send + 'static,
+    {
+        self.map(graphs, move |graph| algo.color(&graph))
+    }
+
+    /// Finishes the queued jobs and waits for the worker threads to exit.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        // Workers exit once the queue is closed and empty
+        self.tasks = None;
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+impl Drop for Runner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring::check_coloring;
+    use crate::graph::*;
+
+    #[test]
+    fn runner_reuses_workers() {
+        let runner = Runner::new(3);
+        assert_eq!(runner.threads(), 3);
+
+        for _ in 0..5 {
+            let graphs = (0..20)
+                .map(|_| Arc::new(AdjList::random(30, 0.3)))
+                .collect::<Vec<_>>();
+            let colorings = runner.color_many(graphs.clone(), ColoringAlgo::SDO);
+
+            assert_eq!(colorings.len(), 20);
+            assert!(graphs
+                .iter()
+                .zip(&colorings)
+                .all(|(g, c)| check_coloring(g, c)));
+        }
+
+        let names = runner.map((0..50).collect(), |_: usize| {
+            thread::current().name().unwrap().to_string()
+        });
+        assert!(names.iter().all(|n| n.starts_with("graml-runner-")));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            runner.map(vec![1, 0, 2], |d: usize| 2 / d)
+        }));
+        assert!(result.is_err());
+        assert_eq!(runner.map(vec![1, 2], |d: usize| 2 / d), vec![2, 1]);
+
+        runner.shutdown();
+    }
+}
@@ -0,0 +1,228 @@
+//! HTML report of a batch comparison of coloring algorithms.
+//!
+//! A ```BatchReport``` collects the number of colors and running time of every
+//! algorithm on every instance. ```write_html``` renders them as a self-contained
+//! page with two heatmaps, sortable by clicking a column header, and the features
+//! of every instance linked from its row.
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::analysis::Features;
+use crate::graph::GraphMeta;
+
+/// Results of all algorithms on a single instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceResult {
+    pub name: String,
+    pub features: Features,
+    /// Number of colors of each algorithm, in the order of the report's algorithms.
+    pub colors: Vec<usize>,
+    pub times: Vec<Duration>,
+    /// Provenance of the instance, missing in reports of older versions.
+    #[serde(default)]
+    pub meta: GraphMeta,
+}
+
+/// Results of a batch of instances.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchReport {
+    pub algorithms: Vec<String>,
+    pub instances: Vec<InstanceResult>,
+}
+
+impl BatchReport {
+    pub fn new(algorithms: Vec<String>) -> Self {
+        Self {
+            algorithms,
+            instances: Vec::new(),
+        }
+    }
+
+    /// Adds the results of an instance.
+    /// Panics if the number of results differs from the number of algorithms.
+    pub fn push(&mut self, instance: InstanceResult) {
+        assert_eq!(instance.colors.len(), self.algorithms.len());
+        assert_eq!(instance.times.len(), self.algorithms.len());
+
+        self.instances.push(instance);
+    }
+
+    /// Writes the report as a self-contained HTML page.
+    /// Cells are shaded from green for the best to red for the worst algorithm
+    /// on the instance.
+    pub fn write_html<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(out, "<html>\n<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(out, "<title>Coloring comparison</title>")?;
+        writeln!(out, "<style>{}</style>", STYLE)?;
+        writeln!(out, "</head>\n<body>")?;
+
+        writeln!(out, "<h1>Number of colors</h1>")?;
+        self.write_heatmap(
+            &mut out,
+            |r| r.colors.iter().map(|&c| c as f64).collect(),
+            |v| format!("{}", v),
+        )?;
+
+        writeln!(out, "<h1>Time (ms)</h1>")?;
+        self.write_heatmap(
+            &mut out,
+            |r| r.times.iter().map(|t| t.as_secs_f64() * 1000.0).collect(),
+            |v| format!("{:.2}", v),
+        )?;
+
+        writeln!(out, "<h1>Instances</h1>")?;
+        writeln!(out, "<table class=\"sortable\">")?;
+        writeln!(
+            out,
+            "<tr><th>instance</th><th>vertices</th><th>edges</th><th>density</th>\
+             <th>max degree</th><th>avg degree</th><th>degeneracy</th>\
+             <th>assortativity</th><th>communities</th><th>modularity</th>\
+             <th>best known</th><th>source</th></tr>"
+        )?;
+        for (i, r) in self.instances.iter().enumerate() {
+            let f = &r.features;
+            writeln!(
+                out,
+                "<tr id=\"instance-{}\"><td>{}</td><td>{}</td><td>{}</td><td>{:.3}</td>\
+                 <td>{}</td><td>{:.2}</td><td>{}</td>\
+                 <td>{:.3}</td><td>{}</td><td>{:.3}</td><td>{}</td><td>{}</td></tr>",
+                i,
+                escape(&r.name),
+                f.vertices,
+                f.edges,
+                f.density,
+                f.max_degree,
+                f.avg_degree,
+                f.degeneracy,
+                f.assortativity,
+                f.communities,
+                f.modularity,
+                r.meta.chromatic.map_or(String::new(), |k| k.to_string()),
+                escape(r.meta.source.as_deref().unwrap_or(""))
+            )?;
+        }
+        writeln!(out, "</table>")?;
+
+        writeln!(out, "<script>{}</script>", SCRIPT)?;
+        writeln!(out, "</body>\n</html>")
+    }
+
+    /// Writes a table with one row per instance and one shaded cell per algorithm.
+    fn write_heatmap<W: Write>(
+        &self,
+        out: &mut W,
+        values: impl Fn(&InstanceResult) -> Vec<f64>,
+        format: impl Fn(f64) -> String,
+    ) -> io::Result<()> {
+        writeln!(out, "<table class=\"sortable\">")?;
+        write!(out, "<tr><th>instance</th>")?;
+        for a in &self.algorithms {
+            write!(out, "<th>{}</th>", escape(a))?;
+        }
+        writeln!(out, "</tr>")?;
+
+        for (i, r) in self.instances.iter().enumerate() {
+            let values = values(r);
+            let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+            write!(
+                out,
+                "<tr><td><a href=\"#instance-{}\">{}</a></td>",
+                i,
+                escape(&r.name)
+            )?;
+            for &v in &values {
+                // Hue 120 is green, 0 red
+                let t = if max > min {
+                    (v - min) / (max - min)
+                } else {
+                    0.0
+                };
+                write!(
+                    out,
+                    "<td data-value=\"{}\" style=\"background: hsl({:.0}, 70%, 75%)\">{}</td>",
+                    v,
+                    120.0 * (1.0 - t),
+                    format(v)
+                )?;
+            }
+            writeln!(out, "</tr>")?;
+        }
+
+        writeln!(out, "</table>")
+    }
+}
+
+const STYLE: &str = "body { font-family: sans-serif; } \
+table { border-collapse: collapse; margin-bottom: 2em; } \
+th, td { border: 1px solid #ccc; padding: 2px 8px; text-align: right; } \
+th { cursor: pointer; background: #eee; } \
+td:first-child { text-align: left; }";
+
+// Sorts a table by the clicked column, numerically where possible
+const SCRIPT: &str = "document.querySelectorAll('table.sortable th').forEach(function (th) {\n\
+  th.addEventListener('click', function () {\n\
+    var table = th.closest('table');\n\
+    var i = Array.prototype.indexOf.call(th.parentNode.children, th);\n\
+    var rows = Array.prototype.slice.call(table.rows, 1);\n\
+    var asc = table.dataset.sorted !== String(i);\n\
+    var key = function (row) {\n\
+      var cell = row.cells[i];\n\
+      var v = cell.dataset.value !== undefined ? cell.dataset.value : cell.textContent;\n\
+      return isNaN(parseFloat(v)) ? v : parseFloat(v);\n\
+    };\n\
+    rows.sort(function (a, b) {\n\
+      var x = key(a), y = key(b);\n\
+      return (x < y ? -1 : x > y ? 1 : 0) * (asc ? 1 : -1);\n\
+    });\n\
+    rows.forEach(function (row) { table.tBodies[0].appendChild(row); });\n\
+    table.dataset.sorted = asc ? String(i) : '';\n\
+  });\n\
+});";
+
+/// Escapes text for use in HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+
+    #[test]
+    fn report_html() {
+        let mut report = BatchReport::new(vec!["lf".to_string(), "sl".to_string()]);
+        report.push(InstanceResult {
+            name: "a<b>".to_string(),
+            features: Features::new(&AdjList::complete(4)),
+            colors: vec![5, 4],
+            times: vec![Duration::from_millis(3), Duration::from_millis(1)],
+            meta: GraphMeta {
+                source: Some("x & y".to_string()),
+                chromatic: Some(4),
+                ..GraphMeta::default()
+            },
+        });
+
+        let mut out = Vec::new();
+        report.write_html(&mut out).unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("<th>lf</th><th>sl</th>"));
+        assert!(html.contains("a&lt;b&gt;"));
+        assert!(!html.contains("a<b>"));
+        // Best green, worst red
+        assert!(html.contains("data-value=\"4\" style=\"background: hsl(120"));
+        assert!(html.contains("data-value=\"5\" style=\"background: hsl(0"));
+        assert!(html.contains("id=\"instance-0\""));
+        assert!(html.contains("<td>4</td><td>x &amp; y</td>"));
+    }
+}
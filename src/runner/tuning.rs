@@ -0,0 +1,387 @@
+//! Tuning of metaheuristic parameters on a set of training instances.
+//!
+//! A ```Tuner``` evaluates configurations drawn from a ```SearchSpace``` on all
+//! training instances and scores them by their average gap to the best coloring
+//! found for each instance, plus a penalty for the time they take.
+//! The resulting ```Profile```s can be saved and later loaded to configure the solvers.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::coloring::metaheuristics::*;
+use crate::coloring::{check_coloring, num_colors, Coloring};
+use crate::graph::StaticGraph;
+
+/// Parameters of a metaheuristic which can be tuned.
+pub trait Tunable: Clone {
+    /// Colors the graph using these parameters.
+    fn color<G: StaticGraph + Sync + ?Sized>(&self, g: &G) -> Coloring;
+}
+
+impl Tunable for GeneticParams {
+    fn color<G: StaticGraph + Sync + ?Sized>(&self, g: &G) -> Coloring {
+        genetic_coloring_with(g, self, &mut Monitor::new())
+    }
+}
+
+impl Tunable for IslandParams {
+    fn color<G: StaticGraph + Sync + ?Sized>(&self, g: &G) -> Coloring {
+        island_coloring_with(g, self, &mut Monitor::new())
+    }
+}
+
+/// Set of configurations the tuner chooses from.
+pub trait SearchSpace {
+    type Params: Tunable;
+
+    /// Returns every configuration in the space.
+    fn grid(&self) -> Vec<Self::Params>;
+
+    /// Returns a configuration drawn uniformly at random from the space.
+    fn sample(&self, rng: &mut StdRng) -> Self::Params;
+}
+
+/// Candidate values for the parameters of the genetic algorithm.
+/// Parameters without candidates are taken from ```base```.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneticSpace {
+    pub base: GeneticParams,
+    pub population: Vec<usize>,
+    pub crossover: Vec<Crossover>,
+    pub mutation_rate: Vec<f32>,
+    /// Fraction of the population carried over into the next generation.
+    pub elitism: Vec<f32>,
+    pub init: Vec<Initialization>,
+}
+
+impl Default for GeneticSpace {
+    fn default() -> Self {
+        Self {
+            base: GeneticParams::default(),
+            population: vec![20, 50, 100],
+            crossover: vec![
+                Crossover::SinglePoint,
+                Crossover::TwoPoint,
+                Crossover::Uniform,
+            ],
+            mutation_rate: vec![0.01, 0.05, 0.2],
+            elitism: vec![0.1, 0.5],
+            init: vec![
+                Initialization::default(),
+                Initialization { sl: 0.2, lf: 0.2 },
+            ],
+        }
+    }
+}
+
+impl GeneticSpace {
+    fn params(
+        &self,
+        population: usize,
+        crossover: Crossover,
+        mutation_rate: f32,
+        elitism: f32,
+        init: Initialization,
+    ) -> GeneticParams {
+        GeneticParams {
+            population,
+            crossover,
+            mutation_rate,
+            elitism: (elitism * population as f32).round() as usize,
+            init,
+            ..self.base.clone()
+        }
+    }
+}
+
+impl SearchSpace for GeneticSpace {
+    type Params = GeneticParams;
+
+    fn grid(&self) -> Vec<GeneticParams> {
+        let mut grid = Vec::new();
+
+        for &population in &self.population {
+            for &crossover in &self.crossover {
+                for &mutation_rate in &self.mutation_rate {
+                    for &elitism in &self.elitism {
+                        for &init in &self.init {
+                            grid.push(self.params(
+                                population,
+                                crossover,
+                                mutation_rate,
+                                elitism,
+                                init,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> GeneticParams {
+        self.params(
+            *choose(&self.population, rng).unwrap_or(&self.base.population),
+            *choose(&self.crossover, rng).unwrap_or(&self.base.crossover),
+            *choose(&self.mutation_rate, rng).unwrap_or(&self.base.mutation_rate),
+            *choose(&self.elitism, rng)
+                .unwrap_or(&(self.base.elitism as f32 / self.base.population as f32)),
+            *choose(&self.init, rng).unwrap_or(&self.base.init),
+        )
+    }
+}
+
+fn choose<'a, T>(candidates: &'a [T], rng: &mut StdRng) -> Option<&'a T> {
+    if candidates.is_empty() {
+        None
+    } else {
+        Some(&candidates[rng.gen_range(0, candidates.len())])
+    }
+}
+
+/// How the tuner selects configurations from the search space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Evaluate every configuration in the space.
+    Grid,
+    /// Evaluate a number of randomly drawn configurations.
+    Random { samples: usize, seed: u64 },
+}
+
+/// Runs configurations on training instances and ranks them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuner {
+    pub strategy: Strategy,
+    /// Number of runs per configuration and instance.
+    pub repetitions: usize,
+    /// Score penalty per second of average running time.
+    pub time_weight: f64,
+}
+
+impl Default for Tuner {
+    fn default() -> Self {
+        Self {
+            strategy: Strategy::Random {
+                samples: 20,
+                seed: 0,
+            },
+            repetitions: 1,
+            time_weight: 0.01,
+        }
+    }
+}
+
+/// Evaluated configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile<P> {
+    pub params: P,
+    /// Average relative gap ```(colors - best) / best``` over all runs, where ```best```
+    /// is the fewest colors any configuration achieved on the instance.
+    pub avg_gap: f64,
+    /// Average running time in seconds.
+    pub avg_time: f64,
+    /// ```avg_gap + time_weight * avg_time```, lower is better.
+    pub score: f64,
+}
+
+impl<P: Serialize + DeserializeOwned> Profile<P> {
+    /// Writes the profile to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(file, self).map_err(io::Error::from)
+    }
+
+    /// Reads a profile from a JSON file written by ```save```.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        serde_json::from_reader(file).map_err(io::Error::from)
+    }
+}
+
+impl Tuner {
+    /// Evaluates configurations from ```space``` on all ```instances```.
+    /// Returns the profiles of all evaluated configurations, best first.
+    pub fn tune<S, G>(&self, space: &S, instances: &[G]) -> Vec<Profile<S::Params>>
+    where
+        S: SearchSpace,
+        G: StaticGraph + Sync,
+    {
+        let configurations = match self.strategy {
+            Strategy::Grid => space.grid(),
+            Strategy::Random { samples, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..samples).map(|_| space.sample(&mut rng)).collect()
+            }
+        };
+
+        let repetitions = self.repetitions.max(1);
+
+        // Colors and seconds of each run, per configuration and instance
+        let runs = configurations
+            .iter()
+            .map(|params| {
+                instances
+                    .iter()
+                    .map(|g| {
+                        (0..repetitions)
+                            .map(|_| {
+                                let start = Instant::now();
+                                let c = params.color(g);
+                                let time = start.elapsed().as_secs_f64();
+
+                                assert!(check_coloring(g, &c));
+                                (num_colors(&c), time)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Fewest colors found on each instance
+        let best = (0..instances.len())
+            .map(|i| {
+                runs.iter()
+                    .flat_map(|r| r[i].iter().map(|&(colors, _)| colors))
+                    .min()
+                    .unwrap_or(0)
+                    .max(1)
+            })
+            .collect::<Vec<_>>();
+
+        let mut profiles = configurations
+            .into_iter()
+            .zip(runs)
+            .map(|(params, runs)| {
+                let mut gap = 0.0;
+                let mut time = 0.0;
+                let mut count = 0;
+
+                for (i, runs) in runs.iter().enumerate() {
+                    for &(colors, t) in runs {
+                        gap += (colors as f64 - best[i] as f64) / best[i] as f64;
+                        time += t;
+                        count += 1;
+                    }
+                }
+
+                let count = count.max(1) as f64;
+                let avg_gap = gap / count;
+                let avg_time = time / count;
+
+                Profile {
+                    params,
+                    avg_gap,
+                    avg_time,
+                    score: avg_gap + self.time_weight * avg_time,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        profiles.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap());
+
+        profiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    fn small_space() -> GeneticSpace {
+        GeneticSpace {
+            base: GeneticParams {
+                generations: 5,
+                ..GeneticParams::default()
+            },
+            population: vec![10, 20],
+            crossover: vec![Crossover::SinglePoint, Crossover::Uniform],
+            mutation_rate: vec![0.05],
+            elitism: vec![0.5],
+            init: vec![Initialization::default()],
+        }
+    }
+
+    #[test]
+    fn grid_size() {
+        let space = GeneticSpace::default();
+
+        assert_eq!(space.grid().len(), 3 * 3 * 3 * 2 * 2);
+        assert_eq!(small_space().grid().len(), 4);
+    }
+
+    #[test]
+    fn sample_from_space() {
+        let space = small_space();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            let params = space.sample(&mut rng);
+            assert!(space.grid().contains(&params));
+        }
+    }
+
+    #[test]
+    fn tune_grid() {
+        let instances = vec![AdjList::random(30, 0.5), AdjList::random(30, 0.2)];
+        let tuner = Tuner {
+            strategy: Strategy::Grid,
+            ..Tuner::default()
+        };
+
+        let profiles = tuner.tune(&small_space(), &instances);
+
+        assert_eq!(profiles.len(), 4);
+        assert!(profiles.windows(2).all(|w| w[0].score <= w[1].score));
+        assert!(profiles.iter().all(|p| p.avg_gap >= 0.0));
+    }
+
+    #[test]
+    fn tune_random() {
+        let instances = vec![AdjList::random(30, 0.5)];
+        let tuner = Tuner {
+            strategy: Strategy::Random {
+                samples: 3,
+                seed: 1,
+            },
+            repetitions: 2,
+            ..Tuner::default()
+        };
+
+        let profiles = tuner.tune(&small_space(), &instances);
+
+        assert_eq!(profiles.len(), 3);
+    }
+
+    #[test]
+    fn profile_roundtrip() {
+        let path = env::temp_dir().join(format!("graml-profile-{}.json", process::id()));
+        let profile = Profile {
+            params: GeneticParams {
+                seed: Some(3),
+                ..GeneticParams::default()
+            },
+            avg_gap: 0.25,
+            avg_time: 1.5,
+            score: 0.265,
+        };
+
+        profile.save(&path).unwrap();
+        let loaded = Profile::<GeneticParams>::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, profile);
+    }
+}